@@ -1,7 +1,12 @@
 pub mod page;
 pub mod util;
 
+use crate::front::page::blog::{BlogList, BlogPost};
+use crate::front::page::catalog::{Categories, Products};
+use crate::front::page::consent::Consent;
 use crate::front::page::home::Home;
+use crate::front::page::product::ProductDetail;
+use crate::front::page::profile::Profile;
 use crate::front::util::ErrorLayout;
 #[cfg(feature = "server")]
 use crate::resources::dto::fullstack_extension::AppExtension;
@@ -14,6 +19,26 @@ enum Route {
     #[layout(ErrorLayout)]
     #[route("/")]
     Home {},
+    #[route("/oauth/consent/:request_id")]
+    Consent { request_id: String },
+    #[route("/profile/:user_id")]
+    Profile { user_id: i32 },
+    #[route("/categories")]
+    Categories {},
+    #[route("/products?:category&:sort&:price_min&:price_max&:cursor")]
+    Products {
+        category: Option<i32>,
+        sort: String,
+        price_min: Option<i64>,
+        price_max: Option<i64>,
+        cursor: Option<String>,
+    },
+    #[route("/product/:id")]
+    ProductDetail { id: i32 },
+    #[route("/blog?:cursor")]
+    BlogList { cursor: Option<String> },
+    #[route("/blog/:slug")]
+    BlogPost { slug: String },
 }
 #[component]
 pub fn app() -> Element {
@@ -26,12 +51,18 @@ pub fn app() -> Element {
 
 #[cfg(feature = "server")]
 pub fn init_router(aex: AppExtension) -> axum::Router {
-    use crate::front::page::component::login;
+    use crate::front::page::component::{login, review};
 
     let login_router = login::init_router(aex.clone());
     let util_router = util::init_router();
+    let profile_router = page::profile::init_router(aex.clone());
+    let review_router = review::init_router(aex.clone());
+    let blog_router = page::blog::init_router();
 
     axum::Router::new()
         .nest("/front", login_router)
         .nest("/front", util_router)
+        .nest("/front", profile_router)
+        .nest("/front", review_router)
+        .nest("/front", blog_router)
 }
@@ -0,0 +1,116 @@
+use dioxus::prelude::*;
+
+#[cfg(feature = "server")]
+use dioxus::fullstack::extract::State;
+
+use crate::front::Route;
+use crate::front::page::component::comment::Comments;
+use crate::front::page::component::login::Login;
+#[cfg(feature = "server")]
+use crate::resources::dto::fullstack_extension::AppDatabase;
+use crate::resources::dto::page::Page;
+use crate::resources::dto::post::{PostDetailDto, PostDto};
+#[cfg(feature = "server")]
+use crate::utils::errors::AppError;
+
+// 코드 하이라이트 색, utils::markdown::HIGHLIGHT_CSS
+const HIGHLIGHT_CSS: &str = "/front/blog/highlight.css";
+
+#[component]
+pub fn BlogList(cursor: Option<String>) -> Element {
+    let page = use_loader(use_reactive!(|cursor| get_posts(cursor)))?();
+
+    rsx! {
+        Login {}
+        h2 {"Blog"}
+        if page.items.is_empty() {
+            p {"아직 글이 없습니다"}
+        }
+        ul {
+            for post in page.items.iter() {
+                li {
+                    key: "{post.id}",
+                    Link { to: Route::BlogPost { slug: post.slug.clone() }, "{post.title}" }
+                    " · {byline(post)}"
+                }
+            }
+        }
+        if cursor.is_some() {
+            Link { to: Route::BlogList { cursor: None }, "First" }
+            " "
+        }
+        if let Some(next) = page.next_cursor.clone() {
+            Link { to: Route::BlogList { cursor: Some(next) }, "Next" }
+        }
+    }
+}
+
+// html은 서버에서 정리한 것이라 그대로 넣음
+#[component]
+pub fn BlogPost(slug: String) -> Element {
+    let PostDetailDto { post, html, .. } = use_loader(use_reactive!(|slug| get_post(slug)))?();
+
+    rsx! {
+        document::Title {"{post.title}"}
+        document::Link { rel: "stylesheet", href: HIGHLIGHT_CSS }
+        Login {}
+        Link { to: Route::BlogList { cursor: None }, "Blog" }
+        article {
+            h2 {"{post.title}"}
+            p { small {"{byline(&post)}"} }
+            div { dangerous_inner_html: "{html}" }
+        }
+        Comments { post_id: post.id }
+    }
+}
+
+// 작성자 · 2026-04-21
+fn byline(post: &PostDto) -> String {
+    let date = post
+        .published_at
+        .unwrap_or(post.created_at)
+        .format("%Y-%m-%d");
+    match &post.author_name {
+        Some(name) => format!("{} · {}", name, date),
+        None => date.to_string(),
+    }
+}
+
+#[post("/front/blog/list", db: State<AppDatabase>)]
+async fn get_posts(cursor: Option<String>) -> Result<Page<PostDto>> {
+    use crate::resources::dto::post::list_posts;
+    use crate::resources::dto::query::ListQuery;
+    use crate::utils::errors::ApiError;
+
+    let list = ListQuery::parse("").map_err(|e| AppError::from(ApiError::from(e)))?;
+    Ok(
+        list_posts(None, list, cursor.as_deref(), None, false, &db.0.0)
+            .await
+            .map_err(AppError::from)?,
+    )
+}
+
+#[post("/front/blog/post", db: State<AppDatabase>)]
+async fn get_post(slug: String) -> Result<PostDetailDto> {
+    Ok(PostDetailDto::published(&slug, &db.0.0)
+        .await
+        .map_err(AppError::from)?)
+}
+
+#[cfg(feature = "server")]
+async fn highlight_css() -> impl axum::response::IntoResponse {
+    use reqwest::header::{CACHE_CONTROL, CONTENT_TYPE};
+
+    (
+        [
+            (CONTENT_TYPE, "text/css; charset=utf-8"),
+            (CACHE_CONTROL, "public, max-age=86400"),
+        ],
+        crate::utils::markdown::HIGHLIGHT_CSS.as_str(),
+    )
+}
+
+#[cfg(feature = "server")]
+pub fn init_router() -> axum::Router {
+    axum::Router::new().route("/blog/highlight.css", axum::routing::get(highlight_css))
+}
@@ -0,0 +1,242 @@
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "server")]
+use dioxus::fullstack::extract::State;
+
+use crate::front::Route;
+use crate::front::page::component::login::Login;
+use crate::resources::dto::category::CategoryNode;
+#[cfg(feature = "server")]
+use crate::resources::dto::fullstack_extension::AppDatabase;
+use crate::resources::dto::page::Page;
+use crate::resources::dto::product::ProductDto;
+#[cfg(feature = "server")]
+use crate::utils::errors::{ApiError, AppError};
+
+// (주소의 sort 값, 목록 API의 sort, select에 보여줄 이름), 첫 번째가 기본값
+const SORTS: [(&str, &str, &str); 4] = [
+    ("", "-id", "최신순"),
+    ("price_asc", "price", "낮은 가격순"),
+    ("price_desc", "-price", "높은 가격순"),
+    ("title", "title", "이름순"),
+];
+
+fn products_in(category: Option<i32>) -> Route {
+    Route::Products {
+        category,
+        sort: String::new(),
+        price_min: None,
+        price_max: None,
+        cursor: None,
+    }
+}
+
+// select에 보여줄 (id, 들여쓴 이름), 트리 순서대로
+fn flatten(nodes: &[CategoryNode], depth: usize, out: &mut Vec<(i32, String)>) {
+    for node in nodes {
+        out.push((
+            node.category.id,
+            format!("{}{}", "— ".repeat(depth), node.category.name),
+        ));
+        flatten(&node.children, depth + 1, out);
+    }
+}
+
+#[component]
+pub fn Categories() -> Element {
+    let categories = use_loader(get_categories)?();
+
+    rsx! {
+        Login {}
+        h2 {"Categories"}
+        Link { to: products_in(None), "전체 상품" }
+        if categories.is_empty() {
+            p {"카테고리가 없습니다"}
+        }
+        CategoryTree { nodes: categories }
+    }
+}
+
+#[component]
+fn CategoryTree(nodes: Vec<CategoryNode>) -> Element {
+    rsx! {
+        ul {
+            for node in nodes {
+                li {
+                    key: "{node.category.id}",
+                    Link { to: products_in(Some(node.category.id)), "{node.category.name}" }
+                    if let Some(description) = &node.category.description {
+                        " · {description}"
+                    }
+                    if !node.children.is_empty() {
+                        CategoryTree { nodes: node.children.clone() }
+                    }
+                }
+            }
+        }
+    }
+}
+
+// 필터는 GET form이라 스크립트 없이도 동작하고, 다음 페이지는 커서를 주소에 담은 링크
+#[component]
+pub fn Products(
+    category: Option<i32>,
+    sort: String,
+    price_min: Option<i64>,
+    price_max: Option<i64>,
+    cursor: Option<String>,
+) -> Element {
+    let ProductListPage { page, categories } =
+        use_loader(use_reactive!(|category,
+                                  sort,
+                                  price_min,
+                                  price_max,
+                                  cursor| {
+            get_product_list(category, sort, price_min, price_max, cursor)
+        }))?();
+    let mut options = Vec::new();
+    flatten(&categories, 0, &mut options);
+    let current = options
+        .iter()
+        .find(|(id, _)| Some(*id) == category)
+        .map(|(_, name)| name.trim_start_matches("— ").to_string());
+
+    rsx! {
+        Login {}
+        h2 { "{current.as_deref().unwrap_or(\"전체 상품\")}" }
+        Link { to: Route::Categories {}, "카테고리 보기" }
+        form {
+            method: "get",
+            action: "/products",
+            label { "Category: "
+                select {
+                    name: "category",
+                    option { value: "", "전체" }
+                    for (id, name) in options.iter() {
+                        option {
+                            key: "{id}",
+                            value: "{id}",
+                            selected: category == Some(*id),
+                            "{name}"
+                        }
+                    }
+                }
+            }
+            label { " Price: "
+                input {
+                    name: "price_min",
+                    r#type: "number",
+                    min: 0,
+                    value: price_min.map(|v| v.to_string()).unwrap_or_default()
+                }
+                " ~ "
+                input {
+                    name: "price_max",
+                    r#type: "number",
+                    min: 0,
+                    value: price_max.map(|v| v.to_string()).unwrap_or_default()
+                }
+            }
+            label { " Sort: "
+                select {
+                    name: "sort",
+                    for (value, _, label) in SORTS {
+                        option { key: "{value}", value: "{value}", selected: sort == value, "{label}" }
+                    }
+                }
+            }
+            button {"Search"}
+        }
+        if page.items.is_empty() {
+            p {"상품이 없습니다"}
+        }
+        ul {
+            for product in page.items.iter() {
+                li {
+                    key: "{product.id}",
+                    Link { to: Route::ProductDetail { id: product.id }, "{product.title}" }
+                    " {product.price}"
+                    if !product.variants.is_empty() {
+                        " (옵션 {product.variants.len()}개)"
+                    }
+                }
+            }
+        }
+        if cursor.is_some() {
+            Link {
+                to: Route::Products {
+                    category,
+                    sort: sort.clone(),
+                    price_min,
+                    price_max,
+                    cursor: None,
+                },
+                "First"
+            }
+            " "
+        }
+        if let Some(next) = page.next_cursor.clone() {
+            Link {
+                to: Route::Products {
+                    category,
+                    sort: sort.clone(),
+                    price_min,
+                    price_max,
+                    cursor: Some(next),
+                },
+                "Next"
+            }
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+struct ProductListPage {
+    page: Page<ProductDto>,
+    // 필터의 카테고리 select
+    categories: Vec<CategoryNode>,
+}
+
+#[post("/front/catalog/categories", db: State<AppDatabase>)]
+async fn get_categories() -> Result<Vec<CategoryNode>> {
+    use crate::resources::dto::category::CategoryDto;
+
+    Ok(CategoryDto::tree(&db.0.0).await.map_err(AppError::from)?)
+}
+
+// 상품 목록 API와 같은 find_products를 쓰고, 필터는 목록 API의 filter, sort 문법으로 바꿔서 넘김
+#[post("/front/catalog/products", db: State<AppDatabase>)]
+async fn get_product_list(
+    category: Option<i32>,
+    sort: String,
+    price_min: Option<i64>,
+    price_max: Option<i64>,
+    cursor: Option<String>,
+) -> Result<ProductListPage> {
+    use crate::resources::dto::category::CategoryDto;
+    use crate::resources::dto::product::find_products;
+    use crate::resources::dto::query::ListQuery;
+
+    let conn = &db.0.0;
+    let sort = SORTS
+        .iter()
+        .find(|(value, _, _)| *value == sort)
+        .unwrap_or(&SORTS[0])
+        .1;
+    let mut query = vec![format!("sort={}", sort)];
+    if let Some(min) = price_min {
+        query.push(format!("filter[price][gte]={}", min));
+    }
+    if let Some(max) = price_max {
+        query.push(format!("filter[price][lte]={}", max));
+    }
+    let list = ListQuery::parse(&query.join("&")).map_err(|e| AppError::from(ApiError::from(e)))?;
+
+    let page = find_products(category, list, cursor.as_deref(), None, false, conn)
+        .await
+        .map_err(AppError::from)?;
+    let categories = CategoryDto::tree(conn).await.map_err(AppError::from)?;
+
+    Ok(ProductListPage { page, categories })
+}
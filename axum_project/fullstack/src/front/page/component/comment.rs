@@ -0,0 +1,293 @@
+use chrono::NaiveDateTime;
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "server")]
+use dioxus::fullstack::{Cookie, TypedHeader, extract::State};
+
+use crate::resources::dto::comment::{BODY_MAX, CommentDto};
+#[cfg(feature = "server")]
+use crate::resources::dto::fullstack_extension::AppDatabase;
+use crate::resources::dto::page::Page;
+#[cfg(feature = "server")]
+use crate::utils::errors::{ApiError, AppError};
+#[cfg(feature = "server")]
+use crate::utils::jwt::current_user;
+
+fn author(comment: &CommentDto) -> String {
+    match (comment.deleted, comment.user_id, &comment.author_name) {
+        (true, _, _) => "-".to_string(),
+        (false, Some(_), Some(name)) => name.clone(),
+        (false, Some(user_id), None) => format!("user #{}", user_id),
+        (false, None, _) => "탈퇴한 사용자".to_string(),
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+struct CommentPage {
+    page: Page<CommentDto>,
+    // 로그인하지 않았으면 None
+    user_id: Option<i32>,
+    // 수정 가능 시간은 서버 시각으로 비교해야 hydrate 결과가 어긋나지 않음
+    now: NaiveDateTime,
+}
+
+// 글의 댓글, 쓰거나 고치거나 지우면 지금 페이지를 다시 불러옴
+#[component]
+pub fn Comments(post_id: i32) -> Element {
+    // 지나온 페이지의 커서, 마지막 값이 지금 페이지
+    let mut cursors = use_signal(Vec::<String>::new);
+    let mut version = use_signal(|| 0u32);
+    let CommentPage { page, user_id, now } = use_loader(move || {
+        version();
+        get_comments(post_id, cursors.read().last().cloned())
+    })?();
+    let reload = move |_| version += 1;
+
+    rsx! {
+        h3 {"Comments"}
+        if user_id.is_some() {
+            CommentForm { post_id, parent_id: None, on_done: reload }
+        } else {
+            p {"로그인하면 댓글을 쓸 수 있습니다"}
+        }
+        if page.items.is_empty() {
+            p {"아직 댓글이 없습니다"}
+        }
+        ul {
+            for comment in page.items.iter() {
+                CommentItem {
+                    key: "{comment.id}",
+                    comment: comment.clone(),
+                    user_id,
+                    now,
+                    on_change: reload
+                }
+            }
+        }
+        if !cursors.read().is_empty() {
+            button {
+                onclick: move |_| {
+                    cursors.write().pop();
+                },
+                "Prev"
+            }
+        }
+        if let Some(next) = page.next_cursor.clone() {
+            button {
+                onclick: move |_| cursors.write().push(next.clone()),
+                "Next"
+            }
+        }
+    }
+}
+
+// 새 댓글이나 답글
+#[component]
+fn CommentForm(post_id: i32, parent_id: Option<i32>, on_done: EventHandler<()>) -> Element {
+    let mut body = use_signal(String::new);
+    let mut error = use_signal(|| None::<String>);
+
+    rsx! {
+        form {
+            onsubmit: move |e| async move {
+                e.prevent_default();
+                match create_comment(post_id, parent_id, body()).await {
+                    Ok(_) => {
+                        body.set(String::new());
+                        error.set(None);
+                        on_done.call(());
+                    }
+                    Err(e) => error.set(Some(e.to_string())),
+                }
+            },
+            textarea {
+                maxlength: BODY_MAX as i64,
+                required: true,
+                value: "{body}",
+                oninput: move |e| body.set(e.value())
+            }
+            br {}
+            button { if parent_id.is_some() {"Reply"} else {"Write comment"} }
+        }
+        if let Some(error) = error() {
+            p {"{error}"}
+        }
+    }
+}
+
+// 댓글 하나와 그 아래 답글들
+#[component]
+fn CommentItem(
+    comment: CommentDto,
+    user_id: Option<i32>,
+    now: NaiveDateTime,
+    on_change: EventHandler<()>,
+) -> Element {
+    let mut replying = use_signal(|| false);
+    let mut editing = use_signal(|| false);
+    let mut draft = use_signal(String::new);
+    let mut error = use_signal(|| None::<String>);
+
+    let id = comment.id;
+    let is_mine = !comment.deleted && user_id.is_some() && comment.user_id == user_id;
+    let can_edit = is_mine && now < comment.editable_until;
+    let body = comment.body.clone().unwrap_or_default();
+    let created_at = comment.created_at.format("%Y-%m-%d %H:%M").to_string();
+
+    rsx! {
+        li {
+            p {
+                strong {"{author(&comment)}"}
+                " · {created_at}"
+                if comment.edited {
+                    " (수정됨)"
+                }
+            }
+            if editing() {
+                form {
+                    onsubmit: move |e| async move {
+                        e.prevent_default();
+                        match edit_comment(id, draft()).await {
+                            Ok(_) => {
+                                editing.set(false);
+                                error.set(None);
+                                on_change.call(());
+                            }
+                            Err(e) => error.set(Some(e.to_string())),
+                        }
+                    },
+                    textarea {
+                        maxlength: BODY_MAX as i64,
+                        required: true,
+                        value: "{draft}",
+                        oninput: move |e| draft.set(e.value())
+                    }
+                    br {}
+                    button {"Save"}
+                    button {
+                        r#type: "button",
+                        onclick: move |_| editing.set(false),
+                        "Cancel"
+                    }
+                }
+            } else if comment.deleted {
+                p { em {"삭제된 댓글입니다"} }
+            } else {
+                p {"{body}"}
+            }
+            if user_id.is_some() && !comment.deleted {
+                button {
+                    onclick: move |_| replying.toggle(),
+                    "Reply"
+                }
+            }
+            if can_edit && !editing() {
+                button {
+                    onclick: move |_| {
+                        draft.set(body.clone());
+                        editing.set(true);
+                    },
+                    "Edit"
+                }
+            }
+            if is_mine {
+                button {
+                    onclick: move |_| async move {
+                        match remove_comment(id).await {
+                            Ok(_) => on_change.call(()),
+                            Err(e) => error.set(Some(e.to_string())),
+                        }
+                    },
+                    "Delete"
+                }
+            }
+            if let Some(error) = error() {
+                p {"{error}"}
+            }
+            if replying() {
+                CommentForm {
+                    post_id: comment.post_id,
+                    parent_id: Some(id),
+                    on_done: move |_| {
+                        replying.set(false);
+                        on_change.call(());
+                    }
+                }
+            }
+            if !comment.replies.is_empty() {
+                ul {
+                    for reply in comment.replies.iter() {
+                        CommentItem {
+                            key: "{reply.id}",
+                            comment: reply.clone(),
+                            user_id,
+                            now,
+                            on_change
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[post("/front/comment/list", header: TypedHeader<Cookie>, db: State<AppDatabase>)]
+async fn get_comments(post_id: i32, cursor: Option<String>) -> Result<CommentPage> {
+    use crate::resources::dto::comment::list_threads;
+    use crate::utils::jwt::claims_from_cookie;
+
+    let page = list_threads(post_id, cursor.as_deref(), None, &db.0.0)
+        .await
+        .map_err(AppError::from)?;
+
+    Ok(CommentPage {
+        page,
+        user_id: claims_from_cookie(&header.0).map(|c| c.user_id),
+        now: chrono::Utc::now().naive_utc(),
+    })
+}
+
+#[post("/front/comment/create", header: TypedHeader<Cookie>, db: State<AppDatabase>)]
+async fn create_comment(post_id: i32, parent_id: Option<i32>, body: String) -> Result<CommentDto> {
+    use crate::resources::dto::comment::CommentReq;
+    use crate::utils::validate::Validate;
+
+    let user_id = current_user(&header.0, &db.0.0).await?.0;
+    let req = CommentReq { parent_id, body };
+    req.validate()
+        .map_err(|e| AppError::from(ApiError::from(e)))?;
+    Ok(req
+        .create(post_id, user_id, &db.0.0)
+        .await
+        .map_err(AppError::from)?)
+}
+
+#[post("/front/comment/edit", header: TypedHeader<Cookie>, db: State<AppDatabase>)]
+async fn edit_comment(id: i32, body: String) -> Result<CommentDto> {
+    use crate::resources::dto::comment::CommentEditReq;
+    use crate::utils::validate::Validate;
+
+    let user_id = current_user(&header.0, &db.0.0).await?.0;
+    let req = CommentEditReq { body };
+    req.validate()
+        .map_err(|e| AppError::from(ApiError::from(e)))?;
+    Ok(req
+        .update(id, user_id, &db.0.0)
+        .await
+        .map_err(AppError::from)?)
+}
+
+#[post("/front/comment/delete", header: TypedHeader<Cookie>, db: State<AppDatabase>)]
+async fn remove_comment(id: i32) -> Result<()> {
+    use crate::resources::dto::admin::{ROLE_ADMIN, has_role};
+    use crate::resources::dto::comment::delete_comment;
+
+    let user_id = current_user(&header.0, &db.0.0).await?.0;
+    let is_admin = has_role(user_id, &[ROLE_ADMIN], &db.0.0)
+        .await
+        .map_err(AppError::from)?;
+    Ok(delete_comment(id, user_id, is_admin, &db.0.0)
+        .await
+        .map_err(AppError::from)?)
+}
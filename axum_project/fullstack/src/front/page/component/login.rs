@@ -7,6 +7,7 @@ use rand::random;
 #[cfg(feature = "server")]
 use reqwest::header::{LOCATION, SET_COOKIE};
 use serde::{Deserialize, Serialize};
+use validate_dto_macro::validate_dto;
 
 use crate::front::Route;
 use crate::front::util::add_no_cache_headers;
@@ -16,14 +17,19 @@ use crate::resources::dto::fullstack_extension::AppExtension;
 #[cfg(feature = "server")]
 use crate::utils::errors::AppError;
 
+#[validate_dto]
 #[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct OauthUrl {
     pub title: String,
+    #[validate(url)]
     pub url: String,
+    #[validate(url)]
     pub redirect_uri: String,
+    #[validate(length(min = 1, max = 20))]
     pub response_type: String,
     // pub scope: String,
+    #[validate(length(min = 1, max = 64))]
     pub state: String,
 }
 
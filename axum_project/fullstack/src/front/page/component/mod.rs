@@ -1,8 +1,10 @@
 #[cfg(feature = "server")]
 use crate::resources::dto::fullstack_extension::AppExtension;
 
+pub mod comment;
 pub mod error_layout;
 pub mod login;
+pub mod review;
 
 #[cfg(feature = "server")]
 pub fn init_router(aex: AppExtension) -> axum::Router {
@@ -0,0 +1,294 @@
+use dioxus::prelude::*;
+
+#[cfg(feature = "server")]
+use dioxus::fullstack::extract::State;
+
+#[cfg(feature = "server")]
+use crate::resources::dto::fullstack_extension::{AppDatabase, AppExtension};
+use crate::resources::dto::page::Page;
+use crate::resources::dto::review::{BODY_MAX, RatingSummaryDto, ReviewDto};
+#[cfg(feature = "server")]
+use crate::utils::errors::{ApiError, AppError};
+#[cfg(feature = "server")]
+use crate::utils::jwt::current_user;
+
+// select에 보여줄 정렬, 값은 목록 API의 sort 문법
+const SORTS: [(&str, &str); 4] = [
+    ("-id", "최신순"),
+    ("id", "오래된순"),
+    ("-rating,-id", "별점 높은순"),
+    ("rating,-id", "별점 낮은순"),
+];
+
+fn stars(rating: i32) -> String {
+    let rating = rating.clamp(0, 5) as usize;
+    format!("{}{}", "★".repeat(rating), "☆".repeat(5 - rating))
+}
+
+fn author(review: &ReviewDto) -> String {
+    match (review.user_id, &review.author_name) {
+        (Some(_), Some(name)) => name.clone(),
+        (Some(user_id), None) => format!("user #{}", user_id),
+        (None, _) => "탈퇴한 사용자".to_string(),
+    }
+}
+
+// 평균 별점과 별점별 막대
+#[component]
+pub fn ReviewSummary(summary: RatingSummaryDto) -> Element {
+    let max = summary.histogram.iter().copied().max().unwrap_or(0).max(1);
+
+    rsx! {
+        if let Some(average) = summary.average {
+            p {
+                strong {"{average:.2}"}
+                " / 5 ({summary.count})"
+            }
+        } else {
+            p {"아직 리뷰가 없습니다"}
+        }
+        table {
+            tbody {
+                for (i, count) in summary.histogram.iter().enumerate().rev() {
+                    tr {
+                        key: "{i}",
+                        td {"{i + 1}"}
+                        td {
+                            width: "200px",
+                            div {
+                                background: "gold",
+                                height: "10px",
+                                width: "{count * 100 / max}%",
+                            }
+                        }
+                        td {"{count}"}
+                    }
+                }
+            }
+        }
+    }
+}
+
+// 새 리뷰를 쓰거나 내 리뷰를 고치는 폼
+#[component]
+pub fn ReviewForm(product_id: i32, mine: Option<ReviewDto>) -> Element {
+    let action = match &mine {
+        Some(review) => format!("/front/review/{}/update_action", review.id),
+        None => format!("/front/review/product/{}/create_action", product_id),
+    };
+    let rating = mine.as_ref().map(|r| r.rating).unwrap_or(5);
+    let body = mine.as_ref().map(|r| r.body.clone()).unwrap_or_default();
+
+    rsx! {
+        form {
+            method: "post",
+            action: "{action}",
+            label { "Rating: "
+                select {
+                    name: "rating",
+                    for r in (1..=5).rev() {
+                        option {
+                            key: "{r}",
+                            value: "{r}",
+                            selected: r == rating,
+                            "{stars(r)}"
+                        }
+                    }
+                }
+            }
+            br {}
+            textarea {
+                name: "body",
+                maxlength: BODY_MAX as i64,
+                required: true,
+                value: "{body}"
+            }
+            br {}
+            button { if mine.is_some() {"Update"} else {"Write review"} }
+        }
+        if let Some(review) = &mine {
+            form {
+                method: "post",
+                action: "/front/review/{review.id}/delete_action",
+                button {"Delete"}
+            }
+        }
+    }
+}
+
+// 상품의 리뷰 목록, 정렬을 바꾸면 첫 페이지부터 다시 불러옴
+#[component]
+pub fn Reviews(product_id: i32) -> Element {
+    let mut sort = use_signal(|| SORTS[0].0.to_string());
+    // 지나온 페이지의 커서, 마지막 값이 지금 페이지
+    let mut cursors = use_signal(Vec::<String>::new);
+    let page =
+        use_loader(move || get_reviews(product_id, sort(), cursors.read().last().cloned()))?();
+
+    rsx! {
+        select {
+            onchange: move |e| {
+                sort.set(e.value());
+                cursors.write().clear();
+            },
+            for (value, label) in SORTS {
+                option { key: "{value}", value: "{value}", selected: sort() == value, "{label}" }
+            }
+        }
+        for review in page.items.iter() {
+            article {
+                key: "{review.id}",
+                p {
+                    strong {"{stars(review.rating)}"}
+                    " {author(review)} · {review.created_at.date()}"
+                }
+                p {"{review.body}"}
+            }
+        }
+        if !cursors.read().is_empty() {
+            button {
+                onclick: move |_| {
+                    cursors.write().pop();
+                },
+                "Prev"
+            }
+        }
+        if let Some(next) = page.next_cursor.clone() {
+            button {
+                onclick: move |_| cursors.write().push(next.clone()),
+                "Next"
+            }
+        }
+    }
+}
+
+#[post("/front/review/list", db: State<AppDatabase>)]
+async fn get_reviews(
+    product_id: i32,
+    sort: String,
+    cursor: Option<String>,
+) -> Result<Page<ReviewDto>> {
+    use crate::resources::dto::query::ListQuery;
+    use crate::resources::dto::review::list_reviews;
+
+    let list = ListQuery::parse(&format!("sort={}", urlencoding::encode(&sort)))
+        .map_err(|e| AppError::from(ApiError::from(e)))?;
+    Ok(
+        list_reviews(product_id, list, cursor.as_deref(), None, false, &db.0.0)
+            .await
+            .map_err(AppError::from)?,
+    )
+}
+
+#[cfg(feature = "server")]
+fn back_to_product(product_id: i32) -> Result<axum::response::Response, AppError> {
+    use axum::{body::Body, http::HeaderValue, response::Response};
+    use reqwest::header::LOCATION;
+
+    let mut response = Response::new(Body::empty());
+    *response.status_mut() = StatusCode::SEE_OTHER;
+    response.headers_mut().insert(
+        LOCATION,
+        HeaderValue::from_str(&format!("/product/{}", product_id))?,
+    );
+
+    Ok(response)
+}
+
+// 에러 메시지를 쿠키에 담아 상품 화면으로 돌려보냄
+#[cfg(feature = "server")]
+fn on_product(product_id: i32) -> impl Fn(ApiError) -> AppError {
+    move |e| AppError::from(e).set_redirection(format!("/product/{}", product_id))
+}
+
+#[cfg(feature = "server")]
+async fn create_action(
+    axum::extract::State(aex): axum::extract::State<AppExtension>,
+    axum_extra::TypedHeader(cookies): axum_extra::TypedHeader<axum_extra::headers::Cookie>,
+    axum::extract::Path(product_id): axum::extract::Path<i32>,
+    axum::Form(req): axum::Form<crate::resources::dto::review::ReviewReq>,
+) -> Result<axum::response::Response, AppError> {
+    use crate::utils::validate::Validate;
+
+    let user_id = current_user(&cookies, &aex.db.0).await?.0;
+    req.validate()
+        .map_err(ApiError::from)
+        .map_err(on_product(product_id))?;
+    req.create(product_id, user_id, &aex.db.0)
+        .await
+        .map_err(on_product(product_id))?;
+
+    back_to_product(product_id)
+}
+
+#[cfg(feature = "server")]
+async fn update_action(
+    axum::extract::State(aex): axum::extract::State<AppExtension>,
+    axum_extra::TypedHeader(cookies): axum_extra::TypedHeader<axum_extra::headers::Cookie>,
+    axum::extract::Path(id): axum::extract::Path<i32>,
+    axum::Form(req): axum::Form<crate::resources::dto::review::ReviewReq>,
+) -> Result<axum::response::Response, AppError> {
+    use crate::utils::validate::Validate;
+
+    let user_id = current_user(&cookies, &aex.db.0).await?.0;
+    let product_id = review_product(id, &aex.db.0).await?;
+    req.validate()
+        .map_err(ApiError::from)
+        .map_err(on_product(product_id))?;
+    req.update(id, user_id, &aex.db.0)
+        .await
+        .map_err(on_product(product_id))?;
+
+    back_to_product(product_id)
+}
+
+#[cfg(feature = "server")]
+async fn delete_action(
+    axum::extract::State(aex): axum::extract::State<AppExtension>,
+    axum_extra::TypedHeader(cookies): axum_extra::TypedHeader<axum_extra::headers::Cookie>,
+    axum::extract::Path(id): axum::extract::Path<i32>,
+) -> Result<axum::response::Response, AppError> {
+    use crate::resources::dto::review::delete_review;
+
+    let user_id = current_user(&cookies, &aex.db.0).await?.0;
+    let product_id = review_product(id, &aex.db.0).await?;
+    delete_review(id, user_id, &aex.db.0)
+        .await
+        .map_err(on_product(product_id))?;
+
+    back_to_product(product_id)
+}
+
+// 수정, 삭제 후 돌아갈 상품
+#[cfg(feature = "server")]
+async fn review_product(id: i32, conn: &sea_orm::DatabaseConnection) -> Result<i32, AppError> {
+    use sea_orm::{EntityTrait, QuerySelect};
+
+    use crate::resources::entities::review;
+
+    review::Entity::find_by_id(id)
+        .select_only()
+        .column(review::Column::ProductId)
+        .into_tuple()
+        .one(conn)
+        .await?
+        .ok_or(AppError::new(
+            StatusCode::NOT_FOUND,
+            "review is not found",
+            Some("/".to_string()),
+        ))
+}
+
+#[cfg(feature = "server")]
+pub fn init_router(aex: AppExtension) -> axum::Router {
+    use axum::routing::post;
+
+    axum::Router::new()
+        .route(
+            "/review/product/{product_id}/create_action",
+            post(create_action),
+        )
+        .route("/review/{id}/update_action", post(update_action))
+        .route("/review/{id}/delete_action", post(delete_action))
+        .with_state(aex)
+}
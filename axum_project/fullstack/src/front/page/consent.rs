@@ -0,0 +1,55 @@
+use dioxus::prelude::*;
+
+#[cfg(feature = "server")]
+use dioxus::fullstack::{Cookie, TypedHeader, extract::State};
+
+use crate::front::page::component::login::Login;
+#[cfg(feature = "server")]
+use crate::resources::dto::fullstack_extension::AppDatabase;
+use crate::resources::dto::oauth::ConsentInfo;
+
+// /oauth/authorize 에서 넘어오는 동의 화면
+#[component]
+pub fn Consent(request_id: String) -> Element {
+    let id = request_id.clone();
+    let ConsentInfo {
+        request_id,
+        client_name,
+        scopes,
+        username: _,
+        csrf_token,
+    } = use_loader(move || get_consent_info(id.clone()))?();
+
+    rsx! {
+        h2 {"{client_name}"}
+        p {"이 앱이 다음 권한을 요청합니다"}
+        ul {
+            for scope in scopes.iter() {
+                li {"{scope}"}
+            }
+        }
+        // 로그인했을 때만 csrf_token이 있음
+        if let Some(csrf_token) = csrf_token {
+            form {
+                method: "post",
+                action: "/oauth/authorize/decision",
+                input { r#type: "hidden", name: "request_id", value: "{request_id}"}
+                input { r#type: "hidden", name: "csrf_token", value: "{csrf_token}"}
+                button { name: "decision", value: "approve", "Approve" }
+                button { name: "decision", value: "deny", "Deny" }
+            }
+        } else {
+            p {"로그인 후 다시 시도해주세요"}
+            Login {}
+        }
+    }
+}
+
+#[post("/front/oauth/consent_info", header: TypedHeader<Cookie>, db: State<AppDatabase>)]
+async fn get_consent_info(request_id: String) -> Result<ConsentInfo> {
+    use crate::utils::jwt::claims_from_cookie;
+
+    let user = claims_from_cookie(&header.0).map(|c| (c.user_id, c.username));
+
+    Ok(ConsentInfo::load(&request_id, user, &db.0.0).await?)
+}
@@ -1,5 +1,6 @@
 use dioxus::prelude::*;
 
+use crate::front::Route;
 use crate::front::page::component::login::Login;
 
 #[component]
@@ -7,5 +8,21 @@ pub fn Home() -> Element {
     rsx! {
         Login  {}
         h1{"Hello World"}
+        nav {
+            Link { to: Route::BlogList { cursor: None }, "Blog" }
+            " "
+            Link { to: Route::Categories {}, "Categories" }
+            " "
+            Link {
+                to: Route::Products {
+                    category: None,
+                    sort: String::new(),
+                    price_min: None,
+                    price_max: None,
+                    cursor: None,
+                },
+                "Products"
+            }
+        }
     }
 }
@@ -1,2 +1,7 @@
+pub mod blog;
+pub mod catalog;
 pub mod component;
+pub mod consent;
 pub mod home;
+pub mod product;
+pub mod profile;
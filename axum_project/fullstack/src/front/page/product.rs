@@ -0,0 +1,170 @@
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "server")]
+use dioxus::fullstack::{Cookie, TypedHeader, extract::State};
+
+use crate::front::Route;
+use crate::front::page::component::login::Login;
+use crate::front::page::component::review::{ReviewForm, ReviewSummary, Reviews};
+use crate::resources::dto::category::CategoryDto;
+#[cfg(feature = "server")]
+use crate::resources::dto::fullstack_extension::{AppDatabase, AppStorage};
+use crate::resources::dto::product::ProductDto;
+use crate::resources::dto::product_image::ProductImageDto;
+use crate::resources::dto::product_variant::ProductVariantDto;
+use crate::resources::dto::review::{RatingSummaryDto, ReviewDto};
+#[cfg(feature = "server")]
+use crate::utils::errors::AppError;
+
+#[component]
+pub fn ProductDetail(id: i32) -> Element {
+    let ProductPage {
+        product,
+        categories,
+        images,
+        summary,
+        mine,
+        is_login,
+    } = use_loader(use_reactive!(|id| get_product_page(id)))?();
+
+    rsx! {
+        Login {}
+        nav {
+            Link { to: Route::Categories {}, "Categories" }
+            for category in categories.iter() {
+                span {
+                    key: "{category.id}",
+                    " › "
+                    Link {
+                        to: Route::Products {
+                            category: Some(category.id),
+                            sort: String::new(),
+                            price_min: None,
+                            price_max: None,
+                            cursor: None,
+                        },
+                        "{category.name}"
+                    }
+                }
+            }
+        }
+        h2 {"{product.title}"}
+        for image in images.iter() {
+            picture {
+                key: "{image.id}",
+                source { r#type: "image/webp", "srcset": srcset(image, "webp") }
+                img {
+                    src: fallback_url(image),
+                    srcset: srcset(image, "jpg"),
+                    sizes: "(max-width: 600px) 100vw, 600px",
+                    alt: image.alt.clone().unwrap_or_else(|| product.title.clone())
+                }
+            }
+        }
+        p {"{product.price}"}
+        if !product.variants.is_empty() {
+            table {
+                tbody {
+                    for variant in product.variants.iter() {
+                        tr {
+                            key: "{variant.id}",
+                            td {"{variant_label(variant)}"}
+                            td {"{variant.price.unwrap_or(product.price)}"}
+                        }
+                    }
+                }
+            }
+        }
+        h3 {"Reviews"}
+        ReviewSummary { summary }
+        if is_login {
+            ReviewForm { product_id: id, mine }
+        } else {
+            p {"로그인하면 리뷰를 쓸 수 있습니다"}
+        }
+        Reviews { product_id: id }
+    }
+}
+
+// Red / M
+fn variant_label(variant: &ProductVariantDto) -> String {
+    variant
+        .attributes
+        .iter()
+        .map(|a| a.value.as_str())
+        .collect::<Vec<_>>()
+        .join(" / ")
+}
+
+// a-600.jpg 600w, a-200.jpg 200w
+fn srcset(image: &ProductImageDto, format: &str) -> String {
+    image
+        .variants
+        .iter()
+        .filter(|v| v.format == format)
+        .map(|v| format!("{} {}w", v.url, v.width))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+// srcset을 모르는 브라우저용, 중간 크기 jpg
+fn fallback_url(image: &ProductImageDto) -> String {
+    let jpgs = image
+        .variants
+        .iter()
+        .filter(|v| v.format == "jpg")
+        .collect::<Vec<_>>();
+    jpgs.get(jpgs.len() / 2)
+        .map(|v| v.url.clone())
+        .unwrap_or_default()
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+struct ProductPage {
+    product: ProductDto,
+    // 최상위부터 상품의 카테고리까지
+    categories: Vec<CategoryDto>,
+    images: Vec<ProductImageDto>,
+    summary: RatingSummaryDto,
+    // 로그인한 유저가 이미 쓴 리뷰
+    mine: Option<ReviewDto>,
+    is_login: bool,
+}
+
+#[post("/front/product/info", header: TypedHeader<Cookie>, db: State<AppDatabase>, storage: State<AppStorage>)]
+async fn get_product_page(id: i32) -> Result<ProductPage> {
+    use crate::resources::dto::product::get_product_detail;
+    use crate::resources::dto::review::{my_review, rating_summary};
+    use crate::utils::jwt::claims_from_cookie;
+
+    let conn = &db.0.0;
+    let product = get_product_detail(id, conn).await.map_err(AppError::from)?;
+    let mut categories = Vec::new();
+    let mut parent_id = Some(product.category_id);
+    while let Some(category_id) = parent_id {
+        let category = CategoryDto::get(category_id, conn)
+            .await
+            .map_err(AppError::from)?;
+        parent_id = category.parent_id;
+        categories.insert(0, category);
+    }
+    let images = ProductImageDto::list(id, conn, storage.0.0.as_ref())
+        .await
+        .map_err(AppError::from)?;
+    let summary = rating_summary(id, conn).await.map_err(AppError::from)?;
+    let user_id = claims_from_cookie(&header.0).map(|c| c.user_id);
+    let mine = match user_id {
+        Some(user_id) => my_review(id, user_id, conn).await.map_err(AppError::from)?,
+        None => None,
+    };
+
+    Ok(ProductPage {
+        product,
+        categories,
+        images,
+        summary,
+        mine,
+        is_login: user_id.is_some(),
+    })
+}
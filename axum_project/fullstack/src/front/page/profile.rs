@@ -0,0 +1,145 @@
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "server")]
+use dioxus::fullstack::{Cookie, TypedHeader, extract::State};
+
+use crate::front::page::component::login::Login;
+#[cfg(feature = "server")]
+use crate::resources::dto::fullstack_extension::{AppDatabase, AppExtension, AppStorage};
+use crate::resources::dto::profile::ProfileDto;
+#[cfg(feature = "server")]
+use crate::utils::errors::AppError;
+#[cfg(feature = "server")]
+use crate::utils::jwt::current_user;
+
+#[component]
+pub fn Profile(user_id: i32) -> Element {
+    let ProfilePage { profile, is_owner } = use_loader(move || get_profile_page(user_id))?();
+    let name = profile
+        .display_name
+        .clone()
+        .unwrap_or(format!("user #{}", profile.id));
+
+    rsx! {
+        Login {}
+        if let Some(url) = &profile.avatar_url {
+            img { src: "{url}", width: 128, height: 128, alt: "avatar" }
+        }
+        h2 {"{name}"}
+        if let Some(bio) = &profile.bio {
+            p {"{bio}"}
+        }
+        if is_owner {
+            form {
+                method: "post",
+                action: "/front/profile/update_action",
+                label { "Name: "
+                    input {
+                        name: "display_name",
+                        value: profile.display_name.clone().unwrap_or_default()
+                    }
+                }
+                br {}
+                label { "Bio: "
+                    textarea {
+                        name: "bio",
+                        value: profile.bio.clone().unwrap_or_default()
+                    }
+                }
+                br {}
+                button {"Save"}
+            }
+            form {
+                method: "post",
+                action: "/front/profile/avatar_action",
+                enctype: "multipart/form-data",
+                input {
+                    name: "avatar",
+                    r#type: "file",
+                    accept: "image/png,image/jpeg,image/gif,image/webp"
+                }
+                button {"Upload"}
+            }
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+struct ProfilePage {
+    profile: ProfileDto,
+    is_owner: bool,
+}
+
+#[post("/front/profile/info", header: TypedHeader<Cookie>, db: State<AppDatabase>, storage: State<AppStorage>)]
+async fn get_profile_page(user_id: i32) -> Result<ProfilePage> {
+    use crate::utils::jwt::claims_from_cookie;
+
+    let profile = ProfileDto::get(user_id, &db.0.0, storage.0.0.as_ref()).await?;
+    let is_owner = claims_from_cookie(&header.0).is_some_and(|c| c.user_id == user_id);
+
+    Ok(ProfilePage { profile, is_owner })
+}
+
+#[cfg(feature = "server")]
+fn back_to_profile(user_id: i32) -> Result<axum::response::Response, AppError> {
+    use axum::{body::Body, http::HeaderValue, response::Response};
+    use reqwest::header::LOCATION;
+
+    let mut response = Response::new(Body::empty());
+    *response.status_mut() = StatusCode::SEE_OTHER;
+    response.headers_mut().insert(
+        LOCATION,
+        HeaderValue::from_str(&format!("/profile/{}", user_id))?,
+    );
+
+    Ok(response)
+}
+
+#[cfg(feature = "server")]
+async fn update_action(
+    axum::extract::State(aex): axum::extract::State<AppExtension>,
+    axum_extra::TypedHeader(cookies): axum_extra::TypedHeader<axum_extra::headers::Cookie>,
+    axum::Form(req): axum::Form<crate::resources::dto::profile::ProfileUpdateReq>,
+) -> Result<axum::response::Response, AppError> {
+    let user_id = current_user(&cookies, &aex.db.0).await?.0;
+    req.update(user_id, &aex.db.0, aex.storage.0.as_ref())
+        .await
+        .map_err(|e| e.set_redirection(format!("/profile/{}", user_id)))?;
+
+    back_to_profile(user_id)
+}
+
+#[cfg(feature = "server")]
+async fn avatar_action(
+    axum::extract::State(aex): axum::extract::State<AppExtension>,
+    axum_extra::TypedHeader(cookies): axum_extra::TypedHeader<axum_extra::headers::Cookie>,
+    mut multipart: axum::extract::Multipart,
+) -> Result<axum::response::Response, AppError> {
+    use crate::resources::dto::profile::AvatarUpload;
+
+    let user_id = current_user(&cookies, &aex.db.0).await?.0;
+    let upload = AvatarUpload::from_multipart(&mut multipart)
+        .await
+        .map_err(|e| e.set_redirection(format!("/profile/{}", user_id)))?;
+    upload
+        .save(user_id, &aex.db.0, aex.storage.0.as_ref())
+        .await
+        .map_err(|e| e.set_redirection(format!("/profile/{}", user_id)))?;
+
+    back_to_profile(user_id)
+}
+
+#[cfg(feature = "server")]
+pub fn init_router(aex: AppExtension) -> axum::Router {
+    use crate::resources::dto::profile::AVATAR_RULES;
+    use axum::routing::post;
+
+    axum::Router::new()
+        .route("/profile/update_action", post(update_action))
+        .route(
+            "/profile/avatar_action",
+            post(avatar_action).layer(AVATAR_RULES.body_limit()),
+        )
+        .with_state(aex)
+}
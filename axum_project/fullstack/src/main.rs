@@ -34,11 +34,25 @@ fn main() {
         // fullstack_extension
         let fulex = fullstack_extension::AppExtension::init().await?;
 
+        // 서버가 꺼지면서 멈춘 가져오기 작업 정리
+        let interrupted = crate::resources::dto::catalog::fail_interrupted(&fulex.db.0).await?;
+        if interrupted > 0 {
+            tracing::warn!("Marked {} interrupted import jobs as failed", interrupted);
+        }
+
+        // 복구 기간이 지난 탈퇴 계정 정리
+        tokio::spawn(crate::resources::dto::account::purge_task(
+            fulex.db.0.clone(),
+            fulex.storage.0.clone(),
+        ));
+
         let api_routers = api::init_route(fulex.clone());
         let ws_routers = ws::init_router(fulex.clone());
         // let hello_router = hello_router(fulex.clone());
         let login_router = auth::init_router(fulex.clone());
         let front_router = front::init_router(fulex.clone());
+        let oauth_router = router::oauth::init_router(fulex.clone());
+        let scim_router = router::scim::init_router(fulex.clone());
 
         let app = Router::new()
             .merge(api_routers)
@@ -46,7 +60,24 @@ fn main() {
             // .merge(hello_router)
             .merge(login_router)
             .merge(front_router)
-            .merge(dioxus::server::router(app));
+            .merge(oauth_router)
+            .merge(scim_router)
+            // 업로드된 파일 (프로필 이미지, 상품 이미지 등)
+            // 파일 키에 임의값이 들어가서 같은 주소의 내용이 바뀌지 않으므로 오래 캐시함
+            .nest_service(
+                crate::utils::storage::MEDIA_URL,
+                tower::ServiceBuilder::new()
+                    .layer(tower_http::set_header::SetResponseHeaderLayer::overriding(
+                        axum::http::header::CACHE_CONTROL,
+                        axum::http::HeaderValue::from_static("public, max-age=31536000, immutable"),
+                    ))
+                    .service(tower_http::services::ServeDir::new(
+                        std::env::var("STORAGE_DIR").unwrap_or("./media".to_string()),
+                    )),
+            )
+            .merge(dioxus::server::router(app))
+            // 서버 함수에서 State로 DB를 꺼낼 수 있도록 함
+            .layer(axum::Extension(fulex));
 
         Ok(app)
         // let listener = tokio::net::TcpListener::bind("0.0.0.0:8000").await.unwrap();
@@ -0,0 +1,498 @@
+use std::{io::Write, time::Duration};
+
+use chrono::{NaiveDateTime, Utc};
+use reqwest::StatusCode;
+use sea_orm::{
+    ActiveValue::Set, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder,
+    TransactionTrait,
+};
+use serde::{Deserialize, Serialize};
+use tracing::{error, info};
+
+use crate::resources::dto::admin::{Provider, revoke_all};
+use crate::resources::dto::profile::{ProfileDto, avatar_keys};
+use crate::resources::entities::{
+    cart, cart_coupon, comment, coupon, coupon_redemption, order_item, orders, payment, post,
+    refresh_token, review, users,
+};
+use crate::utils::errors::{ApiError, AppError};
+use crate::utils::storage::{DynStorage, Storage};
+
+// 탈퇴 후 복구할 수 있는 기간(일), ACCOUNT_RESTORE_DAYS로 바꿀 수 있음
+pub const DEFAULT_RESTORE_DAYS: i64 = 30;
+// 정리 작업 주기
+const PURGE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+pub fn restore_window() -> chrono::Duration {
+    let days = std::env::var("ACCOUNT_RESTORE_DAYS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_RESTORE_DAYS);
+    chrono::Duration::days(days)
+}
+
+// 탈퇴 처리, 행은 남겨두고 모든 토큰을 끊음
+pub async fn soft_delete(user_id: i32, conn: &DatabaseConnection) -> Result<bool, AppError> {
+    let now = Utc::now().naive_utc();
+    let txn = conn.begin().await?;
+    let active = users::ActiveModel {
+        deleted_at: Set(Some(now)),
+        tokens_valid_after: Set(Some(now)),
+        ..Default::default()
+    };
+    let res = users::Entity::update_many()
+        .set(active)
+        .filter(users::Column::Id.eq(user_id))
+        .filter(users::Column::DeletedAt.is_null())
+        .exec(&txn)
+        .await?;
+    revoke_all(user_id, &txn).await?;
+    txn.commit().await?;
+
+    Ok(res.rows_affected > 0)
+}
+
+// 복구 기간 안이면 탈퇴를 취소함, 기간이 지났으면 false
+pub async fn restore(user_id: i32, conn: &DatabaseConnection) -> Result<bool, AppError> {
+    let deadline = Utc::now().naive_utc() - restore_window();
+    let active = users::ActiveModel {
+        deleted_at: Set(None),
+        ..Default::default()
+    };
+    let res = users::Entity::update_many()
+        .set(active)
+        .filter(users::Column::Id.eq(user_id))
+        .filter(users::Column::DeletedAt.gte(deadline))
+        .exec(conn)
+        .await?;
+
+    Ok(res.rows_affected > 0)
+}
+
+pub async fn restore_on_login(user_id: i32, conn: &DatabaseConnection) -> Result<(), AppError> {
+    let deleted = users::Entity::find_by_id(user_id)
+        .one(conn)
+        .await?
+        .is_some_and(|m| m.deleted_at.is_some());
+    if deleted && !restore(user_id, conn).await? {
+        return Err(AppError::new(
+            StatusCode::GONE,
+            "account is deleted",
+            Some("/".to_string()),
+        ));
+    }
+    Ok(())
+}
+
+// 복구 기간이 지난 계정을 지움, 토큰은 FK cascade로 같이 지워짐
+pub async fn purge_expired(
+    conn: &DatabaseConnection,
+    storage: &dyn Storage,
+) -> Result<usize, AppError> {
+    let deadline = Utc::now().naive_utc() - restore_window();
+    let expired = users::Entity::find()
+        .filter(users::Column::DeletedAt.lt(deadline))
+        .all(conn)
+        .await?;
+
+    for model in &expired {
+        users::Entity::delete_by_id(model.id).exec(conn).await?;
+        for key in avatar_keys(model.avatar_key.as_deref()) {
+            let _ = storage.delete(&key).await;
+        }
+    }
+
+    Ok(expired.len())
+}
+
+// 서버가 켜져있는 동안 주기적으로 정리 작업을 실행
+pub async fn purge_task(conn: DatabaseConnection, storage: DynStorage) {
+    let mut interval = tokio::time::interval(PURGE_INTERVAL);
+    loop {
+        interval.tick().await;
+        match purge_expired(&conn, storage.as_ref()).await {
+            Ok(0) => {}
+            Ok(count) => info!("Purged {} deleted accounts", count),
+            Err(e) => error!("Can't purge deleted accounts: {}", e),
+        }
+    }
+}
+
+#[derive(utoipa::ToSchema, Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    #[default]
+    Json,
+    // account.json과 아바타 원본 파일
+    Zip,
+}
+
+#[derive(utoipa::IntoParams, Deserialize, Debug)]
+pub struct ExportReq {
+    #[serde(default)]
+    pub format: ExportFormat,
+}
+
+#[derive(utoipa::ToSchema, Serialize, Deserialize, Debug)]
+pub struct ExportAccount {
+    pub id: i32,
+    pub username: String,
+    pub role: String,
+    pub status: String,
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: NaiveDateTime,
+    #[schema(value_type = String, format = DateTime)]
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(utoipa::ToSchema, Serialize, Deserialize, Debug)]
+pub struct ExportProvider {
+    pub provider: Provider,
+    // 소셜 로그인 제공자의 계정 식별자
+    pub subject: String,
+}
+
+// 토큰 값은 내려주지 않음
+#[derive(utoipa::ToSchema, Serialize, Deserialize, Debug)]
+pub struct ExportSession {
+    pub client_id: Option<String>,
+    pub scope: Option<String>,
+    #[schema(value_type = String, format = DateTime)]
+    pub expires_at: NaiveDateTime,
+}
+
+#[derive(utoipa::ToSchema, Serialize, Deserialize, Debug)]
+pub struct ExportOrderItem {
+    pub product_id: Option<i32>,
+    pub variant_id: Option<i32>,
+    pub sku: Option<String>,
+    pub title: String,
+    pub unit_price: i64,
+    pub quantity: i32,
+}
+
+#[derive(utoipa::ToSchema, Serialize, Deserialize, Debug)]
+pub struct ExportOrder {
+    pub id: i32,
+    pub status: String,
+    pub currency: String,
+    pub total: i64,
+    pub discount: i64,
+    pub coupon_code: Option<String>,
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: NaiveDateTime,
+    pub items: Vec<ExportOrderItem>,
+}
+
+#[derive(utoipa::ToSchema, Serialize, Deserialize, Debug)]
+pub struct ExportPayment {
+    pub order_id: i32,
+    pub provider: String,
+    pub provider_ref: String,
+    pub status: String,
+    pub currency: String,
+    pub amount: i64,
+    pub failure_reason: Option<String>,
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(utoipa::ToSchema, Serialize, Deserialize, Debug)]
+pub struct ExportCartItem {
+    pub product_id: i32,
+    pub variant_id: Option<i32>,
+    pub quantity: i32,
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(utoipa::ToSchema, Serialize, Deserialize, Debug)]
+pub struct ExportCouponRedemption {
+    pub code: Option<String>,
+    pub order_id: i32,
+    pub discount: i64,
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(utoipa::ToSchema, Serialize, Deserialize, Debug)]
+pub struct ExportReview {
+    pub product_id: i32,
+    pub rating: i32,
+    pub body: String,
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: NaiveDateTime,
+    #[schema(value_type = String, format = DateTime)]
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(utoipa::ToSchema, Serialize, Deserialize, Debug)]
+pub struct ExportPost {
+    pub id: i32,
+    pub title: String,
+    pub slug: String,
+    pub body: String,
+    pub status: String,
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub published_at: Option<NaiveDateTime>,
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: NaiveDateTime,
+    #[schema(value_type = String, format = DateTime)]
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(utoipa::ToSchema, Serialize, Deserialize, Debug)]
+pub struct ExportComment {
+    pub id: i32,
+    pub post_id: i32,
+    pub parent_id: Option<i32>,
+    pub body: String,
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: NaiveDateTime,
+    #[schema(value_type = String, format = DateTime)]
+    pub updated_at: NaiveDateTime,
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub deleted_at: Option<NaiveDateTime>,
+}
+
+// 이 서버가 유저에 대해 저장하는 모든 정보
+#[derive(utoipa::ToSchema, Serialize, Deserialize, Debug)]
+pub struct AccountExport {
+    #[schema(value_type = String, format = DateTime)]
+    pub exported_at: NaiveDateTime,
+    pub account: ExportAccount,
+    pub profile: ProfileDto,
+    pub providers: Vec<ExportProvider>,
+    pub sessions: Vec<ExportSession>,
+    pub orders: Vec<ExportOrder>,
+    pub payments: Vec<ExportPayment>,
+    pub cart: Vec<ExportCartItem>,
+    // 장바구니에 적용해둔 쿠폰 코드
+    pub cart_coupon: Option<String>,
+    pub coupon_redemptions: Vec<ExportCouponRedemption>,
+    pub reviews: Vec<ExportReview>,
+    pub posts: Vec<ExportPost>,
+    pub comments: Vec<ExportComment>,
+}
+
+impl AccountExport {
+    pub async fn collect(
+        user_id: i32,
+        conn: &DatabaseConnection,
+        storage: &dyn Storage,
+    ) -> Result<(Self, Option<String>), ApiError> {
+        let model = users::Entity::find_by_id(user_id)
+            .one(conn)
+            .await?
+            .ok_or(ApiError::new(
+                StatusCode::NOT_FOUND,
+                "not_found",
+                "user is not found",
+            ))?;
+        let sessions = refresh_token::Entity::find()
+            .filter(refresh_token::Column::UserId.eq(user_id))
+            .order_by_asc(refresh_token::Column::ExpiresAt)
+            .all(conn)
+            .await?;
+        let orders = orders::Entity::find()
+            .filter(orders::Column::UserId.eq(user_id))
+            .order_by_asc(orders::Column::Id)
+            .find_with_related(order_item::Entity)
+            .all(conn)
+            .await?;
+        let payments = payment::Entity::find()
+            .filter(payment::Column::OrderId.is_in(orders.iter().map(|(order, _)| order.id)))
+            .order_by_asc(payment::Column::Id)
+            .all(conn)
+            .await?;
+        let cart = cart::Entity::find()
+            .filter(cart::Column::UserId.eq(user_id))
+            .order_by_asc(cart::Column::Id)
+            .all(conn)
+            .await?;
+        let cart_coupon = cart_coupon::Entity::find_by_id(user_id)
+            .find_also_related(coupon::Entity)
+            .one(conn)
+            .await?
+            .and_then(|(_, coupon)| coupon);
+        let coupon_redemptions = coupon_redemption::Entity::find()
+            .filter(coupon_redemption::Column::UserId.eq(user_id))
+            .order_by_asc(coupon_redemption::Column::Id)
+            .find_also_related(coupon::Entity)
+            .all(conn)
+            .await?;
+        let reviews = review::Entity::find()
+            .filter(review::Column::UserId.eq(user_id))
+            .order_by_asc(review::Column::Id)
+            .all(conn)
+            .await?;
+        let posts = post::Entity::find()
+            .filter(post::Column::AuthorId.eq(user_id))
+            .order_by_asc(post::Column::Id)
+            .all(conn)
+            .await?;
+        let comments = comment::Entity::find()
+            .filter(comment::Column::UserId.eq(user_id))
+            .order_by_asc(comment::Column::Id)
+            .all(conn)
+            .await?;
+
+        let providers = [
+            (Provider::Google, &model.google_oauth),
+            (Provider::Kakao, &model.kakao_oauth),
+            (Provider::Github, &model.git_hub_oauth),
+            (Provider::Naver, &model.naver_oauth),
+        ]
+        .into_iter()
+        .filter_map(|(provider, subject)| {
+            subject
+                .clone()
+                .map(|subject| ExportProvider { provider, subject })
+        })
+        .collect();
+
+        let avatar_key = model.avatar_key.clone();
+        let export = AccountExport {
+            exported_at: Utc::now().naive_utc(),
+            account: ExportAccount {
+                id: model.id,
+                username: model.username.clone(),
+                role: model.role.clone(),
+                status: model.status.clone(),
+                created_at: model.created_at,
+                updated_at: model.updated_at,
+            },
+            profile: ProfileDto::from_model(model, storage),
+            providers,
+            sessions: sessions
+                .into_iter()
+                .map(|m| ExportSession {
+                    client_id: m.client_id,
+                    scope: m.scope,
+                    expires_at: m.expires_at,
+                })
+                .collect(),
+            orders: orders
+                .into_iter()
+                .map(|(order, items)| ExportOrder {
+                    id: order.id,
+                    status: order.status,
+                    currency: order.currency,
+                    total: order.total,
+                    discount: order.discount,
+                    coupon_code: order.coupon_code,
+                    created_at: order.created_at,
+                    items: items
+                        .into_iter()
+                        .map(|m| ExportOrderItem {
+                            product_id: m.product_id,
+                            variant_id: m.variant_id,
+                            sku: m.sku,
+                            title: m.title,
+                            unit_price: m.unit_price,
+                            quantity: m.quantity,
+                        })
+                        .collect(),
+                })
+                .collect(),
+            payments: payments
+                .into_iter()
+                .map(|m| ExportPayment {
+                    order_id: m.order_id,
+                    provider: m.provider,
+                    provider_ref: m.provider_ref,
+                    status: m.status,
+                    currency: m.currency,
+                    amount: m.amount,
+                    failure_reason: m.failure_reason,
+                    created_at: m.created_at,
+                })
+                .collect(),
+            cart: cart
+                .into_iter()
+                .map(|m| ExportCartItem {
+                    product_id: m.product_id,
+                    variant_id: m.variant_id,
+                    quantity: m.quantity,
+                    created_at: m.created_at,
+                })
+                .collect(),
+            cart_coupon: cart_coupon.map(|c| c.code),
+            coupon_redemptions: coupon_redemptions
+                .into_iter()
+                .map(|(m, coupon)| ExportCouponRedemption {
+                    code: coupon.map(|c| c.code),
+                    order_id: m.order_id,
+                    discount: m.discount,
+                    created_at: m.created_at,
+                })
+                .collect(),
+            reviews: reviews
+                .into_iter()
+                .map(|m| ExportReview {
+                    product_id: m.product_id,
+                    rating: m.rating,
+                    body: m.body,
+                    created_at: m.created_at,
+                    updated_at: m.updated_at,
+                })
+                .collect(),
+            posts: posts
+                .into_iter()
+                .map(|m| ExportPost {
+                    id: m.id,
+                    title: m.title,
+                    slug: m.slug,
+                    body: m.body,
+                    status: m.status,
+                    published_at: m.published_at,
+                    created_at: m.created_at,
+                    updated_at: m.updated_at,
+                })
+                .collect(),
+            comments: comments
+                .into_iter()
+                .map(|m| ExportComment {
+                    id: m.id,
+                    post_id: m.post_id,
+                    parent_id: m.parent_id,
+                    body: m.body,
+                    created_at: m.created_at,
+                    updated_at: m.updated_at,
+                    deleted_at: m.deleted_at,
+                })
+                .collect(),
+        };
+
+        Ok((export, avatar_key))
+    }
+
+    // account.json과 avatar/ 아래에 저장된 아바타 파일을 묶음
+    pub async fn to_zip(
+        &self,
+        avatar_key: Option<&str>,
+        storage: &dyn Storage,
+    ) -> Result<Vec<u8>, AppError> {
+        use zip::{ZipWriter, write::SimpleFileOptions};
+
+        let json = serde_json::to_vec_pretty(self).map_err(AppError::any_t_error)?;
+        let mut files = vec![("account.json".to_string(), json)];
+        for key in avatar_keys(avatar_key) {
+            // 파일이 없어졌다면 아바타만 빼고 내려줌
+            if let Ok(bytes) = storage.get(&key).await {
+                let name = key.rsplit('/').next().unwrap_or(&key);
+                files.push((format!("avatar/{}", name), bytes));
+            }
+        }
+
+        let mut zip = ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        for (name, bytes) in files {
+            zip.start_file(name, SimpleFileOptions::default())
+                .map_err(AppError::any_t_error)?;
+            zip.write_all(&bytes).map_err(AppError::any_t_error)?;
+        }
+        let cursor = zip.finish().map_err(AppError::any_t_error)?;
+
+        Ok(cursor.into_inner())
+    }
+}
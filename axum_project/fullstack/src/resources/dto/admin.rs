@@ -0,0 +1,301 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use reqwest::StatusCode;
+use sea_orm::{
+    ActiveValue::Set, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, TransactionTrait,
+};
+use serde::{Deserialize, Serialize};
+use validate_dto_macro::validate_dto;
+
+use crate::resources::dto::page::Page;
+use crate::resources::dto::query::like_escape;
+use crate::resources::entities::{authorization_code, refresh_token, users};
+use crate::utils::errors::ApiError;
+use crate::utils::storage::Storage;
+
+pub const ROLE_USER: &str = "user";
+// 상품 이미지 등 카탈로그를 관리할 수 있음
+pub const ROLE_EDITOR: &str = "editor";
+// 블로그 글을 쓸 수 있음
+pub const ROLE_AUTHOR: &str = "author";
+pub const ROLE_ADMIN: &str = "admin";
+
+// 권한은 토큰이 아니라 DB에서 확인하므로 role을 바꾸면 바로 반영됨
+pub async fn has_role(
+    user_id: i32,
+    roles: &[&str],
+    conn: &DatabaseConnection,
+) -> Result<bool, sea_orm::DbErr> {
+    Ok(users::Entity::find_by_id(user_id)
+        .one(conn)
+        .await?
+        .is_some_and(|m| roles.contains(&m.role.as_str())))
+}
+
+// users.status 컬럼 값
+#[derive(utoipa::ToSchema, Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum UserStatus {
+    Active,
+    Suspended,
+}
+impl UserStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UserStatus::Active => "active",
+            UserStatus::Suspended => "suspended",
+        }
+    }
+}
+
+#[derive(utoipa::ToSchema, Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Provider {
+    Google,
+    Kakao,
+    Github,
+    Naver,
+}
+impl Provider {
+    fn column(&self) -> users::Column {
+        match self {
+            Provider::Google => users::Column::GoogleOauth,
+            Provider::Kakao => users::Column::KakaoOauth,
+            Provider::Github => users::Column::GitHubOauth,
+            Provider::Naver => users::Column::NaverOauth,
+        }
+    }
+}
+
+// 관리자에게만 보여주는 유저 정보, 소셜 계정 식별자 대신 연결 여부만 내려줌
+#[derive(utoipa::ToSchema, Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AdminUserDto {
+    pub id: i32,
+    pub username: String,
+    pub display_name: Option<String>,
+    pub role: String,
+    pub status: String,
+    pub providers: Vec<Provider>,
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: NaiveDateTime,
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub tokens_valid_after: Option<NaiveDateTime>,
+    // 탈퇴 시각, 복구 기간이 지나면 정리 작업이 지움
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub deleted_at: Option<NaiveDateTime>,
+}
+
+impl From<users::Model> for AdminUserDto {
+    fn from(value: users::Model) -> Self {
+        let providers = [
+            (Provider::Google, &value.google_oauth),
+            (Provider::Kakao, &value.kakao_oauth),
+            (Provider::Github, &value.git_hub_oauth),
+            (Provider::Naver, &value.naver_oauth),
+        ]
+        .into_iter()
+        .filter(|(_, id)| id.is_some())
+        .map(|(provider, _)| provider)
+        .collect();
+
+        AdminUserDto {
+            id: value.id,
+            username: value.username,
+            display_name: value.display_name,
+            role: value.role,
+            status: value.status,
+            providers,
+            created_at: value.created_at,
+            tokens_valid_after: value.tokens_valid_after,
+            deleted_at: value.deleted_at,
+        }
+    }
+}
+
+// 모든 조건은 AND로 묶임, 날짜는 UTC 기준 (created_to 당일 포함)
+#[validate_dto(params)]
+#[derive(utoipa::IntoParams, utoipa::ToSchema, Serialize, Deserialize, Debug, Default)]
+pub struct AdminUserQuery {
+    // 해당 소셜 계정이 연결된 유저만
+    pub provider: Option<Provider>,
+    pub status: Option<UserStatus>,
+    // true면 탈퇴한 유저만, false면 탈퇴하지 않은 유저만
+    pub deleted: Option<bool>,
+    #[schema(value_type = Option<String>, format = Date)]
+    #[param(value_type = Option<String>, format = Date)]
+    pub created_from: Option<NaiveDate>,
+    #[schema(value_type = Option<String>, format = Date)]
+    #[param(value_type = Option<String>, format = Date)]
+    pub created_to: Option<NaiveDate>,
+    #[validate(length(min = 1, max = 100))]
+    pub username: Option<String>,
+    // 기본 20, 최대 100
+    #[validate(range(min = 1, max = 100))]
+    pub limit: Option<u64>,
+    pub cursor: Option<String>,
+    pub total: Option<bool>,
+}
+
+impl AdminUserQuery {
+    pub async fn search(&self, conn: &DatabaseConnection) -> Result<Page<AdminUserDto>, ApiError> {
+        let mut select = users::Entity::find();
+        if let Some(provider) = self.provider {
+            select = select.filter(provider.column().is_not_null());
+        }
+        if let Some(status) = self.status {
+            select = select.filter(users::Column::Status.eq(status.as_str()));
+        }
+        if let Some(deleted) = self.deleted {
+            select = select.filter(if deleted {
+                users::Column::DeletedAt.is_not_null()
+            } else {
+                users::Column::DeletedAt.is_null()
+            });
+        }
+        if let Some(from) = self.created_from {
+            select = select.filter(users::Column::CreatedAt.gte(from.and_time(Default::default())));
+        }
+        if let Some(to) = self.created_to.and_then(|to| to.succ_opt()) {
+            select = select.filter(users::Column::CreatedAt.lt(to.and_time(Default::default())));
+        }
+        if let Some(username) = &self.username {
+            select =
+                select.filter(users::Column::Username.like(format!("%{}%", like_escape(username))));
+        }
+
+        Ok(Page::by_id(
+            select,
+            users::Column::Id,
+            |m| m.id,
+            self.cursor.as_deref(),
+            self.limit,
+            self.total.unwrap_or(false),
+            conn,
+        )
+        .await?)
+    }
+}
+
+fn not_found() -> ApiError {
+    ApiError::new(StatusCode::NOT_FOUND, "not_found", "user is not found")
+}
+
+// 관리자가 자기 자신을 정지하거나 삭제해서 관리자가 없어지는 것을 막음
+pub fn not_self(admin_id: i32, user_id: i32) -> Result<(), ApiError> {
+    if admin_id == user_id {
+        return Err(ApiError::new(
+            StatusCode::BAD_REQUEST,
+            "invalid_target",
+            "can't apply to your own account",
+        ));
+    }
+    Ok(())
+}
+
+// 상태를 바꾸고, 정지할 때는 발급된 토큰을 모두 끊음
+pub async fn set_status(
+    user_id: i32,
+    status: UserStatus,
+    conn: &DatabaseConnection,
+) -> Result<AdminUserDto, ApiError> {
+    let txn = conn.begin().await?;
+    let mut active = users::ActiveModel {
+        status: Set(status.as_str().to_string()),
+        ..Default::default()
+    };
+    if status == UserStatus::Suspended {
+        active.tokens_valid_after = Set(Some(chrono::Utc::now().naive_utc()));
+        revoke_all(user_id, &txn).await?;
+    }
+    let model = users::Entity::update_many()
+        .set(active)
+        .filter(users::Column::Id.eq(user_id))
+        .exec_with_returning(&txn)
+        .await?
+        .pop()
+        .ok_or(not_found())?;
+    txn.commit().await?;
+
+    Ok(model.into())
+}
+
+// 지금까지 발급된 access 토큰은 tokens_valid_after로 거절하고
+// refresh 토큰과 아직 교환하지 않은 인가 코드는 지움
+pub async fn force_logout(
+    user_id: i32,
+    conn: &DatabaseConnection,
+) -> Result<AdminUserDto, ApiError> {
+    let txn = conn.begin().await?;
+    let active = users::ActiveModel {
+        tokens_valid_after: Set(Some(chrono::Utc::now().naive_utc())),
+        ..Default::default()
+    };
+    let model = users::Entity::update_many()
+        .set(active)
+        .filter(users::Column::Id.eq(user_id))
+        .exec_with_returning(&txn)
+        .await?
+        .pop()
+        .ok_or(not_found())?;
+    revoke_all(user_id, &txn).await?;
+    txn.commit().await?;
+
+    Ok(model.into())
+}
+
+pub(crate) async fn revoke_all(
+    user_id: i32,
+    conn: &impl sea_orm::ConnectionTrait,
+) -> Result<(), sea_orm::DbErr> {
+    refresh_token::Entity::delete_many()
+        .filter(refresh_token::Column::UserId.eq(user_id))
+        .exec(conn)
+        .await?;
+    authorization_code::Entity::delete_many()
+        .filter(authorization_code::Column::UserId.eq(user_id))
+        .exec(conn)
+        .await?;
+    Ok(())
+}
+
+// 복구 기간 안의 탈퇴 계정만 되살림
+pub async fn restore_user(
+    user_id: i32,
+    conn: &DatabaseConnection,
+) -> Result<AdminUserDto, ApiError> {
+    use crate::resources::dto::account::restore;
+
+    if !restore(user_id, conn).await? {
+        return Err(ApiError::new(
+            StatusCode::CONFLICT,
+            "not_restorable",
+            "user is not deleted or restore window has passed",
+        ));
+    }
+    let model = users::Entity::find_by_id(user_id)
+        .one(conn)
+        .await?
+        .ok_or(not_found())?;
+
+    Ok(model.into())
+}
+
+// 토큰과 인가 코드는 FK cascade로 같이 지워짐, 아바타 파일은 직접 지움
+pub async fn hard_delete(
+    user_id: i32,
+    conn: &DatabaseConnection,
+    storage: &dyn Storage,
+) -> Result<(), ApiError> {
+    use crate::resources::dto::profile::avatar_keys;
+
+    let model = users::Entity::find_by_id(user_id)
+        .one(conn)
+        .await?
+        .ok_or(not_found())?;
+    users::Entity::delete_by_id(user_id).exec(conn).await?;
+
+    for key in avatar_keys(model.avatar_key.as_deref()) {
+        // 파일이 남아도 유저는 이미 지워졌으므로 무시함
+        let _ = storage.delete(&key).await;
+    }
+    Ok(())
+}
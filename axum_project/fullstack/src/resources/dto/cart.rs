@@ -0,0 +1,249 @@
+use std::collections::HashMap;
+
+use reqwest::StatusCode;
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{
+    ActiveValue::Set, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, SqlErr,
+};
+use serde::{Deserialize, Serialize};
+use validate_dto_macro::validate_dto;
+
+use crate::resources::dto::coupon::{
+    AppliedCouponDto, PriceLine, apply_to_cart, cart_coupon_of, price_coupon, remove_from_cart,
+};
+use crate::resources::dto::money::{Money, MoneyError};
+use crate::resources::dto::product_variant::{find_for_product, unit_price};
+use crate::resources::entities::{cart, product, product_variant};
+use crate::utils::errors::ApiError;
+
+#[derive(utoipa::ToSchema, Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CartItemDto {
+    pub product_id: i32,
+    // 변형이 있는 상품은 고른 변형
+    pub variant_id: Option<i32>,
+    // 변형의 sku, 변형이 없으면 상품의 sku
+    pub sku: Option<String>,
+    pub title: String,
+    // 지금 가격 (변형 가격이 있으면 변형 가격), 주문할 때 다시 읽어서 주문에 복사함
+    pub unit_price: Money,
+    pub quantity: i32,
+    pub line_total: Money,
+}
+
+#[derive(utoipa::ToSchema, Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CartDto {
+    // 담은 순서대로
+    pub items: Vec<CartItemDto>,
+    // 할인 전 합계, 비었거나 담은 뒤에 상품 통화가 바뀌어서 통화가 섞여 있으면 없음
+    pub subtotal: Option<Money>,
+    pub coupon: Option<AppliedCouponDto>,
+    // 쿠폰 할인을 뺀 합계
+    pub total: Option<Money>,
+}
+
+#[validate_dto]
+#[derive(utoipa::ToSchema, Serialize, Deserialize, Debug)]
+pub struct CartItemReq {
+    // 담긴 수량을 이 값으로 바꿈
+    #[validate(range(min = 1, max = 999))]
+    pub quantity: i32,
+}
+
+fn product_not_found() -> ApiError {
+    ApiError::new(StatusCode::NOT_FOUND, "not_found", "product is not found")
+}
+
+// 담은 상품과 쿠폰 계산에 쓸 카테고리별 금액
+async fn load_lines(
+    user_id: i32,
+    conn: &DatabaseConnection,
+) -> Result<(Vec<CartItemDto>, Vec<PriceLine>), ApiError> {
+    let rows = cart::Entity::find()
+        .find_also_related(product::Entity)
+        .filter(cart::Column::UserId.eq(user_id))
+        .order_by_asc(cart::Column::Id)
+        .all(conn)
+        .await?;
+    let variant_ids: Vec<i32> = rows
+        .iter()
+        .filter_map(|(item, _)| item.variant_id)
+        .collect();
+    let variants: HashMap<i32, product_variant::Model> = if variant_ids.is_empty() {
+        HashMap::new()
+    } else {
+        product_variant::Entity::find()
+            .filter(product_variant::Column::Id.is_in(variant_ids))
+            .all(conn)
+            .await?
+            .into_iter()
+            .map(|v| (v.id, v))
+            .collect()
+    };
+
+    let mut items = Vec::with_capacity(rows.len());
+    let mut lines = Vec::with_capacity(rows.len());
+    for (item, product) in rows {
+        let Some(product) = product else {
+            continue;
+        };
+        let variant = item.variant_id.and_then(|id| variants.get(&id));
+        let unit_price = unit_price(&product, variant);
+        let line_total = unit_price.times(item.quantity)?;
+        lines.push(PriceLine {
+            category_id: product.category_id,
+            line_total,
+        });
+        items.push(CartItemDto {
+            product_id: product.id,
+            variant_id: item.variant_id,
+            sku: variant.map(|v| v.sku.clone()).or(product.sku),
+            title: product.title,
+            unit_price,
+            quantity: item.quantity,
+            line_total,
+        });
+    }
+
+    Ok((items, lines))
+}
+
+impl CartDto {
+    // 걸어둔 쿠폰을 지금 장바구니로 다시 계산함, 쓸 수 없게 됐으면 할인 없이 이유만 보여줌
+    pub async fn get(user_id: i32, conn: &DatabaseConnection) -> Result<Self, ApiError> {
+        let (items, lines) = load_lines(user_id, conn).await?;
+        let mut totals = lines.iter().map(|l| l.line_total);
+        // 통화가 섞여 있으면 합계를 보여주지 않음
+        let subtotal = match totals
+            .next()
+            .map(|first| totals.try_fold(first, |acc, line| acc.checked_add(&line)))
+        {
+            Some(Ok(subtotal)) => Some(subtotal),
+            Some(Err(MoneyError::CurrencyMismatch)) | None => None,
+            Some(Err(e)) => return Err(e.into()),
+        };
+
+        let coupon = match cart_coupon_of(user_id, false, conn).await? {
+            Some(coupon) => {
+                let priced = price_coupon(&coupon, user_id, &lines, conn).await;
+                Some(AppliedCouponDto {
+                    code: coupon.code,
+                    discount: priced.as_ref().ok().copied(),
+                    error: priced.err().map(|e| e.message),
+                })
+            }
+            None => None,
+        };
+        let total = match coupon.as_ref().and_then(|c| c.discount) {
+            Some(discount) => subtotal.and_then(|s| s.checked_sub(&discount).ok()),
+            None => subtotal,
+        };
+
+        Ok(CartDto {
+            items,
+            subtotal,
+            coupon,
+            total,
+        })
+    }
+}
+
+impl CartItemReq {
+    // 없으면 담고 있으면 수량을 바꿈, 같은 상품이라도 변형이 다르면 따로 담김
+    // 변형이 있는 상품은 변형을 골라야함 (422)
+    // 한 장바구니에는 같은 통화의 상품만 담을 수 있음 (409)
+    pub async fn put(
+        self,
+        user_id: i32,
+        product_id: i32,
+        variant_id: Option<i32>,
+        conn: &DatabaseConnection,
+    ) -> Result<CartDto, ApiError> {
+        let product = product::Entity::find_by_id(product_id)
+            .one(conn)
+            .await?
+            .ok_or_else(product_not_found)?;
+        find_for_product(product_id, variant_id, conn).await?;
+        let mixed = cart::Entity::find()
+            .find_also_related(product::Entity)
+            .filter(cart::Column::UserId.eq(user_id))
+            .filter(cart::Column::ProductId.ne(product_id))
+            .all(conn)
+            .await?
+            .into_iter()
+            .any(|(_, p)| p.is_some_and(|p| p.currency != product.currency));
+        if mixed {
+            return Err(ApiError::new(
+                StatusCode::CONFLICT,
+                "currency_mismatch",
+                "cart already has products in another currency",
+            ));
+        }
+
+        cart::Entity::insert(cart::ActiveModel {
+            user_id: Set(user_id),
+            product_id: Set(product_id),
+            variant_id: Set(variant_id),
+            quantity: Set(self.quantity),
+            ..Default::default()
+        })
+        .on_conflict(
+            OnConflict::columns([
+                cart::Column::UserId,
+                cart::Column::ProductId,
+                cart::Column::VariantId,
+            ])
+            .update_column(cart::Column::Quantity)
+            .to_owned(),
+        )
+        .exec(conn)
+        .await
+        .map_err(|e| match e.sql_err() {
+            Some(SqlErr::ForeignKeyConstraintViolation(_)) => product_not_found(),
+            _ => e.into(),
+        })?;
+
+        CartDto::get(user_id, conn).await
+    }
+}
+
+pub async fn remove_item(
+    user_id: i32,
+    product_id: i32,
+    variant_id: Option<i32>,
+    conn: &DatabaseConnection,
+) -> Result<CartDto, ApiError> {
+    let delete = cart::Entity::delete_many()
+        .filter(cart::Column::UserId.eq(user_id))
+        .filter(cart::Column::ProductId.eq(product_id));
+    let delete = match variant_id {
+        Some(id) => delete.filter(cart::Column::VariantId.eq(id)),
+        None => delete.filter(cart::Column::VariantId.is_null()),
+    };
+    delete.exec(conn).await?;
+    CartDto::get(user_id, conn).await
+}
+
+// 걸어둔 쿠폰도 같이 뺌
+pub async fn clear(user_id: i32, conn: &impl sea_orm::ConnectionTrait) -> Result<(), ApiError> {
+    cart::Entity::delete_many()
+        .filter(cart::Column::UserId.eq(user_id))
+        .exec(conn)
+        .await?;
+    remove_from_cart(user_id, conn).await
+}
+
+// 지금 장바구니에 쓸 수 없는 쿠폰이면 422 (coupon_not_applicable)
+pub async fn apply_coupon(
+    user_id: i32,
+    code: &str,
+    conn: &DatabaseConnection,
+) -> Result<CartDto, ApiError> {
+    let (_, lines) = load_lines(user_id, conn).await?;
+    apply_to_cart(user_id, code, &lines, conn).await?;
+    CartDto::get(user_id, conn).await
+}
+
+pub async fn remove_coupon(user_id: i32, conn: &DatabaseConnection) -> Result<CartDto, ApiError> {
+    remove_from_cart(user_id, conn).await?;
+    CartDto::get(user_id, conn).await
+}
@@ -0,0 +1,970 @@
+use std::collections::HashMap;
+use std::io::BufRead;
+use std::path::{Path, PathBuf};
+
+use axum::body::Body;
+use chrono::NaiveDateTime;
+use futures_util::{Stream, StreamExt};
+use reqwest::StatusCode;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, DatabaseConnection, DatabaseTransaction,
+    EntityTrait, QueryFilter, QueryOrder, QuerySelect, TransactionTrait,
+};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use tokio::io::AsyncWriteExt;
+use tokio::sync::mpsc;
+use tracing::error;
+use validate_dto_macro::validate_dto;
+
+use crate::resources::dto::category::{slugify, subtree_ids};
+use crate::resources::dto::money::Currency;
+use crate::resources::dto::page::Page;
+use crate::resources::dto::product::record_price;
+use crate::resources::dto::product_variant::has_price_overrides;
+use crate::resources::entities::{category, import_job, product};
+use crate::utils::errors::{ApiError, AppError};
+use crate::utils::validate::{Validate, ValidationErrors};
+
+// 가져오기 파일 최대 크기
+pub const IMPORT_MAX_BYTES: usize = 50 * 1024 * 1024;
+// 작업 기록에 남길 최대 오류 수, 오류 행 수는 따로 셈
+pub const MAX_REPORTED_ERRORS: usize = 1000;
+// 상품 내보내기는 이만큼씩 읽어서 바로 내려보냄
+const EXPORT_CHUNK: u64 = 500;
+
+#[derive(utoipa::ToSchema, Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportKind {
+    Products,
+    Categories,
+}
+impl ImportKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ImportKind::Products => "products",
+            ImportKind::Categories => "categories",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        [ImportKind::Products, ImportKind::Categories]
+            .into_iter()
+            .find(|k| k.as_str() == value)
+    }
+}
+
+#[derive(utoipa::ToSchema, Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum FileFormat {
+    // 첫 줄은 헤더
+    #[default]
+    Csv,
+    // 한 줄에 JSON 객체 하나 (JSON Lines)
+    Jsonl,
+}
+impl FileFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FileFormat::Csv => "csv",
+            FileFormat::Jsonl => "jsonl",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        [FileFormat::Csv, FileFormat::Jsonl]
+            .into_iter()
+            .find(|f| f.as_str() == value)
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            FileFormat::Csv => "text/csv; charset=utf-8",
+            FileFormat::Jsonl => "application/x-ndjson",
+        }
+    }
+}
+
+// import_job.status 컬럼 값
+// succeeded: 오류 없이 끝남 (dry_run이 아니면 반영됨), failed: 오류가 있어서 아무것도 반영되지 않음
+#[derive(utoipa::ToSchema, Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum JobStatus {
+    Pending,
+    Running,
+    Succeeded,
+    Failed,
+}
+impl JobStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JobStatus::Pending => "pending",
+            JobStatus::Running => "running",
+            JobStatus::Succeeded => "succeeded",
+            JobStatus::Failed => "failed",
+        }
+    }
+}
+
+// 상품 파일의 한 행, 내보내기도 같은 모양
+#[validate_dto]
+#[derive(utoipa::ToSchema, Serialize, Deserialize, Debug, Clone)]
+pub struct ProductRow {
+    // 이 값으로 상품을 찾아서 있으면 덮어쓰고 없으면 만듦
+    #[validate(length(min = 1, max = 64))]
+    pub sku: String,
+    #[validate(length(min = 1, max = 100))]
+    pub title: String,
+    // 통화 최소 단위 금액
+    #[validate(range(min = 0, max = 1_000_000_000_000_i64))]
+    pub price: i64,
+    // KRW, USD, EUR, JPY
+    pub currency: String,
+    // 카테고리 slug, 없으면 카테고리 이름으로 찾음
+    #[validate(length(min = 1, max = 60))]
+    pub category: String,
+}
+impl ProductRow {
+    pub const HEADERS: [&'static str; 5] = ["sku", "title", "price", "currency", "category"];
+}
+
+// 카테고리 파일의 한 행, 부모는 파일에서 먼저 나오거나 이미 있어야함
+#[validate_dto]
+#[derive(utoipa::ToSchema, Serialize, Deserialize, Debug, Clone)]
+pub struct CategoryRow {
+    // 이 값으로 카테고리를 찾아서 있으면 덮어쓰고 없으면 만듦
+    #[validate(length(min = 1, max = 60))]
+    pub slug: String,
+    #[validate(length(min = 1, max = 50))]
+    pub name: String,
+    // 부모 slug, 비우면 최상위
+    pub parent: Option<String>,
+    #[validate(length(max = 500))]
+    pub description: Option<String>,
+    pub sort_order: Option<i32>,
+}
+impl CategoryRow {
+    pub const HEADERS: [&'static str; 5] = ["slug", "name", "parent", "description", "sort_order"];
+}
+
+#[validate_dto(params)]
+#[derive(utoipa::IntoParams, utoipa::ToSchema, Serialize, Deserialize, Debug)]
+pub struct ImportQuery {
+    // 기본 csv
+    pub format: Option<FileFormat>,
+    // true면 검사만 하고 반영하지 않음
+    pub dry_run: Option<bool>,
+    // 상품 가져오기에서 없는 카테고리 slug를 만나면 최상위 카테고리로 만듦
+    pub create_categories: Option<bool>,
+}
+
+#[derive(utoipa::IntoParams, Deserialize, Debug)]
+pub struct ExportQuery {
+    #[serde(default)]
+    pub format: FileFormat,
+}
+
+#[validate_dto(params)]
+#[derive(utoipa::IntoParams, utoipa::ToSchema, Serialize, Deserialize, Debug)]
+pub struct ImportJobListQuery {
+    // 기본 20, 최대 100
+    #[validate(range(min = 1, max = 100))]
+    pub limit: Option<u64>,
+    pub cursor: Option<String>,
+}
+
+// row는 파일의 줄 번호 (CSV는 헤더가 1번 줄)
+#[derive(utoipa::ToSchema, Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RowError {
+    pub row: usize,
+    pub field: String,
+    // parse, length, range, currency, not_found, invalid_parent ...
+    pub code: String,
+    pub message: String,
+}
+
+#[derive(utoipa::ToSchema, Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ImportJobDto {
+    pub id: i32,
+    pub user_id: Option<i32>,
+    pub kind: ImportKind,
+    pub format: FileFormat,
+    pub dry_run: bool,
+    pub create_categories: bool,
+    // pending, running, succeeded, failed
+    pub status: String,
+    pub total_rows: i32,
+    // dry_run이면 만들어지거나 바뀌었을 행 수
+    pub created_rows: i32,
+    pub updated_rows: i32,
+    pub error_rows: i32,
+    // 앞에서부터 최대 MAX_REPORTED_ERRORS개
+    pub errors: Vec<RowError>,
+    pub message: Option<String>,
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: NaiveDateTime,
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub finished_at: Option<NaiveDateTime>,
+}
+
+impl From<import_job::Model> for ImportJobDto {
+    fn from(value: import_job::Model) -> Self {
+        ImportJobDto {
+            id: value.id,
+            user_id: value.user_id,
+            kind: ImportKind::parse(&value.kind).unwrap_or(ImportKind::Products),
+            format: FileFormat::parse(&value.format).unwrap_or_default(),
+            dry_run: value.dry_run,
+            create_categories: value.create_categories,
+            status: value.status,
+            total_rows: value.total_rows,
+            created_rows: value.created_rows,
+            updated_rows: value.updated_rows,
+            error_rows: value.error_rows,
+            errors: serde_json::from_value(value.errors).unwrap_or_default(),
+            message: value.message,
+            created_at: value.created_at,
+            finished_at: value.finished_at,
+        }
+    }
+}
+
+#[derive(Default)]
+struct Report {
+    total: i32,
+    created: i32,
+    updated: i32,
+    error_rows: i32,
+    errors: Vec<RowError>,
+    categories_created: i32,
+}
+
+impl Report {
+    // 한 행의 오류를 모두 남기고 오류 행은 한 번만 셈
+    fn fail(&mut self, row: usize, errors: ValidationErrors) {
+        self.error_rows += 1;
+        for e in errors.0 {
+            if self.errors.len() >= MAX_REPORTED_ERRORS {
+                break;
+            }
+            self.errors.push(RowError {
+                row,
+                field: e.field,
+                code: e.code,
+                message: e.message,
+            });
+        }
+    }
+
+    fn fail_one(&mut self, row: usize, field: &str, code: &str, message: impl Into<String>) {
+        let mut errors = ValidationErrors::default();
+        errors.add(field, code, message.into());
+        self.fail(row, errors);
+    }
+}
+
+// 본문을 임시 파일로 받아둠, 메모리에는 한 조각씩만 올라감
+async fn save_upload(body: Body, path: &Path) -> Result<(), ApiError> {
+    let mut file = tokio::fs::File::create(path)
+        .await
+        .map_err(AppError::any_t_error)?;
+    let mut stream = body.into_data_stream();
+    let mut size = 0;
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk
+            .map_err(|e| ApiError::new(StatusCode::BAD_REQUEST, "invalid_body", e.to_string()))?;
+        size += chunk.len();
+        if size > IMPORT_MAX_BYTES {
+            return Err(ApiError::new(
+                StatusCode::PAYLOAD_TOO_LARGE,
+                "file_too_large",
+                format!("file must be at most {} bytes", IMPORT_MAX_BYTES),
+            ));
+        }
+        file.write_all(&chunk)
+            .await
+            .map_err(AppError::any_t_error)?;
+    }
+    file.flush().await.map_err(AppError::any_t_error)?;
+
+    if size == 0 {
+        return Err(ApiError::new(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "empty_file",
+            "file is empty",
+        ));
+    }
+    Ok(())
+}
+
+// 파일을 블로킹 스레드에서 한 행씩 읽어서 보냄, 받는 쪽이 느리면 채널 크기만큼만 앞서 읽음
+fn read_rows<T: DeserializeOwned + Send + 'static>(
+    file: std::fs::File,
+    format: FileFormat,
+) -> mpsc::Receiver<(usize, Result<T, String>)> {
+    let (tx, rx) = mpsc::channel(256);
+    tokio::task::spawn_blocking(move || {
+        let send = |row: usize, item: Result<T, String>| tx.blocking_send((row, item)).is_ok();
+
+        match format {
+            FileFormat::Csv => {
+                let mut reader = csv::ReaderBuilder::new()
+                    .trim(csv::Trim::All)
+                    .from_reader(file);
+                let headers = match reader.headers() {
+                    Ok(headers) => headers.clone(),
+                    Err(e) => {
+                        send(1, Err(e.to_string()));
+                        return;
+                    }
+                };
+                let mut record = csv::StringRecord::new();
+                loop {
+                    match reader.read_record(&mut record) {
+                        Ok(false) => break,
+                        Ok(true) => {
+                            let row = record.position().map_or(0, |p| p.line() as usize);
+                            let item = record
+                                .deserialize(Some(&headers))
+                                .map_err(|e| e.to_string());
+                            if !send(row, item) {
+                                break;
+                            }
+                        }
+                        // 열 개수가 다르거나 UTF-8이 아닌 행은 건너뛰고 계속 읽음
+                        Err(e) => {
+                            let row = e.position().map_or(0, |p| p.line() as usize);
+                            if !send(row, Err(e.to_string())) || e.is_io_error() {
+                                break;
+                            }
+                        }
+                    }
+                }
+            }
+            FileFormat::Jsonl => {
+                for (i, line) in std::io::BufReader::new(file).lines().enumerate() {
+                    let item = match line {
+                        Ok(line) if line.trim().is_empty() => continue,
+                        Ok(line) => serde_json::from_str(&line).map_err(|e| e.to_string()),
+                        Err(e) => {
+                            send(i + 1, Err(e.to_string()));
+                            break;
+                        }
+                    };
+                    if !send(i + 1, item) {
+                        break;
+                    }
+                }
+            }
+        }
+    });
+    rx
+}
+
+// 파일을 받아서 작업을 만들고 백그라운드에서 처리함, 결과는 작업 조회로 확인
+pub async fn start_import(
+    kind: ImportKind,
+    query: ImportQuery,
+    user_id: i32,
+    body: Body,
+    conn: &DatabaseConnection,
+) -> Result<ImportJobDto, ApiError> {
+    let format = query.format.unwrap_or_default();
+    let path = std::env::temp_dir().join(format!(
+        "import-{}.{}",
+        crate::utils::jwt::random_id(),
+        format.as_str()
+    ));
+
+    let job = async {
+        save_upload(body, &path).await?;
+        Ok::<_, ApiError>(
+            import_job::ActiveModel {
+                user_id: Set(Some(user_id)),
+                kind: Set(kind.as_str().to_string()),
+                format: Set(format.as_str().to_string()),
+                dry_run: Set(query.dry_run.unwrap_or(false)),
+                create_categories: Set(query.create_categories.unwrap_or(false)),
+                ..Default::default()
+            }
+            .insert(conn)
+            .await?,
+        )
+    }
+    .await;
+    let job = match job {
+        Ok(job) => job,
+        Err(e) => {
+            let _ = tokio::fs::remove_file(&path).await;
+            return Err(e);
+        }
+    };
+
+    tokio::spawn(run(job.clone(), path, conn.clone()));
+    Ok(job.into())
+}
+
+async fn run(job: import_job::Model, path: PathBuf, conn: DatabaseConnection) {
+    let id = job.id;
+    let running = import_job::ActiveModel {
+        id: Set(id),
+        status: Set(JobStatus::Running.as_str().to_string()),
+        ..Default::default()
+    };
+    if let Err(e) = running.update(&conn).await {
+        error!("Can't start import job {}: {}", id, e);
+    }
+
+    let result = match std::fs::File::open(&path) {
+        Ok(file) => {
+            let format = FileFormat::parse(&job.format).unwrap_or_default();
+            match ImportKind::parse(&job.kind) {
+                Some(ImportKind::Products) => import_products(&job, file, format, &conn).await,
+                Some(ImportKind::Categories) => import_categories(&job, file, format, &conn).await,
+                None => Err(AppError::any_t_error(format!(
+                    "unknown import kind: {}",
+                    job.kind
+                ))),
+            }
+        }
+        Err(e) => Err(AppError::any_t_error(e)),
+    };
+    let _ = tokio::fs::remove_file(&path).await;
+
+    let mut finished = import_job::ActiveModel {
+        id: Set(id),
+        finished_at: Set(Some(chrono::Utc::now().naive_utc())),
+        ..Default::default()
+    };
+    match result {
+        Ok(report) => {
+            let failed = report.error_rows > 0;
+            finished.status = Set(if failed {
+                JobStatus::Failed
+            } else {
+                JobStatus::Succeeded
+            }
+            .as_str()
+            .to_string());
+            finished.total_rows = Set(report.total);
+            finished.created_rows = Set(report.created);
+            finished.updated_rows = Set(report.updated);
+            finished.error_rows = Set(report.error_rows);
+            finished.errors = Set(serde_json::to_value(&report.errors).unwrap_or_default());
+            finished.message = Set(if failed {
+                Some(format!(
+                    "{} rows have errors, nothing was imported",
+                    report.error_rows
+                ))
+            } else if report.categories_created > 0 {
+                Some(format!(
+                    "{} categories were created",
+                    report.categories_created
+                ))
+            } else {
+                None
+            });
+        }
+        Err(e) => {
+            finished.status = Set(JobStatus::Failed.as_str().to_string());
+            finished.message = Set(Some(e.message));
+        }
+    }
+    if let Err(e) = finished.update(&conn).await {
+        error!("Can't finish import job {}: {}", id, e);
+    }
+}
+
+// dry_run이거나 오류 행이 있으면 되돌림
+async fn finish(
+    txn: DatabaseTransaction,
+    job: &import_job::Model,
+    report: &Report,
+) -> Result<(), AppError> {
+    if job.dry_run || report.error_rows > 0 {
+        txn.rollback().await?;
+    } else {
+        txn.commit().await?;
+    }
+    Ok(())
+}
+
+async fn category_ids(txn: &DatabaseTransaction) -> Result<HashMap<String, i32>, AppError> {
+    Ok(category::Entity::find()
+        .select_only()
+        .column(category::Column::Slug)
+        .column(category::Column::Id)
+        .into_tuple::<(String, i32)>()
+        .all(txn)
+        .await?
+        .into_iter()
+        .collect())
+}
+
+// 상품 행의 category 값으로 카테고리를 찾음, 저장된 slug가 먼저이고 없으면 이름 (대소문자 무시)
+#[derive(Default)]
+struct CategoryLookup {
+    by_slug: HashMap<String, i32>,
+    by_name: HashMap<String, Vec<i32>>,
+}
+
+impl CategoryLookup {
+    async fn load(txn: &DatabaseTransaction) -> Result<Self, AppError> {
+        let mut lookup = CategoryLookup::default();
+        let rows = category::Entity::find()
+            .select_only()
+            .column(category::Column::Slug)
+            .column(category::Column::Name)
+            .column(category::Column::Id)
+            .into_tuple::<(String, String, i32)>()
+            .all(txn)
+            .await?;
+        for (slug, name, id) in rows {
+            lookup.insert(slug, &name, id);
+        }
+        Ok(lookup)
+    }
+
+    fn insert(&mut self, slug: String, name: &str, id: i32) {
+        self.by_slug.insert(slug, id);
+        self.by_name
+            .entry(name.trim().to_lowercase())
+            .or_default()
+            .push(id);
+    }
+
+    // 이름이 같은 카테고리가 여럿이면 어느 것인지 알 수 없으므로 에러
+    fn find(&self, value: &str) -> Result<Option<i32>, String> {
+        if let Some(id) = self.by_slug.get(value) {
+            return Ok(Some(*id));
+        }
+        match self.by_name.get(&value.to_lowercase()).map(Vec::as_slice) {
+            Some([id]) => Ok(Some(*id)),
+            Some([_, _, ..]) => Err(format!(
+                "several categories are named '{}', use the slug",
+                value
+            )),
+            _ => Ok(None),
+        }
+    }
+}
+
+// 전체를 한 트랜잭션에서 처리함, 가격이 바뀐 상품은 가격 이력도 남김
+async fn import_products(
+    job: &import_job::Model,
+    file: std::fs::File,
+    format: FileFormat,
+    conn: &DatabaseConnection,
+) -> Result<Report, AppError> {
+    let txn = conn.begin().await?;
+    let mut categories = CategoryLookup::load(&txn).await?;
+    let mut report = Report::default();
+    let mut rows = read_rows::<ProductRow>(file, format);
+
+    while let Some((line, row)) = rows.recv().await {
+        report.total += 1;
+        let row = match row {
+            Ok(row) => row,
+            Err(message) => {
+                report.fail_one(line, "", "parse", message);
+                continue;
+            }
+        };
+
+        let mut errors = row.validate().err().unwrap_or_default();
+        let currency = Currency::parse(&row.currency.to_uppercase());
+        if currency.is_none() {
+            errors.add(
+                "currency",
+                "currency",
+                "must be one of KRW, USD, EUR, JPY".to_string(),
+            );
+        }
+        let category_name = row.category.trim();
+        // 새로 만들 때만 이름으로 slug를 만듦
+        let slug = slugify(category_name);
+        let category_id = match categories.find(category_name) {
+            Ok(Some(id)) => Some(id),
+            Ok(None) if !job.create_categories || slug.is_empty() => {
+                errors.add(
+                    "category",
+                    "not_found",
+                    "category doesn't exist".to_string(),
+                );
+                None
+            }
+            Ok(None) if categories.by_slug.contains_key(&slug) => {
+                errors.add(
+                    "category",
+                    "not_found",
+                    format!(
+                        "category doesn't exist and slug '{}' is used by another category",
+                        slug
+                    ),
+                );
+                None
+            }
+            Ok(None) => None,
+            Err(message) => {
+                errors.add("category", "ambiguous", message);
+                None
+            }
+        };
+        let currency = match (currency, errors.into_result()) {
+            (Some(currency), Ok(())) => currency,
+            (_, result) => {
+                report.fail(line, result.err().unwrap_or_default());
+                continue;
+            }
+        };
+
+        let category_id = match category_id {
+            Some(id) => id,
+            None => {
+                let model = category::ActiveModel {
+                    name: Set(category_name.to_string()),
+                    slug: Set(slug.clone()),
+                    ..Default::default()
+                }
+                .insert(&txn)
+                .await?;
+                categories.insert(slug, category_name, model.id);
+                report.categories_created += 1;
+                model.id
+            }
+        };
+
+        let sku = row.sku.trim().to_string();
+        let existing = product::Entity::find()
+            .filter(product::Column::Sku.eq(&sku))
+            .lock_exclusive()
+            .one(&txn)
+            .await?;
+        let active = product::ActiveModel {
+            title: Set(row.title.trim().to_string()),
+            price: Set(row.price),
+            currency: Set(currency.as_str().to_string()),
+            category_id: Set(category_id),
+            sku: Set(Some(sku)),
+            ..Default::default()
+        };
+        match existing {
+            None => {
+                let model = active.insert(&txn).await?;
+                record_price(&model, job.user_id, &txn).await?;
+                report.created += 1;
+            }
+            Some(before) => {
+                let changed = before.title != row.title.trim()
+                    || before.price != row.price
+                    || before.currency != currency.as_str()
+                    || before.category_id != category_id;
+                if !changed {
+                    continue;
+                }
+                if before.currency != currency.as_str()
+                    && has_price_overrides(before.id, &txn).await?
+                {
+                    report.fail_one(
+                        line,
+                        "currency",
+                        "currency_mismatch",
+                        "variant prices use the current currency",
+                    );
+                    continue;
+                }
+                let model = product::ActiveModel {
+                    id: Set(before.id),
+                    ..active
+                }
+                .update(&txn)
+                .await?;
+                if before.price != model.price || before.currency != model.currency {
+                    record_price(&model, job.user_id, &txn).await?;
+                }
+                report.updated += 1;
+            }
+        }
+    }
+
+    finish(txn, job, &report).await?;
+    Ok(report)
+}
+
+async fn import_categories(
+    job: &import_job::Model,
+    file: std::fs::File,
+    format: FileFormat,
+    conn: &DatabaseConnection,
+) -> Result<Report, AppError> {
+    let txn = conn.begin().await?;
+    let mut categories = category_ids(&txn).await?;
+    let mut report = Report::default();
+    let mut rows = read_rows::<CategoryRow>(file, format);
+
+    while let Some((line, row)) = rows.recv().await {
+        report.total += 1;
+        let row = match row {
+            Ok(row) => row,
+            Err(message) => {
+                report.fail_one(line, "", "parse", message);
+                continue;
+            }
+        };
+
+        let mut errors = row.validate().err().unwrap_or_default();
+        let slug = slugify(&row.slug);
+        if slug.is_empty() {
+            errors.add("slug", "slug", "must contain letters or digits".to_string());
+        }
+        let parent_slug = row.parent.as_deref().map(slugify).filter(|s| !s.is_empty());
+        let parent_id = parent_slug.as_ref().map(|s| categories.get(s).copied());
+        if parent_id == Some(None) {
+            errors.add(
+                "parent",
+                "not_found",
+                "parent category must exist or come earlier in the file".to_string(),
+            );
+        }
+        if let Err(errors) = errors.into_result() {
+            report.fail(line, errors);
+            continue;
+        }
+        let parent_id = parent_id.flatten();
+
+        let active = category::ActiveModel {
+            parent_id: Set(parent_id),
+            name: Set(row.name.trim().to_string()),
+            slug: Set(slug.clone()),
+            description: Set(row
+                .description
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())),
+            sort_order: Set(row.sort_order.unwrap_or(0)),
+            ..Default::default()
+        };
+        match categories.get(&slug).copied() {
+            None => {
+                let model = active.insert(&txn).await?;
+                categories.insert(slug, model.id);
+                report.created += 1;
+            }
+            Some(id) => {
+                // 자기 자신이나 하위 카테고리 아래로는 옮길 수 없음
+                if let Some(parent_id) = parent_id
+                    && subtree_ids(id, &txn).await?.contains(&parent_id)
+                {
+                    report.fail_one(
+                        line,
+                        "parent",
+                        "invalid_parent",
+                        "category can't be moved under itself",
+                    );
+                    continue;
+                }
+                let before = category::Entity::find_by_id(id)
+                    .one(&txn)
+                    .await?
+                    .ok_or_else(|| AppError::any_t_error(format!("category {} is gone", id)))?;
+                let model = category::ActiveModel {
+                    id: Set(id),
+                    ..active
+                }
+                .update(&txn)
+                .await?;
+                if model != before {
+                    report.updated += 1;
+                }
+            }
+        }
+    }
+
+    finish(txn, job, &report).await?;
+    Ok(report)
+}
+
+// 서버가 꺼지면서 끝나지 못한 작업, 임시 파일도 남아있지 않으므로 실패로 바꿈
+pub async fn fail_interrupted(conn: &DatabaseConnection) -> Result<u64, sea_orm::DbErr> {
+    use sea_orm::sea_query::Expr;
+
+    let res = import_job::Entity::update_many()
+        .col_expr(
+            import_job::Column::Status,
+            Expr::value(JobStatus::Failed.as_str()),
+        )
+        .col_expr(
+            import_job::Column::Message,
+            Expr::value("server stopped before the job finished"),
+        )
+        .col_expr(
+            import_job::Column::FinishedAt,
+            Expr::value(chrono::Utc::now().naive_utc()),
+        )
+        .filter(
+            import_job::Column::Status
+                .is_in([JobStatus::Pending.as_str(), JobStatus::Running.as_str()]),
+        )
+        .exec(conn)
+        .await?;
+    Ok(res.rows_affected)
+}
+
+pub async fn get_job(id: i32, conn: &DatabaseConnection) -> Result<ImportJobDto, ApiError> {
+    import_job::Entity::find_by_id(id)
+        .one(conn)
+        .await?
+        .map(ImportJobDto::from)
+        .ok_or_else(|| ApiError::new(StatusCode::NOT_FOUND, "not_found", "job is not found"))
+}
+
+pub async fn list_jobs(
+    query: ImportJobListQuery,
+    conn: &DatabaseConnection,
+) -> Result<Page<ImportJobDto>, ApiError> {
+    Ok(Page::by_id(
+        import_job::Entity::find(),
+        import_job::Column::Id,
+        |m: &import_job::Model| m.id,
+        query.cursor.as_deref(),
+        query.limit,
+        false,
+        conn,
+    )
+    .await?)
+}
+
+// headers가 있으면 CSV 첫 줄로 씀
+fn encode<T: Serialize>(
+    format: FileFormat,
+    headers: Option<&[&str]>,
+    rows: &[T],
+) -> Result<Vec<u8>, AppError> {
+    match format {
+        FileFormat::Csv => {
+            let mut writer = csv::WriterBuilder::new()
+                .has_headers(false)
+                .from_writer(Vec::new());
+            if let Some(headers) = headers {
+                writer
+                    .write_record(headers)
+                    .map_err(AppError::any_t_error)?;
+            }
+            for row in rows {
+                writer.serialize(row).map_err(AppError::any_t_error)?;
+            }
+            writer.into_inner().map_err(AppError::any_t_error)
+        }
+        FileFormat::Jsonl => {
+            let mut buf = Vec::new();
+            for row in rows {
+                serde_json::to_writer(&mut buf, row).map_err(AppError::any_t_error)?;
+                buf.push(b'\n');
+            }
+            Ok(buf)
+        }
+    }
+}
+
+// id 순으로 EXPORT_CHUNK개씩 읽어서 바로 내려보냄, sku가 없는 상품은 sku가 빈 칸
+pub fn export_products(
+    format: FileFormat,
+    conn: DatabaseConnection,
+) -> impl Stream<Item = Result<Vec<u8>, AppError>> + Send + 'static {
+    futures_util::stream::try_unfold(Some(0), move |last| {
+        let conn = conn.clone();
+        async move {
+            let Some(last) = last else {
+                return Ok(None);
+            };
+            let models = product::Entity::find()
+                .find_also_related(category::Entity)
+                .filter(product::Column::Id.gt(last))
+                .order_by_asc(product::Column::Id)
+                .limit(EXPORT_CHUNK)
+                .all(&conn)
+                .await?;
+            let next = match models.last() {
+                Some((p, _)) if models.len() as u64 == EXPORT_CHUNK => Some(p.id),
+                _ => None,
+            };
+            let rows = models
+                .into_iter()
+                .map(|(p, c)| ProductRow {
+                    sku: p.sku.unwrap_or_default(),
+                    title: p.title,
+                    price: p.price,
+                    currency: p.currency,
+                    category: c.map(|c| c.slug).unwrap_or_default(),
+                })
+                .collect::<Vec<_>>();
+            let headers = (last == 0).then_some(&ProductRow::HEADERS[..]);
+            Ok(Some((encode(format, headers, &rows)?, next)))
+        }
+    })
+}
+
+// 부모가 항상 먼저 나오도록 깊이 순, 같은 깊이는 sort_order, id 순
+pub async fn export_categories(
+    format: FileFormat,
+    conn: &DatabaseConnection,
+) -> Result<Vec<u8>, ApiError> {
+    let models = category::Entity::find()
+        .order_by_asc(category::Column::SortOrder)
+        .order_by_asc(category::Column::Id)
+        .all(conn)
+        .await?;
+    let by_id: HashMap<i32, &category::Model> = models.iter().map(|m| (m.id, m)).collect();
+    let depth = |model: &category::Model| {
+        let mut depth = 0;
+        let mut parent_id = model.parent_id;
+        while let Some(parent) = parent_id.and_then(|id| by_id.get(&id)) {
+            depth += 1;
+            parent_id = parent.parent_id;
+        }
+        depth
+    };
+
+    let mut rows = models
+        .iter()
+        .map(|m| {
+            (
+                depth(m),
+                CategoryRow {
+                    slug: m.slug.clone(),
+                    name: m.name.clone(),
+                    parent: m
+                        .parent_id
+                        .and_then(|id| by_id.get(&id))
+                        .map(|p| p.slug.clone()),
+                    description: m.description.clone(),
+                    sort_order: Some(m.sort_order),
+                },
+            )
+        })
+        .collect::<Vec<_>>();
+    rows.sort_by_key(|(depth, _)| *depth);
+    let rows = rows.into_iter().map(|(_, row)| row).collect::<Vec<_>>();
+
+    Ok(encode(format, Some(&CategoryRow::HEADERS), &rows)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn categories_are_found_by_slug_then_name() {
+        let mut lookup = CategoryLookup::default();
+        lookup.insert("shoes".to_string(), "Shoes", 1);
+        lookup.insert("bags".to_string(), "Shoes & Bags", 2);
+        lookup.insert("kids-tops".to_string(), "Tops", 3);
+        lookup.insert("tops".to_string(), "Tops", 4);
+
+        assert_eq!(lookup.find("shoes"), Ok(Some(1)));
+        assert_eq!(lookup.find("shoes & bags"), Ok(Some(2)));
+        // slug가 이름보다 먼저
+        assert_eq!(lookup.find("tops"), Ok(Some(4)));
+        assert!(lookup.find("TOPS").is_err());
+        // 이름으로 만든 slug(shoes-bags)로는 찾지 않음
+        assert_eq!(lookup.find("shoes-bags"), Ok(None));
+    }
+}
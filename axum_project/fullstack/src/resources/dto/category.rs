@@ -0,0 +1,283 @@
+use serde::{Deserialize, Serialize};
+use validate_dto_macro::validate_dto;
+
+#[cfg(feature = "server")]
+use crate::resources::dto::page::Page;
+#[cfg(feature = "server")]
+use crate::utils::errors::ApiError;
+
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct CategoryDto {
+    pub id: i32,
+    // 없으면 최상위 카테고리
+    pub parent_id: Option<i32>,
+    pub name: String,
+    // 주소에 쓰는 이름, 전체에서 하나뿐
+    pub slug: String,
+    pub description: Option<String>,
+    // 같은 부모 아래에서 작은 값이 먼저
+    pub sort_order: i32,
+}
+
+// 카테고리 트리의 한 노드
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct CategoryNode {
+    #[serde(flatten)]
+    pub category: CategoryDto,
+    #[cfg_attr(feature = "server", schema(no_recursion))]
+    pub children: Vec<CategoryNode>,
+}
+
+// 등록, 수정 본문, slug를 비워두면 이름으로 만듦
+#[validate_dto]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct CategoryReq {
+    pub parent_id: Option<i32>,
+    #[validate(length(min = 1, max = 50))]
+    pub name: String,
+    #[validate(length(min = 1, max = 60))]
+    pub slug: Option<String>,
+    #[validate(length(max = 500))]
+    pub description: Option<String>,
+    pub sort_order: Option<i32>,
+}
+
+// 소문자로 바꾸고 글자와 숫자가 아닌 부분은 -로 이음, 한글은 그대로 둠
+pub fn slugify(value: &str) -> String {
+    value
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+#[cfg(feature = "server")]
+impl From<crate::resources::entities::category::Model> for CategoryDto {
+    fn from(value: crate::resources::entities::category::Model) -> Self {
+        CategoryDto {
+            id: value.id,
+            parent_id: value.parent_id,
+            name: value.name,
+            slug: value.slug,
+            description: value.description,
+            sort_order: value.sort_order,
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+fn not_found() -> ApiError {
+    ApiError::new(
+        reqwest::StatusCode::NOT_FOUND,
+        "not_found",
+        "category is not found",
+    )
+}
+
+// 부모가 없으면 fk_category_parent, slug가 겹치면 idx_category_slug 위반
+#[cfg(feature = "server")]
+fn conflict(err: sea_orm::DbErr) -> ApiError {
+    use reqwest::StatusCode;
+    use sea_orm::SqlErr;
+
+    match err.sql_err() {
+        Some(SqlErr::ForeignKeyConstraintViolation(_)) => ApiError::new(
+            StatusCode::CONFLICT,
+            "parent_not_found",
+            "parent category doesn't exist",
+        ),
+        Some(SqlErr::UniqueConstraintViolation(_)) => {
+            ApiError::new(StatusCode::CONFLICT, "slug_exists", "slug is already used")
+        }
+        _ => err.into(),
+    }
+}
+
+// id부터 시작해서 모든 하위 카테고리 id, 카테고리가 없으면 빈 목록
+#[cfg(feature = "server")]
+pub async fn subtree_ids(
+    id: i32,
+    conn: &impl sea_orm::ConnectionTrait,
+) -> Result<Vec<i32>, sea_orm::DbErr> {
+    use sea_orm::{DbBackend, Statement};
+
+    let stmt = Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        "WITH RECURSIVE subtree AS (
+             SELECT id FROM category WHERE id = $1
+             UNION
+             SELECT c.id FROM category c JOIN subtree s ON c.parent_id = s.id
+         )
+         SELECT id FROM subtree",
+        [id.into()],
+    );
+    conn.query_all(stmt)
+        .await?
+        .iter()
+        .map(|row| row.try_get::<i32>("", "id"))
+        .collect()
+}
+
+#[cfg(feature = "server")]
+impl CategoryReq {
+    fn into_active(self) -> Result<crate::resources::entities::category::ActiveModel, ApiError> {
+        use sea_orm::ActiveValue::Set;
+
+        use crate::utils::validate::ValidationErrors;
+
+        let name = self.name.trim().to_string();
+        let slug = slugify(self.slug.as_deref().unwrap_or(&name));
+        if slug.is_empty() {
+            let mut errors = ValidationErrors::default();
+            errors.add("slug", "slug", "must contain letters or digits".to_string());
+            errors.into_result()?;
+        }
+
+        Ok(crate::resources::entities::category::ActiveModel {
+            parent_id: Set(self.parent_id),
+            name: Set(name),
+            slug: Set(slug),
+            description: Set(self
+                .description
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())),
+            sort_order: Set(self.sort_order.unwrap_or(0)),
+            ..Default::default()
+        })
+    }
+
+    pub async fn create(self, conn: &sea_orm::DatabaseConnection) -> Result<CategoryDto, ApiError> {
+        use sea_orm::ActiveModelTrait;
+
+        let model = self.into_active()?.insert(conn).await.map_err(conflict)?;
+        Ok(model.into())
+    }
+
+    // 자기 자신이나 하위 카테고리 아래로는 옮길 수 없음
+    pub async fn update(
+        self,
+        id: i32,
+        conn: &sea_orm::DatabaseConnection,
+    ) -> Result<CategoryDto, ApiError> {
+        use reqwest::StatusCode;
+        use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+
+        use crate::resources::entities::category;
+
+        if let Some(parent_id) = self.parent_id
+            && subtree_ids(id, conn).await?.contains(&parent_id)
+        {
+            return Err(ApiError::new(
+                StatusCode::CONFLICT,
+                "invalid_parent",
+                "category can't be moved under itself",
+            ));
+        }
+
+        let model = category::Entity::update_many()
+            .set(self.into_active()?)
+            .filter(category::Column::Id.eq(id))
+            .exec_with_returning(conn)
+            .await
+            .map_err(conflict)?
+            .pop()
+            .ok_or_else(not_found)?;
+        Ok(model.into())
+    }
+}
+
+#[cfg(feature = "server")]
+impl CategoryDto {
+    pub async fn list(
+        cursor: Option<&str>,
+        limit: Option<u64>,
+        with_total: bool,
+        conn: &sea_orm::DatabaseConnection,
+    ) -> Result<Page<CategoryDto>, ApiError> {
+        use sea_orm::EntityTrait;
+
+        use crate::resources::entities::category;
+
+        Ok(Page::by_id(
+            category::Entity::find(),
+            category::Column::Id,
+            |m: &category::Model| m.id,
+            cursor,
+            limit,
+            with_total,
+            conn,
+        )
+        .await?)
+    }
+
+    // 전체 트리, 형제끼리는 sort_order, 이름 순
+    pub async fn tree(conn: &sea_orm::DatabaseConnection) -> Result<Vec<CategoryNode>, ApiError> {
+        use std::collections::HashMap;
+
+        use sea_orm::{EntityTrait, QueryOrder};
+
+        use crate::resources::entities::category;
+
+        let rows = category::Entity::find()
+            .order_by_asc(category::Column::SortOrder)
+            .order_by_asc(category::Column::Name)
+            .all(conn)
+            .await?;
+
+        let mut children: HashMap<Option<i32>, Vec<CategoryDto>> = HashMap::new();
+        for row in rows {
+            children.entry(row.parent_id).or_default().push(row.into());
+        }
+
+        fn build(
+            parent: Option<i32>,
+            children: &mut HashMap<Option<i32>, Vec<CategoryDto>>,
+        ) -> Vec<CategoryNode> {
+            children
+                .remove(&parent)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|category| CategoryNode {
+                    children: build(Some(category.id), children),
+                    category,
+                })
+                .collect()
+        }
+
+        Ok(build(None, &mut children))
+    }
+
+    pub async fn get(id: i32, conn: &sea_orm::DatabaseConnection) -> Result<Self, ApiError> {
+        use sea_orm::EntityTrait;
+
+        crate::resources::entities::category::Entity::find_by_id(id)
+            .one(conn)
+            .await?
+            .map(CategoryDto::from)
+            .ok_or_else(not_found)
+    }
+
+    // 하위 카테고리나 상품이 남아있으면 지울 수 없음
+    pub async fn delete(id: i32, conn: &sea_orm::DatabaseConnection) -> Result<(), ApiError> {
+        use sea_orm::EntityTrait;
+
+        let res = crate::resources::entities::category::Entity::delete_by_id(id)
+            .exec(conn)
+            .await
+            .map_err(|e| {
+                ApiError::conflict_on(
+                    e,
+                    "category_in_use",
+                    "category still has products or subcategories",
+                )
+            })?;
+        if res.rows_affected == 0 {
+            return Err(not_found());
+        }
+        Ok(())
+    }
+}
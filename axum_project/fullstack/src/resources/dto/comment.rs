@@ -0,0 +1,374 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use validate_dto_macro::validate_dto;
+
+#[cfg(feature = "server")]
+use crate::resources::dto::page::Page;
+#[cfg(feature = "server")]
+use crate::utils::errors::ApiError;
+
+pub const BODY_MAX: usize = 2000;
+// 작성 후 이 시간이 지나면 본문을 고칠 수 없음, 삭제는 언제든 가능
+pub const EDIT_WINDOW_MINUTES: i64 = 15;
+
+// replies는 같은 스레드의 답글을 단계별로 담음
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct CommentDto {
+    pub id: i32,
+    pub post_id: i32,
+    pub parent_id: Option<i32>,
+    // 탈퇴한 유저나 지운 댓글은 None
+    pub user_id: Option<i32>,
+    pub author_name: Option<String>,
+    // 지운 댓글은 None, 답글은 남아있음
+    pub body: Option<String>,
+    pub deleted: bool,
+    pub edited: bool,
+    #[cfg_attr(feature = "server", schema(value_type = String, format = DateTime))]
+    pub created_at: NaiveDateTime,
+    #[cfg_attr(feature = "server", schema(value_type = String, format = DateTime))]
+    pub editable_until: NaiveDateTime,
+    #[cfg_attr(feature = "server", schema(no_recursion))]
+    pub replies: Vec<CommentDto>,
+}
+
+#[cfg(feature = "server")]
+impl From<crate::resources::entities::comment::Model> for CommentDto {
+    fn from(value: crate::resources::entities::comment::Model) -> Self {
+        let deleted = value.deleted_at.is_some();
+        CommentDto {
+            id: value.id,
+            post_id: value.post_id,
+            parent_id: value.parent_id,
+            user_id: value.user_id.filter(|_| !deleted),
+            author_name: None,
+            body: (!deleted).then_some(value.body),
+            deleted,
+            edited: value.updated_at > value.created_at,
+            created_at: value.created_at,
+            editable_until: value.created_at + chrono::Duration::minutes(EDIT_WINDOW_MINUTES),
+            replies: Vec::new(),
+        }
+    }
+}
+
+// 댓글 작성 본문, parent_id를 주면 그 댓글의 답글
+#[validate_dto]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct CommentReq {
+    pub parent_id: Option<i32>,
+    #[validate(length(min = 1, max = 2000))]
+    pub body: String,
+}
+
+// 댓글 수정 본문
+#[validate_dto]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct CommentEditReq {
+    #[validate(length(min = 1, max = 2000))]
+    pub body: String,
+}
+
+#[cfg(feature = "server")]
+fn not_found(what: &str) -> ApiError {
+    ApiError::new(
+        reqwest::StatusCode::NOT_FOUND,
+        "not_found",
+        format!("{} is not found", what),
+    )
+}
+
+#[cfg(feature = "server")]
+fn not_author() -> ApiError {
+    ApiError::new(
+        reqwest::StatusCode::FORBIDDEN,
+        "forbidden",
+        "only the author can change this comment",
+    )
+}
+
+// 게시된 글에만 댓글을 달고 볼 수 있음
+#[cfg(feature = "server")]
+async fn published_post(
+    post_id: i32,
+    conn: &impl sea_orm::ConnectionTrait,
+) -> Result<(), ApiError> {
+    use sea_orm::{ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter};
+
+    use crate::resources::dto::post::PostStatus;
+    use crate::resources::entities::post;
+
+    let count = post::Entity::find_by_id(post_id)
+        .filter(post::Column::Status.eq(PostStatus::Published.as_str()))
+        .count(conn)
+        .await?;
+    if count == 0 {
+        return Err(not_found("post"));
+    }
+    Ok(())
+}
+
+// 작성자 본인의 지우지 않은 댓글을 잠금, 관리자는 남의 댓글도 지울 수 있음
+#[cfg(feature = "server")]
+async fn find_own(
+    id: i32,
+    user_id: i32,
+    is_admin: bool,
+    txn: &sea_orm::DatabaseTransaction,
+) -> Result<crate::resources::entities::comment::Model, ApiError> {
+    use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QuerySelect};
+
+    use crate::resources::entities::comment;
+
+    let model = comment::Entity::find_by_id(id)
+        .filter(comment::Column::DeletedAt.is_null())
+        .lock_exclusive()
+        .one(txn)
+        .await?
+        .ok_or_else(|| not_found("comment"))?;
+    if !is_admin && model.user_id != Some(user_id) {
+        return Err(not_author());
+    }
+    Ok(model)
+}
+
+// 작성자 이름을 한 번에 채움
+#[cfg(feature = "server")]
+async fn fill_authors(
+    items: &mut [CommentDto],
+    conn: &sea_orm::DatabaseConnection,
+) -> Result<(), sea_orm::DbErr> {
+    use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QuerySelect};
+    use std::collections::HashMap;
+
+    use crate::resources::entities::users;
+
+    let ids: Vec<i32> = items.iter().filter_map(|c| c.user_id).collect();
+    if ids.is_empty() {
+        return Ok(());
+    }
+    let names: HashMap<i32, Option<String>> = users::Entity::find()
+        .select_only()
+        .column(users::Column::Id)
+        .column(users::Column::DisplayName)
+        .filter(users::Column::Id.is_in(ids))
+        .into_tuple::<(i32, Option<String>)>()
+        .all(conn)
+        .await?
+        .into_iter()
+        .collect();
+    for item in items.iter_mut() {
+        item.author_name = item
+            .user_id
+            .and_then(|id| names.get(&id).cloned().flatten());
+    }
+    Ok(())
+}
+
+#[cfg(feature = "server")]
+async fn with_author(
+    model: crate::resources::entities::comment::Model,
+    conn: &sea_orm::DatabaseConnection,
+) -> Result<CommentDto, ApiError> {
+    let mut dto = [CommentDto::from(model)];
+    fill_authors(&mut dto, conn).await?;
+    let [dto] = dto;
+    Ok(dto)
+}
+
+// 부모 id별로 모아둔 답글을 아래로 붙임
+#[cfg(feature = "server")]
+fn attach_replies(
+    comment: &mut CommentDto,
+    children: &mut std::collections::HashMap<i32, Vec<CommentDto>>,
+) {
+    if let Some(mut replies) = children.remove(&comment.id) {
+        for reply in replies.iter_mut() {
+            attach_replies(reply, children);
+        }
+        comment.replies = replies;
+    }
+}
+
+#[cfg(feature = "server")]
+impl CommentReq {
+    // 답글의 root_id는 부모의 스레드를 따라감, 지운 댓글에는 답글을 달 수 없음
+    pub async fn create(
+        self,
+        post_id: i32,
+        user_id: i32,
+        conn: &sea_orm::DatabaseConnection,
+    ) -> Result<CommentDto, ApiError> {
+        use reqwest::StatusCode;
+        use sea_orm::{ActiveModelTrait, ActiveValue::Set, ColumnTrait, EntityTrait, QueryFilter};
+
+        use crate::resources::entities::comment;
+
+        published_post(post_id, conn).await?;
+        let root_id = match self.parent_id {
+            Some(parent_id) => {
+                let parent = comment::Entity::find_by_id(parent_id)
+                    .filter(comment::Column::PostId.eq(post_id))
+                    .one(conn)
+                    .await?
+                    .ok_or_else(|| not_found("parent comment"))?;
+                if parent.deleted_at.is_some() {
+                    return Err(ApiError::new(
+                        StatusCode::CONFLICT,
+                        "comment_deleted",
+                        "can't reply to a deleted comment",
+                    ));
+                }
+                Some(parent.root_id.unwrap_or(parent.id))
+            }
+            None => None,
+        };
+
+        let model = comment::ActiveModel {
+            post_id: Set(post_id),
+            parent_id: Set(self.parent_id),
+            root_id: Set(root_id),
+            user_id: Set(Some(user_id)),
+            body: Set(self.body.trim().to_string()),
+            ..Default::default()
+        }
+        .insert(conn)
+        .await?;
+        with_author(model, conn).await
+    }
+}
+
+#[cfg(feature = "server")]
+impl CommentEditReq {
+    // 작성자만, 작성 후 EDIT_WINDOW_MINUTES 안에만
+    pub async fn update(
+        self,
+        id: i32,
+        user_id: i32,
+        conn: &sea_orm::DatabaseConnection,
+    ) -> Result<CommentDto, ApiError> {
+        use reqwest::StatusCode;
+        use sea_orm::{ActiveModelTrait, ActiveValue::Set, TransactionTrait};
+
+        use crate::resources::entities::comment;
+
+        let now = chrono::Utc::now().naive_utc();
+        let txn = conn.begin().await?;
+        let before = find_own(id, user_id, false, &txn).await?;
+        if now > before.created_at + chrono::Duration::minutes(EDIT_WINDOW_MINUTES) {
+            return Err(ApiError::new(
+                StatusCode::FORBIDDEN,
+                "edit_window_closed",
+                format!(
+                    "comments can be edited only within {} minutes",
+                    EDIT_WINDOW_MINUTES
+                ),
+            ));
+        }
+
+        let mut active: comment::ActiveModel = before.into();
+        active.body = Set(self.body.trim().to_string());
+        active.updated_at = Set(now);
+        let model = active.update(&txn).await?;
+        txn.commit().await?;
+
+        with_author(model, conn).await
+    }
+}
+
+// 행은 남기고 본문과 작성자를 비움, 답글은 그대로 보임
+#[cfg(feature = "server")]
+pub async fn delete_comment(
+    id: i32,
+    user_id: i32,
+    is_admin: bool,
+    conn: &sea_orm::DatabaseConnection,
+) -> Result<(), ApiError> {
+    use sea_orm::{ActiveModelTrait, ActiveValue::Set, TransactionTrait};
+
+    use crate::resources::entities::comment;
+
+    let txn = conn.begin().await?;
+    let mut active: comment::ActiveModel = find_own(id, user_id, is_admin, &txn).await?.into();
+    active.body = Set(String::new());
+    active.user_id = Set(None);
+    active.deleted_at = Set(Some(chrono::Utc::now().naive_utc()));
+    active.update(&txn).await?;
+    txn.commit().await?;
+    Ok(())
+}
+
+// 최상위 댓글을 오래된 순으로 한 페이지 가져오고 각 스레드의 답글을 모두 붙임
+#[cfg(feature = "server")]
+pub async fn list_threads(
+    post_id: i32,
+    cursor: Option<&str>,
+    limit: Option<u64>,
+    conn: &sea_orm::DatabaseConnection,
+) -> Result<Page<CommentDto>, ApiError> {
+    use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QueryOrder};
+    use std::collections::HashMap;
+
+    use crate::resources::dto::query::ListQuery;
+    use crate::resources::entities::comment;
+
+    published_post(post_id, conn).await?;
+    // 정렬을 주지 않으면 기본키 오름차순
+    let mut page: Page<CommentDto> = ListQuery::<comment::Entity>::parse("")?
+        .page(
+            comment::Entity::find()
+                .filter(comment::Column::PostId.eq(post_id))
+                .filter(comment::Column::RootId.is_null()),
+            cursor,
+            limit,
+            false,
+            conn,
+        )
+        .await?;
+    fill_authors(&mut page.items, conn).await?;
+
+    let root_ids: Vec<i32> = page.items.iter().map(|c| c.id).collect();
+    if root_ids.is_empty() {
+        return Ok(page);
+    }
+    let mut replies: Vec<CommentDto> = comment::Entity::find()
+        .filter(comment::Column::RootId.is_in(root_ids))
+        .order_by_asc(comment::Column::Id)
+        .all(conn)
+        .await?
+        .into_iter()
+        .map(CommentDto::from)
+        .collect();
+    fill_authors(&mut replies, conn).await?;
+
+    let mut children: HashMap<i32, Vec<CommentDto>> = HashMap::new();
+    for reply in replies {
+        if let Some(parent_id) = reply.parent_id {
+            children.entry(parent_id).or_default().push(reply);
+        }
+    }
+    for item in page.items.iter_mut() {
+        attach_replies(item, &mut children);
+    }
+    Ok(page)
+}
+
+// 커서 페이지네이션에 쓰는 컬럼, 목록 API에 filter, sort는 열지 않음
+#[cfg(feature = "server")]
+impl crate::resources::dto::query::QueryFields for crate::resources::entities::comment::Entity {
+    const FIELDS: &'static [crate::resources::dto::query::QueryField<Self::Column>] = {
+        use crate::resources::dto::query::{FieldKind, QueryField};
+        use crate::resources::entities::comment::Column;
+
+        &[QueryField {
+            name: "id",
+            column: Column::Id,
+            kind: FieldKind::Int,
+            sortable: true,
+        }]
+    };
+    const ID: Self::Column = crate::resources::entities::comment::Column::Id;
+}
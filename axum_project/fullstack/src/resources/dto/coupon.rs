@@ -0,0 +1,789 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::NaiveDateTime;
+use reqwest::StatusCode;
+use sea_orm::sea_query::{Expr, OnConflict};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, ConnectionTrait, DatabaseConnection,
+    EntityTrait, PaginatorTrait, QueryFilter, QuerySelect, TransactionTrait,
+};
+use serde::{Deserialize, Serialize};
+use validate_dto_macro::validate_dto;
+
+use crate::resources::dto::category::subtree_ids;
+use crate::resources::dto::money::{Currency, Money, MoneyError};
+use crate::resources::dto::page::Page;
+use crate::resources::entities::{
+    cart_coupon, category, coupon, coupon_category, coupon_redemption,
+};
+use crate::utils::errors::ApiError;
+use crate::utils::validate::ValidationErrors;
+
+// coupon.kind 컬럼 값
+#[derive(utoipa::ToSchema, Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum CouponKind {
+    Percent,
+    Fixed,
+}
+impl CouponKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CouponKind::Percent => "percent",
+            CouponKind::Fixed => "fixed",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        [CouponKind::Percent, CouponKind::Fixed]
+            .into_iter()
+            .find(|k| k.as_str() == value)
+    }
+}
+
+#[derive(utoipa::ToSchema, Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CouponDto {
+    pub id: i32,
+    pub code: String,
+    pub kind: CouponKind,
+    // percent면 할인율(1~100), fixed면 currency 통화의 최소 단위 금액
+    pub value: i64,
+    // 없으면 모든 통화의 주문에 쓸 수 있음 (percent만 가능)
+    pub currency: Option<Currency>,
+    // 할인 대상 상품 합계 기준
+    pub min_order: Option<Money>,
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub starts_at: Option<NaiveDateTime>,
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub ends_at: Option<NaiveDateTime>,
+    pub max_uses: Option<i32>,
+    pub max_uses_per_user: Option<i32>,
+    pub active: bool,
+    // 비어 있으면 모든 상품, 하위 카테고리 상품도 포함됨
+    pub category_ids: Vec<i32>,
+    // 취소, 환불되지 않은 주문에 쓰인 횟수
+    pub used: i64,
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: NaiveDateTime,
+    #[schema(value_type = String, format = DateTime)]
+    pub updated_at: NaiveDateTime,
+}
+
+impl CouponDto {
+    fn new(value: coupon::Model, category_ids: Vec<i32>, used: i64) -> Self {
+        let currency = value.currency.as_deref().and_then(Currency::parse);
+        CouponDto {
+            id: value.id,
+            code: value.code,
+            kind: CouponKind::parse(&value.kind).unwrap_or(CouponKind::Percent),
+            value: value.value,
+            currency,
+            min_order: value
+                .min_order
+                .zip(currency)
+                .map(|(amount, c)| Money::new(amount, c)),
+            starts_at: value.starts_at,
+            ends_at: value.ends_at,
+            max_uses: value.max_uses,
+            max_uses_per_user: value.max_uses_per_user,
+            active: value.active,
+            category_ids,
+            used,
+            created_at: value.created_at,
+            updated_at: value.updated_at,
+        }
+    }
+}
+
+fn default_active() -> bool {
+    true
+}
+
+// 수정할 때도 모든 필드를 다시 보냄, category_ids도 통째로 바뀜
+#[validate_dto]
+#[derive(utoipa::ToSchema, Serialize, Deserialize, Debug, Clone)]
+pub struct CouponReq {
+    // 영문, 숫자, -, _ 만 가능, 대문자로 저장되고 대소문자를 구분하지 않음
+    #[validate(length(min = 3, max = 32))]
+    pub code: String,
+    pub kind: CouponKind,
+    #[validate(range(min = 1, max = 1_000_000_000_000_i64))]
+    pub value: i64,
+    // fixed이거나 min_order가 있으면 필요함
+    pub currency: Option<Currency>,
+    #[validate(range(min = 1, max = 1_000_000_000_000_i64))]
+    pub min_order: Option<i64>,
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub starts_at: Option<NaiveDateTime>,
+    #[schema(value_type = Option<String>, format = DateTime)]
+    pub ends_at: Option<NaiveDateTime>,
+    #[validate(range(min = 1))]
+    pub max_uses: Option<i32>,
+    #[validate(range(min = 1))]
+    pub max_uses_per_user: Option<i32>,
+    #[serde(default = "default_active")]
+    pub active: bool,
+    #[serde(default)]
+    pub category_ids: Vec<i32>,
+}
+
+#[validate_dto(params)]
+#[derive(utoipa::IntoParams, utoipa::ToSchema, Serialize, Deserialize, Debug)]
+pub struct CouponListQuery {
+    pub active: Option<bool>,
+    // 기본 20, 최대 100
+    #[validate(range(min = 1, max = 100))]
+    pub limit: Option<u64>,
+    pub cursor: Option<String>,
+    pub total: Option<bool>,
+}
+
+#[validate_dto]
+#[derive(utoipa::ToSchema, Serialize, Deserialize, Debug)]
+pub struct ApplyCouponReq {
+    #[validate(length(min = 1, max = 32))]
+    pub code: String,
+}
+
+// 장바구니에 걸어둔 쿠폰
+#[derive(utoipa::ToSchema, Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AppliedCouponDto {
+    pub code: String,
+    // 지금 장바구니에 쓸 수 없으면 없고 error에 이유가 있음
+    pub discount: Option<Money>,
+    pub error: Option<String>,
+}
+
+fn not_found() -> ApiError {
+    ApiError::new(StatusCode::NOT_FOUND, "not_found", "coupon is not found")
+}
+
+fn not_applicable(message: impl Into<String>) -> ApiError {
+    ApiError::new(
+        StatusCode::UNPROCESSABLE_ENTITY,
+        "coupon_not_applicable",
+        message,
+    )
+}
+
+pub fn normalize_code(code: &str) -> String {
+    code.trim().to_uppercase()
+}
+
+impl CouponReq {
+    fn into_active(self) -> Result<(coupon::ActiveModel, Vec<i32>), ApiError> {
+        let mut errors = ValidationErrors::default();
+        let code = normalize_code(&self.code);
+        if !code
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            errors.add(
+                "code",
+                "format",
+                "must contain only letters, digits, - and _".to_string(),
+            );
+        }
+        if self.kind == CouponKind::Percent && self.value > 100 {
+            errors.add("value", "range", "must be between 1 and 100".to_string());
+        }
+        if self.currency.is_none() && (self.kind == CouponKind::Fixed || self.min_order.is_some()) {
+            errors.add(
+                "currency",
+                "required",
+                "is required for fixed coupons and min_order".to_string(),
+            );
+        }
+        if let (Some(starts_at), Some(ends_at)) = (self.starts_at, self.ends_at)
+            && ends_at <= starts_at
+        {
+            errors.add("ends_at", "range", "must be after starts_at".to_string());
+        }
+        errors.into_result()?;
+
+        let mut category_ids = self.category_ids;
+        category_ids.sort_unstable();
+        category_ids.dedup();
+
+        Ok((
+            coupon::ActiveModel {
+                code: Set(code),
+                kind: Set(self.kind.as_str().to_string()),
+                value: Set(self.value),
+                currency: Set(self.currency.map(|c| c.as_str().to_string())),
+                min_order: Set(self.min_order),
+                starts_at: Set(self.starts_at),
+                ends_at: Set(self.ends_at),
+                max_uses: Set(self.max_uses),
+                max_uses_per_user: Set(self.max_uses_per_user),
+                active: Set(self.active),
+                ..Default::default()
+            },
+            category_ids,
+        ))
+    }
+
+    pub async fn create(self, conn: &DatabaseConnection) -> Result<CouponDto, ApiError> {
+        let (active, category_ids) = self.into_active()?;
+        let txn = conn.begin().await?;
+
+        let model = active
+            .insert(&txn)
+            .await
+            .map_err(|e| ApiError::conflict_on(e, "coupon_exists", "coupon code already exists"))?;
+        set_categories(model.id, &category_ids, &txn).await?;
+
+        txn.commit().await?;
+        Ok(CouponDto::new(model, category_ids, 0))
+    }
+
+    pub async fn update(self, id: i32, conn: &DatabaseConnection) -> Result<CouponDto, ApiError> {
+        let (mut active, category_ids) = self.into_active()?;
+        active.updated_at = Set(chrono::Utc::now().naive_utc());
+        let txn = conn.begin().await?;
+
+        let model = coupon::Entity::update_many()
+            .set(active)
+            .filter(coupon::Column::Id.eq(id))
+            .exec_with_returning(&txn)
+            .await
+            .map_err(|e| ApiError::conflict_on(e, "coupon_exists", "coupon code already exists"))?
+            .pop()
+            .ok_or_else(not_found)?;
+        coupon_category::Entity::delete_many()
+            .filter(coupon_category::Column::CouponId.eq(id))
+            .exec(&txn)
+            .await?;
+        set_categories(id, &category_ids, &txn).await?;
+
+        txn.commit().await?;
+        let used = used_counts(&[id], conn).await?;
+        Ok(CouponDto::new(
+            model,
+            category_ids,
+            used.get(&id).copied().unwrap_or(0),
+        ))
+    }
+}
+
+// 없는 카테고리가 있으면 422
+async fn set_categories(
+    coupon_id: i32,
+    category_ids: &[i32],
+    conn: &impl ConnectionTrait,
+) -> Result<(), ApiError> {
+    if category_ids.is_empty() {
+        return Ok(());
+    }
+    let found = category::Entity::find()
+        .filter(category::Column::Id.is_in(category_ids.iter().copied()))
+        .count(conn)
+        .await?;
+    if found != category_ids.len() as u64 {
+        let mut errors = ValidationErrors::default();
+        errors.add(
+            "category_ids",
+            "exists",
+            "contains unknown categories".to_string(),
+        );
+        errors.into_result()?;
+    }
+
+    coupon_category::Entity::insert_many(category_ids.iter().map(|&category_id| {
+        coupon_category::ActiveModel {
+            coupon_id: Set(coupon_id),
+            category_id: Set(category_id),
+        }
+    }))
+    .exec(conn)
+    .await?;
+    Ok(())
+}
+
+async fn used_counts(
+    coupon_ids: &[i32],
+    conn: &impl ConnectionTrait,
+) -> Result<HashMap<i32, i64>, ApiError> {
+    let rows: Vec<(i32, i64)> = coupon_redemption::Entity::find()
+        .select_only()
+        .column(coupon_redemption::Column::CouponId)
+        .column_as(Expr::col(coupon_redemption::Column::Id).count(), "count")
+        .filter(coupon_redemption::Column::CouponId.is_in(coupon_ids.iter().copied()))
+        .group_by(coupon_redemption::Column::CouponId)
+        .into_tuple()
+        .all(conn)
+        .await?;
+    Ok(rows.into_iter().collect())
+}
+
+async fn categories_of(
+    coupon_ids: &[i32],
+    conn: &impl ConnectionTrait,
+) -> Result<HashMap<i32, Vec<i32>>, ApiError> {
+    let mut map: HashMap<i32, Vec<i32>> = HashMap::new();
+    for row in coupon_category::Entity::find()
+        .filter(coupon_category::Column::CouponId.is_in(coupon_ids.iter().copied()))
+        .all(conn)
+        .await?
+    {
+        map.entry(row.coupon_id).or_default().push(row.category_id);
+    }
+    for ids in map.values_mut() {
+        ids.sort_unstable();
+    }
+    Ok(map)
+}
+
+async fn with_details(
+    models: Vec<coupon::Model>,
+    conn: &DatabaseConnection,
+) -> Result<Vec<CouponDto>, ApiError> {
+    let ids = models.iter().map(|m| m.id).collect::<Vec<_>>();
+    let mut categories = categories_of(&ids, conn).await?;
+    let used = used_counts(&ids, conn).await?;
+    Ok(models
+        .into_iter()
+        .map(|m| {
+            let category_ids = categories.remove(&m.id).unwrap_or_default();
+            let used = used.get(&m.id).copied().unwrap_or(0);
+            CouponDto::new(m, category_ids, used)
+        })
+        .collect())
+}
+
+impl CouponDto {
+    pub async fn get(id: i32, conn: &DatabaseConnection) -> Result<Self, ApiError> {
+        let model = coupon::Entity::find_by_id(id)
+            .one(conn)
+            .await?
+            .ok_or_else(not_found)?;
+        Ok(with_details(vec![model], conn).await?.remove(0))
+    }
+
+    pub async fn list(
+        query: CouponListQuery,
+        conn: &DatabaseConnection,
+    ) -> Result<Page<Self>, ApiError> {
+        let mut select = coupon::Entity::find();
+        if let Some(active) = query.active {
+            select = select.filter(coupon::Column::Active.eq(active));
+        }
+        let page: Page<coupon::Model> = Page::by_id(
+            select,
+            coupon::Column::Id,
+            |m: &coupon::Model| m.id,
+            query.cursor.as_deref(),
+            query.limit,
+            query.total.unwrap_or(false),
+            conn,
+        )
+        .await?;
+        Ok(Page {
+            items: with_details(page.items, conn).await?,
+            next_cursor: page.next_cursor,
+            total: page.total,
+        })
+    }
+}
+
+// 사용 기록은 같이 지워지지만 주문에는 쿠폰 코드와 할인 금액이 남음
+pub async fn delete_coupon(id: i32, conn: &DatabaseConnection) -> Result<(), ApiError> {
+    let result = coupon::Entity::delete_by_id(id).exec(conn).await?;
+    if result.rows_affected == 0 {
+        return Err(not_found());
+    }
+    Ok(())
+}
+
+// 할인 계산에 쓰는 장바구니 한 줄
+pub struct PriceLine {
+    pub category_id: i32,
+    pub line_total: Money,
+}
+
+// 쿠폰 자체 조건(활성, 기간, 통화, 카테고리, 최소 주문 금액)만 보고 할인 금액을 계산함
+// categories는 하위 카테고리까지 펼친 목록, 비어 있으면 모든 상품이 대상
+// 할인은 대상 상품 합계를 넘지 않음, percent는 최소 단위 아래를 버림
+pub fn evaluate(
+    coupon: &coupon::Model,
+    categories: &HashSet<i32>,
+    lines: &[PriceLine],
+    now: NaiveDateTime,
+) -> Result<Money, ApiError> {
+    if !coupon.active {
+        return Err(not_applicable("coupon is not active"));
+    }
+    if coupon.starts_at.is_some_and(|at| now < at) {
+        return Err(not_applicable("coupon is not valid yet"));
+    }
+    if coupon.ends_at.is_some_and(|at| now >= at) {
+        return Err(not_applicable("coupon has expired"));
+    }
+
+    let Some(currency) = lines.first().map(|l| l.line_total.currency) else {
+        return Err(not_applicable("cart is empty"));
+    };
+    if let Some(code) = coupon.currency.as_deref()
+        && code != currency.as_str()
+    {
+        return Err(not_applicable(format!(
+            "coupon is only for {} orders",
+            code
+        )));
+    }
+
+    let mut subtotal = Money::zero(currency);
+    let mut eligible = false;
+    for line in lines {
+        if categories.is_empty() || categories.contains(&line.category_id) {
+            subtotal = subtotal
+                .checked_add(&line.line_total)
+                .map_err(|e| match e {
+                    MoneyError::CurrencyMismatch => {
+                        not_applicable("cart has products in different currencies")
+                    }
+                    MoneyError::Overflow => e.into(),
+                })?;
+            eligible = true;
+        }
+    }
+    if !eligible {
+        return Err(not_applicable(
+            "no product in the cart is eligible for this coupon",
+        ));
+    }
+    if let Some(min_order) = coupon.min_order
+        && subtotal.amount < min_order
+    {
+        return Err(not_applicable(format!(
+            "eligible products must total at least {}",
+            Money::new(min_order, currency)
+        )));
+    }
+
+    let amount = match CouponKind::parse(&coupon.kind) {
+        Some(CouponKind::Percent) => {
+            subtotal
+                .amount
+                .checked_mul(coupon.value)
+                .ok_or(MoneyError::Overflow)?
+                / 100
+        }
+        Some(CouponKind::Fixed) => coupon.value,
+        None => 0,
+    };
+    Ok(Money::new(amount.min(subtotal.amount), currency))
+}
+
+// 코드 사용 횟수와 유저별 사용 횟수
+async fn check_usage(
+    coupon: &coupon::Model,
+    user_id: i32,
+    conn: &impl ConnectionTrait,
+) -> Result<(), ApiError> {
+    let redemptions =
+        coupon_redemption::Entity::find().filter(coupon_redemption::Column::CouponId.eq(coupon.id));
+    if let Some(max_uses) = coupon.max_uses
+        && redemptions.clone().count(conn).await? >= max_uses as u64
+    {
+        return Err(not_applicable("coupon has been used up"));
+    }
+    if let Some(max_uses) = coupon.max_uses_per_user
+        && redemptions
+            .filter(coupon_redemption::Column::UserId.eq(user_id))
+            .count(conn)
+            .await?
+            >= max_uses as u64
+    {
+        return Err(not_applicable(
+            "coupon has already been used the maximum number of times",
+        ));
+    }
+    Ok(())
+}
+
+// 프로모션 엔진, 장바구니 가격을 계산할 때마다 쿠폰의 모든 조건을 다시 확인함
+pub async fn price_coupon(
+    coupon: &coupon::Model,
+    user_id: i32,
+    lines: &[PriceLine],
+    conn: &impl ConnectionTrait,
+) -> Result<Money, ApiError> {
+    let mut categories = HashSet::new();
+    for category_id in categories_of(&[coupon.id], conn)
+        .await?
+        .remove(&coupon.id)
+        .unwrap_or_default()
+    {
+        categories.extend(subtree_ids(category_id, conn).await?);
+    }
+    let discount = evaluate(coupon, &categories, lines, chrono::Utc::now().naive_utc())?;
+    check_usage(coupon, user_id, conn).await?;
+    Ok(discount)
+}
+
+// 장바구니에 걸어둔 쿠폰, lock이면 주문이 끝날 때까지 쿠폰을 잠금
+// 같은 쿠폰으로 동시에 주문해도 사용 횟수를 넘지 않도록 함
+pub async fn cart_coupon_of(
+    user_id: i32,
+    lock: bool,
+    conn: &impl ConnectionTrait,
+) -> Result<Option<coupon::Model>, ApiError> {
+    let Some(applied) = cart_coupon::Entity::find_by_id(user_id).one(conn).await? else {
+        return Ok(None);
+    };
+    let mut select = coupon::Entity::find_by_id(applied.coupon_id);
+    if lock {
+        select = select.lock_exclusive();
+    }
+    Ok(select.one(conn).await?)
+}
+
+// 쓸 수 없는 쿠폰은 걸지 않음, 이미 걸린 쿠폰이 있으면 바꿈
+pub async fn apply_to_cart(
+    user_id: i32,
+    code: &str,
+    lines: &[PriceLine],
+    conn: &DatabaseConnection,
+) -> Result<(), ApiError> {
+    let coupon = coupon::Entity::find()
+        .filter(coupon::Column::Code.eq(normalize_code(code)))
+        .one(conn)
+        .await?
+        .ok_or_else(not_found)?;
+    price_coupon(&coupon, user_id, lines, conn).await?;
+
+    cart_coupon::Entity::insert(cart_coupon::ActiveModel {
+        user_id: Set(user_id),
+        coupon_id: Set(coupon.id),
+        created_at: Set(chrono::Utc::now().naive_utc()),
+    })
+    .on_conflict(
+        OnConflict::column(cart_coupon::Column::UserId)
+            .update_columns([
+                cart_coupon::Column::CouponId,
+                cart_coupon::Column::CreatedAt,
+            ])
+            .to_owned(),
+    )
+    .exec(conn)
+    .await?;
+    Ok(())
+}
+
+pub async fn remove_from_cart(user_id: i32, conn: &impl ConnectionTrait) -> Result<(), ApiError> {
+    cart_coupon::Entity::delete_by_id(user_id)
+        .exec(conn)
+        .await?;
+    Ok(())
+}
+
+pub async fn redeem(
+    coupon: &coupon::Model,
+    user_id: i32,
+    order_id: i32,
+    discount: Money,
+    conn: &impl ConnectionTrait,
+) -> Result<(), ApiError> {
+    coupon_redemption::ActiveModel {
+        coupon_id: Set(coupon.id),
+        user_id: Set(Some(user_id)),
+        order_id: Set(order_id),
+        discount: Set(discount.amount),
+        ..Default::default()
+    }
+    .insert(conn)
+    .await?;
+    Ok(())
+}
+
+// 취소, 환불된 주문의 쿠폰 사용은 횟수에서 빠짐
+pub async fn release_redemption(
+    order_id: i32,
+    conn: &impl ConnectionTrait,
+) -> Result<(), ApiError> {
+    coupon_redemption::Entity::delete_many()
+        .filter(coupon_redemption::Column::OrderId.eq(order_id))
+        .exec(conn)
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(date: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(&format!("{}T00:00:00", date), "%Y-%m-%dT%H:%M:%S").unwrap()
+    }
+
+    fn coupon(kind: CouponKind, value: i64) -> coupon::Model {
+        coupon::Model {
+            id: 1,
+            code: "TEST".to_string(),
+            kind: kind.as_str().to_string(),
+            value,
+            currency: None,
+            min_order: None,
+            starts_at: None,
+            ends_at: None,
+            max_uses: None,
+            max_uses_per_user: None,
+            active: true,
+            created_at: at("2026-01-01"),
+            updated_at: at("2026-01-01"),
+        }
+    }
+
+    fn line(category_id: i32, amount: i64, currency: Currency) -> PriceLine {
+        PriceLine {
+            category_id,
+            line_total: Money::new(amount, currency),
+        }
+    }
+
+    fn error(result: Result<Money, ApiError>) -> String {
+        let err = result.unwrap_err();
+        assert_eq!(err.error, "coupon_not_applicable");
+        err.message
+    }
+
+    #[test]
+    fn only_within_validity_window() {
+        let mut fixed = coupon(CouponKind::Fixed, 1000);
+        fixed.starts_at = Some(at("2026-03-01"));
+        fixed.ends_at = Some(at("2026-04-01"));
+        let lines = [line(1, 5000, Currency::Krw)];
+        let none = HashSet::new();
+
+        assert_eq!(
+            error(evaluate(&fixed, &none, &lines, at("2026-02-28"))),
+            "coupon is not valid yet"
+        );
+        assert_eq!(
+            evaluate(&fixed, &none, &lines, at("2026-03-01")).unwrap(),
+            Money::new(1000, Currency::Krw)
+        );
+        // 끝나는 시각은 포함하지 않음
+        assert_eq!(
+            error(evaluate(&fixed, &none, &lines, at("2026-04-01"))),
+            "coupon has expired"
+        );
+
+        fixed.active = false;
+        assert_eq!(
+            error(evaluate(&fixed, &none, &lines, at("2026-03-15"))),
+            "coupon is not active"
+        );
+    }
+
+    #[test]
+    fn min_order_counts_eligible_products_only() {
+        let mut fixed = coupon(CouponKind::Fixed, 1000);
+        fixed.min_order = Some(10000);
+        let categories = HashSet::from([1]);
+        let now = at("2026-03-01");
+
+        let lines = [line(1, 6000, Currency::Krw), line(2, 6000, Currency::Krw)];
+        assert!(error(evaluate(&fixed, &categories, &lines, now)).starts_with("eligible products"));
+
+        let lines = [line(1, 6000, Currency::Krw), line(1, 4000, Currency::Krw)];
+        assert_eq!(
+            evaluate(&fixed, &categories, &lines, now).unwrap(),
+            Money::new(1000, Currency::Krw)
+        );
+    }
+
+    #[test]
+    fn category_restriction() {
+        let percent = coupon(CouponKind::Percent, 10);
+        let now = at("2026-03-01");
+        let lines = [line(1, 10000, Currency::Krw), line(2, 20000, Currency::Krw)];
+
+        // 비어 있으면 모든 상품이 대상
+        assert_eq!(
+            evaluate(&percent, &HashSet::new(), &lines, now).unwrap(),
+            Money::new(3000, Currency::Krw)
+        );
+        assert_eq!(
+            evaluate(&percent, &HashSet::from([2]), &lines, now).unwrap(),
+            Money::new(2000, Currency::Krw)
+        );
+        assert_eq!(
+            error(evaluate(&percent, &HashSet::from([3]), &lines, now)),
+            "no product in the cart is eligible for this coupon"
+        );
+    }
+
+    #[test]
+    fn currency_mismatch() {
+        let mut fixed = coupon(CouponKind::Fixed, 500);
+        fixed.currency = Some("USD".to_string());
+        let now = at("2026-03-01");
+
+        assert_eq!(
+            error(evaluate(
+                &fixed,
+                &HashSet::new(),
+                &[line(1, 5000, Currency::Krw)],
+                now
+            )),
+            "coupon is only for USD orders"
+        );
+        assert_eq!(
+            evaluate(
+                &fixed,
+                &HashSet::new(),
+                &[line(1, 5000, Currency::Usd)],
+                now
+            )
+            .unwrap(),
+            Money::new(500, Currency::Usd)
+        );
+
+        fixed.currency = None;
+        let mixed = [line(1, 5000, Currency::Usd), line(1, 5000, Currency::Eur)];
+        assert_eq!(
+            error(evaluate(&fixed, &HashSet::new(), &mixed, now)),
+            "cart has products in different currencies"
+        );
+    }
+
+    #[test]
+    fn percent_rounds_down() {
+        let percent = coupon(CouponKind::Percent, 15);
+        let now = at("2026-03-01");
+
+        // 999 * 15% = 149.85
+        assert_eq!(
+            evaluate(
+                &percent,
+                &HashSet::new(),
+                &[line(1, 999, Currency::Usd)],
+                now
+            )
+            .unwrap(),
+            Money::new(149, Currency::Usd)
+        );
+        assert_eq!(
+            evaluate(&percent, &HashSet::new(), &[line(1, 6, Currency::Krw)], now).unwrap(),
+            Money::new(0, Currency::Krw)
+        );
+    }
+
+    #[test]
+    fn discount_is_capped_at_eligible_total() {
+        let fixed = coupon(CouponKind::Fixed, 10000);
+        let now = at("2026-03-01");
+        let lines = [line(1, 3000, Currency::Krw), line(2, 50000, Currency::Krw)];
+
+        assert_eq!(
+            evaluate(&fixed, &HashSet::from([1]), &lines, now).unwrap(),
+            Money::new(3000, Currency::Krw)
+        );
+        assert_eq!(
+            evaluate(&fixed, &HashSet::new(), &lines, now).unwrap(),
+            Money::new(10000, Currency::Krw)
+        );
+    }
+}
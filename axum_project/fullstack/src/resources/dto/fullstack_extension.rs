@@ -2,6 +2,8 @@ use crate::{
     database,
     // router::hello::state::{HelloState, get_hello_state},
     utils::errors::AppError,
+    utils::payment::{self, DynPayment},
+    utils::storage::{DynStorage, LocalStorage},
     ws::{self, state::WsState},
 };
 use axum::extract::FromRef;
@@ -14,12 +16,21 @@ pub struct AppDatabase(pub DatabaseConnection);
 #[derive(Clone, FromRef)]
 pub struct AppReqwest(pub reqwest::Client);
 
+#[derive(Clone)]
+pub struct AppStorage(pub DynStorage);
+
+// PAYMENT_PROVIDER를 설정하지 않았으면 없음
+#[derive(Clone)]
+pub struct AppPayment(pub Option<DynPayment>);
+
 // newtype패턴을 이용해서 고아규칙을 회피하며, 기존라우터에서 사용하는 state를 제공함으로서
 #[derive(Clone, FromFullstackContextRef)]
 pub struct AppExtension {
     pub db: AppDatabase,
     pub reqwest: AppReqwest,
     pub ws: WsState,
+    pub storage: AppStorage,
+    pub payment: AppPayment,
     // pub hello: HelloState,
 }
 
@@ -29,6 +40,8 @@ impl AppExtension {
         let reqwest = AppReqwest(reqwest::Client::new());
         Ok(AppExtension {
             ws: ws::state::init_state(),
+            storage: AppStorage(std::sync::Arc::new(LocalStorage::from_env())),
+            payment: AppPayment(payment::from_env(reqwest.0.clone())?),
             // hello: get_hello_state(db.0.clone()),
             db,
             reqwest,
@@ -0,0 +1,594 @@
+use chrono::NaiveDateTime;
+use reqwest::StatusCode;
+use sea_orm::sea_query::{Expr, OnConflict};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, DatabaseConnection, DatabaseTransaction,
+    EntityTrait, QueryFilter, QuerySelect, SqlErr, TransactionTrait,
+};
+use serde::{Deserialize, Serialize};
+use validate_dto_macro::validate_dto;
+
+use crate::resources::dto::page::Page;
+use crate::resources::dto::product_variant::find_for_product;
+use crate::resources::entities::{inventory, product, stock_ledger};
+use crate::utils::errors::ApiError;
+
+// 재고 변동 종류, stock_ledger.kind 컬럼 값
+#[derive(utoipa::ToSchema, Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum StockKind {
+    // 입고, 실사 등으로 창고 수량을 직접 바꿈
+    Adjust,
+    // 주문 등으로 수량을 잡아둠
+    Reserve,
+    // 잡아둔 수량을 풀어줌
+    Release,
+    // 잡아둔 수량을 출고해서 창고 수량에서 뺌
+    Commit,
+}
+impl StockKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StockKind::Adjust => "adjust",
+            StockKind::Reserve => "reserve",
+            StockKind::Release => "release",
+            StockKind::Commit => "commit",
+        }
+    }
+}
+
+#[derive(utoipa::ToSchema, Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct InventoryDto {
+    pub product_id: i32,
+    // 변형이 있는 상품은 변형마다 재고가 따로 있음
+    pub variant_id: Option<i32>,
+    // 창고에 있는 수량
+    pub on_hand: i32,
+    // 예약되어 팔 수 없는 수량
+    pub reserved: i32,
+    // on_hand - reserved
+    pub available: i32,
+    pub low_stock_threshold: i32,
+    // available이 low_stock_threshold 이하인지
+    pub low_stock: bool,
+}
+
+impl From<inventory::Model> for InventoryDto {
+    fn from(value: inventory::Model) -> Self {
+        let available = value.on_hand - value.reserved;
+        InventoryDto {
+            product_id: value.product_id,
+            variant_id: value.variant_id,
+            on_hand: value.on_hand,
+            reserved: value.reserved,
+            available,
+            low_stock_threshold: value.low_stock_threshold,
+            low_stock: available <= value.low_stock_threshold,
+        }
+    }
+}
+
+#[derive(utoipa::ToSchema, Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct StockLedgerDto {
+    pub id: i32,
+    pub product_id: i32,
+    pub variant_id: Option<i32>,
+    // adjust, reserve, release, commit
+    pub kind: String,
+    pub on_hand_delta: i32,
+    pub reserved_delta: i32,
+    // 주문 번호 등 예약을 묶는 값
+    pub reference: Option<String>,
+    pub note: Option<String>,
+    // 변동을 만든 유저, 시스템 작업이면 없음
+    pub user_id: Option<i32>,
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: NaiveDateTime,
+}
+
+impl From<stock_ledger::Model> for StockLedgerDto {
+    fn from(value: stock_ledger::Model) -> Self {
+        StockLedgerDto {
+            id: value.id,
+            product_id: value.product_id,
+            variant_id: value.variant_id,
+            kind: value.kind,
+            on_hand_delta: value.on_hand_delta,
+            reserved_delta: value.reserved_delta,
+            reference: value.reference,
+            note: value.note,
+            user_id: value.user_id,
+            created_at: value.created_at,
+        }
+    }
+}
+
+// 보내지 않은 값은 그대로 둠
+#[validate_dto]
+#[derive(utoipa::ToSchema, Serialize, Deserialize, Debug)]
+pub struct StockAdjustReq {
+    // 창고 수량 증감, 예약된 수량보다 적게 만들 수는 없음
+    #[validate(range(min = -1_000_000, max = 1_000_000))]
+    pub delta: Option<i32>,
+    #[validate(range(min = 0, max = 1_000_000))]
+    pub low_stock_threshold: Option<i32>,
+    #[validate(length(max = 200))]
+    pub note: Option<String>,
+}
+
+#[validate_dto]
+#[derive(utoipa::ToSchema, Serialize, Deserialize, Debug)]
+pub struct StockMoveReq {
+    #[validate(range(min = 1, max = 1_000_000))]
+    pub quantity: i32,
+    // 같은 reference로 예약한 수량까지만 해제, 확정할 수 있음
+    #[validate(length(min = 1, max = 100))]
+    pub reference: String,
+}
+
+#[validate_dto(params)]
+#[derive(utoipa::IntoParams, utoipa::ToSchema, Serialize, Deserialize, Debug)]
+pub struct StockListQuery {
+    // 기본 20, 최대 100
+    #[validate(range(min = 1, max = 100))]
+    pub limit: Option<u64>,
+    pub cursor: Option<String>,
+    pub total: Option<bool>,
+}
+
+fn product_not_found() -> ApiError {
+    ApiError::new(StatusCode::NOT_FOUND, "not_found", "product is not found")
+}
+
+fn insufficient(message: impl Into<String>) -> ApiError {
+    ApiError::new(StatusCode::CONFLICT, "insufficient_stock", message)
+}
+
+// 변형이 없는 상품은 variant_id가 null인 행 하나
+fn of_target<S: QueryFilter>(select: S, product_id: i32, variant_id: Option<i32>) -> S {
+    let select = select.filter(inventory::Column::ProductId.eq(product_id));
+    match variant_id {
+        Some(id) => select.filter(inventory::Column::VariantId.eq(id)),
+        None => select.filter(inventory::Column::VariantId.is_null()),
+    }
+}
+
+// 재고 행을 잠그고 가져옴, 행이 없으면 수량 0으로 만듦
+// 잠금은 트랜잭션이 끝날 때 풀리므로 같은 상품(변형)의 재고 변경은 차례로 처리됨
+// 변형은 그 상품의 것이어야함, 변형을 골라야 하는지는 새로 재고를 잡을 때만 확인함
+async fn lock(
+    product_id: i32,
+    variant_id: Option<i32>,
+    txn: &DatabaseTransaction,
+) -> Result<inventory::Model, ApiError> {
+    if variant_id.is_some() {
+        find_for_product(product_id, variant_id, txn).await?;
+    }
+    inventory::Entity::insert(inventory::ActiveModel {
+        product_id: Set(product_id),
+        variant_id: Set(variant_id),
+        ..Default::default()
+    })
+    .on_conflict(
+        OnConflict::columns([inventory::Column::ProductId, inventory::Column::VariantId])
+            .do_nothing()
+            .to_owned(),
+    )
+    .do_nothing()
+    .exec(txn)
+    .await
+    .map_err(|e| match e.sql_err() {
+        Some(SqlErr::ForeignKeyConstraintViolation(_)) => product_not_found(),
+        _ => e.into(),
+    })?;
+
+    of_target(inventory::Entity::find(), product_id, variant_id)
+        .lock_exclusive()
+        .one(txn)
+        .await?
+        .ok_or_else(product_not_found)
+}
+
+// 재고 변동 한 건, stock_ledger 한 행이 됨
+struct Movement {
+    kind: StockKind,
+    on_hand_delta: i32,
+    reserved_delta: i32,
+    reference: Option<String>,
+    note: Option<String>,
+    user_id: Option<i32>,
+}
+
+impl Movement {
+    // 잠근 재고 행에 변동을 반영하고 기록을 남김
+    async fn apply(
+        self,
+        model: inventory::Model,
+        txn: &DatabaseTransaction,
+    ) -> Result<InventoryDto, ApiError> {
+        let product_id = model.product_id;
+        let variant_id = model.variant_id;
+        let on_hand = model.on_hand + self.on_hand_delta;
+        let reserved = model.reserved + self.reserved_delta;
+
+        let mut active: inventory::ActiveModel = model.into();
+        active.on_hand = Set(on_hand);
+        active.reserved = Set(reserved);
+        active.updated_at = Set(chrono::Utc::now().naive_utc());
+        let model = active.update(txn).await?;
+
+        stock_ledger::ActiveModel {
+            product_id: Set(product_id),
+            variant_id: Set(variant_id),
+            kind: Set(self.kind.as_str().to_string()),
+            on_hand_delta: Set(self.on_hand_delta),
+            reserved_delta: Set(self.reserved_delta),
+            reference: Set(self.reference),
+            note: Set(self.note),
+            user_id: Set(self.user_id),
+            ..Default::default()
+        }
+        .insert(txn)
+        .await?;
+
+        Ok(model.into())
+    }
+}
+
+// reference로 예약하고 아직 해제, 확정하지 않은 수량
+async fn outstanding(
+    model: &inventory::Model,
+    reference: &str,
+    txn: &DatabaseTransaction,
+) -> Result<i32, ApiError> {
+    let select = stock_ledger::Entity::find()
+        .select_only()
+        .column_as(Expr::col(stock_ledger::Column::ReservedDelta).sum(), "sum")
+        .filter(stock_ledger::Column::ProductId.eq(model.product_id))
+        .filter(stock_ledger::Column::Reference.eq(reference));
+    let select = match model.variant_id {
+        Some(id) => select.filter(stock_ledger::Column::VariantId.eq(id)),
+        None => select.filter(stock_ledger::Column::VariantId.is_null()),
+    };
+    let sum: Option<Option<i64>> = select.into_tuple().one(txn).await?;
+    Ok(sum.flatten().unwrap_or(0) as i32)
+}
+
+// 팔 수 있는 수량이 모자라면 409, 주문 생성 같은 다른 작업과 같은 트랜잭션에서 부름
+// 변형이 있는 상품은 변형을 골라야함 (422)
+pub async fn reserve(
+    product_id: i32,
+    variant_id: Option<i32>,
+    quantity: i32,
+    reference: &str,
+    user_id: Option<i32>,
+    txn: &DatabaseTransaction,
+) -> Result<InventoryDto, ApiError> {
+    if variant_id.is_none() {
+        find_for_product(product_id, None, txn).await?;
+    }
+    let model = lock(product_id, variant_id, txn).await?;
+    let available = model.on_hand - model.reserved;
+    if available < quantity {
+        return Err(insufficient(match variant_id {
+            Some(id) => format!("only {} left for variant {}", available.max(0), id),
+            None => format!("only {} left for product {}", available.max(0), product_id),
+        }));
+    }
+    Movement {
+        kind: StockKind::Reserve,
+        on_hand_delta: 0,
+        reserved_delta: quantity,
+        reference: Some(reference.to_string()),
+        note: None,
+        user_id,
+    }
+    .apply(model, txn)
+    .await
+}
+
+// 예약 취소, reference로 잡아둔 수량보다 많이 풀 수 없음
+pub async fn release(
+    product_id: i32,
+    variant_id: Option<i32>,
+    quantity: i32,
+    reference: &str,
+    user_id: Option<i32>,
+    txn: &DatabaseTransaction,
+) -> Result<InventoryDto, ApiError> {
+    let model = lock(product_id, variant_id, txn).await?;
+    let held = outstanding(&model, reference, txn).await?;
+    if held < quantity {
+        return Err(insufficient(format!(
+            "only {} reserved for {}",
+            held, reference
+        )));
+    }
+    Movement {
+        kind: StockKind::Release,
+        on_hand_delta: 0,
+        reserved_delta: -quantity,
+        reference: Some(reference.to_string()),
+        note: None,
+        user_id,
+    }
+    .apply(model, txn)
+    .await
+}
+
+// 예약한 수량을 출고함, 창고 수량과 예약 수량이 같이 줄어듦
+pub async fn commit(
+    product_id: i32,
+    variant_id: Option<i32>,
+    quantity: i32,
+    reference: &str,
+    user_id: Option<i32>,
+    txn: &DatabaseTransaction,
+) -> Result<InventoryDto, ApiError> {
+    let model = lock(product_id, variant_id, txn).await?;
+    let held = outstanding(&model, reference, txn).await?;
+    if held < quantity {
+        return Err(insufficient(format!(
+            "only {} reserved for {}",
+            held, reference
+        )));
+    }
+    Movement {
+        kind: StockKind::Commit,
+        on_hand_delta: -quantity,
+        reserved_delta: -quantity,
+        reference: Some(reference.to_string()),
+        note: None,
+        user_id,
+    }
+    .apply(model, txn)
+    .await
+}
+
+// API로 직접 예약, 해제, 확정할 때 쓰는 단건 트랜잭션
+impl StockMoveReq {
+    pub async fn reserve(
+        self,
+        product_id: i32,
+        variant_id: Option<i32>,
+        user_id: i32,
+        conn: &DatabaseConnection,
+    ) -> Result<InventoryDto, ApiError> {
+        let txn = conn.begin().await?;
+        let dto = reserve(
+            product_id,
+            variant_id,
+            self.quantity,
+            self.reference.trim(),
+            Some(user_id),
+            &txn,
+        )
+        .await?;
+        txn.commit().await?;
+        Ok(dto)
+    }
+
+    pub async fn release(
+        self,
+        product_id: i32,
+        variant_id: Option<i32>,
+        user_id: i32,
+        conn: &DatabaseConnection,
+    ) -> Result<InventoryDto, ApiError> {
+        let txn = conn.begin().await?;
+        let dto = release(
+            product_id,
+            variant_id,
+            self.quantity,
+            self.reference.trim(),
+            Some(user_id),
+            &txn,
+        )
+        .await?;
+        txn.commit().await?;
+        Ok(dto)
+    }
+
+    pub async fn commit(
+        self,
+        product_id: i32,
+        variant_id: Option<i32>,
+        user_id: i32,
+        conn: &DatabaseConnection,
+    ) -> Result<InventoryDto, ApiError> {
+        let txn = conn.begin().await?;
+        let dto = commit(
+            product_id,
+            variant_id,
+            self.quantity,
+            self.reference.trim(),
+            Some(user_id),
+            &txn,
+        )
+        .await?;
+        txn.commit().await?;
+        Ok(dto)
+    }
+}
+
+impl StockAdjustReq {
+    // 변형이 있는 상품은 변형마다 조정함 (422)
+    pub async fn apply(
+        self,
+        product_id: i32,
+        variant_id: Option<i32>,
+        user_id: i32,
+        conn: &DatabaseConnection,
+    ) -> Result<InventoryDto, ApiError> {
+        let txn = conn.begin().await?;
+        if variant_id.is_none() {
+            find_for_product(product_id, None, &txn).await?;
+        }
+        let mut model = lock(product_id, variant_id, &txn).await?;
+
+        if let Some(threshold) = self.low_stock_threshold {
+            let mut active: inventory::ActiveModel = model.into();
+            active.low_stock_threshold = Set(threshold);
+            model = active.update(&txn).await?;
+        }
+
+        let dto = match self.delta.filter(|d| *d != 0) {
+            Some(delta) => {
+                if model.on_hand + delta < model.reserved {
+                    return Err(insufficient(format!(
+                        "on hand can't be less than reserved ({})",
+                        model.reserved
+                    )));
+                }
+                let note = self
+                    .note
+                    .map(|v| v.trim().to_string())
+                    .filter(|v| !v.is_empty());
+                Movement {
+                    kind: StockKind::Adjust,
+                    on_hand_delta: delta,
+                    reserved_delta: 0,
+                    reference: None,
+                    note,
+                    user_id: Some(user_id),
+                }
+                .apply(model, &txn)
+                .await?
+            }
+            None => model.into(),
+        };
+        txn.commit().await?;
+        Ok(dto)
+    }
+}
+
+impl InventoryDto {
+    // 재고 행이 없는 상품(변형)은 수량 0
+    pub async fn get(
+        product_id: i32,
+        variant_id: Option<i32>,
+        conn: &DatabaseConnection,
+    ) -> Result<Self, ApiError> {
+        if let Some(model) = of_target(inventory::Entity::find(), product_id, variant_id)
+            .one(conn)
+            .await?
+        {
+            return Ok(model.into());
+        }
+        product::Entity::find_by_id(product_id)
+            .one(conn)
+            .await?
+            .ok_or_else(product_not_found)?;
+        find_for_product(product_id, variant_id, conn).await?;
+        Ok(InventoryDto {
+            product_id,
+            variant_id,
+            on_hand: 0,
+            reserved: 0,
+            available: 0,
+            low_stock_threshold: 0,
+            low_stock: true,
+        })
+    }
+
+    // 팔 수 있는 수량이 기준 이하인 상품(변형), 재고 행이 없는 상품은 빠짐
+    pub async fn low_stock(
+        query: StockListQuery,
+        conn: &DatabaseConnection,
+    ) -> Result<Page<Self>, ApiError> {
+        let select = inventory::Entity::find().filter(
+            Expr::expr(
+                Expr::col(inventory::Column::OnHand).sub(Expr::col(inventory::Column::Reserved)),
+            )
+            .lte(Expr::col(inventory::Column::LowStockThreshold)),
+        );
+        Ok(Page::by_id(
+            select,
+            inventory::Column::Id,
+            |m: &inventory::Model| m.id,
+            query.cursor.as_deref(),
+            query.limit,
+            query.total.unwrap_or(false),
+            conn,
+        )
+        .await?)
+    }
+}
+
+impl StockLedgerDto {
+    // 오래된 기록부터, variant_id가 없으면 상품의 모든 변형 기록
+    pub async fn list(
+        product_id: i32,
+        variant_id: Option<i32>,
+        query: StockListQuery,
+        conn: &DatabaseConnection,
+    ) -> Result<Page<Self>, ApiError> {
+        let mut select =
+            stock_ledger::Entity::find().filter(stock_ledger::Column::ProductId.eq(product_id));
+        if let Some(id) = variant_id {
+            select = select.filter(stock_ledger::Column::VariantId.eq(id));
+        }
+        Ok(Page::by_id(
+            select,
+            stock_ledger::Column::Id,
+            |m: &stock_ledger::Model| m.id,
+            query.cursor.as_deref(),
+            query.limit,
+            query.total.unwrap_or(false),
+            conn,
+        )
+        .await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::testing;
+
+    // 동시에 들어온 예약은 잠금 때문에 차례로 처리되어 창고 수량만큼만 성공해야함
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    #[ignore = "needs DATABASE_URL"]
+    async fn concurrent_reserves_never_oversell() {
+        const ON_HAND: i32 = 3;
+        const ATTEMPTS: usize = 8;
+
+        let conn = testing::connect().await;
+        let product = testing::product(1000, &conn).await;
+        inventory::ActiveModel {
+            product_id: Set(product.id),
+            on_hand: Set(ON_HAND),
+            ..Default::default()
+        }
+        .insert(&conn)
+        .await
+        .unwrap();
+
+        let tasks = (0..ATTEMPTS)
+            .map(|i| {
+                let conn = conn.clone();
+                let product_id = product.id;
+                tokio::spawn(async move {
+                    let txn = conn.begin().await?;
+                    let dto =
+                        reserve(product_id, None, 1, &format!("test:{}", i), None, &txn).await?;
+                    txn.commit().await?;
+                    Ok::<_, ApiError>(dto)
+                })
+            })
+            .collect::<Vec<_>>();
+        let mut succeeded = 0;
+        for task in tasks {
+            match task.await.unwrap() {
+                Ok(_) => succeeded += 1,
+                Err(e) => assert_eq!(e.error, "insufficient_stock", "{}", e),
+            }
+        }
+
+        let stock = InventoryDto::get(product.id, None, &conn).await.unwrap();
+        testing::remove_product(product, &conn).await;
+        assert_eq!(succeeded, ON_HAND);
+        assert_eq!(stock.reserved, ON_HAND);
+        assert!(stock.reserved <= stock.on_hand);
+    }
+}
@@ -1,3 +1,36 @@
 #[cfg(feature = "server")]
+pub mod account;
+#[cfg(feature = "server")]
+pub mod admin;
+#[cfg(feature = "server")]
+pub mod cart;
+#[cfg(feature = "server")]
+pub mod catalog;
+pub mod category;
+pub mod comment;
+#[cfg(feature = "server")]
+pub mod coupon;
+#[cfg(feature = "server")]
 pub mod fullstack_extension;
+#[cfg(feature = "server")]
+pub mod inventory;
+pub mod money;
+pub mod oauth;
+#[cfg(feature = "server")]
+pub mod order;
+pub mod page;
+#[cfg(feature = "server")]
+pub mod payment;
+pub mod post;
+pub mod product;
+pub mod product_image;
+pub mod product_variant;
+pub mod profile;
+#[cfg(feature = "server")]
+pub mod query;
+pub mod review;
+#[cfg(feature = "server")]
+pub mod scim;
+pub mod search;
+pub mod token;
 pub mod user;
@@ -0,0 +1,167 @@
+use serde::{Deserialize, Serialize};
+use validate_dto_macro::validate_dto;
+
+// ISO 4217 통화 코드, DB에는 as_str() 값이 저장됨
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum Currency {
+    #[default]
+    Krw,
+    Usd,
+    Eur,
+    Jpy,
+}
+impl Currency {
+    pub const ALL: [Currency; 4] = [Currency::Krw, Currency::Usd, Currency::Eur, Currency::Jpy];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Currency::Krw => "KRW",
+            Currency::Usd => "USD",
+            Currency::Eur => "EUR",
+            Currency::Jpy => "JPY",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Currency::ALL.into_iter().find(|c| c.as_str() == value)
+    }
+
+    // 최소 단위의 소수 자리수, KRW 1000 = 1000원, USD 1000 = $10.00
+    pub fn exponent(&self) -> u32 {
+        match self {
+            Currency::Krw | Currency::Jpy => 0,
+            Currency::Usd | Currency::Eur => 2,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoneyError {
+    CurrencyMismatch,
+    Overflow,
+}
+
+#[cfg(feature = "server")]
+impl From<MoneyError> for crate::utils::errors::ApiError {
+    fn from(value: MoneyError) -> Self {
+        use reqwest::StatusCode;
+        match value {
+            MoneyError::CurrencyMismatch => crate::utils::errors::ApiError::new(
+                StatusCode::CONFLICT,
+                "currency_mismatch",
+                "amounts are in different currencies",
+            ),
+            MoneyError::Overflow => crate::utils::errors::ApiError::new(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "amount_too_large",
+                "amount is too large",
+            ),
+        }
+    }
+}
+
+// 금액은 항상 통화의 최소 단위(정수)로 다룸
+#[validate_dto]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Money {
+    // 수량을 곱하고 여러 줄을 더해도 i64를 넘지 않도록 상한을 둠
+    #[validate(range(min = 0, max = 1_000_000_000_000_i64))]
+    pub amount: i64,
+    pub currency: Currency,
+}
+
+impl Money {
+    pub fn new(amount: i64, currency: Currency) -> Self {
+        Money { amount, currency }
+    }
+
+    pub fn zero(currency: Currency) -> Self {
+        Money::new(0, currency)
+    }
+
+    // DB의 (금액, 통화 코드) 컬럼에서 만듦, 모르는 코드는 기본 통화로 읽음
+    pub fn from_columns(amount: i64, currency: &str) -> Self {
+        Money::new(amount, Currency::parse(currency).unwrap_or_default())
+    }
+
+    pub fn times(&self, quantity: i32) -> Result<Self, MoneyError> {
+        self.amount
+            .checked_mul(quantity as i64)
+            .map(|amount| Money::new(amount, self.currency))
+            .ok_or(MoneyError::Overflow)
+    }
+
+    // 통화가 다르면 더할 수 없음
+    pub fn checked_add(&self, other: &Money) -> Result<Self, MoneyError> {
+        self.same_currency(other)?;
+        self.amount
+            .checked_add(other.amount)
+            .map(|amount| Money::new(amount, self.currency))
+            .ok_or(MoneyError::Overflow)
+    }
+
+    pub fn checked_sub(&self, other: &Money) -> Result<Self, MoneyError> {
+        self.same_currency(other)?;
+        self.amount
+            .checked_sub(other.amount)
+            .map(|amount| Money::new(amount, self.currency))
+            .ok_or(MoneyError::Overflow)
+    }
+
+    fn same_currency(&self, other: &Money) -> Result<(), MoneyError> {
+        if self.currency == other.currency {
+            Ok(())
+        } else {
+            Err(MoneyError::CurrencyMismatch)
+        }
+    }
+}
+
+// 12000 KRW, 12.50 USD
+impl std::fmt::Display for Money {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let exponent = self.currency.exponent();
+        if exponent == 0 {
+            return write!(f, "{} {}", self.amount, self.currency.as_str());
+        }
+        let unit = 10_i64.pow(exponent);
+        let sign = if self.amount < 0 { "-" } else { "" };
+        let amount = self.amount.abs();
+        write!(
+            f,
+            "{}{}.{:0width$} {}",
+            sign,
+            amount / unit,
+            amount % unit,
+            self.currency.as_str(),
+            width = exponent as usize
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic_reports_overflow_and_currency_mismatch() {
+        let price = Money::new(i64::MAX / 2, Currency::Krw);
+        assert_eq!(price.times(2), Ok(Money::new(i64::MAX - 1, Currency::Krw)));
+        assert_eq!(price.times(3), Err(MoneyError::Overflow));
+        assert_eq!(
+            price.checked_add(&Money::new(i64::MAX, Currency::Krw)),
+            Err(MoneyError::Overflow)
+        );
+        assert_eq!(
+            price.checked_add(&Money::new(1, Currency::Usd)),
+            Err(MoneyError::CurrencyMismatch)
+        );
+        assert_eq!(
+            Money::new(100, Currency::Usd).checked_sub(&Money::new(30, Currency::Usd)),
+            Ok(Money::new(70, Currency::Usd))
+        );
+    }
+}
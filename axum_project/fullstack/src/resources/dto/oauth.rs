@@ -0,0 +1,536 @@
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "server")]
+use crate::utils::errors::{AppError, OAuthError};
+
+// 인가 요청은 10분, 발급된 인가 코드는 5분만 유효함
+#[cfg(feature = "server")]
+const REQUEST_EXPIRES_MIN: i64 = 10;
+#[cfg(feature = "server")]
+const CODE_EXPIRES_MIN: i64 = 5;
+// scope를 지정하지 않으면 로그인 확인만 허용
+#[cfg(feature = "server")]
+const DEFAULT_SCOPE: &str = "openid";
+
+// 외부 앱이 /oauth/authorize 로 보내는 인가 요청 (RFC 6749 4.1.1, RFC 7636 4.3)
+#[cfg_attr(feature = "server", derive(utoipa::IntoParams))]
+#[derive(Deserialize, Serialize, Debug)]
+pub struct AuthorizeReq {
+    pub response_type: String,
+    pub client_id: String,
+    pub redirect_uri: String,
+    pub scope: Option<String>,
+    pub state: Option<String>,
+    pub code_challenge: Option<String>,
+    pub code_challenge_method: Option<String>,
+    pub nonce: Option<String>,
+}
+
+// 동의 화면에 보여줄 정보, 로그인하지 않았다면 username과 csrf_token이 없음
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ConsentInfo {
+    pub request_id: String,
+    pub client_name: String,
+    pub scopes: Vec<String>,
+    pub username: Option<String>,
+    // form에 같이 보내야 하는 값, 다른 사이트에서 동의를 위조하지 못하게 함
+    pub csrf_token: Option<String>,
+}
+
+// 동의 화면의 form, decision은 approve 또는 deny
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[derive(Deserialize, Serialize, Debug)]
+pub struct ConsentDecision {
+    pub request_id: String,
+    pub csrf_token: String,
+    pub decision: String,
+}
+
+// /oauth/token 요청 본문 (RFC 6749 4.1.3, 6)
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[derive(Deserialize, Serialize, Debug)]
+pub struct GrantReq {
+    pub grant_type: String,
+    pub code: Option<String>,
+    pub redirect_uri: Option<String>,
+    pub code_verifier: Option<String>,
+    pub refresh_token: Option<String>,
+    pub scope: Option<String>,
+    pub client_id: Option<String>,
+    pub client_secret: Option<String>,
+}
+
+// RFC 6749 5.1 응답, openid scope가 있으면 id_token도 같이 내려줌
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[derive(Deserialize, Serialize, Debug)]
+pub struct GrantRes {
+    pub access_token: String,
+    pub token_type: String,
+    pub expires_in: i64,
+    pub refresh_token: String,
+    pub scope: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id_token: Option<String>,
+}
+
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[derive(Deserialize, Serialize, Debug)]
+pub struct UserInfoRes {
+    pub sub: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preferred_username: Option<String>,
+}
+
+// OpenID Connect Discovery 1.0 의 provider metadata
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[derive(Deserialize, Serialize, Debug)]
+pub struct DiscoveryRes {
+    pub issuer: String,
+    pub authorization_endpoint: String,
+    pub token_endpoint: String,
+    pub userinfo_endpoint: String,
+    pub jwks_uri: String,
+    pub introspection_endpoint: String,
+    pub revocation_endpoint: String,
+    pub response_types_supported: Vec<String>,
+    pub grant_types_supported: Vec<String>,
+    pub subject_types_supported: Vec<String>,
+    pub id_token_signing_alg_values_supported: Vec<String>,
+    pub scopes_supported: Vec<String>,
+    pub token_endpoint_auth_methods_supported: Vec<String>,
+    pub code_challenge_methods_supported: Vec<String>,
+    pub claims_supported: Vec<String>,
+}
+
+// redirect_uri에 이미 쿼리가 있을 수 있으므로 구분자를 골라서 붙임
+#[cfg(feature = "server")]
+fn with_query(uri: &str, params: &[(&str, &str)]) -> String {
+    let query = params
+        .iter()
+        .map(|(k, v)| format!("{}={}", k, urlencoding::encode(v)))
+        .collect::<Vec<_>>()
+        .join("&");
+    let sep = if uri.contains('?') { '&' } else { '?' };
+    format!("{}{}{}", uri, sep, query)
+}
+
+// 클라이언트에게 돌려보내는 에러 (RFC 6749 4.1.2.1), state는 그대로 돌려줘야함
+#[cfg(feature = "server")]
+fn error_redirect(uri: &str, error: &str, state: Option<&str>) -> String {
+    match state {
+        Some(state) => with_query(uri, &[("error", error), ("state", state)]),
+        None => with_query(uri, &[("error", error)]),
+    }
+}
+
+#[cfg(feature = "server")]
+fn has_scope(scope: &str, target: &str) -> bool {
+    scope.split_whitespace().any(|s| s == target)
+}
+
+#[cfg(feature = "server")]
+impl AuthorizeReq {
+    // 요청을 검사해서 저장하고, 동의 화면 주소를 돌려줌
+    // client_id나 redirect_uri가 잘못되었다면 클라이언트로 돌려보내면 안됨 (RFC 6749 4.1.2.1)
+    pub async fn start(&self, conn: &sea_orm::DatabaseConnection) -> Result<String, AppError> {
+        use reqwest::StatusCode;
+        use sea_orm::{ActiveModelTrait, ActiveValue::Set, EntityTrait};
+
+        use crate::resources::entities::{authorization_code, oauth_client};
+
+        let client = oauth_client::Entity::find_by_id(self.client_id.clone())
+            .one(conn)
+            .await?
+            .ok_or(AppError::new(
+                StatusCode::BAD_REQUEST,
+                "unknown client",
+                None,
+            ))?;
+        if !client
+            .redirect_uris
+            .split_whitespace()
+            .any(|uri| uri == self.redirect_uri)
+        {
+            return Err(AppError::new(
+                StatusCode::BAD_REQUEST,
+                "redirect_uri is not registered",
+                None,
+            ));
+        }
+
+        // 여기서부터는 클라이언트에게 에러를 돌려보냄
+        let state = self.state.as_deref();
+        if self.response_type != "code" {
+            return Ok(error_redirect(
+                &self.redirect_uri,
+                "unsupported_response_type",
+                state,
+            ));
+        }
+
+        let scope = self.scope.as_deref().unwrap_or(DEFAULT_SCOPE);
+        if scope.split_whitespace().next().is_none()
+            || !scope
+                .split_whitespace()
+                .all(|s| has_scope(&client.allowed_scopes, s))
+        {
+            return Ok(error_redirect(&self.redirect_uri, "invalid_scope", state));
+        }
+
+        // challenge만 있고 method가 없으면 plain (RFC 7636 4.3)
+        let method = match (&self.code_challenge, &self.code_challenge_method) {
+            (Some(_), Some(method)) if method == "S256" || method == "plain" => {
+                Some(method.clone())
+            }
+            (Some(_), None) => Some("plain".to_string()),
+            (None, None) if !client.is_public => None,
+            _ => {
+                return Ok(error_redirect(&self.redirect_uri, "invalid_request", state));
+            }
+        };
+
+        let request_id = crate::utils::jwt::random_id();
+        let expires_at = chrono::Utc::now() + chrono::Duration::minutes(REQUEST_EXPIRES_MIN);
+        authorization_code::ActiveModel {
+            request_id: Set(request_id.clone()),
+            code: Set(None),
+            client_id: Set(client.client_id),
+            user_id: Set(None),
+            redirect_uri: Set(self.redirect_uri.clone()),
+            scope: Set(scope.split_whitespace().collect::<Vec<_>>().join(" ")),
+            state: Set(self.state.clone()),
+            nonce: Set(self.nonce.clone()),
+            code_challenge: Set(self.code_challenge.clone()),
+            code_challenge_method: Set(method),
+            expires_at: Set(expires_at.naive_utc()),
+        }
+        .insert(conn)
+        .await?;
+
+        Ok(format!("/oauth/consent/{}", request_id))
+    }
+}
+
+// 아직 동의하지 않았고, 만료되지 않은 인가 요청만 가져옴
+#[cfg(feature = "server")]
+async fn find_pending_request(
+    request_id: &str,
+    conn: &sea_orm::DatabaseConnection,
+) -> Result<crate::resources::entities::authorization_code::Model, AppError> {
+    use reqwest::StatusCode;
+    use sea_orm::EntityTrait;
+
+    use crate::resources::entities::authorization_code;
+
+    authorization_code::Entity::find_by_id(request_id.to_string())
+        .one(conn)
+        .await?
+        .filter(|req| req.code.is_none() && req.expires_at > chrono::Utc::now().naive_utc())
+        .ok_or(AppError::new(
+            StatusCode::BAD_REQUEST,
+            "authorization request is expired",
+            None,
+        ))
+}
+
+#[cfg(feature = "server")]
+impl ConsentInfo {
+    // user는 로그인한 유저의 (id, username)
+    pub async fn load(
+        request_id: &str,
+        user: Option<(i32, String)>,
+        conn: &sea_orm::DatabaseConnection,
+    ) -> Result<Self, AppError> {
+        use sea_orm::EntityTrait;
+
+        use crate::resources::entities::oauth_client;
+
+        let req = find_pending_request(request_id, conn).await?;
+        let client = oauth_client::Entity::find_by_id(req.client_id)
+            .one(conn)
+            .await?
+            .ok_or(AppError::any_error())?;
+
+        let csrf_token = user
+            .as_ref()
+            .map(|(id, _)| crate::utils::jwt::consent_csrf_token(&req.request_id, *id));
+        Ok(ConsentInfo {
+            request_id: req.request_id,
+            client_name: client.name,
+            scopes: req
+                .scope
+                .split_whitespace()
+                .map(|s| s.to_string())
+                .collect(),
+            username: user.map(|(_, username)| username),
+            csrf_token,
+        })
+    }
+}
+
+#[cfg(feature = "server")]
+impl ConsentDecision {
+    // 동의하면 인가 코드를 발급하고, 거절하면 access_denied를 담아서 클라이언트로 돌려보냄
+    // 이 유저에게 보여준 동의 화면에서 보낸 form이 아니면 403
+    pub async fn decide(
+        &self,
+        user_id: i32,
+        conn: &sea_orm::DatabaseConnection,
+    ) -> Result<String, AppError> {
+        use sea_orm::{ActiveModelTrait, ActiveValue::Set, IntoActiveModel, ModelTrait};
+
+        if !crate::utils::jwt::verify_consent_csrf_token(
+            &self.csrf_token,
+            &self.request_id,
+            user_id,
+        ) {
+            return Err(AppError::new(
+                reqwest::StatusCode::FORBIDDEN,
+                "invalid consent form, reload the page",
+                None,
+            ));
+        }
+
+        let req = find_pending_request(&self.request_id, conn).await?;
+        let redirect_uri = req.redirect_uri.clone();
+        let state = req.state.clone();
+
+        if self.decision != "approve" {
+            req.delete(conn).await?;
+            return Ok(error_redirect(
+                &redirect_uri,
+                "access_denied",
+                state.as_deref(),
+            ));
+        }
+
+        let code = crate::utils::jwt::random_id();
+        let expires_at = chrono::Utc::now() + chrono::Duration::minutes(CODE_EXPIRES_MIN);
+        let mut active = req.into_active_model();
+        active.code = Set(Some(code.clone()));
+        active.user_id = Set(Some(user_id));
+        active.expires_at = Set(expires_at.naive_utc());
+        active.update(conn).await?;
+
+        Ok(match state {
+            Some(state) => with_query(&redirect_uri, &[("code", &code), ("state", &state)]),
+            None => with_query(&redirect_uri, &[("code", &code)]),
+        })
+    }
+}
+
+#[cfg(feature = "server")]
+impl GrantReq {
+    pub async fn exchange(
+        &self,
+        client: &crate::resources::entities::oauth_client::Model,
+        conn: &sea_orm::DatabaseConnection,
+    ) -> Result<GrantRes, OAuthError> {
+        match self.grant_type.as_str() {
+            "authorization_code" => self.exchange_code(client, conn).await,
+            "refresh_token" => self.exchange_refresh(client, conn).await,
+            _ => Err(OAuthError::unsupported_grant_type()),
+        }
+    }
+
+    async fn exchange_code(
+        &self,
+        client: &crate::resources::entities::oauth_client::Model,
+        conn: &sea_orm::DatabaseConnection,
+    ) -> Result<GrantRes, OAuthError> {
+        use sea_orm::{ColumnTrait, EntityTrait, ModelTrait, QueryFilter};
+
+        use crate::resources::entities::authorization_code;
+        use crate::utils::oidc::verify_pkce;
+
+        let code = self
+            .code
+            .as_deref()
+            .ok_or(OAuthError::invalid_request("code is required"))?;
+
+        let req = authorization_code::Entity::find()
+            .filter(authorization_code::Column::Code.eq(code))
+            .one(conn)
+            .await?
+            .ok_or(OAuthError::invalid_grant("invalid code"))?;
+        // 인가 코드는 한번만 사용할 수 있음
+        req.clone().delete(conn).await?;
+
+        if req.client_id != client.client_id
+            || req.expires_at < chrono::Utc::now().naive_utc()
+            || self.redirect_uri.as_deref() != Some(req.redirect_uri.as_str())
+        {
+            return Err(OAuthError::invalid_grant("invalid code"));
+        }
+
+        if let (Some(challenge), Some(method)) = (&req.code_challenge, &req.code_challenge_method) {
+            let verifier = self
+                .code_verifier
+                .as_deref()
+                .ok_or(OAuthError::invalid_grant("code_verifier is required"))?;
+            if !verify_pkce(verifier, challenge, method) {
+                return Err(OAuthError::invalid_grant("code_verifier mismatch"));
+            }
+        }
+
+        let user_id = req
+            .user_id
+            .ok_or(OAuthError::invalid_grant("invalid code"))?;
+        issue_tokens(user_id, client, &req.scope, req.nonce, conn).await
+    }
+
+    async fn exchange_refresh(
+        &self,
+        client: &crate::resources::entities::oauth_client::Model,
+        conn: &sea_orm::DatabaseConnection,
+    ) -> Result<GrantRes, OAuthError> {
+        use sea_orm::{ColumnTrait, EntityTrait, ModelTrait, QueryFilter};
+
+        use crate::resources::entities::refresh_token;
+        use crate::utils::jwt::validate_refresh_token;
+
+        let token = self
+            .refresh_token
+            .as_deref()
+            .ok_or(OAuthError::invalid_request("refresh_token is required"))?;
+        validate_refresh_token(token).map_err(|_| OAuthError::invalid_grant("invalid token"))?;
+
+        let model = refresh_token::Entity::find()
+            .filter(refresh_token::Column::Token.eq(token))
+            .filter(refresh_token::Column::ClientId.eq(&client.client_id))
+            .one(conn)
+            .await?
+            .ok_or(OAuthError::invalid_grant("invalid token"))?;
+        // 리프레시 토큰도 매번 새로 발급함 (rotation)
+        model.clone().delete(conn).await?;
+
+        if model.expires_at < chrono::Utc::now().naive_utc() {
+            return Err(OAuthError::invalid_grant("token is expired"));
+        }
+
+        // 처음 허가받은 scope보다 넓게 요청할 수 없음 (RFC 6749 6)
+        let granted = model.scope.unwrap_or_default();
+        let scope = match &self.scope {
+            Some(scope) => {
+                if !scope.split_whitespace().all(|s| has_scope(&granted, s)) {
+                    return Err(OAuthError::invalid_scope());
+                }
+                scope.split_whitespace().collect::<Vec<_>>().join(" ")
+            }
+            None => granted,
+        };
+
+        issue_tokens(model.user_id, client, &scope, None, conn).await
+    }
+}
+
+#[cfg(feature = "server")]
+async fn issue_tokens(
+    user_id: i32,
+    client: &crate::resources::entities::oauth_client::Model,
+    scope: &str,
+    nonce: Option<String>,
+    conn: &sea_orm::DatabaseConnection,
+) -> Result<GrantRes, OAuthError> {
+    use sea_orm::EntityTrait;
+
+    use crate::resources::entities::users;
+    use crate::utils::jwt::{ACCESS_TOKEN_EXPIRES_IN, TokenClient, create_token_for};
+    use crate::utils::oidc::{IdTokenClaims, create_id_token, issuer};
+
+    let user = users::Entity::find_by_id(user_id)
+        .one(conn)
+        .await?
+        .ok_or(OAuthError::invalid_grant("user is not found"))?;
+
+    let (access_token, refresh_token) = create_token_for(
+        user.id,
+        user.username.clone(),
+        Some(TokenClient {
+            client_id: &client.client_id,
+            scope,
+        }),
+        conn,
+    )
+    .await?;
+
+    let id_token = if has_scope(scope, "openid") {
+        let now = chrono::Utc::now().timestamp() as u64;
+        let claims = IdTokenClaims {
+            iss: issuer()?,
+            sub: user.id.to_string(),
+            aud: client.client_id.clone(),
+            exp: now + ACCESS_TOKEN_EXPIRES_IN as u64,
+            iat: now,
+            nonce,
+            preferred_username: has_scope(scope, "profile").then(|| user.username.clone()),
+        };
+        Some(create_id_token(&claims)?)
+    } else {
+        None
+    };
+
+    Ok(GrantRes {
+        access_token,
+        token_type: "Bearer".to_string(),
+        expires_in: ACCESS_TOKEN_EXPIRES_IN,
+        refresh_token,
+        scope: scope.to_string(),
+        id_token,
+    })
+}
+
+#[cfg(feature = "server")]
+impl UserInfoRes {
+    // 외부 클라이언트에게 발급된 openid scope의 access 토큰만 허용
+    pub async fn from_bearer(
+        token: &str,
+        conn: &sea_orm::DatabaseConnection,
+    ) -> Result<Self, OAuthError> {
+        use crate::utils::jwt::{is_token_active, validate_jwt_token};
+
+        let claims = validate_jwt_token(token).map_err(|_| OAuthError::invalid_token())?;
+        let scope = claims.scope.clone().unwrap_or_default();
+        if claims.client_id.is_none()
+            || !has_scope(&scope, "openid")
+            || !is_token_active(&claims, conn).await?
+        {
+            return Err(OAuthError::invalid_token());
+        }
+
+        Ok(UserInfoRes {
+            sub: claims.user_id.to_string(),
+            preferred_username: has_scope(&scope, "profile").then_some(claims.username),
+        })
+    }
+}
+
+#[cfg(feature = "server")]
+impl DiscoveryRes {
+    pub fn new() -> Result<Self, AppError> {
+        let issuer = crate::utils::oidc::issuer()?;
+        let strings = |v: &[&str]| v.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+
+        Ok(DiscoveryRes {
+            authorization_endpoint: format!("{}/oauth/authorize", issuer),
+            token_endpoint: format!("{}/oauth/token", issuer),
+            userinfo_endpoint: format!("{}/oauth/userinfo", issuer),
+            jwks_uri: format!("{}/oauth/jwks", issuer),
+            introspection_endpoint: format!("{}/api/auth/introspect", issuer),
+            revocation_endpoint: format!("{}/api/auth/revoke", issuer),
+            issuer,
+            response_types_supported: strings(&["code"]),
+            grant_types_supported: strings(&["authorization_code", "refresh_token"]),
+            subject_types_supported: strings(&["public"]),
+            id_token_signing_alg_values_supported: strings(&["RS256"]),
+            scopes_supported: strings(&["openid", "profile"]),
+            token_endpoint_auth_methods_supported: strings(&[
+                "client_secret_basic",
+                "client_secret_post",
+                "none",
+            ]),
+            code_challenge_methods_supported: strings(&["S256", "plain"]),
+            claims_supported: strings(&["sub", "iss", "aud", "preferred_username"]),
+        })
+    }
+}
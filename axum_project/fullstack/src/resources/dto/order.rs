@@ -0,0 +1,543 @@
+use std::collections::HashMap;
+
+use chrono::NaiveDateTime;
+use reqwest::StatusCode;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, DatabaseConnection, DatabaseTransaction,
+    EntityTrait, QueryFilter, QueryOrder, QuerySelect, TransactionTrait,
+};
+use serde::{Deserialize, Serialize};
+use validate_dto_macro::validate_dto;
+
+use crate::resources::dto::cart::clear;
+use crate::resources::dto::coupon::{
+    PriceLine, cart_coupon_of, price_coupon, redeem, release_redemption,
+};
+use crate::resources::dto::inventory::{commit, release, reserve};
+use crate::resources::dto::money::{Money, MoneyError};
+use crate::resources::dto::page::Page;
+use crate::resources::dto::product_variant::unit_price;
+use crate::resources::entities::{
+    cart, order_item, orders, product, product_variant, stock_ledger,
+};
+use crate::utils::errors::{ApiError, AppError};
+
+// orders.status 컬럼 값
+#[derive(utoipa::ToSchema, Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum OrderStatus {
+    Pending,
+    Paid,
+    Shipped,
+    Delivered,
+    Cancelled,
+    Refunded,
+}
+impl OrderStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OrderStatus::Pending => "pending",
+            OrderStatus::Paid => "paid",
+            OrderStatus::Shipped => "shipped",
+            OrderStatus::Delivered => "delivered",
+            OrderStatus::Cancelled => "cancelled",
+            OrderStatus::Refunded => "refunded",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        [
+            OrderStatus::Pending,
+            OrderStatus::Paid,
+            OrderStatus::Shipped,
+            OrderStatus::Delivered,
+            OrderStatus::Cancelled,
+            OrderStatus::Refunded,
+        ]
+        .into_iter()
+        .find(|s| s.as_str() == value)
+    }
+
+    // pending -> paid -> shipped -> delivered
+    // 결제 전에는 취소, 결제 후에는 환불만 가능하고 cancelled, refunded는 끝 상태
+    pub fn can_become(&self, next: OrderStatus) -> bool {
+        use OrderStatus::*;
+
+        matches!(
+            (self, next),
+            (Pending, Paid)
+                | (Pending, Cancelled)
+                | (Paid, Shipped)
+                | (Paid, Refunded)
+                | (Shipped, Delivered)
+                | (Delivered, Refunded)
+        )
+    }
+}
+
+// 재고 예약을 묶는 값 (stock_ledger.reference)
+pub fn stock_reference(order_id: i32) -> String {
+    format!("order:{}", order_id)
+}
+
+#[derive(utoipa::ToSchema, Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct OrderSummaryDto {
+    pub id: i32,
+    // 탈퇴한 유저의 주문이면 없음
+    pub user_id: Option<i32>,
+    // pending, paid, shipped, delivered, cancelled, refunded
+    pub status: String,
+    // 쿠폰 할인을 뺀 결제 금액
+    pub total: Money,
+    pub discount: Money,
+    pub coupon_code: Option<String>,
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: NaiveDateTime,
+    #[schema(value_type = String, format = DateTime)]
+    pub updated_at: NaiveDateTime,
+}
+
+impl From<orders::Model> for OrderSummaryDto {
+    fn from(value: orders::Model) -> Self {
+        OrderSummaryDto {
+            id: value.id,
+            user_id: value.user_id,
+            status: value.status,
+            total: Money::from_columns(value.total, &value.currency),
+            discount: Money::from_columns(value.discount, &value.currency),
+            coupon_code: value.coupon_code,
+            created_at: value.created_at,
+            updated_at: value.updated_at,
+        }
+    }
+}
+
+// 주문 시점의 상품명과 가격
+#[derive(utoipa::ToSchema, Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct OrderItemDto {
+    // 상품이 지워졌으면 없음
+    pub product_id: Option<i32>,
+    // 변형을 주문했다면 그 변형, 변형이 지워졌으면 없음
+    pub variant_id: Option<i32>,
+    // 주문 시점의 변형 sku (변형이 없으면 상품 sku)
+    pub sku: Option<String>,
+    pub title: String,
+    pub unit_price: Money,
+    pub quantity: i32,
+    pub line_total: Money,
+}
+
+impl OrderItemDto {
+    // 상품 가격은 주문 통화로 저장됨
+    fn new(value: order_item::Model, currency: &str) -> Result<Self, MoneyError> {
+        let unit_price = Money::from_columns(value.unit_price, currency);
+        Ok(OrderItemDto {
+            product_id: value.product_id,
+            variant_id: value.variant_id,
+            sku: value.sku,
+            title: value.title,
+            unit_price,
+            quantity: value.quantity,
+            line_total: unit_price.times(value.quantity)?,
+        })
+    }
+}
+
+#[derive(utoipa::ToSchema, Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct OrderDto {
+    #[serde(flatten)]
+    pub order: OrderSummaryDto,
+    pub items: Vec<OrderItemDto>,
+}
+
+#[validate_dto(params)]
+#[derive(utoipa::IntoParams, utoipa::ToSchema, Serialize, Deserialize, Debug)]
+pub struct OrderListQuery {
+    pub status: Option<OrderStatus>,
+    // 기본 20, 최대 100
+    #[validate(range(min = 1, max = 100))]
+    pub limit: Option<u64>,
+    pub cursor: Option<String>,
+    pub total: Option<bool>,
+}
+
+#[derive(utoipa::ToSchema, Serialize, Deserialize, Debug)]
+pub struct OrderStatusReq {
+    pub status: OrderStatus,
+}
+
+fn not_found() -> ApiError {
+    ApiError::new(StatusCode::NOT_FOUND, "not_found", "order is not found")
+}
+
+fn cart_empty() -> ApiError {
+    ApiError::new(
+        StatusCode::UNPROCESSABLE_ENTITY,
+        "cart_empty",
+        "cart is empty",
+    )
+}
+
+async fn items_of(
+    order_id: i32,
+    conn: &impl sea_orm::ConnectionTrait,
+) -> Result<Vec<order_item::Model>, ApiError> {
+    Ok(order_item::Entity::find()
+        .filter(order_item::Column::OrderId.eq(order_id))
+        .order_by_asc(order_item::Column::Id)
+        .all(conn)
+        .await?)
+}
+
+impl OrderDto {
+    // owner가 있으면 그 유저의 주문만 찾음
+    pub async fn get(
+        order_id: i32,
+        owner: Option<i32>,
+        conn: &DatabaseConnection,
+    ) -> Result<Self, ApiError> {
+        let mut select = orders::Entity::find_by_id(order_id);
+        if let Some(owner) = owner {
+            select = select.filter(orders::Column::UserId.eq(owner));
+        }
+        let order = select.one(conn).await?.ok_or_else(not_found)?;
+        Self::with_items(order, conn).await
+    }
+
+    async fn with_items(
+        order: orders::Model,
+        conn: &impl sea_orm::ConnectionTrait,
+    ) -> Result<Self, ApiError> {
+        let items = items_of(order.id, conn).await?;
+        Ok(OrderDto {
+            items: items
+                .into_iter()
+                .map(|i| OrderItemDto::new(i, &order.currency))
+                .collect::<Result<_, _>>()?,
+            order: order.into(),
+        })
+    }
+}
+
+impl OrderSummaryDto {
+    // owner가 없으면 모든 유저의 주문
+    pub async fn list(
+        owner: Option<i32>,
+        query: OrderListQuery,
+        conn: &DatabaseConnection,
+    ) -> Result<Page<Self>, ApiError> {
+        let mut select = orders::Entity::find();
+        if let Some(owner) = owner {
+            select = select.filter(orders::Column::UserId.eq(owner));
+        }
+        if let Some(status) = query.status {
+            select = select.filter(orders::Column::Status.eq(status.as_str()));
+        }
+        Ok(Page::by_id(
+            select,
+            orders::Column::Id,
+            |m: &orders::Model| m.id,
+            query.cursor.as_deref(),
+            query.limit,
+            query.total.unwrap_or(false),
+            conn,
+        )
+        .await?)
+    }
+}
+
+// 장바구니 한 줄, 주문할 때의 상품과 변형
+struct CheckoutLine<'a> {
+    product: &'a product::Model,
+    variant: Option<&'a product_variant::Model>,
+    quantity: i32,
+    unit_price: Money,
+}
+
+// 장바구니를 주문으로 바꿈, 가격은 지금 변형 가격(없으면 product.price)을 복사하고 재고를 예약함
+// 재고가 모자란 상품이 하나라도 있으면 주문을 만들지 않음 (409)
+// 담은 뒤에 변형이 생긴 상품이 있으면 주문을 만들지 않음 (422, variant_required)
+// 걸어둔 쿠폰을 쓸 수 없게 됐으면 주문을 만들지 않음 (422)
+pub async fn checkout(user_id: i32, conn: &DatabaseConnection) -> Result<OrderDto, ApiError> {
+    let txn = conn.begin().await?;
+
+    // 같은 장바구니로 동시에 주문하지 않도록 잠금, 먼저 끝난 주문이 장바구니를 비움
+    // 여러 주문이 같은 상품 재고를 잠그므로 교착을 피하려고 (상품 id, 변형 id) 순서로 처리함
+    let items = cart::Entity::find()
+        .filter(cart::Column::UserId.eq(user_id))
+        .order_by_asc(cart::Column::ProductId)
+        .order_by_asc(cart::Column::VariantId)
+        .lock_exclusive()
+        .all(&txn)
+        .await?;
+    if items.is_empty() {
+        return Err(cart_empty());
+    }
+
+    let products: HashMap<i32, product::Model> = product::Entity::find()
+        .filter(product::Column::Id.is_in(items.iter().map(|i| i.product_id)))
+        .all(&txn)
+        .await?
+        .into_iter()
+        .map(|p| (p.id, p))
+        .collect();
+    // 변형을 고르지 않은 줄도 확인해야 하므로 상품의 변형을 모두 읽음
+    let variants: HashMap<i32, product_variant::Model> = product_variant::Entity::find()
+        .filter(product_variant::Column::ProductId.is_in(products.keys().copied()))
+        .all(&txn)
+        .await?
+        .into_iter()
+        .map(|v| (v.id, v))
+        .collect();
+    let mut lines = Vec::with_capacity(items.len());
+    for item in &items {
+        let Some(product) = products.get(&item.product_id) else {
+            continue;
+        };
+        let variant = item.variant_id.and_then(|id| variants.get(&id));
+        if variant.is_none() && variants.values().any(|v| v.product_id == product.id) {
+            return Err(ApiError::new(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "variant_required",
+                format!(
+                    "product {} now has variants, pick one in the cart",
+                    product.id
+                ),
+            ));
+        }
+        lines.push(CheckoutLine {
+            product,
+            variant,
+            quantity: item.quantity,
+            unit_price: unit_price(product, variant),
+        });
+    }
+    let price_lines = lines
+        .iter()
+        .map(|line| {
+            Ok(PriceLine {
+                category_id: line.product.category_id,
+                line_total: line.unit_price.times(line.quantity)?,
+            })
+        })
+        .collect::<Result<Vec<_>, MoneyError>>()?;
+    // 장바구니에 담은 뒤 상품 통화가 바뀌었을 수 있으므로 다시 확인함
+    let mut line_totals = price_lines.iter().map(|l| l.line_total);
+    let first = line_totals.next().ok_or_else(cart_empty)?;
+    let subtotal = line_totals.try_fold(first, |acc, line| acc.checked_add(&line))?;
+
+    let coupon = cart_coupon_of(user_id, true, &txn).await?;
+    let discount = match &coupon {
+        Some(coupon) => price_coupon(coupon, user_id, &price_lines, &txn).await?,
+        None => Money::zero(subtotal.currency),
+    };
+
+    let order = orders::ActiveModel {
+        user_id: Set(Some(user_id)),
+        status: Set(OrderStatus::Pending.as_str().to_string()),
+        total: Set(subtotal.amount - discount.amount),
+        currency: Set(subtotal.currency.as_str().to_string()),
+        discount: Set(discount.amount),
+        coupon_code: Set(coupon.as_ref().map(|c| c.code.clone())),
+        ..Default::default()
+    }
+    .insert(&txn)
+    .await?;
+    if let Some(coupon) = &coupon {
+        redeem(coupon, user_id, order.id, discount, &txn).await?;
+    }
+
+    let reference = stock_reference(order.id);
+    let mut order_items = Vec::with_capacity(lines.len());
+    for line in lines {
+        let variant_id = line.variant.map(|v| v.id);
+        reserve(
+            line.product.id,
+            variant_id,
+            line.quantity,
+            &reference,
+            Some(user_id),
+            &txn,
+        )
+        .await?;
+        let item = order_item::ActiveModel {
+            order_id: Set(order.id),
+            product_id: Set(Some(line.product.id)),
+            variant_id: Set(variant_id),
+            sku: Set(line
+                .variant
+                .map(|v| v.sku.clone())
+                .or(line.product.sku.clone())),
+            title: Set(line.product.title.clone()),
+            unit_price: Set(line.unit_price.amount),
+            quantity: Set(line.quantity),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await?;
+        order_items.push(OrderItemDto::new(item, &order.currency)?);
+    }
+
+    clear(user_id, &txn).await?;
+    txn.commit().await?;
+
+    Ok(OrderDto {
+        order: order.into(),
+        items: order_items,
+    })
+}
+
+// 상태가 바뀔 때 재고도 같이 바꿈
+async fn reserved_without_variant(
+    product_id: i32,
+    reference: &str,
+    txn: &DatabaseTransaction,
+) -> Result<bool, ApiError> {
+    Ok(stock_ledger::Entity::find()
+        .filter(stock_ledger::Column::ProductId.eq(product_id))
+        .filter(stock_ledger::Column::VariantId.is_null())
+        .filter(stock_ledger::Column::Reference.eq(reference))
+        .one(txn)
+        .await?
+        .is_some())
+}
+
+// 취소, 결제 후 환불은 예약을 풀고, 배송은 예약한 수량을 출고함
+async fn apply_stock(
+    order_id: i32,
+    from: OrderStatus,
+    to: OrderStatus,
+    actor: Option<i32>,
+    txn: &DatabaseTransaction,
+) -> Result<(), ApiError> {
+    use OrderStatus::*;
+
+    let reserved = matches!(from, Pending | Paid);
+    let releases = reserved && matches!(to, Cancelled | Refunded);
+    let commits = to == Shipped;
+    if !releases && !commits {
+        return Ok(());
+    }
+
+    let reference = stock_reference(order_id);
+    let mut items = items_of(order_id, txn).await?;
+    items.sort_by_key(|i| (i.product_id, i.variant_id));
+    for item in items {
+        // 지워진 상품은 재고 기록도 같이 지워졌으므로 건너뜀
+        let Some(product_id) = item.product_id else {
+            continue;
+        };
+        // 지워진 변형도 마찬가지, 변형 없이 예약한 기록이 없으면 지워진 변형의 줄임
+        let variant_id = item.variant_id;
+        if variant_id.is_none() && !reserved_without_variant(product_id, &reference, txn).await? {
+            continue;
+        }
+        if releases {
+            release(
+                product_id,
+                variant_id,
+                item.quantity,
+                &reference,
+                actor,
+                txn,
+            )
+            .await?;
+        } else {
+            commit(
+                product_id,
+                variant_id,
+                item.quantity,
+                &reference,
+                actor,
+                txn,
+            )
+            .await?;
+        }
+    }
+    Ok(())
+}
+
+// 허용되지 않은 상태 변경은 409 (invalid_transition)
+// owner가 있으면 그 유저의 주문만 바꿈, actor는 재고 기록에 남길 유저
+pub async fn transition(
+    order_id: i32,
+    next: OrderStatus,
+    owner: Option<i32>,
+    actor: Option<i32>,
+    conn: &DatabaseConnection,
+) -> Result<OrderDto, ApiError> {
+    let txn = conn.begin().await?;
+    let order = transition_in(order_id, next, owner, actor, &txn).await?;
+    txn.commit().await?;
+    Ok(order)
+}
+
+// 결제 처리처럼 다른 변경과 같은 트랜잭션에서 상태를 바꿀 때 씀
+pub async fn transition_in(
+    order_id: i32,
+    next: OrderStatus,
+    owner: Option<i32>,
+    actor: Option<i32>,
+    txn: &DatabaseTransaction,
+) -> Result<OrderDto, ApiError> {
+    let mut select = orders::Entity::find_by_id(order_id);
+    if let Some(owner) = owner {
+        select = select.filter(orders::Column::UserId.eq(owner));
+    }
+    let order = select
+        .lock_exclusive()
+        .one(txn)
+        .await?
+        .ok_or_else(not_found)?;
+
+    let current = OrderStatus::parse(&order.status)
+        .ok_or_else(|| AppError::any_t_error(format!("unknown order status: {}", order.status)))?;
+    if !current.can_become(next) {
+        return Err(ApiError::new(
+            StatusCode::CONFLICT,
+            "invalid_transition",
+            format!(
+                "order can't be changed from {} to {}",
+                current.as_str(),
+                next.as_str()
+            ),
+        ));
+    }
+
+    apply_stock(order.id, current, next, actor, txn).await?;
+    if matches!(next, OrderStatus::Cancelled | OrderStatus::Refunded) {
+        release_redemption(order.id, txn).await?;
+    }
+
+    let mut active: orders::ActiveModel = order.into();
+    active.status = Set(next.as_str().to_string());
+    active.updated_at = Set(chrono::Utc::now().naive_utc());
+    let order = active.update(txn).await?;
+    OrderDto::with_items(order, txn).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_transitions() {
+        use OrderStatus::*;
+
+        let all = [Pending, Paid, Shipped, Delivered, Cancelled, Refunded];
+        // 행은 현재 상태, 열은 다음 상태 (all 순서)
+        let allowed = [
+            [false, true, false, false, true, false],
+            [false, false, true, false, false, true],
+            [false, false, false, true, false, false],
+            [false, false, false, false, false, true],
+            [false, false, false, false, false, false],
+            [false, false, false, false, false, false],
+        ];
+        for (from, row) in all.iter().zip(allowed) {
+            for (to, expected) in all.iter().zip(row) {
+                assert_eq!(from.can_become(*to), expected, "{:?} -> {:?}", from, to);
+            }
+        }
+    }
+}
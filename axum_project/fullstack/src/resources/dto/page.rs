@@ -0,0 +1,76 @@
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "server")]
+use crate::utils::errors::AppError;
+
+pub const DEFAULT_LIMIT: u64 = 20;
+pub const MAX_LIMIT: u64 = 100;
+
+// 목록 응답 공통 형식
+// next_cursor가 없으면 마지막 페이지, total은 요청했을 때만 내려줌
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub next_cursor: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total: Option<u64>,
+}
+
+pub fn clamp_limit(limit: Option<u64>) -> u64 {
+    limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT)
+}
+
+#[cfg(feature = "server")]
+impl<T> Page<T> {
+    // OFFSET 대신 정수 기본키를 기준으로 keyset 페이지네이션
+    // limit + 1개를 가져와서 다음 페이지가 있는지 확인함
+    pub async fn by_id<E, C>(
+        select: sea_orm::Select<E>,
+        column: C,
+        id_of: impl Fn(&E::Model) -> i32,
+        cursor: Option<&str>,
+        limit: Option<u64>,
+        with_total: bool,
+        conn: &sea_orm::DatabaseConnection,
+    ) -> Result<Self, AppError>
+    where
+        E: sea_orm::EntityTrait,
+        E::Model: Into<T> + Sync,
+        C: sea_orm::ColumnTrait,
+    {
+        use reqwest::StatusCode;
+        use sea_orm::{PaginatorTrait, QueryFilter, QueryOrder, QuerySelect};
+
+        let limit = clamp_limit(limit);
+
+        // 전체 개수는 커서와 관계없이 조건에 맞는 개수
+        let total = if with_total {
+            Some(select.clone().count(conn).await?)
+        } else {
+            None
+        };
+
+        let mut select = select.order_by_asc(column).limit(limit + 1);
+        if let Some(cursor) = cursor {
+            let after = cursor
+                .parse::<i32>()
+                .map_err(|_| AppError::new(StatusCode::BAD_REQUEST, "invalid cursor", None))?;
+            select = select.filter(column.gt(after));
+        }
+
+        let mut rows = select.all(conn).await?;
+        let next_cursor = if rows.len() as u64 > limit {
+            rows.truncate(limit as usize);
+            rows.last().map(|m| id_of(m).to_string())
+        } else {
+            None
+        };
+
+        Ok(Page {
+            items: rows.into_iter().map(|m| m.into()).collect(),
+            next_cursor,
+            total,
+        })
+    }
+}
@@ -0,0 +1,687 @@
+use axum::http::HeaderMap;
+use chrono::NaiveDateTime;
+use reqwest::StatusCode;
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, DatabaseConnection, DatabaseTransaction,
+    EntityTrait, QueryFilter, QueryOrder, QuerySelect, TransactionTrait, TryInsertResult,
+};
+use serde::{Deserialize, Serialize};
+use tracing::{error, warn};
+use validate_dto_macro::validate_dto;
+
+use crate::resources::dto::money::Money;
+use crate::resources::dto::order::{OrderStatus, transition_in};
+use crate::resources::entities::{orders, payment, payment_event};
+use crate::utils::errors::ApiError;
+use crate::utils::payment::{PaymentProvider, PaymentStatus};
+
+#[derive(utoipa::ToSchema, Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PaymentDto {
+    pub id: i32,
+    pub order_id: i32,
+    // mock, stripe
+    pub provider: String,
+    // 대행사의 결제 식별자
+    pub provider_ref: String,
+    pub amount: Money,
+    // pending, succeeded, declined
+    pub status: String,
+    pub failure_reason: Option<String>,
+    // 결제를 만들 때만 내려줌, 브라우저에서 결제창을 띄울 때 씀
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_secret: Option<String>,
+    #[schema(value_type = String, format = DateTime)]
+    pub created_at: NaiveDateTime,
+    #[schema(value_type = String, format = DateTime)]
+    pub updated_at: NaiveDateTime,
+}
+
+impl From<payment::Model> for PaymentDto {
+    fn from(value: payment::Model) -> Self {
+        PaymentDto {
+            id: value.id,
+            order_id: value.order_id,
+            provider: value.provider,
+            provider_ref: value.provider_ref,
+            amount: Money::from_columns(value.amount, &value.currency),
+            status: value.status,
+            failure_reason: value.failure_reason,
+            client_secret: None,
+            created_at: value.created_at,
+            updated_at: value.updated_at,
+        }
+    }
+}
+
+#[validate_dto]
+#[derive(utoipa::ToSchema, Serialize, Deserialize, Debug)]
+pub struct PaymentConfirmReq {
+    // stripe: 결제 수단 id (pm_...), mock: success, decline, delay
+    #[validate(length(min = 1, max = 200))]
+    pub payment_method: String,
+}
+
+fn not_found(what: &str) -> ApiError {
+    ApiError::new(
+        StatusCode::NOT_FOUND,
+        "not_found",
+        format!("{} is not found", what),
+    )
+}
+
+fn not_payable() -> ApiError {
+    ApiError::new(
+        StatusCode::CONFLICT,
+        "order_not_payable",
+        "only pending orders can be paid",
+    )
+}
+
+async fn find_order(
+    order_id: i32,
+    user_id: i32,
+    conn: &DatabaseConnection,
+) -> Result<orders::Model, ApiError> {
+    orders::Entity::find_by_id(order_id)
+        .filter(orders::Column::UserId.eq(user_id))
+        .one(conn)
+        .await?
+        .ok_or_else(|| not_found("order"))
+}
+
+// 결제 결과를 반영함, 승인되면 주문을 paid로 바꿈
+// 승인 응답과 웹훅이 모두 여기로 오므로 같은 결과가 여러 번 와도 한 번만 반영됨
+async fn settle(
+    payment: payment::Model,
+    status: PaymentStatus,
+    failure_reason: Option<String>,
+    amount: Option<i64>,
+    txn: &DatabaseTransaction,
+) -> Result<payment::Model, ApiError> {
+    // 승인된 결제는 늦게 도착한 다른 이벤트로 바뀌지 않음
+    if payment.status == PaymentStatus::Succeeded.as_str() || status == PaymentStatus::Pending {
+        return Ok(payment);
+    }
+    if status == PaymentStatus::Succeeded && amount.is_some_and(|a| a != payment.amount) {
+        error!(
+            "Payment {} amount mismatch: expected {}, got {:?}",
+            payment.id, payment.amount, amount
+        );
+        return Ok(payment);
+    }
+
+    let order_id = payment.order_id;
+    let mut active: payment::ActiveModel = payment.into();
+    active.status = Set(status.as_str().to_string());
+    active.failure_reason = Set(failure_reason);
+    active.updated_at = Set(chrono::Utc::now().naive_utc());
+    let payment = active.update(txn).await?;
+
+    if status == PaymentStatus::Succeeded {
+        let order = orders::Entity::find_by_id(order_id)
+            .lock_exclusive()
+            .one(txn)
+            .await?
+            .ok_or_else(|| not_found("order"))?;
+        if order.status == OrderStatus::Pending.as_str() {
+            transition_in(order.id, OrderStatus::Paid, None, None, txn).await?;
+        } else {
+            // 이미 다른 결제로 결제됐거나 취소된 주문, 대행사에서 환불해야함
+            warn!(
+                "Payment {} succeeded but order {} is {}, refund is needed",
+                payment.id, order.id, order.status
+            );
+        }
+    }
+
+    Ok(payment)
+}
+
+// pending 주문의 결제를 만듦, 금액은 주문 총액
+// 진행 중이거나 승인된 결제가 있으면 새로 만들지 않음, 진행 중인 결제는 confirm으로 이어서 승인함
+pub async fn create_intent(
+    order_id: i32,
+    user_id: i32,
+    provider: &dyn PaymentProvider,
+    conn: &DatabaseConnection,
+) -> Result<PaymentDto, ApiError> {
+    // 같은 주문에 동시에 들어온 요청이 결제를 두 개 만들지 않도록 대행사 호출까지 주문 행을 잠가둠
+    let txn = conn.begin().await?;
+    let order = orders::Entity::find_by_id(order_id)
+        .filter(orders::Column::UserId.eq(user_id))
+        .lock_exclusive()
+        .one(&txn)
+        .await?
+        .ok_or_else(|| not_found("order"))?;
+    if order.status != OrderStatus::Pending.as_str() {
+        return Err(not_payable());
+    }
+    let open = payment::Entity::find()
+        .filter(payment::Column::OrderId.eq(order.id))
+        .filter(payment::Column::Provider.eq(provider.name()))
+        .filter(payment::Column::Status.is_in([
+            PaymentStatus::Pending.as_str(),
+            PaymentStatus::Succeeded.as_str(),
+        ]))
+        .one(&txn)
+        .await?;
+    if let Some(open) = open {
+        return Err(ApiError::new(
+            StatusCode::CONFLICT,
+            "payment_in_progress",
+            format!("payment {} of this order is {}", open.id, open.status),
+        ));
+    }
+
+    let intent = provider
+        .create_intent(order.id, Money::from_columns(order.total, &order.currency))
+        .await?;
+    let model = payment::ActiveModel {
+        order_id: Set(order.id),
+        provider: Set(provider.name().to_string()),
+        provider_ref: Set(intent.provider_ref),
+        amount: Set(order.total),
+        currency: Set(order.currency.clone()),
+        status: Set(intent.status.as_str().to_string()),
+        failure_reason: Set(intent.failure_reason),
+        ..Default::default()
+    }
+    .insert(&txn)
+    .await?;
+    txn.commit().await?;
+
+    Ok(PaymentDto {
+        client_secret: intent.client_secret,
+        ..model.into()
+    })
+}
+
+// 결제 승인, 대행사 호출은 트랜잭션 밖에서 하고 결과만 잠근 결제 행에 반영함
+pub async fn confirm(
+    payment_id: i32,
+    user_id: i32,
+    req: PaymentConfirmReq,
+    provider: &dyn PaymentProvider,
+    conn: &DatabaseConnection,
+) -> Result<PaymentDto, ApiError> {
+    let model = payment::Entity::find_by_id(payment_id)
+        .one(conn)
+        .await?
+        .ok_or_else(|| not_found("payment"))?;
+    let order = find_order(model.order_id, user_id, conn)
+        .await
+        .map_err(|_| not_found("payment"))?;
+
+    if model.status == PaymentStatus::Succeeded.as_str() {
+        return Ok(model.into());
+    }
+    if model.provider != provider.name() {
+        return Err(ApiError::new(
+            StatusCode::CONFLICT,
+            "provider_mismatch",
+            "payment was created with another provider",
+        ));
+    }
+    if order.status != OrderStatus::Pending.as_str() {
+        return Err(not_payable());
+    }
+
+    let intent = provider
+        .confirm(&model.provider_ref, req.payment_method.trim())
+        .await?;
+
+    let txn = conn.begin().await?;
+    let model = payment::Entity::find_by_id(payment_id)
+        .lock_exclusive()
+        .one(&txn)
+        .await?
+        .ok_or_else(|| not_found("payment"))?;
+    let model = settle(model, intent.status, intent.failure_reason, None, &txn).await?;
+    txn.commit().await?;
+
+    Ok(model.into())
+}
+
+// 서명이 맞는 웹훅만 처리함, 같은 이벤트 id는 한 번만 반영됨
+// 모르는 결제나 결제와 관계없는 이벤트는 대행사가 다시 보내지 않도록 그냥 받음
+pub async fn handle_webhook(
+    provider: &dyn PaymentProvider,
+    headers: &HeaderMap,
+    body: &[u8],
+    conn: &DatabaseConnection,
+) -> Result<(), ApiError> {
+    let Some(event) = provider.parse_webhook(headers, body)? else {
+        return Ok(());
+    };
+
+    let txn = conn.begin().await?;
+    let model = payment::Entity::find()
+        .filter(payment::Column::Provider.eq(provider.name()))
+        .filter(payment::Column::ProviderRef.eq(&event.provider_ref))
+        .lock_exclusive()
+        .one(&txn)
+        .await?;
+
+    let inserted = payment_event::Entity::insert(payment_event::ActiveModel {
+        provider: Set(provider.name().to_string()),
+        event_id: Set(event.event_id.clone()),
+        payment_id: Set(model.as_ref().map(|m| m.id)),
+        kind: Set(event.kind.clone()),
+        ..Default::default()
+    })
+    .on_conflict(
+        OnConflict::columns([
+            payment_event::Column::Provider,
+            payment_event::Column::EventId,
+        ])
+        .do_nothing()
+        .to_owned(),
+    )
+    .do_nothing()
+    .exec(&txn)
+    .await?;
+    if matches!(inserted, TryInsertResult::Conflicted) {
+        return Ok(());
+    }
+
+    match model {
+        Some(model) => {
+            settle(
+                model,
+                event.status,
+                event.failure_reason,
+                event.amount,
+                &txn,
+            )
+            .await?;
+        }
+        None => warn!(
+            "Webhook {} for unknown payment {}",
+            event.event_id, event.provider_ref
+        ),
+    }
+    txn.commit().await?;
+    Ok(())
+}
+
+// 주문의 결제 시도, 오래된 것부터
+pub async fn list_for_order(
+    order_id: i32,
+    user_id: i32,
+    conn: &DatabaseConnection,
+) -> Result<Vec<PaymentDto>, ApiError> {
+    let order = find_order(order_id, user_id, conn).await?;
+    Ok(payment::Entity::find()
+        .filter(payment::Column::OrderId.eq(order.id))
+        .order_by_asc(payment::Column::Id)
+        .all(conn)
+        .await?
+        .into_iter()
+        .map(PaymentDto::from)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use axum::http::HeaderValue;
+    use sea_orm::{EntityTrait, PaginatorTrait};
+    use serde_json::json;
+
+    use super::*;
+    use crate::utils::payment::{
+        MOCK_DECLINE, MOCK_DELAY, MOCK_SUCCESS, MockProvider, SIGNATURE_HEADER, sign,
+    };
+    use crate::utils::testing;
+
+    const SECRET: &str = "whsec_test";
+    const TOTAL: i64 = 15000;
+
+    // mock이 보내는 웹훅은 닿지 않는 주소로 보내고, 테스트에서 직접 웹훅을 넣음
+    fn provider() -> MockProvider {
+        MockProvider::new(
+            reqwest::Client::new(),
+            SECRET.to_string(),
+            "http://127.0.0.1:9/api/payment/webhook".to_string(),
+            Duration::from_secs(60),
+        )
+    }
+
+    fn webhook(event_id: &str, kind: &str, provider_ref: &str, amount: Option<i64>) -> Vec<u8> {
+        json!({
+            "id": event_id,
+            "type": kind,
+            "data": { "object": { "id": provider_ref, "status": "", "amount": amount } },
+        })
+        .to_string()
+        .into_bytes()
+    }
+
+    fn signed(secret: &str, timestamp: i64, body: &[u8]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            SIGNATURE_HEADER,
+            HeaderValue::from_str(&sign(secret, timestamp, body)).unwrap(),
+        );
+        headers
+    }
+
+    async fn deliver(
+        provider: &MockProvider,
+        body: &[u8],
+        conn: &DatabaseConnection,
+    ) -> Result<(), ApiError> {
+        let headers = signed(SECRET, chrono::Utc::now().timestamp(), body);
+        handle_webhook(provider, &headers, body, conn).await
+    }
+
+    async fn order_status(order: &orders::Model, conn: &DatabaseConnection) -> String {
+        orders::Entity::find_by_id(order.id)
+            .one(conn)
+            .await
+            .unwrap()
+            .unwrap()
+            .status
+    }
+
+    async fn payment_status(payment: &PaymentDto, conn: &DatabaseConnection) -> String {
+        payment::Entity::find_by_id(payment.id)
+            .one(conn)
+            .await
+            .unwrap()
+            .unwrap()
+            .status
+    }
+
+    // 주문 하나와 그 주문의 결제 하나
+    async fn setup(
+        provider: &MockProvider,
+        conn: &DatabaseConnection,
+    ) -> (orders::Model, PaymentDto) {
+        let user = testing::user(conn).await;
+        let order = testing::order(&user, TOTAL, conn).await;
+        let payment = create_intent(order.id, user.id, provider, conn)
+            .await
+            .unwrap();
+        assert_eq!(payment.status, PaymentStatus::Pending.as_str());
+        assert_eq!(payment.amount.amount, TOTAL);
+        (order, payment)
+    }
+
+    // 결제를 지워도 웹훅 기록은 남으므로 먼저 지움
+    async fn cleanup(order: orders::Model, conn: &DatabaseConnection) {
+        let payments = payment::Entity::find()
+            .filter(payment::Column::OrderId.eq(order.id))
+            .all(conn)
+            .await
+            .unwrap();
+        payment_event::Entity::delete_many()
+            .filter(payment_event::Column::PaymentId.is_in(payments.iter().map(|p| p.id)))
+            .exec(conn)
+            .await
+            .unwrap();
+        testing::remove_order(order, conn).await;
+    }
+
+    fn method(value: &str) -> PaymentConfirmReq {
+        PaymentConfirmReq {
+            payment_method: value.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    #[ignore = "needs DATABASE_URL"]
+    async fn success_marks_order_paid() {
+        let conn = testing::connect().await;
+        let provider = provider();
+        let (order, payment) = setup(&provider, &conn).await;
+        let user_id = order.user_id.unwrap();
+
+        let confirmed = confirm(payment.id, user_id, method(MOCK_SUCCESS), &provider, &conn)
+            .await
+            .unwrap();
+        assert_eq!(confirmed.status, PaymentStatus::Succeeded.as_str());
+        assert_eq!(
+            order_status(&order, &conn).await,
+            OrderStatus::Paid.as_str()
+        );
+
+        // 늦게 도착한 실패 웹훅은 승인된 결제를 바꾸지 않음
+        let body = webhook(
+            &testing::unique("evt"),
+            "payment_intent.payment_failed",
+            &payment.provider_ref,
+            None,
+        );
+        deliver(&provider, &body, &conn).await.unwrap();
+        assert_eq!(
+            payment_status(&payment, &conn).await,
+            PaymentStatus::Succeeded.as_str()
+        );
+
+        let err = create_intent(order.id, user_id, &provider, &conn)
+            .await
+            .unwrap_err();
+        assert_eq!(err.error, "order_not_payable");
+        cleanup(order, &conn).await;
+    }
+
+    #[tokio::test]
+    #[ignore = "needs DATABASE_URL"]
+    async fn decline_keeps_order_pending() {
+        let conn = testing::connect().await;
+        let provider = provider();
+        let (order, payment) = setup(&provider, &conn).await;
+        let user_id = order.user_id.unwrap();
+
+        let declined = confirm(payment.id, user_id, method(MOCK_DECLINE), &provider, &conn)
+            .await
+            .unwrap();
+        assert_eq!(declined.status, PaymentStatus::Declined.as_str());
+        assert!(declined.failure_reason.is_some());
+        assert_eq!(
+            order_status(&order, &conn).await,
+            OrderStatus::Pending.as_str()
+        );
+
+        // 거절된 뒤에 다시 결제할 수 있음
+        let retried = confirm(payment.id, user_id, method(MOCK_SUCCESS), &provider, &conn)
+            .await
+            .unwrap();
+        assert_eq!(retried.status, PaymentStatus::Succeeded.as_str());
+        assert_eq!(
+            order_status(&order, &conn).await,
+            OrderStatus::Paid.as_str()
+        );
+        cleanup(order, &conn).await;
+    }
+
+    #[tokio::test]
+    #[ignore = "needs DATABASE_URL"]
+    async fn open_payment_blocks_new_intent() {
+        let conn = testing::connect().await;
+        let provider = provider();
+        let (order, payment) = setup(&provider, &conn).await;
+        let user_id = order.user_id.unwrap();
+
+        let err = create_intent(order.id, user_id, &provider, &conn)
+            .await
+            .unwrap_err();
+        assert_eq!(err.error, "payment_in_progress");
+
+        // 거절된 결제만 있으면 새로 만들 수 있음
+        confirm(payment.id, user_id, method(MOCK_DECLINE), &provider, &conn)
+            .await
+            .unwrap();
+        let created = create_intent(order.id, user_id, &provider, &conn)
+            .await
+            .unwrap();
+        assert_ne!(created.id, payment.id);
+        cleanup(order, &conn).await;
+    }
+
+    #[tokio::test]
+    #[ignore = "needs DATABASE_URL"]
+    async fn delay_waits_for_webhook() {
+        let conn = testing::connect().await;
+        let provider = provider();
+        let (order, payment) = setup(&provider, &conn).await;
+        let user_id = order.user_id.unwrap();
+
+        let pending = confirm(payment.id, user_id, method(MOCK_DELAY), &provider, &conn)
+            .await
+            .unwrap();
+        assert_eq!(pending.status, PaymentStatus::Pending.as_str());
+        assert_eq!(
+            order_status(&order, &conn).await,
+            OrderStatus::Pending.as_str()
+        );
+
+        let processing = webhook(
+            &testing::unique("evt"),
+            "payment_intent.processing",
+            &payment.provider_ref,
+            None,
+        );
+        deliver(&provider, &processing, &conn).await.unwrap();
+        assert_eq!(
+            order_status(&order, &conn).await,
+            OrderStatus::Pending.as_str()
+        );
+
+        let succeeded = webhook(
+            &testing::unique("evt"),
+            "payment_intent.succeeded",
+            &payment.provider_ref,
+            Some(TOTAL),
+        );
+        deliver(&provider, &succeeded, &conn).await.unwrap();
+        assert_eq!(
+            payment_status(&payment, &conn).await,
+            PaymentStatus::Succeeded.as_str()
+        );
+        assert_eq!(
+            order_status(&order, &conn).await,
+            OrderStatus::Paid.as_str()
+        );
+        cleanup(order, &conn).await;
+    }
+
+    #[tokio::test]
+    #[ignore = "needs DATABASE_URL"]
+    async fn duplicate_event_is_applied_once() {
+        let conn = testing::connect().await;
+        let provider = provider();
+        let (order, payment) = setup(&provider, &conn).await;
+        let event_id = testing::unique("evt");
+
+        let failed = webhook(
+            &event_id,
+            "payment_intent.payment_failed",
+            &payment.provider_ref,
+            None,
+        );
+        deliver(&provider, &failed, &conn).await.unwrap();
+        assert_eq!(
+            payment_status(&payment, &conn).await,
+            PaymentStatus::Declined.as_str()
+        );
+
+        // 같은 이벤트 id는 본문이 달라도 다시 반영하지 않음
+        for _ in 0..2 {
+            let replayed = webhook(
+                &event_id,
+                "payment_intent.succeeded",
+                &payment.provider_ref,
+                Some(TOTAL),
+            );
+            deliver(&provider, &replayed, &conn).await.unwrap();
+        }
+        assert_eq!(
+            payment_status(&payment, &conn).await,
+            PaymentStatus::Declined.as_str()
+        );
+        assert_eq!(
+            order_status(&order, &conn).await,
+            OrderStatus::Pending.as_str()
+        );
+        let events = payment_event::Entity::find()
+            .filter(payment_event::Column::EventId.eq(&event_id))
+            .count(&conn)
+            .await
+            .unwrap();
+        assert_eq!(events, 1);
+
+        cleanup(order, &conn).await;
+    }
+
+    #[tokio::test]
+    #[ignore = "needs DATABASE_URL"]
+    async fn bad_or_stale_signature_changes_nothing() {
+        let conn = testing::connect().await;
+        let provider = provider();
+        let (order, payment) = setup(&provider, &conn).await;
+        let event_id = testing::unique("evt");
+        let body = webhook(
+            &event_id,
+            "payment_intent.succeeded",
+            &payment.provider_ref,
+            Some(TOTAL),
+        );
+
+        let now = chrono::Utc::now().timestamp();
+        for headers in [
+            signed("whsec_other", now, &body),
+            signed(SECRET, now - 3600, &body),
+            HeaderMap::new(),
+        ] {
+            let err = handle_webhook(&provider, &headers, &body, &conn)
+                .await
+                .unwrap_err();
+            assert_eq!(err.code, 400);
+        }
+
+        assert_eq!(
+            payment_status(&payment, &conn).await,
+            PaymentStatus::Pending.as_str()
+        );
+        assert_eq!(
+            order_status(&order, &conn).await,
+            OrderStatus::Pending.as_str()
+        );
+        let events = payment_event::Entity::find()
+            .filter(payment_event::Column::EventId.eq(&event_id))
+            .count(&conn)
+            .await
+            .unwrap();
+        assert_eq!(events, 0);
+        cleanup(order, &conn).await;
+    }
+
+    #[tokio::test]
+    #[ignore = "needs DATABASE_URL"]
+    async fn amount_mismatch_does_not_mark_paid() {
+        let conn = testing::connect().await;
+        let provider = provider();
+        let (order, payment) = setup(&provider, &conn).await;
+
+        let body = webhook(
+            &testing::unique("evt"),
+            "payment_intent.succeeded",
+            &payment.provider_ref,
+            Some(TOTAL - 1),
+        );
+        deliver(&provider, &body, &conn).await.unwrap();
+        assert_eq!(
+            payment_status(&payment, &conn).await,
+            PaymentStatus::Pending.as_str()
+        );
+        assert_eq!(
+            order_status(&order, &conn).await,
+            OrderStatus::Pending.as_str()
+        );
+        cleanup(order, &conn).await;
+    }
+}
@@ -0,0 +1,433 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use validate_dto_macro::validate_dto;
+
+#[cfg(feature = "server")]
+use crate::resources::dto::page::Page;
+#[cfg(feature = "server")]
+use crate::utils::errors::ApiError;
+
+// post.status 컬럼 값, 초안은 작성자와 관리자만 볼 수 있음
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum PostStatus {
+    #[default]
+    Draft,
+    Published,
+}
+impl PostStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PostStatus::Draft => "draft",
+            PostStatus::Published => "published",
+        }
+    }
+}
+
+// 목록에 쓰는 글 정보, 본문은 상세에서만 내려줌
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct PostDto {
+    pub id: i32,
+    // 탈퇴한 유저의 글은 None
+    pub author_id: Option<i32>,
+    pub author_name: Option<String>,
+    pub title: String,
+    pub slug: String,
+    pub status: PostStatus,
+    // 초안이면 None
+    #[cfg_attr(feature = "server", schema(value_type = Option<String>, format = DateTime))]
+    pub published_at: Option<NaiveDateTime>,
+    #[cfg_attr(feature = "server", schema(value_type = String, format = DateTime))]
+    pub created_at: NaiveDateTime,
+    #[cfg_attr(feature = "server", schema(value_type = String, format = DateTime))]
+    pub updated_at: NaiveDateTime,
+}
+
+#[cfg(feature = "server")]
+impl From<crate::resources::entities::post::Model> for PostDto {
+    fn from(value: crate::resources::entities::post::Model) -> Self {
+        let status = if value.status == PostStatus::Published.as_str() {
+            PostStatus::Published
+        } else {
+            PostStatus::Draft
+        };
+        PostDto {
+            id: value.id,
+            author_id: value.author_id,
+            author_name: None,
+            title: value.title,
+            slug: value.slug,
+            status,
+            published_at: value
+                .published_at
+                .filter(|_| status == PostStatus::Published),
+            created_at: value.created_at,
+            updated_at: value.updated_at,
+        }
+    }
+}
+
+// 본문 Markdown 원문과 서버에서 바꾼 HTML, html은 정리된 것이라 그대로 넣어도 됨
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct PostDetailDto {
+    #[serde(flatten)]
+    pub post: PostDto,
+    pub body: String,
+    pub html: String,
+}
+
+#[cfg(feature = "server")]
+impl From<crate::resources::entities::post::Model> for PostDetailDto {
+    fn from(value: crate::resources::entities::post::Model) -> Self {
+        let html = crate::utils::markdown::render(&value.body);
+        let body = value.body.clone();
+        PostDetailDto {
+            post: value.into(),
+            body,
+            html,
+        }
+    }
+}
+
+// 작성, 수정 본문, slug를 비워두면 제목으로 만들고 status를 비워두면 초안
+#[validate_dto]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct PostReq {
+    #[validate(length(min = 1, max = 200))]
+    pub title: String,
+    #[validate(length(min = 1, max = 120))]
+    pub slug: Option<String>,
+    #[validate(length(max = 100000))]
+    pub body: String,
+    pub status: Option<PostStatus>,
+}
+
+#[cfg(feature = "server")]
+fn not_found() -> ApiError {
+    ApiError::new(
+        reqwest::StatusCode::NOT_FOUND,
+        "not_found",
+        "post is not found",
+    )
+}
+
+#[cfg(feature = "server")]
+fn not_author() -> ApiError {
+    ApiError::new(
+        reqwest::StatusCode::FORBIDDEN,
+        "forbidden",
+        "only the author can change this post",
+    )
+}
+
+// slug가 겹치면 idx_post_slug 위반
+#[cfg(feature = "server")]
+fn conflict(err: sea_orm::DbErr) -> ApiError {
+    use sea_orm::SqlErr;
+
+    match err.sql_err() {
+        Some(SqlErr::UniqueConstraintViolation(_)) => ApiError::new(
+            reqwest::StatusCode::CONFLICT,
+            "slug_exists",
+            "slug is already used",
+        ),
+        _ => err.into(),
+    }
+}
+
+// 작성자 본인의 글을 잠금, 관리자는 남의 글도 고칠 수 있음
+#[cfg(feature = "server")]
+async fn find_own(
+    id: i32,
+    user_id: i32,
+    is_admin: bool,
+    conn: &impl sea_orm::ConnectionTrait,
+) -> Result<crate::resources::entities::post::Model, ApiError> {
+    use sea_orm::{EntityTrait, QuerySelect};
+
+    use crate::resources::entities::post;
+
+    let model = post::Entity::find_by_id(id)
+        .lock_exclusive()
+        .one(conn)
+        .await?
+        .ok_or_else(not_found)?;
+    if !is_admin && model.author_id != Some(user_id) {
+        return Err(not_author());
+    }
+    Ok(model)
+}
+
+// 목록의 작성자 이름을 한 번에 채움
+#[cfg(feature = "server")]
+async fn fill_authors(
+    items: &mut [PostDto],
+    conn: &sea_orm::DatabaseConnection,
+) -> Result<(), sea_orm::DbErr> {
+    use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QuerySelect};
+    use std::collections::HashMap;
+
+    use crate::resources::entities::users;
+
+    let ids: Vec<i32> = items.iter().filter_map(|p| p.author_id).collect();
+    if ids.is_empty() {
+        return Ok(());
+    }
+    let names: HashMap<i32, Option<String>> = users::Entity::find()
+        .select_only()
+        .column(users::Column::Id)
+        .column(users::Column::DisplayName)
+        .filter(users::Column::Id.is_in(ids))
+        .into_tuple::<(i32, Option<String>)>()
+        .all(conn)
+        .await?
+        .into_iter()
+        .collect();
+    for item in items.iter_mut() {
+        item.author_name = item
+            .author_id
+            .and_then(|id| names.get(&id).cloned().flatten());
+    }
+    Ok(())
+}
+
+#[cfg(feature = "server")]
+async fn with_author(
+    model: crate::resources::entities::post::Model,
+    conn: &sea_orm::DatabaseConnection,
+) -> Result<PostDetailDto, ApiError> {
+    let mut dto = PostDetailDto::from(model);
+    fill_authors(std::slice::from_mut(&mut dto.post), conn).await?;
+    Ok(dto)
+}
+
+#[cfg(feature = "server")]
+impl PostReq {
+    fn slug(&self) -> Result<String, ApiError> {
+        use crate::resources::dto::category::slugify;
+        use crate::utils::validate::ValidationErrors;
+
+        // 제목으로 만들면 컬럼 길이(120)를 넘을 수 있음
+        let slug: String = slugify(self.slug.as_deref().unwrap_or(&self.title))
+            .chars()
+            .take(120)
+            .collect();
+        let slug = slug.trim_end_matches('-').to_string();
+        if slug.is_empty() {
+            let mut errors = ValidationErrors::default();
+            errors.add("slug", "slug", "must contain letters or digits".to_string());
+            errors.into_result()?;
+        }
+        Ok(slug)
+    }
+
+    pub async fn create(
+        self,
+        author_id: i32,
+        conn: &sea_orm::DatabaseConnection,
+    ) -> Result<PostDetailDto, ApiError> {
+        use sea_orm::{ActiveModelTrait, ActiveValue::Set};
+
+        use crate::resources::entities::post;
+
+        let slug = self.slug()?;
+        let status = self.status.unwrap_or_default();
+        let model = post::ActiveModel {
+            author_id: Set(Some(author_id)),
+            title: Set(self.title.trim().to_string()),
+            slug: Set(slug),
+            body: Set(self.body),
+            status: Set(status.as_str().to_string()),
+            published_at: Set(
+                (status == PostStatus::Published).then(|| chrono::Utc::now().naive_utc())
+            ),
+            ..Default::default()
+        }
+        .insert(conn)
+        .await
+        .map_err(conflict)?;
+        with_author(model, conn).await
+    }
+
+    // 처음 게시할 때만 published_at을 정함, 고쳐도 게시 시각은 그대로
+    pub async fn update(
+        self,
+        id: i32,
+        user_id: i32,
+        is_admin: bool,
+        conn: &sea_orm::DatabaseConnection,
+    ) -> Result<PostDetailDto, ApiError> {
+        use sea_orm::{ActiveModelTrait, ActiveValue::Set, TransactionTrait};
+
+        use crate::resources::entities::post;
+
+        let slug = self.slug()?;
+        let status = self.status.unwrap_or_default();
+        let now = chrono::Utc::now().naive_utc();
+
+        let txn = conn.begin().await?;
+        let before = find_own(id, user_id, is_admin, &txn).await?;
+        let published_at = before
+            .published_at
+            .or((status == PostStatus::Published).then_some(now));
+
+        let mut active: post::ActiveModel = before.into();
+        active.title = Set(self.title.trim().to_string());
+        active.slug = Set(slug);
+        active.body = Set(self.body);
+        active.status = Set(status.as_str().to_string());
+        active.published_at = Set(published_at);
+        active.updated_at = Set(now);
+        let model = active.update(&txn).await.map_err(conflict)?;
+        txn.commit().await?;
+
+        with_author(model, conn).await
+    }
+}
+
+#[cfg(feature = "server")]
+pub async fn delete_post(
+    id: i32,
+    user_id: i32,
+    is_admin: bool,
+    conn: &sea_orm::DatabaseConnection,
+) -> Result<(), ApiError> {
+    use sea_orm::{ModelTrait, TransactionTrait};
+
+    let txn = conn.begin().await?;
+    find_own(id, user_id, is_admin, &txn)
+        .await?
+        .delete(&txn)
+        .await?;
+    txn.commit().await?;
+    Ok(())
+}
+
+#[cfg(feature = "server")]
+impl PostDetailDto {
+    // 게시된 글만, 초안은 없는 글처럼 404
+    pub async fn published(
+        slug: &str,
+        conn: &sea_orm::DatabaseConnection,
+    ) -> Result<Self, ApiError> {
+        use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+
+        use crate::resources::entities::post;
+
+        let model = post::Entity::find()
+            .filter(post::Column::Slug.eq(slug))
+            .filter(post::Column::Status.eq(PostStatus::Published.as_str()))
+            .one(conn)
+            .await?
+            .ok_or_else(not_found)?;
+        with_author(model, conn).await
+    }
+
+    // 작성자가 초안을 미리 볼 때
+    pub async fn get_own(
+        id: i32,
+        user_id: i32,
+        is_admin: bool,
+        conn: &sea_orm::DatabaseConnection,
+    ) -> Result<Self, ApiError> {
+        use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+
+        use crate::resources::entities::post;
+
+        let model = post::Entity::find()
+            .filter(post::Column::Id.eq(id))
+            .one(conn)
+            .await?
+            .ok_or_else(not_found)?;
+        if !is_admin && model.author_id != Some(user_id) {
+            return Err(not_author());
+        }
+        with_author(model, conn).await
+    }
+}
+
+// 게시된 글 목록, author_id를 주면 그 유저의 글만 (초안 포함)
+// 정렬을 주지 않으면 최신순
+#[cfg(feature = "server")]
+pub async fn list_posts(
+    author_id: Option<i32>,
+    list: crate::resources::dto::query::ListQuery<crate::resources::entities::post::Entity>,
+    cursor: Option<&str>,
+    limit: Option<u64>,
+    with_total: bool,
+    conn: &sea_orm::DatabaseConnection,
+) -> Result<Page<PostDto>, ApiError> {
+    use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+
+    use crate::resources::entities::post;
+
+    let select = match author_id {
+        Some(author_id) => post::Entity::find().filter(post::Column::AuthorId.eq(author_id)),
+        None => {
+            post::Entity::find().filter(post::Column::Status.eq(PostStatus::Published.as_str()))
+        }
+    };
+    let mut page: Page<PostDto> = list.page(select, cursor, limit, with_total, conn).await?;
+    fill_authors(&mut page.items, conn).await?;
+    Ok(page)
+}
+
+// 목록 API(filter, sort)에서 쓸 수 있는 컬럼
+#[cfg(feature = "server")]
+impl crate::resources::dto::query::QueryFields for crate::resources::entities::post::Entity {
+    const FIELDS: &'static [crate::resources::dto::query::QueryField<Self::Column>] = {
+        use crate::resources::dto::query::{FieldKind, QueryField};
+        use crate::resources::entities::post::Column;
+
+        &[
+            QueryField {
+                name: "id",
+                column: Column::Id,
+                kind: FieldKind::Int,
+                sortable: true,
+            },
+            QueryField {
+                name: "title",
+                column: Column::Title,
+                kind: FieldKind::Text,
+                sortable: true,
+            },
+            QueryField {
+                name: "author_id",
+                column: Column::AuthorId,
+                kind: FieldKind::Int,
+                sortable: false,
+            },
+            QueryField {
+                name: "status",
+                column: Column::Status,
+                kind: FieldKind::Text,
+                sortable: false,
+            },
+            QueryField {
+                name: "published_at",
+                column: Column::PublishedAt,
+                kind: FieldKind::DateTime,
+                sortable: false,
+            },
+            QueryField {
+                name: "created_at",
+                column: Column::CreatedAt,
+                kind: FieldKind::DateTime,
+                sortable: true,
+            },
+            QueryField {
+                name: "updated_at",
+                column: Column::UpdatedAt,
+                kind: FieldKind::DateTime,
+                sortable: true,
+            },
+        ]
+    };
+    const ID: Self::Column = crate::resources::entities::post::Column::Id;
+    const NEWEST_FIRST: bool = true;
+}
@@ -0,0 +1,361 @@
+use serde::{Deserialize, Serialize};
+use validate_dto_macro::validate_dto;
+
+use crate::resources::dto::money::Money;
+use crate::resources::dto::product_variant::{ProductOptionDto, ProductVariantDto};
+#[cfg(feature = "server")]
+use crate::utils::errors::ApiError;
+
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct ProductDto {
+    pub id: i32,
+    pub title: String,
+    pub price: Money,
+    pub category_id: i32,
+    // 외부 시트와 맞추는 상품 코드, 가져오기는 이 값으로 찾아서 덮어씀
+    pub sku: Option<String>,
+    // 사이즈, 색상 같은 옵션과 옵션 조합별 변형
+    #[serde(default)]
+    pub options: Vec<ProductOptionDto>,
+    #[serde(default)]
+    pub variants: Vec<ProductVariantDto>,
+}
+
+#[cfg(feature = "server")]
+impl From<crate::resources::entities::product::Model> for ProductDto {
+    fn from(value: crate::resources::entities::product::Model) -> Self {
+        ProductDto {
+            id: value.id,
+            title: value.title,
+            price: Money::from_columns(value.price, &value.currency),
+            category_id: value.category_id,
+            sku: value.sku,
+            options: Vec::new(),
+            variants: Vec::new(),
+        }
+    }
+}
+
+// 상품 등록, 수정 본문, category_id는 이미 있는 카테고리여야함
+#[validate_dto]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ProductReq {
+    #[validate(length(min = 1, max = 100))]
+    pub title: String,
+    #[validate(nested)]
+    pub price: Money,
+    pub category_id: i32,
+    #[validate(length(min = 1, max = 64))]
+    pub sku: Option<String>,
+}
+
+// 가격이 바뀐 시점과 그때의 가격
+#[cfg(feature = "server")]
+#[derive(utoipa::ToSchema, Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct ProductPriceDto {
+    pub price: Money,
+    #[schema(value_type = String, format = DateTime)]
+    pub changed_at: chrono::NaiveDateTime,
+}
+
+#[cfg(feature = "server")]
+fn not_found() -> ApiError {
+    ApiError::new(
+        reqwest::StatusCode::NOT_FOUND,
+        "not_found",
+        "product is not found",
+    )
+}
+
+// 없는 카테고리를 가리키면 fk_product_category, sku가 겹치면 idx_product_sku 위반
+#[cfg(feature = "server")]
+fn conflict(err: sea_orm::DbErr) -> ApiError {
+    use reqwest::StatusCode;
+    use sea_orm::SqlErr;
+
+    match err.sql_err() {
+        Some(SqlErr::ForeignKeyConstraintViolation(_)) => ApiError::new(
+            StatusCode::CONFLICT,
+            "category_not_found",
+            "category doesn't exist",
+        ),
+        Some(SqlErr::UniqueConstraintViolation(_)) => {
+            ApiError::new(StatusCode::CONFLICT, "sku_exists", "sku is already used")
+        }
+        _ => err.into(),
+    }
+}
+
+#[cfg(feature = "server")]
+impl ProductReq {
+    fn into_active(self) -> crate::resources::entities::product::ActiveModel {
+        use sea_orm::ActiveValue::Set;
+
+        crate::resources::entities::product::ActiveModel {
+            title: Set(self.title.trim().to_string()),
+            price: Set(self.price.amount),
+            currency: Set(self.price.currency.as_str().to_string()),
+            category_id: Set(self.category_id),
+            sku: Set(self
+                .sku
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())),
+            ..Default::default()
+        }
+    }
+
+    // user_id는 가격 이력에 남길 유저
+    pub async fn create(
+        self,
+        user_id: Option<i32>,
+        conn: &sea_orm::DatabaseConnection,
+    ) -> Result<ProductDto, ApiError> {
+        use sea_orm::{ActiveModelTrait, TransactionTrait};
+
+        let txn = conn.begin().await?;
+        let model = self.into_active().insert(&txn).await.map_err(conflict)?;
+        record_price(&model, user_id, &txn).await?;
+        txn.commit().await?;
+        Ok(model.into())
+    }
+
+    // 가격이나 통화가 바뀌었을 때만 이력을 남김
+    pub async fn update(
+        self,
+        id: i32,
+        user_id: Option<i32>,
+        conn: &sea_orm::DatabaseConnection,
+    ) -> Result<ProductDto, ApiError> {
+        use reqwest::StatusCode;
+        use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QuerySelect, TransactionTrait};
+
+        use crate::resources::dto::product_variant::{attach_variants, has_price_overrides};
+        use crate::resources::entities::product;
+
+        let txn = conn.begin().await?;
+        let before = product::Entity::find_by_id(id)
+            .lock_exclusive()
+            .one(&txn)
+            .await?
+            .ok_or_else(not_found)?;
+        let model = product::Entity::update_many()
+            .set(self.into_active())
+            .filter(product::Column::Id.eq(id))
+            .exec_with_returning(&txn)
+            .await
+            .map_err(conflict)?
+            .pop()
+            .ok_or_else(not_found)?;
+        if before.currency != model.currency && has_price_overrides(id, &txn).await? {
+            return Err(ApiError::new(
+                StatusCode::CONFLICT,
+                "currency_mismatch",
+                "variant prices use the current currency",
+            ));
+        }
+        if before.price != model.price || before.currency != model.currency {
+            record_price(&model, user_id, &txn).await?;
+        }
+        txn.commit().await?;
+
+        let mut product = [ProductDto::from(model)];
+        attach_variants(&mut product, conn).await?;
+        let [product] = product;
+        Ok(product)
+    }
+}
+
+// 지금 가격을 가격 이력에 남김, 가져오기에서도 씀
+#[cfg(feature = "server")]
+pub async fn record_price(
+    model: &crate::resources::entities::product::Model,
+    user_id: Option<i32>,
+    conn: &impl sea_orm::ConnectionTrait,
+) -> Result<(), sea_orm::DbErr> {
+    use sea_orm::{ActiveModelTrait, ActiveValue::Set};
+
+    use crate::resources::entities::product_price_history;
+
+    product_price_history::ActiveModel {
+        product_id: Set(model.id),
+        amount: Set(model.price),
+        currency: Set(model.currency.clone()),
+        user_id: Set(user_id),
+        ..Default::default()
+    }
+    .insert(conn)
+    .await?;
+    Ok(())
+}
+
+// 오래된 것부터, 상품을 만들 때의 가격이 첫 항목
+#[cfg(feature = "server")]
+pub async fn price_history(
+    id: i32,
+    conn: &sea_orm::DatabaseConnection,
+) -> Result<Vec<ProductPriceDto>, ApiError> {
+    use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QueryOrder};
+
+    use crate::resources::entities::product_price_history;
+
+    get_product(id, conn).await?;
+    Ok(product_price_history::Entity::find()
+        .filter(product_price_history::Column::ProductId.eq(id))
+        .order_by_asc(product_price_history::Column::Id)
+        .all(conn)
+        .await?
+        .into_iter()
+        .map(|m| ProductPriceDto {
+            price: Money::from_columns(m.amount, &m.currency),
+            changed_at: m.changed_at,
+        })
+        .collect())
+}
+
+#[cfg(feature = "server")]
+pub async fn get_product(
+    id: i32,
+    conn: &sea_orm::DatabaseConnection,
+) -> Result<ProductDto, ApiError> {
+    use sea_orm::EntityTrait;
+
+    crate::resources::entities::product::Entity::find_by_id(id)
+        .one(conn)
+        .await?
+        .map(ProductDto::from)
+        .ok_or_else(not_found)
+}
+
+// 옵션과 변형까지 채운 상품
+#[cfg(feature = "server")]
+pub async fn get_product_detail(
+    id: i32,
+    conn: &sea_orm::DatabaseConnection,
+) -> Result<ProductDto, ApiError> {
+    use crate::resources::dto::product_variant::attach_variants;
+
+    let mut product = [get_product(id, conn).await?];
+    attach_variants(&mut product, conn).await?;
+    let [product] = product;
+    Ok(product)
+}
+
+// 상품 목록 API, 카테고리별 목록 API, 상품 목록 화면이 같이 씀
+// category_id가 있으면 그 카테고리와 모든 하위 카테고리의 상품, 없는 카테고리면 404
+#[cfg(feature = "server")]
+pub async fn find_products(
+    category_id: Option<i32>,
+    list: crate::resources::dto::query::ListQuery<crate::resources::entities::product::Entity>,
+    cursor: Option<&str>,
+    limit: Option<u64>,
+    with_total: bool,
+    conn: &sea_orm::DatabaseConnection,
+) -> Result<crate::resources::dto::page::Page<ProductDto>, ApiError> {
+    use reqwest::StatusCode;
+    use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+
+    use crate::resources::dto::category::subtree_ids;
+    use crate::resources::dto::product_variant::attach_variants;
+    use crate::resources::entities::product;
+
+    let mut select = product::Entity::find();
+    if let Some(category_id) = category_id {
+        let ids = subtree_ids(category_id, conn).await?;
+        if ids.is_empty() {
+            return Err(ApiError::new(
+                StatusCode::NOT_FOUND,
+                "not_found",
+                "category is not found",
+            ));
+        }
+        select = select.filter(product::Column::CategoryId.is_in(ids));
+    }
+
+    let mut page = list.page(select, cursor, limit, with_total, conn).await?;
+    attach_variants(&mut page.items, conn).await?;
+    Ok(page)
+}
+
+// 이미지 행은 FK로 같이 지워지고, 저장소의 파일은 그 뒤에 지움
+#[cfg(feature = "server")]
+pub async fn delete_product(
+    id: i32,
+    conn: &sea_orm::DatabaseConnection,
+    storage: &dyn crate::utils::storage::Storage,
+) -> Result<(), ApiError> {
+    use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QuerySelect};
+
+    use crate::resources::dto::product_image::image_keys;
+    use crate::resources::entities::product_image;
+
+    let prefixes: Vec<String> = product_image::Entity::find()
+        .select_only()
+        .column(product_image::Column::StorageKey)
+        .filter(product_image::Column::ProductId.eq(id))
+        .into_tuple()
+        .all(conn)
+        .await?;
+
+    let res = crate::resources::entities::product::Entity::delete_by_id(id)
+        .exec(conn)
+        .await?;
+    if res.rows_affected == 0 {
+        return Err(not_found());
+    }
+
+    for key in prefixes.iter().flat_map(|p| image_keys(p)) {
+        let _ = storage.delete(&key).await;
+    }
+    Ok(())
+}
+
+// 목록 API(filter, sort)에서 쓸 수 있는 컬럼
+#[cfg(feature = "server")]
+impl crate::resources::dto::query::QueryFields for crate::resources::entities::product::Entity {
+    const FIELDS: &'static [crate::resources::dto::query::QueryField<Self::Column>] = {
+        use crate::resources::dto::query::{FieldKind, QueryField};
+        use crate::resources::entities::product::Column;
+
+        &[
+            QueryField {
+                name: "id",
+                column: Column::Id,
+                kind: FieldKind::Int,
+                sortable: true,
+            },
+            QueryField {
+                name: "title",
+                column: Column::Title,
+                kind: FieldKind::Text,
+                sortable: true,
+            },
+            QueryField {
+                name: "price",
+                column: Column::Price,
+                kind: FieldKind::Int,
+                sortable: true,
+            },
+            QueryField {
+                name: "currency",
+                column: Column::Currency,
+                kind: FieldKind::Text,
+                sortable: true,
+            },
+            QueryField {
+                name: "category_id",
+                column: Column::CategoryId,
+                kind: FieldKind::Int,
+                sortable: true,
+            },
+            QueryField {
+                name: "sku",
+                column: Column::Sku,
+                kind: FieldKind::Text,
+                sortable: false,
+            },
+        ]
+    };
+    const ID: Self::Column = crate::resources::entities::product::Column::Id;
+}
@@ -0,0 +1,465 @@
+use serde::{Deserialize, Serialize};
+use validate_dto_macro::validate_dto;
+
+#[cfg(feature = "server")]
+use crate::utils::errors::{ApiError, AppError};
+#[cfg(feature = "server")]
+use crate::utils::storage::Storage;
+
+// 원본은 10MB, 가로세로 8000px까지 받음
+pub const IMAGE_MAX_BYTES: usize = 10 * 1024 * 1024;
+pub const IMAGE_MAX_SIDE: u32 = 8000;
+// 파생 이미지의 최대 가로 크기, 원본보다 크게 늘리지는 않음
+pub const IMAGE_WIDTHS: [u32; 3] = [1200, 600, 200];
+// 파생 이미지 형식, 브라우저가 webp를 지원하지 않으면 jpg를 씀
+pub const IMAGE_FORMATS: [&str; 2] = ["webp", "jpg"];
+#[cfg(feature = "server")]
+const JPEG_QUALITY: u8 = 85;
+
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct ImageVariant {
+    pub width: u32,
+    pub height: u32,
+    // webp, jpg
+    pub format: String,
+    pub url: String,
+}
+
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct ProductImageDto {
+    pub id: i32,
+    pub product_id: i32,
+    pub alt: Option<String>,
+    // 작은 값이 먼저
+    pub position: i32,
+    pub is_primary: bool,
+    // 큰 크기부터
+    pub variants: Vec<ImageVariant>,
+}
+
+// 보내지 않은 값은 그대로 둠
+#[validate_dto]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[derive(Deserialize, Serialize, Debug)]
+pub struct ProductImageUpdateReq {
+    #[validate(length(max = 200))]
+    pub alt: Option<String>,
+    pub position: Option<i32>,
+    // true면 이 상품의 다른 대표 이미지는 해제됨
+    pub is_primary: Option<bool>,
+}
+
+// 이미지 id를 보여줄 순서대로 나열, 상품의 모든 이미지가 있어야함
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[derive(Deserialize, Serialize, Debug)]
+pub struct ProductImageOrderReq {
+    pub image_ids: Vec<i32>,
+}
+
+#[cfg(feature = "server")]
+fn variant_key(prefix: &str, width: u32, format: &str) -> String {
+    format!("{}-{}.{}", prefix, width, format)
+}
+
+// 원본 크기에 맞춘 파생 이미지 크기, 비율을 유지함
+#[cfg(feature = "server")]
+fn fit(width: u32, height: u32, max_width: u32) -> (u32, u32) {
+    if width <= max_width {
+        return (width, height);
+    }
+    let h = (height as u64 * max_width as u64 / width as u64).max(1) as u32;
+    (max_width, h)
+}
+
+// 저장된 이미지의 모든 파생 파일 키
+#[cfg(feature = "server")]
+pub(crate) fn image_keys(prefix: &str) -> Vec<String> {
+    IMAGE_WIDTHS
+        .iter()
+        .flat_map(|&w| IMAGE_FORMATS.iter().map(move |f| variant_key(prefix, w, f)))
+        .collect()
+}
+
+#[cfg(feature = "server")]
+impl ProductImageDto {
+    pub fn from_model(
+        model: crate::resources::entities::product_image::Model,
+        storage: &dyn Storage,
+    ) -> Self {
+        let variants = IMAGE_WIDTHS
+            .iter()
+            .flat_map(|&max_width| {
+                let (width, height) = fit(model.width as u32, model.height as u32, max_width);
+                let key = &model.storage_key;
+                IMAGE_FORMATS.iter().map(move |format| ImageVariant {
+                    width,
+                    height,
+                    format: format.to_string(),
+                    url: storage.url(&variant_key(key, max_width, format)),
+                })
+            })
+            .collect();
+
+        ProductImageDto {
+            id: model.id,
+            product_id: model.product_id,
+            alt: model.alt,
+            position: model.position,
+            is_primary: model.is_primary,
+            variants,
+        }
+    }
+
+    pub async fn list(
+        product_id: i32,
+        conn: &sea_orm::DatabaseConnection,
+        storage: &dyn Storage,
+    ) -> Result<Vec<Self>, ApiError> {
+        use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QueryOrder};
+
+        use crate::resources::entities::product_image;
+
+        Ok(product_image::Entity::find()
+            .filter(product_image::Column::ProductId.eq(product_id))
+            .order_by_asc(product_image::Column::Position)
+            .order_by_asc(product_image::Column::Id)
+            .all(conn)
+            .await?
+            .into_iter()
+            .map(|m| Self::from_model(m, storage))
+            .collect())
+    }
+}
+
+#[cfg(feature = "server")]
+fn not_found() -> ApiError {
+    ApiError::new(
+        reqwest::StatusCode::NOT_FOUND,
+        "not_found",
+        "image is not found",
+    )
+}
+
+#[cfg(feature = "server")]
+async fn find_image(
+    product_id: i32,
+    image_id: i32,
+    conn: &impl sea_orm::ConnectionTrait,
+) -> Result<crate::resources::entities::product_image::Model, ApiError> {
+    use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+
+    use crate::resources::entities::product_image;
+
+    product_image::Entity::find_by_id(image_id)
+        .filter(product_image::Column::ProductId.eq(product_id))
+        .one(conn)
+        .await?
+        .ok_or_else(not_found)
+}
+
+// 대표 이미지는 하나뿐이므로 새로 지정하기 전에 다른 이미지를 해제함
+#[cfg(feature = "server")]
+async fn clear_primary(
+    product_id: i32,
+    conn: &impl sea_orm::ConnectionTrait,
+) -> Result<(), ApiError> {
+    use sea_orm::{ActiveValue::Set, ColumnTrait, EntityTrait, QueryFilter};
+
+    use crate::resources::entities::product_image;
+
+    product_image::Entity::update_many()
+        .set(product_image::ActiveModel {
+            is_primary: Set(false),
+            ..Default::default()
+        })
+        .filter(product_image::Column::ProductId.eq(product_id))
+        .filter(product_image::Column::IsPrimary.eq(true))
+        .exec(conn)
+        .await?;
+    Ok(())
+}
+
+// multipart의 image 필드와 선택 alt 필드
+#[cfg(feature = "server")]
+pub struct ProductImageUpload {
+    pub bytes: Vec<u8>,
+    pub alt: Option<String>,
+}
+
+// multipart 문서화용
+#[cfg(feature = "server")]
+#[derive(utoipa::ToSchema)]
+#[allow(dead_code)]
+pub struct ProductImageForm {
+    #[schema(value_type = String, format = Binary)]
+    pub image: Vec<u8>,
+    pub alt: Option<String>,
+}
+
+#[cfg(feature = "server")]
+pub const IMAGE_RULES: crate::utils::upload::ImageRules = crate::utils::upload::ImageRules {
+    field: "image",
+    text_fields: &["alt"],
+    max_bytes: IMAGE_MAX_BYTES,
+    max_side: IMAGE_MAX_SIDE,
+};
+
+#[cfg(feature = "server")]
+impl ProductImageUpload {
+    pub async fn from_multipart(
+        multipart: &mut axum::extract::Multipart,
+    ) -> Result<Self, ApiError> {
+        let mut upload = IMAGE_RULES.read(multipart).await?;
+        let alt = upload
+            .fields
+            .remove("alt")
+            .map(|v| v.trim().chars().take(200).collect::<String>())
+            .filter(|v| !v.is_empty());
+        Ok(ProductImageUpload {
+            bytes: upload.bytes,
+            alt,
+        })
+    }
+
+    // 반환값은 원본 크기와 (키 끝부분, 파일) 목록
+    #[allow(clippy::type_complexity)]
+    fn derivatives(
+        bytes: &[u8],
+    ) -> Result<((u32, u32), Vec<(u32, &'static str, Vec<u8>)>), ApiError> {
+        use image::codecs::{jpeg::JpegEncoder, webp::WebPEncoder};
+        use image::imageops::FilterType;
+
+        let img = IMAGE_RULES.decode(bytes)?;
+        let (width, height) = (img.width(), img.height());
+        let mut files = Vec::new();
+        for max_width in IMAGE_WIDTHS {
+            let (w, h) = fit(width, height, max_width);
+            let resized = if (w, h) == (width, height) {
+                img.clone()
+            } else {
+                img.resize_exact(w, h, FilterType::Lanczos3)
+            };
+
+            // webp는 무손실 인코딩만 지원됨
+            let mut webp = Vec::new();
+            resized
+                .to_rgba8()
+                .write_with_encoder(WebPEncoder::new_lossless(&mut webp))
+                .map_err(AppError::any_t_error)?;
+            files.push((max_width, "webp", webp));
+
+            // jpeg는 투명도가 없으므로 RGB로 바꿈
+            let mut jpg = Vec::new();
+            resized
+                .to_rgb8()
+                .write_with_encoder(JpegEncoder::new_with_quality(&mut jpg, JPEG_QUALITY))
+                .map_err(AppError::any_t_error)?;
+            files.push((max_width, "jpg", jpg));
+        }
+
+        Ok(((width, height), files))
+    }
+
+    // 파생 이미지를 먼저 저장한 뒤 행을 추가함, 첫 이미지는 대표 이미지가 됨
+    // 키에 임의값을 넣어서 같은 주소의 파일이 바뀌지 않도록 함 (오래 캐시해도 됨)
+    pub async fn save(
+        self,
+        product_id: i32,
+        conn: &sea_orm::DatabaseConnection,
+        storage: &dyn Storage,
+    ) -> Result<ProductImageDto, ApiError> {
+        use sea_orm::sea_query::Expr;
+        use sea_orm::{
+            ActiveModelTrait, ActiveValue::Set, ColumnTrait, EntityTrait, QueryFilter, QuerySelect,
+        };
+
+        use crate::resources::entities::{product, product_image};
+        use crate::utils::upload::encode_blocking;
+
+        if product::Entity::find_by_id(product_id)
+            .one(conn)
+            .await?
+            .is_none()
+        {
+            return Err(ApiError::new(
+                reqwest::StatusCode::NOT_FOUND,
+                "not_found",
+                "product is not found",
+            ));
+        }
+
+        let bytes = self.bytes;
+        let ((width, height), files) = encode_blocking(move || Self::derivatives(&bytes)).await?;
+
+        let prefix = format!("products/{}/{}", product_id, crate::utils::jwt::random_id());
+        for (max_width, format, bytes) in files {
+            storage
+                .put(&variant_key(&prefix, max_width, format), bytes)
+                .await?;
+        }
+
+        let (count, last): (i64, Option<i32>) = product_image::Entity::find()
+            .select_only()
+            .column_as(Expr::col(product_image::Column::Id).count(), "count")
+            .column_as(Expr::col(product_image::Column::Position).max(), "last")
+            .filter(product_image::Column::ProductId.eq(product_id))
+            .into_tuple()
+            .one(conn)
+            .await?
+            .unwrap_or((0, None));
+
+        let inserted = product_image::ActiveModel {
+            product_id: Set(product_id),
+            storage_key: Set(prefix.clone()),
+            width: Set(width as i32),
+            height: Set(height as i32),
+            alt: Set(self.alt),
+            position: Set(last.map(|p| p + 1).unwrap_or(0)),
+            is_primary: Set(count == 0),
+            ..Default::default()
+        }
+        .insert(conn)
+        .await;
+
+        match inserted {
+            Ok(model) => Ok(ProductImageDto::from_model(model, storage)),
+            Err(e) => {
+                // 행을 만들지 못했으면 올린 파일을 치움
+                for key in image_keys(&prefix) {
+                    let _ = storage.delete(&key).await;
+                }
+                Err(e.into())
+            }
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+impl ProductImageUpdateReq {
+    pub async fn update(
+        self,
+        product_id: i32,
+        image_id: i32,
+        conn: &sea_orm::DatabaseConnection,
+        storage: &dyn Storage,
+    ) -> Result<ProductImageDto, ApiError> {
+        use sea_orm::{ActiveModelTrait, ActiveValue::Set, IntoActiveModel, TransactionTrait};
+
+        let txn = conn.begin().await?;
+        let model = find_image(product_id, image_id, &txn).await?;
+        if self.is_primary == Some(true) && !model.is_primary {
+            clear_primary(product_id, &txn).await?;
+        }
+
+        let mut active = model.into_active_model();
+        if let Some(alt) = self.alt {
+            let alt = alt.trim().to_string();
+            active.alt = Set(Some(alt).filter(|v| !v.is_empty()));
+        }
+        if let Some(position) = self.position {
+            active.position = Set(position);
+        }
+        if let Some(is_primary) = self.is_primary {
+            active.is_primary = Set(is_primary);
+        }
+        let model = active.update(&txn).await?;
+        txn.commit().await?;
+
+        Ok(ProductImageDto::from_model(model, storage))
+    }
+}
+
+#[cfg(feature = "server")]
+impl ProductImageOrderReq {
+    pub async fn reorder(
+        self,
+        product_id: i32,
+        conn: &sea_orm::DatabaseConnection,
+        storage: &dyn Storage,
+    ) -> Result<Vec<ProductImageDto>, ApiError> {
+        use std::collections::HashSet;
+
+        use sea_orm::{ActiveValue::Set, ColumnTrait, EntityTrait, QueryFilter, TransactionTrait};
+
+        use crate::resources::entities::product_image;
+
+        let txn = conn.begin().await?;
+        let current = product_image::Entity::find()
+            .filter(product_image::Column::ProductId.eq(product_id))
+            .all(&txn)
+            .await?
+            .into_iter()
+            .map(|m| m.id)
+            .collect::<HashSet<_>>();
+        let requested = self.image_ids.iter().copied().collect::<HashSet<_>>();
+        if requested.len() != self.image_ids.len() || requested != current {
+            return Err(ApiError::new(
+                reqwest::StatusCode::UNPROCESSABLE_ENTITY,
+                "invalid_order",
+                "image_ids must list every image of the product once",
+            ));
+        }
+
+        for (position, id) in self.image_ids.iter().enumerate() {
+            product_image::Entity::update_many()
+                .set(product_image::ActiveModel {
+                    position: Set(position as i32),
+                    ..Default::default()
+                })
+                .filter(product_image::Column::Id.eq(*id))
+                .exec(&txn)
+                .await?;
+        }
+        txn.commit().await?;
+
+        ProductImageDto::list(product_id, conn, storage).await
+    }
+}
+
+// 대표 이미지를 지우면 다음 순서의 이미지가 대표가 됨
+#[cfg(feature = "server")]
+pub async fn delete_image(
+    product_id: i32,
+    image_id: i32,
+    conn: &sea_orm::DatabaseConnection,
+    storage: &dyn Storage,
+) -> Result<(), ApiError> {
+    use sea_orm::{
+        ActiveValue::Set, ColumnTrait, EntityTrait, QueryFilter, QueryOrder, TransactionTrait,
+    };
+
+    use crate::resources::entities::product_image;
+
+    let txn = conn.begin().await?;
+    let model = find_image(product_id, image_id, &txn).await?;
+    product_image::Entity::delete_by_id(model.id)
+        .exec(&txn)
+        .await?;
+    if model.is_primary {
+        let next = product_image::Entity::find()
+            .filter(product_image::Column::ProductId.eq(product_id))
+            .order_by_asc(product_image::Column::Position)
+            .order_by_asc(product_image::Column::Id)
+            .one(&txn)
+            .await?;
+        if let Some(next) = next {
+            product_image::Entity::update_many()
+                .set(product_image::ActiveModel {
+                    is_primary: Set(true),
+                    ..Default::default()
+                })
+                .filter(product_image::Column::Id.eq(next.id))
+                .exec(&txn)
+                .await?;
+        }
+    }
+    txn.commit().await?;
+
+    // 파일이 남아도 상품에는 영향이 없음
+    for key in image_keys(&model.storage_key) {
+        let _ = storage.delete(&key).await;
+    }
+    Ok(())
+}
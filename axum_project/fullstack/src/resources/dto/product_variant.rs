@@ -0,0 +1,732 @@
+use serde::{Deserialize, Serialize};
+use validate_dto_macro::validate_dto;
+
+use crate::resources::dto::money::Money;
+#[cfg(feature = "server")]
+use crate::resources::dto::product::{ProductDto, get_product_detail};
+#[cfg(feature = "server")]
+use crate::utils::errors::ApiError;
+
+// 상품당 옵션 수, 옵션당 값 수
+pub const MAX_OPTIONS: usize = 3;
+pub const MAX_OPTION_VALUES: usize = 50;
+
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct OptionValueDto {
+    pub id: i32,
+    pub value: String,
+}
+
+// 사이즈, 색상 같은 옵션, 값은 추가한 순서
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct ProductOptionDto {
+    pub id: i32,
+    pub name: String,
+    pub values: Vec<OptionValueDto>,
+}
+
+// 옵션 이름과 고른 값, {"option": "size", "value": "M"}
+#[validate_dto]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct VariantAttribute {
+    #[validate(length(min = 1, max = 50))]
+    pub option: String,
+    #[validate(length(min = 1, max = 50))]
+    pub value: String,
+}
+
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct ProductVariantDto {
+    pub id: i32,
+    pub sku: String,
+    // None이면 상품 가격을 씀
+    pub price: Option<Money>,
+    // 옵션 순서대로
+    pub attributes: Vec<VariantAttribute>,
+}
+
+// 옵션을 만들 때 값도 같이 받음, 변형이 있는 상품에는 옵션을 추가할 수 없음
+#[validate_dto]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ProductOptionReq {
+    #[validate(length(min = 1, max = 50))]
+    pub name: String,
+    pub values: Vec<String>,
+}
+
+#[validate_dto]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct OptionValueReq {
+    #[validate(length(min = 1, max = 50))]
+    pub value: String,
+}
+
+// 상품의 모든 옵션에 값을 하나씩 골라야함, 가격의 통화는 상품과 같아야함
+#[validate_dto]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ProductVariantReq {
+    #[validate(length(min = 1, max = 64))]
+    pub sku: String,
+    #[validate(nested)]
+    pub price: Option<Money>,
+    #[validate(nested)]
+    pub attributes: Vec<VariantAttribute>,
+}
+
+// 장바구니, 재고 API에서 변형을 고름, 변형이 있는 상품은 반드시 골라야함
+#[validate_dto(params)]
+#[cfg_attr(feature = "server", derive(utoipa::IntoParams, utoipa::ToSchema))]
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default)]
+pub struct VariantQuery {
+    #[validate(range(min = 1))]
+    pub variant_id: Option<i32>,
+}
+
+#[cfg(feature = "server")]
+fn not_found(what: &str) -> ApiError {
+    ApiError::new(
+        reqwest::StatusCode::NOT_FOUND,
+        "not_found",
+        format!("{} is not found", what),
+    )
+}
+
+#[cfg(feature = "server")]
+fn conflict_error(error: &str, message: &str) -> ApiError {
+    ApiError::new(reqwest::StatusCode::CONFLICT, error, message)
+}
+
+// 옵션 값을 쓰는 변형이 있으면 fk_product_variant_value_option_value 위반
+#[cfg(feature = "server")]
+fn in_use(err: sea_orm::DbErr) -> ApiError {
+    ApiError::conflict_on(err, "option_in_use", "option is used by variants")
+}
+
+// 같은 상품의 옵션, 변형을 바꾸는 요청은 상품 행을 잠가서 하나씩 처리함
+#[cfg(feature = "server")]
+async fn lock_product(
+    product_id: i32,
+    txn: &sea_orm::DatabaseTransaction,
+) -> Result<crate::resources::entities::product::Model, ApiError> {
+    use sea_orm::{EntityTrait, QuerySelect};
+
+    crate::resources::entities::product::Entity::find_by_id(product_id)
+        .lock_exclusive()
+        .one(txn)
+        .await?
+        .ok_or_else(|| not_found("product"))
+}
+
+#[cfg(feature = "server")]
+async fn has_variants(
+    product_id: i32,
+    conn: &impl sea_orm::ConnectionTrait,
+) -> Result<bool, sea_orm::DbErr> {
+    use sea_orm::{ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter};
+
+    use crate::resources::entities::product_variant;
+
+    Ok(product_variant::Entity::find()
+        .filter(product_variant::Column::ProductId.eq(product_id))
+        .count(conn)
+        .await?
+        > 0)
+}
+
+// 상품에 맞는 변형인지 확인하고 가져옴
+// 변형이 있는 상품인데 고르지 않았으면 422 (variant_required)
+#[cfg(feature = "server")]
+pub(crate) async fn find_for_product(
+    product_id: i32,
+    variant_id: Option<i32>,
+    conn: &impl sea_orm::ConnectionTrait,
+) -> Result<Option<crate::resources::entities::product_variant::Model>, ApiError> {
+    use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+
+    use crate::resources::entities::product_variant;
+
+    match variant_id {
+        Some(id) => Ok(Some(
+            product_variant::Entity::find_by_id(id)
+                .filter(product_variant::Column::ProductId.eq(product_id))
+                .one(conn)
+                .await?
+                .ok_or_else(|| not_found("variant"))?,
+        )),
+        None if has_variants(product_id, conn).await? => Err(ApiError::new(
+            reqwest::StatusCode::UNPROCESSABLE_ENTITY,
+            "variant_required",
+            "product has variants, pick one with variant_id",
+        )),
+        None => Ok(None),
+    }
+}
+
+// 변형이 가격을 덮어썼으면 그 가격, 아니면 상품 가격
+#[cfg(feature = "server")]
+pub(crate) fn unit_price(
+    product: &crate::resources::entities::product::Model,
+    variant: Option<&crate::resources::entities::product_variant::Model>,
+) -> Money {
+    variant
+        .and_then(|v| v.price.zip(v.currency.as_deref()))
+        .map(|(amount, currency)| Money::from_columns(amount, currency))
+        .unwrap_or_else(|| Money::from_columns(product.price, &product.currency))
+}
+
+// 상품 가격을 덮어쓴 변형이 있는지, 상품 통화를 바꿀 때 확인함
+#[cfg(feature = "server")]
+pub(crate) async fn has_price_overrides(
+    product_id: i32,
+    conn: &impl sea_orm::ConnectionTrait,
+) -> Result<bool, sea_orm::DbErr> {
+    use sea_orm::{ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter};
+
+    use crate::resources::entities::product_variant;
+
+    Ok(product_variant::Entity::find()
+        .filter(product_variant::Column::ProductId.eq(product_id))
+        .filter(product_variant::Column::Price.is_not_null())
+        .count(conn)
+        .await?
+        > 0)
+}
+
+// 상품들의 옵션과 변형을 한 번에 읽어서 채움
+#[cfg(feature = "server")]
+pub async fn attach_variants(
+    items: &mut [ProductDto],
+    conn: &impl sea_orm::ConnectionTrait,
+) -> Result<(), sea_orm::DbErr> {
+    use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QueryOrder};
+    use std::collections::HashMap;
+
+    use crate::resources::entities::{
+        product_option, product_option_value, product_variant, product_variant_value,
+    };
+
+    let ids: Vec<i32> = items.iter().map(|p| p.id).collect();
+    if ids.is_empty() {
+        return Ok(());
+    }
+
+    let options = product_option::Entity::find()
+        .filter(product_option::Column::ProductId.is_in(ids.clone()))
+        .order_by_asc(product_option::Column::Id)
+        .all(conn)
+        .await?;
+    let values = if options.is_empty() {
+        Vec::new()
+    } else {
+        product_option_value::Entity::find()
+            .filter(product_option_value::Column::OptionId.is_in(options.iter().map(|o| o.id)))
+            .order_by_asc(product_option_value::Column::Id)
+            .all(conn)
+            .await?
+    };
+    let variants = product_variant::Entity::find()
+        .filter(product_variant::Column::ProductId.is_in(ids))
+        .order_by_asc(product_variant::Column::Id)
+        .all(conn)
+        .await?;
+    let links = if variants.is_empty() {
+        Vec::new()
+    } else {
+        product_variant_value::Entity::find()
+            .filter(product_variant_value::Column::VariantId.is_in(variants.iter().map(|v| v.id)))
+            .all(conn)
+            .await?
+    };
+
+    // 옵션 값 id -> (옵션 순서, 옵션 이름, 값)
+    let option_order: HashMap<i32, (usize, &str)> = options
+        .iter()
+        .enumerate()
+        .map(|(i, o)| (o.id, (i, o.name.as_str())))
+        .collect();
+    let value_of: HashMap<i32, (usize, &str, &str)> = values
+        .iter()
+        .filter_map(|v| {
+            let (order, name) = option_order.get(&v.option_id)?;
+            Some((v.id, (*order, *name, v.value.as_str())))
+        })
+        .collect();
+    let mut attributes: HashMap<i32, Vec<(usize, VariantAttribute)>> = HashMap::new();
+    for link in &links {
+        if let Some((order, option, value)) = value_of.get(&link.option_value_id) {
+            attributes.entry(link.variant_id).or_default().push((
+                *order,
+                VariantAttribute {
+                    option: option.to_string(),
+                    value: value.to_string(),
+                },
+            ));
+        }
+    }
+
+    for item in items.iter_mut() {
+        item.options = options
+            .iter()
+            .filter(|o| o.product_id == item.id)
+            .map(|o| ProductOptionDto {
+                id: o.id,
+                name: o.name.clone(),
+                values: values
+                    .iter()
+                    .filter(|v| v.option_id == o.id)
+                    .map(|v| OptionValueDto {
+                        id: v.id,
+                        value: v.value.clone(),
+                    })
+                    .collect(),
+            })
+            .collect();
+        item.variants = variants
+            .iter()
+            .filter(|v| v.product_id == item.id)
+            .map(|v| {
+                let mut attrs = attributes.remove(&v.id).unwrap_or_default();
+                attrs.sort_by_key(|(order, _)| *order);
+                ProductVariantDto {
+                    id: v.id,
+                    sku: v.sku.clone(),
+                    price: v
+                        .price
+                        .zip(v.currency.as_deref())
+                        .map(|(amount, currency)| Money::from_columns(amount, currency)),
+                    attributes: attrs.into_iter().map(|(_, a)| a).collect(),
+                }
+            })
+            .collect();
+    }
+    Ok(())
+}
+
+#[cfg(feature = "server")]
+fn find_option(product: ProductDto, option_id: i32) -> Result<ProductOptionDto, ApiError> {
+    product
+        .options
+        .into_iter()
+        .find(|o| o.id == option_id)
+        .ok_or_else(|| not_found("option"))
+}
+
+#[cfg(feature = "server")]
+fn find_variant(product: ProductDto, variant_id: i32) -> Result<ProductVariantDto, ApiError> {
+    product
+        .variants
+        .into_iter()
+        .find(|v| v.id == variant_id)
+        .ok_or_else(|| not_found("variant"))
+}
+
+// 앞뒤 공백을 지우고 중복을 뺀 값, 순서는 유지함
+#[cfg(feature = "server")]
+fn clean_values(values: &[String]) -> Result<Vec<String>, ApiError> {
+    use crate::utils::validate::ValidationErrors;
+
+    let mut cleaned: Vec<String> = Vec::new();
+    for value in values.iter().map(|v| v.trim()) {
+        if !value.is_empty() && !cleaned.iter().any(|c| c == value) {
+            cleaned.push(value.to_string());
+        }
+    }
+
+    let mut errors = ValidationErrors::default();
+    if cleaned.is_empty() || cleaned.len() > MAX_OPTION_VALUES {
+        errors.add(
+            "values",
+            "length",
+            format!("must have 1 to {} values", MAX_OPTION_VALUES),
+        );
+    }
+    if cleaned.iter().any(|v| v.chars().count() > 50) {
+        errors.add(
+            "values",
+            "length",
+            "each value must be 50 characters or less".to_string(),
+        );
+    }
+    errors.into_result()?;
+    Ok(cleaned)
+}
+
+#[cfg(feature = "server")]
+impl ProductOptionReq {
+    pub async fn create(
+        self,
+        product_id: i32,
+        conn: &sea_orm::DatabaseConnection,
+    ) -> Result<ProductOptionDto, ApiError> {
+        use sea_orm::{
+            ActiveModelTrait, ActiveValue::Set, ColumnTrait, EntityTrait, PaginatorTrait,
+            QueryFilter, TransactionTrait,
+        };
+
+        use crate::resources::entities::{product_option, product_option_value};
+
+        let values = clean_values(&self.values)?;
+
+        let txn = conn.begin().await?;
+        lock_product(product_id, &txn).await?;
+        if has_variants(product_id, &txn).await? {
+            return Err(conflict_error(
+                "variants_exist",
+                "delete the variants before changing options",
+            ));
+        }
+        let count = product_option::Entity::find()
+            .filter(product_option::Column::ProductId.eq(product_id))
+            .count(&txn)
+            .await?;
+        if count as usize >= MAX_OPTIONS {
+            return Err(conflict_error(
+                "too_many_options",
+                &format!("a product can have up to {} options", MAX_OPTIONS),
+            ));
+        }
+
+        let option = product_option::ActiveModel {
+            product_id: Set(product_id),
+            name: Set(self.name.trim().to_string()),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await
+        .map_err(|e| ApiError::conflict_on(e, "option_exists", "option name is already used"))?;
+        product_option_value::Entity::insert_many(values.into_iter().map(|value| {
+            product_option_value::ActiveModel {
+                option_id: Set(option.id),
+                value: Set(value),
+                ..Default::default()
+            }
+        }))
+        .exec(&txn)
+        .await?;
+        txn.commit().await?;
+
+        find_option(get_product_detail(product_id, conn).await?, option.id)
+    }
+}
+
+#[cfg(feature = "server")]
+impl OptionValueReq {
+    pub async fn add(
+        self,
+        product_id: i32,
+        option_id: i32,
+        conn: &sea_orm::DatabaseConnection,
+    ) -> Result<ProductOptionDto, ApiError> {
+        use sea_orm::{
+            ActiveModelTrait, ActiveValue::Set, ColumnTrait, EntityTrait, PaginatorTrait,
+            QueryFilter, TransactionTrait,
+        };
+
+        use crate::resources::entities::{product_option, product_option_value};
+
+        let txn = conn.begin().await?;
+        lock_product(product_id, &txn).await?;
+        product_option::Entity::find_by_id(option_id)
+            .filter(product_option::Column::ProductId.eq(product_id))
+            .one(&txn)
+            .await?
+            .ok_or_else(|| not_found("option"))?;
+        let count = product_option_value::Entity::find()
+            .filter(product_option_value::Column::OptionId.eq(option_id))
+            .count(&txn)
+            .await?;
+        if count as usize >= MAX_OPTION_VALUES {
+            return Err(conflict_error(
+                "too_many_values",
+                &format!("an option can have up to {} values", MAX_OPTION_VALUES),
+            ));
+        }
+        product_option_value::ActiveModel {
+            option_id: Set(option_id),
+            value: Set(self.value.trim().to_string()),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await
+        .map_err(|e| ApiError::conflict_on(e, "value_exists", "value is already used"))?;
+        txn.commit().await?;
+
+        find_option(get_product_detail(product_id, conn).await?, option_id)
+    }
+}
+
+// 변형이 쓰고 있는 옵션은 지울 수 없음
+#[cfg(feature = "server")]
+pub async fn delete_option(
+    product_id: i32,
+    option_id: i32,
+    conn: &sea_orm::DatabaseConnection,
+) -> Result<(), ApiError> {
+    use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+
+    use crate::resources::entities::product_option;
+
+    let res = product_option::Entity::delete_many()
+        .filter(product_option::Column::Id.eq(option_id))
+        .filter(product_option::Column::ProductId.eq(product_id))
+        .exec(conn)
+        .await
+        .map_err(in_use)?;
+    if res.rows_affected == 0 {
+        return Err(not_found("option"));
+    }
+    Ok(())
+}
+
+#[cfg(feature = "server")]
+pub async fn delete_option_value(
+    product_id: i32,
+    option_id: i32,
+    value_id: i32,
+    conn: &sea_orm::DatabaseConnection,
+) -> Result<(), ApiError> {
+    use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+
+    use crate::resources::entities::{product_option, product_option_value};
+
+    product_option::Entity::find_by_id(option_id)
+        .filter(product_option::Column::ProductId.eq(product_id))
+        .one(conn)
+        .await?
+        .ok_or_else(|| not_found("option"))?;
+    let res = product_option_value::Entity::delete_many()
+        .filter(product_option_value::Column::Id.eq(value_id))
+        .filter(product_option_value::Column::OptionId.eq(option_id))
+        .exec(conn)
+        .await
+        .map_err(in_use)?;
+    if res.rows_affected == 0 {
+        return Err(not_found("value"));
+    }
+    Ok(())
+}
+
+#[cfg(feature = "server")]
+impl ProductVariantReq {
+    // 속성을 옵션 값 id로 바꿈, 모든 옵션에 정확히 하나씩 있어야함
+    async fn resolve(
+        &self,
+        product_id: i32,
+        txn: &sea_orm::DatabaseTransaction,
+    ) -> Result<Vec<i32>, ApiError> {
+        use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+
+        use crate::resources::entities::{product_option, product_option_value};
+        use crate::utils::validate::ValidationErrors;
+
+        let options = product_option::Entity::find()
+            .filter(product_option::Column::ProductId.eq(product_id))
+            .find_with_related(product_option_value::Entity)
+            .all(txn)
+            .await?;
+
+        let mut errors = ValidationErrors::default();
+        if options.is_empty() {
+            errors.add(
+                "attributes",
+                "attributes",
+                "product has no options".to_string(),
+            );
+        }
+        for attr in &self.attributes {
+            if !options.iter().any(|(o, _)| o.name == attr.option.trim()) {
+                errors.add(
+                    "attributes",
+                    "attributes",
+                    format!("unknown option '{}'", attr.option.trim()),
+                );
+            }
+        }
+
+        let mut value_ids = Vec::with_capacity(options.len());
+        for (option, values) in &options {
+            let picked: Vec<&VariantAttribute> = self
+                .attributes
+                .iter()
+                .filter(|a| a.option.trim() == option.name)
+                .collect();
+            let [attr] = picked.as_slice() else {
+                errors.add(
+                    "attributes",
+                    "attributes",
+                    format!("pick exactly one value for '{}'", option.name),
+                );
+                continue;
+            };
+            match values.iter().find(|v| v.value == attr.value.trim()) {
+                Some(v) => value_ids.push(v.id),
+                None => errors.add(
+                    "attributes",
+                    "attributes",
+                    format!(
+                        "'{}' is not a value of '{}'",
+                        attr.value.trim(),
+                        option.name
+                    ),
+                ),
+            }
+        }
+        errors.into_result()?;
+
+        value_ids.sort_unstable();
+        Ok(value_ids)
+    }
+
+    // 같은 옵션 조합의 다른 변형이 있으면 409
+    async fn check_unique(
+        product_id: i32,
+        except: Option<i32>,
+        value_ids: &[i32],
+        txn: &sea_orm::DatabaseTransaction,
+    ) -> Result<(), ApiError> {
+        use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+        use std::collections::HashMap;
+
+        use crate::resources::entities::{product_variant, product_variant_value};
+
+        let mut select = product_variant::Entity::find()
+            .filter(product_variant::Column::ProductId.eq(product_id));
+        if let Some(id) = except {
+            select = select.filter(product_variant::Column::Id.ne(id));
+        }
+        let mut combos: HashMap<i32, Vec<i32>> = HashMap::new();
+        for (variant, links) in select
+            .find_with_related(product_variant_value::Entity)
+            .all(txn)
+            .await?
+        {
+            combos.insert(
+                variant.id,
+                links.into_iter().map(|l| l.option_value_id).collect(),
+            );
+        }
+        for ids in combos.values_mut() {
+            ids.sort_unstable();
+            if ids == value_ids {
+                return Err(conflict_error(
+                    "variant_exists",
+                    "a variant with the same options already exists",
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    // 변형 행과 옵션 값 연결을 저장함, variant_id가 있으면 수정
+    async fn save(
+        self,
+        product_id: i32,
+        variant_id: Option<i32>,
+        conn: &sea_orm::DatabaseConnection,
+    ) -> Result<ProductVariantDto, ApiError> {
+        use sea_orm::{
+            ActiveModelTrait, ActiveValue::Set, ColumnTrait, EntityTrait, QueryFilter,
+            TransactionTrait,
+        };
+
+        use crate::resources::entities::{product_variant, product_variant_value};
+
+        let txn = conn.begin().await?;
+        let product = lock_product(product_id, &txn).await?;
+        if let Some(id) = variant_id {
+            product_variant::Entity::find_by_id(id)
+                .filter(product_variant::Column::ProductId.eq(product_id))
+                .one(&txn)
+                .await?
+                .ok_or_else(|| not_found("variant"))?;
+        }
+        if self
+            .price
+            .is_some_and(|p| p.currency.as_str() != product.currency)
+        {
+            return Err(conflict_error(
+                "currency_mismatch",
+                "variant price must use the product currency",
+            ));
+        }
+        let value_ids = self.resolve(product_id, &txn).await?;
+        Self::check_unique(product_id, variant_id, &value_ids, &txn).await?;
+
+        let mut active = product_variant::ActiveModel {
+            product_id: Set(product_id),
+            sku: Set(self.sku.trim().to_string()),
+            price: Set(self.price.map(|p| p.amount)),
+            currency: Set(self.price.map(|p| p.currency.as_str().to_string())),
+            ..Default::default()
+        };
+        let sku_exists = |e| ApiError::conflict_on(e, "sku_exists", "sku is already used");
+        let variant = match variant_id {
+            Some(id) => {
+                active.id = Set(id);
+                let variant = active.update(&txn).await.map_err(sku_exists)?;
+                product_variant_value::Entity::delete_many()
+                    .filter(product_variant_value::Column::VariantId.eq(id))
+                    .exec(&txn)
+                    .await?;
+                variant
+            }
+            None => active.insert(&txn).await.map_err(sku_exists)?,
+        };
+        product_variant_value::Entity::insert_many(value_ids.into_iter().map(|value_id| {
+            product_variant_value::ActiveModel {
+                variant_id: Set(variant.id),
+                option_value_id: Set(value_id),
+            }
+        }))
+        .exec(&txn)
+        .await?;
+        txn.commit().await?;
+
+        find_variant(get_product_detail(product_id, conn).await?, variant.id)
+    }
+
+    pub async fn create(
+        self,
+        product_id: i32,
+        conn: &sea_orm::DatabaseConnection,
+    ) -> Result<ProductVariantDto, ApiError> {
+        self.save(product_id, None, conn).await
+    }
+
+    pub async fn update(
+        self,
+        product_id: i32,
+        variant_id: i32,
+        conn: &sea_orm::DatabaseConnection,
+    ) -> Result<ProductVariantDto, ApiError> {
+        self.save(product_id, Some(variant_id), conn).await
+    }
+}
+
+#[cfg(feature = "server")]
+pub async fn delete_variant(
+    product_id: i32,
+    variant_id: i32,
+    conn: &sea_orm::DatabaseConnection,
+) -> Result<(), ApiError> {
+    use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+
+    use crate::resources::entities::product_variant;
+
+    let res = product_variant::Entity::delete_many()
+        .filter(product_variant::Column::Id.eq(variant_id))
+        .filter(product_variant::Column::ProductId.eq(product_id))
+        .exec(conn)
+        .await?;
+    if res.rows_affected == 0 {
+        return Err(not_found("variant"));
+    }
+    Ok(())
+}
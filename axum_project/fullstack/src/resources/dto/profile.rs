@@ -0,0 +1,259 @@
+use serde::{Deserialize, Serialize};
+use validate_dto_macro::validate_dto;
+
+#[cfg(feature = "server")]
+use crate::utils::errors::AppError;
+#[cfg(feature = "server")]
+use crate::utils::storage::Storage;
+
+// 업로드 원본은 5MB, 가로세로 4000px까지, 썸네일은 정사각형 PNG로 저장
+pub const AVATAR_MAX_BYTES: usize = 5 * 1024 * 1024;
+pub const AVATAR_MAX_SIDE: u32 = 4000;
+pub const AVATAR_SIZES: [u32; 2] = [256, 64];
+pub const DISPLAY_NAME_MAX: usize = 50;
+pub const BIO_MAX: usize = 500;
+
+// 누구나 볼 수 있는 프로필, 로그인 이메일(username)은 내려주지 않음
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct ProfileDto {
+    pub id: i32,
+    pub display_name: Option<String>,
+    pub bio: Option<String>,
+    // 256px
+    pub avatar_url: Option<String>,
+    // 64px
+    pub avatar_thumb_url: Option<String>,
+}
+
+// 빈 문자열은 값을 지우는 것으로 취급
+#[validate_dto]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[derive(Deserialize, Serialize, Debug)]
+pub struct ProfileUpdateReq {
+    #[validate(length(max = 50))]
+    pub display_name: Option<String>,
+    #[validate(length(max = 500))]
+    pub bio: Option<String>,
+}
+
+// 앞뒤 공백을 지우고, 빈 문자열은 None으로 취급
+#[cfg(feature = "server")]
+pub(crate) fn normalize_text(
+    value: Option<String>,
+    max: usize,
+    name: &str,
+) -> Result<Option<String>, AppError> {
+    let value = value
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty());
+    match value {
+        Some(v) if v.chars().count() > max => Err(AppError::new(
+            reqwest::StatusCode::BAD_REQUEST,
+            format!("{} must be {} characters or less", name, max),
+            None,
+        )),
+        v => Ok(v),
+    }
+}
+
+#[cfg(feature = "server")]
+fn avatar_key(prefix: &str, size: u32) -> String {
+    format!("{}-{}.png", prefix, size)
+}
+
+// 저장된 아바타의 모든 크기 파일 키
+#[cfg(feature = "server")]
+pub(crate) fn avatar_keys(prefix: Option<&str>) -> Vec<String> {
+    prefix
+        .map(|prefix| {
+            AVATAR_SIZES
+                .iter()
+                .map(|&size| avatar_key(prefix, size))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(feature = "server")]
+impl ProfileDto {
+    pub fn from_model(
+        model: crate::resources::entities::users::Model,
+        storage: &dyn Storage,
+    ) -> Self {
+        let [large, small] = AVATAR_SIZES;
+        let url = |size| {
+            model
+                .avatar_key
+                .as_deref()
+                .map(|prefix| storage.url(&avatar_key(prefix, size)))
+        };
+
+        ProfileDto {
+            id: model.id,
+            avatar_url: url(large),
+            avatar_thumb_url: url(small),
+            display_name: model.display_name,
+            bio: model.bio,
+        }
+    }
+
+    pub async fn get(
+        id: i32,
+        conn: &sea_orm::DatabaseConnection,
+        storage: &dyn Storage,
+    ) -> Result<Self, AppError> {
+        Ok(Self::get_with_etag(id, conn, storage).await?.0)
+    }
+
+    // PATCH의 If-Match에 넣을 ETag를 같이 돌려줌
+    pub async fn get_with_etag(
+        id: i32,
+        conn: &sea_orm::DatabaseConnection,
+        storage: &dyn Storage,
+    ) -> Result<(Self, axum_extra::headers::ETag), AppError> {
+        use crate::resources::dto::user::user_etag;
+
+        use reqwest::StatusCode;
+        use sea_orm::EntityTrait;
+
+        use crate::resources::entities::users;
+
+        let model = users::Entity::find_by_id(id)
+            .one(conn)
+            .await?
+            .filter(|m| m.deleted_at.is_none())
+            .ok_or(AppError::new(
+                StatusCode::NOT_FOUND,
+                "user is not found",
+                None,
+            ))?;
+
+        let etag = user_etag(&model);
+        Ok((Self::from_model(model, storage), etag))
+    }
+}
+
+#[cfg(feature = "server")]
+impl ProfileUpdateReq {
+    pub async fn update(
+        self,
+        user_id: i32,
+        conn: &sea_orm::DatabaseConnection,
+        storage: &dyn Storage,
+    ) -> Result<ProfileDto, AppError> {
+        use sea_orm::ActiveValue::Set;
+
+        use crate::resources::dto::user::update_versioned;
+        use crate::resources::entities::users;
+
+        let model = users::ActiveModel {
+            display_name: Set(normalize_text(
+                self.display_name,
+                DISPLAY_NAME_MAX,
+                "display_name",
+            )?),
+            bio: Set(normalize_text(self.bio, BIO_MAX, "bio")?),
+            ..Default::default()
+        };
+        let model = update_versioned(model, user_id, None, conn)
+            .await?
+            .ok_or(AppError::auth_error())?;
+
+        Ok(ProfileDto::from_model(model, storage))
+    }
+}
+
+// multipart 문서화용
+#[cfg(feature = "server")]
+#[derive(utoipa::ToSchema)]
+#[allow(dead_code)]
+pub struct AvatarForm {
+    #[schema(value_type = String, format = Binary)]
+    pub avatar: Vec<u8>,
+}
+
+// multipart의 avatar 필드
+#[cfg(feature = "server")]
+pub struct AvatarUpload {
+    pub bytes: Vec<u8>,
+}
+
+#[cfg(feature = "server")]
+pub const AVATAR_RULES: crate::utils::upload::ImageRules = crate::utils::upload::ImageRules {
+    field: "avatar",
+    text_fields: &[],
+    max_bytes: AVATAR_MAX_BYTES,
+    max_side: AVATAR_MAX_SIDE,
+};
+
+#[cfg(feature = "server")]
+impl AvatarUpload {
+    pub async fn from_multipart(
+        multipart: &mut axum::extract::Multipart,
+    ) -> Result<Self, AppError> {
+        let upload = AVATAR_RULES.read(multipart).await?;
+        Ok(AvatarUpload {
+            bytes: upload.bytes,
+        })
+    }
+
+    fn thumbnails(bytes: &[u8]) -> Result<Vec<(u32, Vec<u8>)>, AppError> {
+        use image::{ImageFormat, imageops::FilterType};
+
+        let img = AVATAR_RULES.decode(bytes)?;
+        AVATAR_SIZES
+            .iter()
+            .map(|&size| {
+                let mut buf = std::io::Cursor::new(Vec::new());
+                img.resize_to_fill(size, size, FilterType::Lanczos3)
+                    .write_to(&mut buf, ImageFormat::Png)
+                    .map_err(AppError::any_t_error)?;
+                Ok((size, buf.into_inner()))
+            })
+            .collect()
+    }
+
+    // 새 썸네일을 저장한 뒤 이전 파일을 지움
+    // 키에 임의값을 넣어서 브라우저 캐시에 예전 이미지가 남지 않도록 함
+    pub async fn save(
+        self,
+        user_id: i32,
+        conn: &sea_orm::DatabaseConnection,
+        storage: &dyn Storage,
+    ) -> Result<ProfileDto, AppError> {
+        use sea_orm::{ActiveValue::Set, EntityTrait};
+
+        use crate::resources::dto::user::update_versioned;
+        use crate::resources::entities::users;
+        use crate::utils::upload::encode_blocking;
+
+        let model = users::Entity::find_by_id(user_id)
+            .one(conn)
+            .await?
+            .ok_or(AppError::auth_error())?;
+
+        let thumbnails = encode_blocking(move || Self::thumbnails(&self.bytes)).await?;
+
+        let prefix = format!("avatars/{}/{}", user_id, crate::utils::jwt::random_id());
+        for (size, bytes) in thumbnails {
+            storage.put(&avatar_key(&prefix, size), bytes).await?;
+        }
+
+        let old = model.avatar_key.clone();
+        let active = users::ActiveModel {
+            avatar_key: Set(Some(prefix)),
+            ..Default::default()
+        };
+        let model = update_versioned(active, user_id, None, conn)
+            .await?
+            .ok_or(AppError::auth_error())?;
+
+        for key in avatar_keys(old.as_deref()) {
+            // 이전 파일이 남아도 프로필에는 영향이 없음
+            let _ = storage.delete(&key).await;
+        }
+
+        Ok(ProfileDto::from_model(model, storage))
+    }
+}
@@ -0,0 +1,477 @@
+use axum::extract::FromRequestParts;
+use axum::http::request::Parts;
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::{NaiveDate, NaiveDateTime};
+use sea_orm::{
+    ColumnTrait, Condition, DatabaseConnection, EntityTrait, IdenStatic, ModelTrait, Order,
+    PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Select, Value,
+};
+
+use crate::resources::dto::page::{Page, clamp_limit};
+use crate::utils::errors::ApiError;
+use crate::utils::validate::ValidationErrors;
+
+// 목록 API 공통 쿼리 문법
+//   filter[username][like]=kim&filter[id][gte]=10&filter[display_name][null]=false
+//   sort=-created_at,username  (-는 내림차순, 기본키가 항상 마지막 정렬키로 붙음)
+// 연산자: eq(생략 가능), ne, gt, gte, lt, lte, like(부분 일치, %와 _도 글자 그대로), in(쉼표 구분), null(true/false)
+
+const MAX_IN_VALUES: usize = 100;
+// filter, sort 말고 목록 API가 받는 파라미터, 나머지는 422로 거절함
+const PAGE_PARAMS: [&str; 3] = ["limit", "cursor", "total"];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FieldKind {
+    Int,
+    Text,
+    // 2026-01-01 또는 2026-01-01T09:00:00
+    DateTime,
+}
+
+pub struct QueryField<C: 'static> {
+    pub name: &'static str,
+    pub column: C,
+    pub kind: FieldKind,
+    // null이 될 수 있는 컬럼은 커서를 만들 수 없으므로 정렬하지 않음
+    pub sortable: bool,
+}
+
+// 엔티티마다 쿼리에서 쓸 수 있는 컬럼을 허용 목록으로 정함
+// 목록에 없는 필드는 422로 거절함
+pub trait QueryFields: EntityTrait {
+    const FIELDS: &'static [QueryField<Self::Column>];
+    // 정수 기본키, 정렬이 겹칠 때 순서를 정하는 마지막 키
+    const ID: Self::Column;
+    // 정렬을 요청하지 않았을 때 기본키 내림차순(최신순)으로 보여줄지
+    const NEWEST_FIRST: bool = false;
+
+    fn field(name: &str) -> Option<&'static QueryField<Self::Column>> {
+        Self::FIELDS.iter().find(|f| f.name == name)
+    }
+}
+
+#[derive(Clone, Copy)]
+struct SortKey<C> {
+    column: C,
+    kind: FieldKind,
+    desc: bool,
+}
+
+pub struct ListQuery<E: QueryFields> {
+    pub condition: Condition,
+    sort: Vec<SortKey<E::Column>>,
+}
+
+fn parse_value(kind: FieldKind, raw: &str) -> Option<Value> {
+    match kind {
+        FieldKind::Int => raw.parse::<i64>().ok().map(Value::from),
+        FieldKind::Text => Some(Value::from(raw.to_string())),
+        FieldKind::DateTime => NaiveDateTime::parse_from_str(raw, "%Y-%m-%dT%H:%M:%S%.f")
+            .ok()
+            .or_else(|| {
+                NaiveDate::parse_from_str(raw, "%Y-%m-%d")
+                    .ok()
+                    .map(|d| d.and_time(Default::default()))
+            })
+            .map(Value::from),
+    }
+}
+
+// 커서에 넣을 수 있도록 값을 문자열로 바꿈, parse_value의 반대
+fn value_to_string(value: &Value) -> Option<String> {
+    match value {
+        Value::SmallInt(Some(v)) => Some(v.to_string()),
+        Value::Int(Some(v)) => Some(v.to_string()),
+        Value::BigInt(Some(v)) => Some(v.to_string()),
+        Value::String(Some(v)) => Some(v.to_string()),
+        Value::ChronoDateTime(Some(v)) => Some(v.format("%Y-%m-%dT%H:%M:%S%.f").to_string()),
+        _ => None,
+    }
+}
+
+// LIKE 패턴 문자는 그대로 비교되도록 이스케이프
+pub fn like_escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+fn decode_pair(pair: &str) -> (String, String) {
+    let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+    let decode = |s: &str| {
+        let s = s.replace('+', " ");
+        urlencoding::decode(&s).map(|v| v.into_owned()).unwrap_or(s)
+    };
+    (decode(key), decode(value))
+}
+
+// filter[field] 또는 filter[field][op]
+fn parse_filter_key(key: &str) -> Option<(&str, &str)> {
+    let rest = key.strip_prefix("filter[")?;
+    let (field, rest) = rest.split_once(']')?;
+    if rest.is_empty() {
+        return Some((field, "eq"));
+    }
+    let op = rest.strip_prefix('[')?.strip_suffix(']')?;
+    Some((field, op))
+}
+
+impl<E: QueryFields> ListQuery<E> {
+    pub fn parse(query: &str) -> Result<Self, ValidationErrors> {
+        let mut errors = ValidationErrors::default();
+        let mut condition = Condition::all();
+        let mut sort = Vec::new();
+
+        for (key, raw) in query.split('&').filter(|p| !p.is_empty()).map(decode_pair) {
+            if key == "sort" {
+                for name in raw.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+                    let (desc, name) = match name.strip_prefix('-') {
+                        Some(name) => (true, name),
+                        None => (false, name),
+                    };
+                    match E::field(name) {
+                        Some(f) if f.sortable => sort.push(SortKey {
+                            column: f.column,
+                            kind: f.kind,
+                            desc,
+                        }),
+                        _ => errors.add(
+                            "sort",
+                            "sort",
+                            format!("can't sort by '{}', allowed: {}", name, Self::sortable()),
+                        ),
+                    }
+                }
+                continue;
+            }
+
+            if PAGE_PARAMS.contains(&key.as_str()) {
+                continue;
+            }
+            let Some((name, op)) = parse_filter_key(&key) else {
+                errors.add(
+                    &key,
+                    "unknown",
+                    format!(
+                        "unknown parameter '{}', allowed: filter[field][op], sort, {}",
+                        key,
+                        PAGE_PARAMS.join(", ")
+                    ),
+                );
+                continue;
+            };
+            let Some(field) = E::field(name) else {
+                errors.add(
+                    &key,
+                    "filter",
+                    format!("unknown field '{}', allowed: {}", name, Self::filterable()),
+                );
+                continue;
+            };
+            match Self::expr(field, op, &raw) {
+                Ok(expr) => condition = condition.add(expr),
+                Err(message) => errors.add(&key, "filter", message),
+            }
+        }
+
+        errors.into_result()?;
+        Ok(ListQuery { condition, sort })
+    }
+
+    fn expr(
+        field: &QueryField<E::Column>,
+        op: &str,
+        raw: &str,
+    ) -> Result<sea_orm::sea_query::SimpleExpr, String> {
+        let col = field.column;
+        let value = || parse_value(field.kind, raw).ok_or(format!("invalid value '{}'", raw));
+
+        Ok(match op {
+            "eq" => col.eq(value()?),
+            "ne" => col.ne(value()?),
+            "gt" => col.gt(value()?),
+            "gte" => col.gte(value()?),
+            "lt" => col.lt(value()?),
+            "lte" => col.lte(value()?),
+            "like" if field.kind == FieldKind::Text => col.like(format!("%{}%", like_escape(raw))),
+            "in" => {
+                let values = raw
+                    .split(',')
+                    .map(|v| parse_value(field.kind, v.trim()))
+                    .collect::<Option<Vec<_>>>()
+                    .ok_or(format!("invalid value '{}'", raw))?;
+                if values.is_empty() || values.len() > MAX_IN_VALUES {
+                    return Err(format!("in takes 1 to {} values", MAX_IN_VALUES));
+                }
+                col.is_in(values)
+            }
+            "null" => match raw {
+                "true" => col.is_null(),
+                "false" => col.is_not_null(),
+                _ => return Err("null takes true or false".to_string()),
+            },
+            _ => return Err(format!("unsupported operator '{}'", op)),
+        })
+    }
+
+    fn filterable() -> String {
+        E::FIELDS
+            .iter()
+            .map(|f| f.name)
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    fn sortable() -> String {
+        E::FIELDS
+            .iter()
+            .filter(|f| f.sortable)
+            .map(|f| f.name)
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    // 요청한 정렬 뒤에 기본키를 붙여서 순서가 항상 하나로 정해지도록 함
+    fn sort_keys(&self) -> Vec<SortKey<E::Column>> {
+        let mut keys = self.sort.clone();
+        let has_id = keys.iter().any(|k| k.column.as_str() == E::ID.as_str());
+        if !has_id {
+            keys.push(SortKey {
+                column: E::ID,
+                kind: FieldKind::Int,
+                desc: keys.is_empty() && E::NEWEST_FIRST,
+            });
+        }
+        keys
+    }
+
+    // 정렬키 값들로 keyset 페이지네이션
+    // (a, b, id) 순서라면 a > x or (a = x and b > y) or (a = x and b = y and id > z)
+    fn after(keys: &[SortKey<E::Column>], cursor: &str) -> Result<Condition, ApiError> {
+        let invalid = || {
+            ApiError::new(
+                reqwest::StatusCode::BAD_REQUEST,
+                "invalid_cursor",
+                "invalid cursor, it must be used with the same sort",
+            )
+        };
+        let bytes = URL_SAFE_NO_PAD.decode(cursor).map_err(|_| invalid())?;
+        let raws: Vec<String> = serde_json::from_slice(&bytes).map_err(|_| invalid())?;
+        if raws.len() != keys.len() {
+            return Err(invalid());
+        }
+        let values = keys
+            .iter()
+            .zip(&raws)
+            .map(|(k, raw)| parse_value(k.kind, raw))
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(invalid)?;
+
+        let mut any = Condition::any();
+        for i in 0..keys.len() {
+            let mut all = Condition::all();
+            for (key, value) in keys.iter().zip(&values).take(i) {
+                all = all.add(key.column.eq(value.clone()));
+            }
+            let (key, value) = (&keys[i], values[i].clone());
+            all = all.add(if key.desc {
+                key.column.lt(value)
+            } else {
+                key.column.gt(value)
+            });
+            any = any.add(all);
+        }
+        Ok(any)
+    }
+
+    fn cursor_of(keys: &[SortKey<E::Column>], model: &E::Model) -> Option<String>
+    where
+        E::Model: ModelTrait<Entity = E>,
+    {
+        let raws = keys
+            .iter()
+            .map(|k| value_to_string(&model.get(k.column)))
+            .collect::<Option<Vec<_>>>()?;
+        let json = serde_json::to_vec(&raws).ok()?;
+        Some(URL_SAFE_NO_PAD.encode(json))
+    }
+
+    // select에 필터, 정렬, 커서를 적용해서 한 페이지를 가져옴
+    pub async fn page<T>(
+        &self,
+        select: Select<E>,
+        cursor: Option<&str>,
+        limit: Option<u64>,
+        with_total: bool,
+        conn: &DatabaseConnection,
+    ) -> Result<Page<T>, ApiError>
+    where
+        E::Model: ModelTrait<Entity = E> + Into<T> + Sync,
+    {
+        let limit = clamp_limit(limit);
+        let select = select.filter(self.condition.clone());
+
+        let total = if with_total {
+            Some(select.clone().count(conn).await?)
+        } else {
+            None
+        };
+
+        let keys = self.sort_keys();
+        let mut select = select.limit(limit + 1);
+        for key in &keys {
+            select = select.order_by(key.column, if key.desc { Order::Desc } else { Order::Asc });
+        }
+        if let Some(cursor) = cursor {
+            select = select.filter(Self::after(&keys, cursor)?);
+        }
+
+        let mut rows = select.all(conn).await?;
+        let next_cursor = if rows.len() as u64 > limit {
+            rows.truncate(limit as usize);
+            rows.last().and_then(|m| Self::cursor_of(&keys, m))
+        } else {
+            None
+        };
+
+        Ok(Page {
+            items: rows.into_iter().map(|m| m.into()).collect(),
+            next_cursor,
+            total,
+        })
+    }
+}
+
+// 쿼리 문자열 전체를 읽으므로 limit, cursor 같은 다른 파라미터는 Query로 따로 받으면 됨
+impl<E, S> FromRequestParts<S> for ListQuery<E>
+where
+    E: QueryFields,
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(Self::parse(parts.uri.query().unwrap_or_default())?)
+    }
+}
+
+// API 문서의 filter, sort 설명은 허용 목록(FIELDS)으로 만듦
+impl<E: QueryFields> utoipa::IntoParams for ListQuery<E> {
+    fn into_params(
+        _parameter_in_provider: impl Fn() -> Option<utoipa::openapi::path::ParameterIn>,
+    ) -> Vec<utoipa::openapi::path::Parameter> {
+        use std::collections::HashMap;
+
+        use utoipa::PartialSchema;
+        use utoipa::openapi::Required;
+        use utoipa::openapi::path::{ParameterBuilder, ParameterIn, ParameterStyle};
+
+        let example = E::FIELDS
+            .iter()
+            .find(|f| f.sortable && f.column.as_str() != E::ID.as_str())
+            .map_or("id", |f| f.name);
+        let default_order = if E::NEWEST_FIRST {
+            "newest first by default"
+        } else {
+            "oldest first by default"
+        };
+
+        vec![
+            ParameterBuilder::new()
+                .name("filter")
+                .parameter_in(ParameterIn::Query)
+                .required(Required::False)
+                .style(Some(ParameterStyle::DeepObject))
+                .explode(Some(true))
+                .schema(Some(HashMap::<String, HashMap<String, String>>::schema()))
+                .description(Some(format!(
+                    "fields: {}\n\nfilter[field][op]=value, op: eq(default), ne, gt, gte, lt, lte, like, in(comma separated), null(true|false)\n\nlike matches a substring, % and _ are literal",
+                    Self::filterable()
+                )))
+                .build(),
+            ParameterBuilder::new()
+                .name("sort")
+                .parameter_in(ParameterIn::Query)
+                .required(Required::False)
+                .schema(Some(String::schema()))
+                .description(Some(format!(
+                    "sortable: {}\n\ncomma separated, prefix with - for descending (e.g. -{},id), {}",
+                    Self::sortable(),
+                    example,
+                    default_order
+                )))
+                .build(),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::entities::{comment, post, product, review, users};
+
+    fn nullable_sortable<E: QueryFields>() -> Vec<&'static str> {
+        E::FIELDS
+            .iter()
+            .filter(|f| f.sortable && f.column.def().is_null())
+            .map(|f| f.name)
+            .collect()
+    }
+
+    #[test]
+    fn nullable_columns_are_not_sortable() {
+        assert!(nullable_sortable::<product::Entity>().is_empty());
+        assert!(nullable_sortable::<post::Entity>().is_empty());
+        assert!(nullable_sortable::<comment::Entity>().is_empty());
+        assert!(nullable_sortable::<review::Entity>().is_empty());
+        assert!(nullable_sortable::<users::Entity>().is_empty());
+    }
+
+    #[test]
+    fn rejects_sort_by_nullable_sku() {
+        let err = ListQuery::<product::Entity>::parse("sort=-sku")
+            .err()
+            .unwrap();
+        assert_eq!(err.0[0].field, "sort");
+        assert!(ListQuery::<product::Entity>::parse("filter[sku][null]=false&sort=-price").is_ok());
+    }
+
+    #[test]
+    fn params_are_documented_from_fields() {
+        use utoipa::IntoParams;
+
+        let params = ListQuery::<review::Entity>::into_params(|| None);
+        let filter = params[0].description.as_deref().unwrap();
+        let sort = params[1].description.as_deref().unwrap();
+        for f in review::Entity::FIELDS {
+            assert!(filter.contains(f.name));
+            assert_eq!(sort.contains(f.name), f.sortable, "{}", f.name);
+        }
+        assert!(sort.ends_with("newest first by default"));
+    }
+
+    #[test]
+    fn like_matches_wildcards_literally() {
+        use sea_orm::{DbBackend, QueryTrait};
+
+        let list = ListQuery::<users::Entity>::parse("filter[username][like]=50%25_off").unwrap();
+        let sql = users::Entity::find()
+            .filter(list.condition)
+            .build(DbBackend::Postgres)
+            .to_string();
+        assert!(sql.contains(r"LIKE E'%50\\%\\_off%'"), "{}", sql);
+    }
+
+    #[test]
+    fn rejects_unknown_params() {
+        let err = ListQuery::<users::Entity>::parse("username=kim&id=1&limit=10")
+            .err()
+            .unwrap();
+        let fields: Vec<_> = err.0.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(fields, ["username", "id"]);
+        assert!(
+            ListQuery::<users::Entity>::parse("limit=10&cursor=abc&total=true&sort=id").is_ok()
+        );
+    }
+}
@@ -0,0 +1,463 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use validate_dto_macro::validate_dto;
+
+#[cfg(feature = "server")]
+use crate::resources::dto::page::Page;
+#[cfg(feature = "server")]
+use crate::utils::errors::ApiError;
+
+pub const BODY_MAX: usize = 2000;
+
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct ReviewDto {
+    pub id: i32,
+    pub product_id: i32,
+    // 탈퇴한 유저의 리뷰는 None
+    pub user_id: Option<i32>,
+    pub author_name: Option<String>,
+    pub rating: i32,
+    pub body: String,
+    #[cfg_attr(feature = "server", schema(value_type = String, format = DateTime))]
+    pub created_at: NaiveDateTime,
+    #[cfg_attr(feature = "server", schema(value_type = String, format = DateTime))]
+    pub updated_at: NaiveDateTime,
+}
+
+#[cfg(feature = "server")]
+impl From<crate::resources::entities::review::Model> for ReviewDto {
+    fn from(value: crate::resources::entities::review::Model) -> Self {
+        ReviewDto {
+            id: value.id,
+            product_id: value.product_id,
+            user_id: value.user_id,
+            author_name: None,
+            rating: value.rating,
+            body: value.body,
+            created_at: value.created_at,
+            updated_at: value.updated_at,
+        }
+    }
+}
+
+// 상품별 별점 요약, 리뷰가 없으면 average는 None
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct RatingSummaryDto {
+    pub product_id: i32,
+    pub count: i32,
+    pub average: Option<f64>,
+    // 1점부터 5점까지 리뷰 수
+    pub histogram: Vec<i32>,
+}
+
+#[cfg(feature = "server")]
+impl RatingSummaryDto {
+    fn empty(product_id: i32) -> Self {
+        RatingSummaryDto {
+            product_id,
+            count: 0,
+            average: None,
+            histogram: vec![0; 5],
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+impl From<crate::resources::entities::product_rating::Model> for RatingSummaryDto {
+    fn from(value: crate::resources::entities::product_rating::Model) -> Self {
+        // 소수 둘째 자리까지
+        let average = (value.review_count > 0)
+            .then(|| (value.rating_sum as f64 / value.review_count as f64 * 100.0).round() / 100.0);
+        RatingSummaryDto {
+            product_id: value.product_id,
+            count: value.review_count,
+            average,
+            histogram: vec![
+                value.star_1,
+                value.star_2,
+                value.star_3,
+                value.star_4,
+                value.star_5,
+            ],
+        }
+    }
+}
+
+// 리뷰 작성, 수정 본문
+#[validate_dto]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ReviewReq {
+    #[validate(range(min = 1, max = 5))]
+    pub rating: i32,
+    #[validate(length(min = 1, max = 2000))]
+    pub body: String,
+}
+
+#[cfg(feature = "server")]
+fn not_found(what: &str) -> ApiError {
+    ApiError::new(
+        reqwest::StatusCode::NOT_FOUND,
+        "not_found",
+        format!("{} is not found", what),
+    )
+}
+
+#[cfg(feature = "server")]
+fn not_author() -> ApiError {
+    ApiError::new(
+        reqwest::StatusCode::FORBIDDEN,
+        "forbidden",
+        "only the author can change this review",
+    )
+}
+
+// 없는 상품이면 fk_review_product, 이미 리뷰했으면 idx_review_product_user 위반
+#[cfg(feature = "server")]
+fn conflict(err: sea_orm::DbErr) -> ApiError {
+    use reqwest::StatusCode;
+    use sea_orm::SqlErr;
+
+    match err.sql_err() {
+        Some(SqlErr::ForeignKeyConstraintViolation(_)) => not_found("product"),
+        Some(SqlErr::UniqueConstraintViolation(_)) => ApiError::new(
+            StatusCode::CONFLICT,
+            "review_exists",
+            "you already reviewed this product",
+        ),
+        _ => err.into(),
+    }
+}
+
+// 1점부터 5점까지 리뷰 수를 세는 컬럼
+#[cfg(feature = "server")]
+const STAR_COLUMNS: [crate::resources::entities::product_rating::Column; 5] = {
+    use crate::resources::entities::product_rating::Column;
+
+    [
+        Column::Star1,
+        Column::Star2,
+        Column::Star3,
+        Column::Star4,
+        Column::Star5,
+    ]
+};
+
+// 집계 행에 증감만 더하므로 같은 상품에 리뷰가 동시에 달려도 값이 어긋나지 않음
+// before, after는 바뀌기 전, 후의 별점, 새 리뷰면 before가, 삭제면 after가 None
+#[cfg(feature = "server")]
+async fn apply_rating(
+    product_id: i32,
+    before: Option<i32>,
+    after: Option<i32>,
+    txn: &sea_orm::DatabaseTransaction,
+) -> Result<(), sea_orm::DbErr> {
+    use sea_orm::sea_query::{Expr, OnConflict, SimpleExpr};
+    use sea_orm::{ActiveValue::Set, EntityTrait};
+
+    use crate::resources::entities::product_rating::{self, Column};
+
+    if before == after {
+        return Ok(());
+    }
+
+    let count = after.is_some() as i32 - before.is_some() as i32;
+    let sum = after.unwrap_or(0) as i64 - before.unwrap_or(0) as i64;
+    let mut stars = [0; 5];
+    if let Some(r) = before {
+        stars[(r - 1) as usize] -= 1;
+    }
+    if let Some(r) = after {
+        stars[(r - 1) as usize] += 1;
+    }
+
+    let mut updates: Vec<(Column, SimpleExpr)> = vec![
+        (
+            Column::ReviewCount,
+            Expr::col((product_rating::Entity, Column::ReviewCount)).add(count),
+        ),
+        (
+            Column::RatingSum,
+            Expr::col((product_rating::Entity, Column::RatingSum)).add(sum),
+        ),
+    ];
+    for (column, delta) in STAR_COLUMNS.into_iter().zip(stars) {
+        if delta != 0 {
+            updates.push((
+                column,
+                Expr::col((product_rating::Entity, column)).add(delta),
+            ));
+        }
+    }
+
+    product_rating::Entity::insert(product_rating::ActiveModel {
+        product_id: Set(product_id),
+        review_count: Set(count),
+        rating_sum: Set(sum),
+        star_1: Set(stars[0]),
+        star_2: Set(stars[1]),
+        star_3: Set(stars[2]),
+        star_4: Set(stars[3]),
+        star_5: Set(stars[4]),
+    })
+    .on_conflict(
+        OnConflict::column(Column::ProductId)
+            .values(updates)
+            .to_owned(),
+    )
+    .exec_without_returning(txn)
+    .await?;
+    Ok(())
+}
+
+// 작성자 본인의 리뷰를 잠금, 남의 리뷰면 403
+#[cfg(feature = "server")]
+async fn find_own(
+    id: i32,
+    user_id: i32,
+    txn: &sea_orm::DatabaseTransaction,
+) -> Result<crate::resources::entities::review::Model, ApiError> {
+    use sea_orm::{EntityTrait, QuerySelect};
+
+    use crate::resources::entities::review;
+
+    let model = review::Entity::find_by_id(id)
+        .lock_exclusive()
+        .one(txn)
+        .await?
+        .ok_or_else(|| not_found("review"))?;
+    if model.user_id != Some(user_id) {
+        return Err(not_author());
+    }
+    Ok(model)
+}
+
+// 목록의 작성자 이름을 한 번에 채움
+#[cfg(feature = "server")]
+async fn fill_authors(
+    items: &mut [ReviewDto],
+    conn: &sea_orm::DatabaseConnection,
+) -> Result<(), sea_orm::DbErr> {
+    use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QuerySelect};
+    use std::collections::HashMap;
+
+    use crate::resources::entities::users;
+
+    let ids: Vec<i32> = items.iter().filter_map(|r| r.user_id).collect();
+    if ids.is_empty() {
+        return Ok(());
+    }
+    let names: HashMap<i32, Option<String>> = users::Entity::find()
+        .select_only()
+        .column(users::Column::Id)
+        .column(users::Column::DisplayName)
+        .filter(users::Column::Id.is_in(ids))
+        .into_tuple::<(i32, Option<String>)>()
+        .all(conn)
+        .await?
+        .into_iter()
+        .collect();
+    for item in items.iter_mut() {
+        item.author_name = item
+            .user_id
+            .and_then(|id| names.get(&id).cloned().flatten());
+    }
+    Ok(())
+}
+
+#[cfg(feature = "server")]
+impl ReviewReq {
+    pub async fn create(
+        self,
+        product_id: i32,
+        user_id: i32,
+        conn: &sea_orm::DatabaseConnection,
+    ) -> Result<ReviewDto, ApiError> {
+        use sea_orm::{ActiveModelTrait, ActiveValue::Set, TransactionTrait};
+
+        use crate::resources::entities::review;
+
+        let txn = conn.begin().await?;
+        let model = review::ActiveModel {
+            product_id: Set(product_id),
+            user_id: Set(Some(user_id)),
+            rating: Set(self.rating),
+            body: Set(self.body.trim().to_string()),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await
+        .map_err(conflict)?;
+        apply_rating(product_id, None, Some(model.rating), &txn).await?;
+        txn.commit().await?;
+
+        let mut dto = [ReviewDto::from(model)];
+        fill_authors(&mut dto, conn).await?;
+        let [dto] = dto;
+        Ok(dto)
+    }
+
+    pub async fn update(
+        self,
+        id: i32,
+        user_id: i32,
+        conn: &sea_orm::DatabaseConnection,
+    ) -> Result<ReviewDto, ApiError> {
+        use sea_orm::{ActiveModelTrait, ActiveValue::Set, TransactionTrait};
+
+        use crate::resources::entities::review;
+
+        let txn = conn.begin().await?;
+        let before = find_own(id, user_id, &txn).await?;
+        let product_id = before.product_id;
+        let old_rating = before.rating;
+
+        let mut active: review::ActiveModel = before.into();
+        active.rating = Set(self.rating);
+        active.body = Set(self.body.trim().to_string());
+        active.updated_at = Set(chrono::Utc::now().naive_utc());
+        let model = active.update(&txn).await?;
+        apply_rating(product_id, Some(old_rating), Some(model.rating), &txn).await?;
+        txn.commit().await?;
+
+        let mut dto = [ReviewDto::from(model)];
+        fill_authors(&mut dto, conn).await?;
+        let [dto] = dto;
+        Ok(dto)
+    }
+}
+
+#[cfg(feature = "server")]
+pub async fn delete_review(
+    id: i32,
+    user_id: i32,
+    conn: &sea_orm::DatabaseConnection,
+) -> Result<(), ApiError> {
+    use sea_orm::{ModelTrait, TransactionTrait};
+
+    let txn = conn.begin().await?;
+    let model = find_own(id, user_id, &txn).await?;
+    let (product_id, rating) = (model.product_id, model.rating);
+    model.delete(&txn).await?;
+    apply_rating(product_id, Some(rating), None, &txn).await?;
+    txn.commit().await?;
+    Ok(())
+}
+
+// 상품의 리뷰 목록, 정렬을 주지 않으면 최신순
+#[cfg(feature = "server")]
+pub async fn list_reviews(
+    product_id: i32,
+    list: crate::resources::dto::query::ListQuery<crate::resources::entities::review::Entity>,
+    cursor: Option<&str>,
+    limit: Option<u64>,
+    with_total: bool,
+    conn: &sea_orm::DatabaseConnection,
+) -> Result<Page<ReviewDto>, ApiError> {
+    use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+
+    use crate::resources::dto::product::get_product;
+    use crate::resources::entities::review;
+
+    get_product(product_id, conn).await?;
+    let mut page: Page<ReviewDto> = list
+        .page(
+            review::Entity::find().filter(review::Column::ProductId.eq(product_id)),
+            cursor,
+            limit,
+            with_total,
+            conn,
+        )
+        .await?;
+    fill_authors(&mut page.items, conn).await?;
+    Ok(page)
+}
+
+#[cfg(feature = "server")]
+pub async fn rating_summary(
+    product_id: i32,
+    conn: &sea_orm::DatabaseConnection,
+) -> Result<RatingSummaryDto, ApiError> {
+    use sea_orm::EntityTrait;
+
+    use crate::resources::dto::product::get_product;
+    use crate::resources::entities::product_rating;
+
+    get_product(product_id, conn).await?;
+    Ok(product_rating::Entity::find_by_id(product_id)
+        .one(conn)
+        .await?
+        .map(RatingSummaryDto::from)
+        .unwrap_or_else(|| RatingSummaryDto::empty(product_id)))
+}
+
+// 로그인한 유저가 이 상품에 남긴 리뷰
+#[cfg(feature = "server")]
+pub async fn my_review(
+    product_id: i32,
+    user_id: i32,
+    conn: &sea_orm::DatabaseConnection,
+) -> Result<Option<ReviewDto>, ApiError> {
+    use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+
+    use crate::resources::entities::review;
+
+    let Some(model) = review::Entity::find()
+        .filter(review::Column::ProductId.eq(product_id))
+        .filter(review::Column::UserId.eq(user_id))
+        .one(conn)
+        .await?
+    else {
+        return Ok(None);
+    };
+    let mut dto = [ReviewDto::from(model)];
+    fill_authors(&mut dto, conn).await?;
+    let [dto] = dto;
+    Ok(Some(dto))
+}
+
+// 목록 API(filter, sort)에서 쓸 수 있는 컬럼
+#[cfg(feature = "server")]
+impl crate::resources::dto::query::QueryFields for crate::resources::entities::review::Entity {
+    const FIELDS: &'static [crate::resources::dto::query::QueryField<Self::Column>] = {
+        use crate::resources::dto::query::{FieldKind, QueryField};
+        use crate::resources::entities::review::Column;
+
+        &[
+            QueryField {
+                name: "id",
+                column: Column::Id,
+                kind: FieldKind::Int,
+                sortable: true,
+            },
+            QueryField {
+                name: "rating",
+                column: Column::Rating,
+                kind: FieldKind::Int,
+                sortable: true,
+            },
+            QueryField {
+                name: "user_id",
+                column: Column::UserId,
+                kind: FieldKind::Int,
+                sortable: false,
+            },
+            QueryField {
+                name: "created_at",
+                column: Column::CreatedAt,
+                kind: FieldKind::DateTime,
+                sortable: true,
+            },
+            QueryField {
+                name: "updated_at",
+                column: Column::UpdatedAt,
+                kind: FieldKind::DateTime,
+                sortable: true,
+            },
+        ]
+    };
+    const ID: Self::Column = crate::resources::entities::review::Column::Id;
+    const NEWEST_FIRST: bool = true;
+}
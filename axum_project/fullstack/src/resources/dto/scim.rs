@@ -0,0 +1,747 @@
+use chrono::NaiveDateTime;
+use sea_orm::sea_query::{Expr, Func, SimpleExpr};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, Condition, DatabaseConnection, EntityTrait,
+    PaginatorTrait, QueryFilter, QueryOrder, QuerySelect,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::resources::dto::admin::{UserStatus, set_status};
+use crate::resources::dto::query::like_escape;
+use crate::resources::dto::user::{UserDto, update_versioned};
+use crate::resources::entities::users;
+use crate::utils::errors::{AppError, ScimError};
+
+pub const USER_SCHEMA: &str = "urn:ietf:params:scim:schemas:core:2.0:User";
+pub const LIST_SCHEMA: &str = "urn:ietf:params:scim:api:messages:2.0:ListResponse";
+pub const PATCH_SCHEMA: &str = "urn:ietf:params:scim:api:messages:2.0:PatchOp";
+
+pub const DEFAULT_COUNT: u64 = 100;
+pub const MAX_COUNT: u64 = 200;
+
+// 상대 주소로도 동작하지만, OIDC_ISSUER가 있으면 절대 주소로 내려줌
+fn location(id: i32) -> String {
+    let base = crate::utils::oidc::issuer().unwrap_or_default();
+    format!("{}/scim/v2/Users/{}", base, id)
+}
+
+fn timestamp(value: NaiveDateTime) -> String {
+    value
+        .and_utc()
+        .to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+}
+
+#[derive(utoipa::ToSchema, Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ScimName {
+    pub formatted: Option<String>,
+}
+
+#[derive(utoipa::ToSchema, Serialize, Deserialize, Debug, Clone)]
+pub struct ScimEmail {
+    pub value: String,
+    pub primary: bool,
+}
+
+#[derive(utoipa::ToSchema, Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScimMeta {
+    pub resource_type: String,
+    pub created: String,
+    pub last_modified: String,
+    pub location: String,
+    pub version: String,
+}
+
+// users 한 행을 SCIM User 리소스로 표현
+// 로그인 이메일(username)이 userName이자 대표 이메일, display_name이 displayName과 name.formatted
+#[derive(utoipa::ToSchema, Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScimUser {
+    pub schemas: Vec<String>,
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_id: Option<String>,
+    pub user_name: String,
+    pub name: ScimName,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    pub emails: Vec<ScimEmail>,
+    pub active: bool,
+    pub meta: ScimMeta,
+}
+
+impl From<users::Model> for ScimUser {
+    fn from(value: users::Model) -> Self {
+        ScimUser {
+            schemas: vec![USER_SCHEMA.to_string()],
+            id: value.id.to_string(),
+            external_id: value.external_id,
+            emails: vec![ScimEmail {
+                value: value.username.clone(),
+                primary: true,
+            }],
+            user_name: value.username,
+            name: ScimName {
+                formatted: value.display_name.clone(),
+            },
+            display_name: value.display_name,
+            active: value.status == UserStatus::Active.as_str(),
+            meta: ScimMeta {
+                resource_type: "User".to_string(),
+                created: timestamp(value.created_at),
+                last_modified: timestamp(value.updated_at),
+                location: location(value.id),
+                version: format!("W/\"{}-{}\"", value.id, value.version),
+            },
+        }
+    }
+}
+
+// POST, PUT 본문, 모르는 속성은 무시함
+#[derive(utoipa::ToSchema, Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ScimUserReq {
+    pub external_id: Option<String>,
+    pub user_name: String,
+    pub name: Option<ScimName>,
+    pub display_name: Option<String>,
+    // 없으면 true
+    pub active: Option<bool>,
+}
+
+impl ScimUserReq {
+    fn from_model(model: &users::Model) -> Self {
+        ScimUserReq {
+            external_id: model.external_id.clone(),
+            user_name: model.username.clone(),
+            name: None,
+            display_name: model.display_name.clone(),
+            active: Some(model.status == UserStatus::Active.as_str()),
+        }
+    }
+
+    // displayName이 없으면 name.formatted를 사용
+    fn display_name(&self) -> Option<String> {
+        self.display_name
+            .clone()
+            .or(self.name.as_ref().and_then(|n| n.formatted.clone()))
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+    }
+
+    fn check(&self) -> Result<(), ScimError> {
+        let user_name = self.user_name.trim();
+        if user_name.is_empty() || user_name.chars().count() > 100 {
+            return Err(ScimError::invalid_value(
+                "userName must be 1 to 100 characters",
+            ));
+        }
+        if self
+            .display_name()
+            .is_some_and(|v| v.chars().count() > crate::resources::dto::profile::DISPLAY_NAME_MAX)
+        {
+            return Err(ScimError::invalid_value("displayName is too long"));
+        }
+        Ok(())
+    }
+
+    // 다른 (탈퇴하지 않은) 유저가 같은 userName이나 externalId를 쓰고 있으면 409
+    async fn check_unique(
+        &self,
+        except: Option<i32>,
+        conn: &DatabaseConnection,
+    ) -> Result<(), ScimError> {
+        let mut same = Condition::any().add(lower_eq(users::Column::Username, &self.user_name));
+        if let Some(external_id) = &self.external_id {
+            same = same.add(users::Column::ExternalId.eq(external_id));
+        }
+        let mut select = users::Entity::find()
+            .filter(users::Column::DeletedAt.is_null())
+            .filter(same);
+        if let Some(id) = except {
+            select = select.filter(users::Column::Id.ne(id));
+        }
+        if select.count(conn).await? > 0 {
+            return Err(ScimError::uniqueness(
+                "userName or externalId is already in use",
+            ));
+        }
+        Ok(())
+    }
+
+    pub async fn create(self, conn: &DatabaseConnection) -> Result<ScimUser, ScimError> {
+        self.check()?;
+        self.check_unique(None, conn).await?;
+
+        let status = if self.active.unwrap_or(true) {
+            UserStatus::Active
+        } else {
+            UserStatus::Suspended
+        };
+        let model = users::ActiveModel {
+            username: Set(self.user_name.trim().to_string()),
+            display_name: Set(self.display_name()),
+            external_id: Set(self.external_id.clone()),
+            status: Set(status.as_str().to_string()),
+            ..Default::default()
+        }
+        .insert(conn)
+        .await?;
+
+        Ok(model.into())
+    }
+
+    // PUT은 전체 교체, PATCH도 적용한 결과를 이걸로 저장함
+    pub async fn replace(self, id: i32, conn: &DatabaseConnection) -> Result<ScimUser, ScimError> {
+        self.check()?;
+        let current = find_active(id, conn).await?;
+        self.check_unique(Some(id), conn).await?;
+
+        let active = users::ActiveModel {
+            username: Set(self.user_name.trim().to_string()),
+            display_name: Set(self.display_name()),
+            external_id: Set(self.external_id.clone()),
+            ..Default::default()
+        };
+        let mut model = update_versioned(active, id, None, conn)
+            .await?
+            .ok_or(ScimError::not_found())?;
+
+        // 비활성화는 관리자 정지와 같음, 발급된 토큰을 모두 끊음
+        let status = if self.active.unwrap_or(true) {
+            UserStatus::Active
+        } else {
+            UserStatus::Suspended
+        };
+        if current.status != status.as_str() {
+            set_status(id, status, conn).await?;
+            model = find_active(id, conn).await?;
+        }
+
+        Ok(model.into())
+    }
+}
+
+fn lower_eq(column: users::Column, value: &str) -> SimpleExpr {
+    Expr::expr(Func::lower(Expr::col(column))).eq(value.trim().to_lowercase())
+}
+
+// 탈퇴(soft delete)한 유저는 SCIM에서 없는 리소스로 취급
+pub async fn find_active(id: i32, conn: &DatabaseConnection) -> Result<users::Model, ScimError> {
+    users::Entity::find_by_id(id)
+        .filter(users::Column::DeletedAt.is_null())
+        .one(conn)
+        .await?
+        .ok_or(ScimError::not_found())
+}
+
+pub fn parse_id(id: &str) -> Result<i32, ScimError> {
+    id.parse().map_err(|_| ScimError::not_found())
+}
+
+#[derive(utoipa::ToSchema, Serialize, Deserialize, Debug, Clone)]
+pub struct ScimPatchOp {
+    // add, replace, remove (대소문자 구분 없음)
+    pub op: String,
+    pub path: Option<String>,
+    #[schema(value_type = Option<Object>)]
+    pub value: Option<serde_json::Value>,
+}
+
+#[derive(utoipa::ToSchema, Serialize, Deserialize, Debug, Clone)]
+pub struct ScimPatchReq {
+    pub schemas: Vec<String>,
+    #[serde(rename = "Operations")]
+    pub operations: Vec<ScimPatchOp>,
+}
+
+// IdP마다 active를 "False" 같은 문자열로 보내기도 함
+fn as_bool(value: &serde_json::Value) -> Option<bool> {
+    match value {
+        serde_json::Value::Bool(v) => Some(*v),
+        serde_json::Value::String(v) => v.to_lowercase().parse().ok(),
+        _ => None,
+    }
+}
+
+fn as_text(value: &serde_json::Value) -> Option<String> {
+    value.as_str().map(str::to_string)
+}
+
+impl ScimPatchReq {
+    fn set(
+        req: &mut ScimUserReq,
+        path: &str,
+        value: Option<&serde_json::Value>,
+    ) -> Result<(), ScimError> {
+        let invalid = || ScimError::invalid_value(format!("invalid value for {}", path));
+        match path.to_lowercase().as_str() {
+            "active" => req.active = Some(value.and_then(as_bool).ok_or_else(invalid)?),
+            "username" => req.user_name = value.and_then(as_text).ok_or_else(invalid)?,
+            "displayname" | "name.formatted" => {
+                req.name = None;
+                req.display_name = value.map(|v| as_text(v).ok_or_else(invalid)).transpose()?;
+            }
+            "externalid" => {
+                req.external_id = value.map(|v| as_text(v).ok_or_else(invalid)).transpose()?
+            }
+            "name" => {
+                req.name = None;
+                req.display_name = value.and_then(|v| v.get("formatted")).and_then(as_text);
+            }
+            _ => {
+                return Err(ScimError::invalid_path(format!(
+                    "unsupported path {}",
+                    path
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn apply(&self, req: &mut ScimUserReq) -> Result<(), ScimError> {
+        if !self.schemas.iter().any(|s| s == PATCH_SCHEMA) {
+            return Err(ScimError::invalid_value("PatchOp schema is required"));
+        }
+        for op in &self.operations {
+            match (op.op.to_lowercase().as_str(), &op.path) {
+                ("add" | "replace", Some(path)) => Self::set(req, path, op.value.as_ref())?,
+                // path가 없으면 value 객체의 속성을 하나씩 적용
+                ("add" | "replace", None) => {
+                    let Some(serde_json::Value::Object(values)) = &op.value else {
+                        return Err(ScimError::invalid_value("value must be an object"));
+                    };
+                    for (path, value) in values {
+                        Self::set(req, path, Some(value))?;
+                    }
+                }
+                ("remove", Some(path)) => match path.to_lowercase().as_str() {
+                    "displayname" | "name.formatted" | "name" | "externalid" => {
+                        Self::set(req, path, None)?
+                    }
+                    _ => {
+                        return Err(ScimError::new(
+                            reqwest::StatusCode::BAD_REQUEST,
+                            Some("mutability"),
+                            format!("{} can't be removed", path),
+                        ));
+                    }
+                },
+                ("remove", None) => {
+                    return Err(ScimError::new(
+                        reqwest::StatusCode::BAD_REQUEST,
+                        Some("noTarget"),
+                        "path is required for remove",
+                    ));
+                }
+                (op, _) => {
+                    return Err(ScimError::invalid_value(format!("unsupported op {}", op)));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub async fn patch(self, id: i32, conn: &DatabaseConnection) -> Result<ScimUser, ScimError> {
+        let current = find_active(id, conn).await?;
+        let mut req = ScimUserReq::from_model(&current);
+        self.apply(&mut req)?;
+        req.replace(id, conn).await
+    }
+}
+
+#[derive(utoipa::IntoParams, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+#[into_params(parameter_in = Query)]
+pub struct ScimListReq {
+    // userName eq "kim@example.com" and active eq true
+    pub filter: Option<String>,
+    // 1부터 시작
+    pub start_index: Option<u64>,
+    pub count: Option<u64>,
+}
+
+#[derive(utoipa::ToSchema, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ScimListRes {
+    pub schemas: Vec<String>,
+    pub total_results: u64,
+    pub start_index: u64,
+    pub items_per_page: u64,
+    #[serde(rename = "Resources")]
+    pub resources: Vec<ScimUser>,
+}
+
+impl ScimListReq {
+    pub async fn list(&self, conn: &DatabaseConnection) -> Result<ScimListRes, ScimError> {
+        let start_index = self.start_index.unwrap_or(1).max(1);
+        let count = self.count.unwrap_or(DEFAULT_COUNT).min(MAX_COUNT);
+
+        let mut select = users::Entity::find().filter(users::Column::DeletedAt.is_null());
+        if let Some(filter) = self.filter.as_deref().filter(|f| !f.trim().is_empty()) {
+            select = select.filter(parse_filter(filter)?);
+        }
+
+        let total_results = select.clone().count(conn).await?;
+        let rows = select
+            .order_by_asc(users::Column::Id)
+            .offset(start_index - 1)
+            .limit(count)
+            .all(conn)
+            .await?;
+
+        Ok(ScimListRes {
+            schemas: vec![LIST_SCHEMA.to_string()],
+            total_results,
+            start_index,
+            items_per_page: rows.len() as u64,
+            resources: rows.into_iter().map(ScimUser::from).collect(),
+        })
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Word(String),
+    Text(String),
+}
+
+fn tokenize(filter: &str) -> Result<Vec<Token>, ScimError> {
+    let mut tokens = Vec::new();
+    let mut chars = filter.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c == '"' {
+            chars.next();
+            let mut text = String::new();
+            loop {
+                match chars.next() {
+                    Some('"') => break,
+                    Some('\\') => text.extend(chars.next()),
+                    Some(c) => text.push(c),
+                    None => return Err(ScimError::invalid_filter("unterminated string")),
+                }
+            }
+            tokens.push(Token::Text(text));
+        } else if c == '(' || c == ')' || c == '[' || c == ']' {
+            return Err(ScimError::invalid_filter(
+                "grouping and complex attribute filters are not supported",
+            ));
+        } else {
+            let mut word = String::new();
+            while let Some(&c) = chars.peek() {
+                if c.is_whitespace() || c == '"' {
+                    break;
+                }
+                word.push(c);
+                chars.next();
+            }
+            tokens.push(Token::Word(word));
+        }
+    }
+    Ok(tokens)
+}
+
+// RFC 7644 3.4.2.2 중 "속성 연산자 값"을 and로 이은 형태만 지원함
+fn parse_filter(filter: &str) -> Result<Condition, ScimError> {
+    let tokens = tokenize(filter)?;
+    let mut condition = Condition::all();
+    let mut rest = tokens.as_slice();
+
+    loop {
+        let (expr, next) = match rest {
+            [Token::Word(attr), Token::Word(op), tail @ ..] if op.eq_ignore_ascii_case("pr") => {
+                (present(attr)?, tail)
+            }
+            [Token::Word(attr), Token::Word(op), value, tail @ ..] => {
+                (compare(attr, &op.to_lowercase(), value)?, tail)
+            }
+            _ => {
+                return Err(ScimError::invalid_filter(
+                    "expected: attribute operator value",
+                ));
+            }
+        };
+        condition = condition.add(expr);
+
+        match next {
+            [] => return Ok(condition),
+            [Token::Word(and), tail @ ..] if and.eq_ignore_ascii_case("and") => rest = tail,
+            _ => return Err(ScimError::invalid_filter("only 'and' is supported")),
+        }
+    }
+}
+
+enum FilterAttr {
+    Id,
+    Text(users::Column),
+    Active,
+    Time(users::Column),
+}
+
+fn filter_attr(attr: &str) -> Result<FilterAttr, ScimError> {
+    Ok(match attr.to_lowercase().as_str() {
+        "id" => FilterAttr::Id,
+        "username" | "emails" | "emails.value" => FilterAttr::Text(users::Column::Username),
+        "externalid" => FilterAttr::Text(users::Column::ExternalId),
+        "displayname" | "name.formatted" => FilterAttr::Text(users::Column::DisplayName),
+        "active" => FilterAttr::Active,
+        "meta.created" => FilterAttr::Time(users::Column::CreatedAt),
+        "meta.lastmodified" => FilterAttr::Time(users::Column::UpdatedAt),
+        _ => {
+            return Err(ScimError::invalid_filter(format!(
+                "unsupported attribute {}",
+                attr
+            )));
+        }
+    })
+}
+
+fn present(attr: &str) -> Result<SimpleExpr, ScimError> {
+    Ok(match filter_attr(attr)? {
+        FilterAttr::Text(column) | FilterAttr::Time(column) => column.is_not_null(),
+        FilterAttr::Id | FilterAttr::Active => Expr::value(true),
+    })
+}
+
+fn compare(attr: &str, op: &str, value: &Token) -> Result<SimpleExpr, ScimError> {
+    let invalid = || ScimError::invalid_filter(format!("invalid value for {}", attr));
+    let unsupported = || ScimError::invalid_filter(format!("{} doesn't support {}", attr, op));
+
+    match filter_attr(attr)? {
+        FilterAttr::Id => {
+            let Token::Text(id) = value else {
+                return Err(invalid());
+            };
+            // 숫자가 아닌 id는 어떤 유저와도 일치하지 않음
+            let id = id.parse::<i32>().unwrap_or(-1);
+            match op {
+                "eq" => Ok(users::Column::Id.eq(id)),
+                "ne" => Ok(users::Column::Id.ne(id)),
+                _ => Err(unsupported()),
+            }
+        }
+        FilterAttr::Active => {
+            let active = match value {
+                Token::Word(v) => v.to_lowercase().parse::<bool>().map_err(|_| invalid())?,
+                Token::Text(_) => return Err(invalid()),
+            };
+            let is_active = users::Column::Status.eq(UserStatus::Active.as_str());
+            let is_inactive = users::Column::Status.ne(UserStatus::Active.as_str());
+            match op {
+                "eq" => Ok(if active { is_active } else { is_inactive }),
+                "ne" => Ok(if active { is_inactive } else { is_active }),
+                _ => Err(unsupported()),
+            }
+        }
+        // SCIM 문자열 속성은 기본적으로 대소문자를 구분하지 않음
+        FilterAttr::Text(column) => {
+            let Token::Text(text) = value else {
+                return Err(invalid());
+            };
+            let lower = || Expr::expr(Func::lower(Expr::col(column)));
+            let pattern = like_escape(&text.to_lowercase());
+            match op {
+                "eq" => Ok(lower().eq(text.to_lowercase())),
+                "ne" => Ok(lower().ne(text.to_lowercase())),
+                "co" => Ok(lower().like(format!("%{}%", pattern))),
+                "sw" => Ok(lower().like(format!("{}%", pattern))),
+                "ew" => Ok(lower().like(format!("%{}", pattern))),
+                _ => Err(unsupported()),
+            }
+        }
+        FilterAttr::Time(column) => {
+            let Token::Text(text) = value else {
+                return Err(invalid());
+            };
+            let time = chrono::DateTime::parse_from_rfc3339(text)
+                .map_err(|_| invalid())?
+                .naive_utc();
+            match op {
+                "eq" => Ok(column.eq(time)),
+                "gt" => Ok(column.gt(time)),
+                "ge" => Ok(column.gte(time)),
+                "lt" => Ok(column.lt(time)),
+                "le" => Ok(column.lte(time)),
+                _ => Err(unsupported()),
+            }
+        }
+    }
+}
+
+// SCIM으로 미리 만든 계정에 처음 구글 로그인할 때 구글 계정을 연결함
+// IdP가 만든 계정(externalId가 있음)만 이메일로 연결함
+// 구글이 확인한 이메일이어야 하고, SCIM_GOOGLE_DOMAIN이 있으면 그 Workspace 도메인(hd)의 계정이어야 함
+fn allowed_domain(hosted_domain: Option<&str>) -> bool {
+    match std::env::var("SCIM_GOOGLE_DOMAIN")
+        .ok()
+        .filter(|d| !d.is_empty())
+    {
+        Some(domain) => hosted_domain.is_some_and(|hd| hd.eq_ignore_ascii_case(&domain)),
+        None => true,
+    }
+}
+
+pub async fn link_provisioned(
+    email: &str,
+    email_verified: bool,
+    hosted_domain: Option<&str>,
+    google_sub: &str,
+    conn: &DatabaseConnection,
+) -> Result<Option<UserDto>, AppError> {
+    if !email_verified || !allowed_domain(hosted_domain) {
+        return Ok(None);
+    }
+    let Some(model) = users::Entity::find()
+        .filter(lower_eq(users::Column::Username, email))
+        .filter(users::Column::ExternalId.is_not_null())
+        .filter(users::Column::GoogleOauth.is_null())
+        .filter(users::Column::DeletedAt.is_null())
+        .one(conn)
+        .await?
+    else {
+        return Ok(None);
+    };
+
+    let active = users::ActiveModel {
+        google_oauth: Set(Some(google_sub.to_string())),
+        ..Default::default()
+    };
+    Ok(update_versioned(active, model.id, None, conn)
+        .await?
+        .map(UserDto::from))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn patch(operations: serde_json::Value) -> ScimPatchReq {
+        serde_json::from_value(json!({ "schemas": [PATCH_SCHEMA], "Operations": operations }))
+            .unwrap()
+    }
+
+    fn user() -> ScimUserReq {
+        ScimUserReq {
+            external_id: Some("ext".to_string()),
+            user_name: "kim@example.com".to_string(),
+            display_name: Some("Kim".to_string()),
+            active: Some(true),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn patch_deactivates_with_path_or_value_object() {
+        let mut req = user();
+        patch(json!([{ "op": "replace", "path": "active", "value": false }]))
+            .apply(&mut req)
+            .unwrap();
+        assert_eq!(req.active, Some(false));
+
+        // path 없이 문자열 "False"로 보내는 IdP도 있음
+        let mut req = user();
+        patch(json!([{ "op": "Replace", "value": { "active": "False" } }]))
+            .apply(&mut req)
+            .unwrap();
+        assert_eq!(req.active, Some(false));
+        assert_eq!(req.display_name.as_deref(), Some("Kim"));
+    }
+
+    #[test]
+    fn patch_rejects_bad_operations() {
+        let cases = [
+            (
+                json!([{ "op": "replace", "path": "active", "value": 1 }]),
+                "invalidValue",
+            ),
+            (
+                json!([{ "op": "replace", "path": "password", "value": "x" }]),
+                "invalidPath",
+            ),
+            (
+                json!([{ "op": "remove", "path": "userName" }]),
+                "mutability",
+            ),
+            (json!([{ "op": "remove" }]), "noTarget"),
+            (json!([{ "op": "move", "path": "active" }]), "invalidValue"),
+        ];
+        for (operations, scim_type) in cases {
+            let err = patch(operations.clone()).apply(&mut user()).unwrap_err();
+            assert_eq!(err.status, "400", "{}", operations);
+            assert_eq!(err.scim_type.as_deref(), Some(scim_type), "{}", operations);
+        }
+
+        let mut req = patch(json!([]));
+        req.schemas.clear();
+        assert!(req.apply(&mut user()).is_err());
+    }
+
+    #[test]
+    fn remove_clears_optional_attributes() {
+        let mut req = user();
+        patch(json!([
+            { "op": "remove", "path": "displayName" },
+            { "op": "remove", "path": "externalId" }
+        ]))
+        .apply(&mut req)
+        .unwrap();
+        assert_eq!(req.display_name(), None);
+        assert_eq!(req.external_id, None);
+    }
+
+    // DB를 보기 전에 거절해야 하므로 연결 없이 확인함
+    #[tokio::test]
+    async fn unverified_google_email_is_never_linked() {
+        let conn = DatabaseConnection::Disconnected;
+        let linked = link_provisioned("kim@example.com", false, None, "sub", &conn)
+            .await
+            .unwrap();
+        assert!(linked.is_none());
+    }
+
+    #[test]
+    fn tokenize_quoted_values() {
+        assert_eq!(
+            tokenize(r#"userName eq "a \"b\" c""#).unwrap(),
+            vec![
+                Token::Word("userName".to_string()),
+                Token::Word("eq".to_string()),
+                Token::Text(r#"a "b" c"#.to_string()),
+            ]
+        );
+        assert!(tokenize(r#"userName eq "open"#).is_err());
+    }
+
+    #[test]
+    fn parse_supported_filters_only() {
+        for filter in [
+            r#"userName eq "kim@example.com""#,
+            r#"USERNAME Eq "kim@example.com" and active eq true"#,
+            r#"externalId pr"#,
+            r#"meta.lastModified gt "2026-01-01T00:00:00Z""#,
+        ] {
+            assert!(parse_filter(filter).is_ok(), "{}", filter);
+        }
+        for filter in [
+            r#"userName eq "a" or userName eq "b""#,
+            r#"(userName eq "a")"#,
+            r#"emails[type eq "work"]"#,
+            r#"password eq "x""#,
+            r#"active eq "true""#,
+            r#"userName gt "a""#,
+            r#"meta.created gt "yesterday""#,
+            r#"userName eq"#,
+        ] {
+            let err = parse_filter(filter).unwrap_err();
+            assert_eq!(
+                err.scim_type.as_deref(),
+                Some("invalidFilter"),
+                "{}",
+                filter
+            );
+        }
+    }
+}
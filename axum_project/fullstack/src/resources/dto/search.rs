@@ -0,0 +1,387 @@
+use serde::{Deserialize, Serialize};
+use validate_dto_macro::validate_dto;
+
+use crate::resources::dto::money::Currency;
+#[cfg(feature = "server")]
+use crate::resources::dto::money::Money;
+use crate::resources::dto::product::ProductDto;
+#[cfg(feature = "server")]
+use crate::utils::errors::ApiError;
+
+// 통화별 가격 구간 경계(통화 최소 단위), [0, b0), [b0, b1), ... [b3, ∞)
+pub fn price_buckets(currency: Currency) -> [i64; 4] {
+    match currency {
+        Currency::Krw => [10_000, 30_000, 50_000, 100_000],
+        Currency::Jpy => [1_000, 3_000, 5_000, 10_000],
+        // $10, $30, $50, $100
+        Currency::Usd | Currency::Eur => [1_000, 3_000, 5_000, 10_000],
+    }
+}
+// 본문 검색 결과가 없을 때 오타 검색으로 찾을 최소 유사도
+pub const FUZZY_THRESHOLD: f32 = 0.3;
+
+#[validate_dto(params)]
+#[cfg_attr(feature = "server", derive(utoipa::IntoParams, utoipa::ToSchema))]
+#[derive(Deserialize, Serialize, Debug)]
+pub struct ProductSearchReq {
+    // 검색어, "정확한 구절", -제외, or 를 쓸 수 있음 (websearch_to_tsquery)
+    #[validate(length(min = 1, max = 100))]
+    pub q: String,
+    // 하위 카테고리까지 포함
+    pub category_id: Option<i32>,
+    // 이 통화의 상품만 찾음, price_min이나 price_max를 쓰려면 필요함
+    pub currency: Option<Currency>,
+    // currency의 최소 단위 금액
+    #[validate(range(min = 0))]
+    pub price_min: Option<i64>,
+    #[validate(range(min = 0))]
+    pub price_max: Option<i64>,
+    // 기본 20, 최대 100
+    #[validate(range(min = 1, max = 100))]
+    pub limit: Option<u64>,
+    // 순위순이므로 커서 대신 offset
+    #[validate(range(min = 0, max = 10000))]
+    pub offset: Option<u64>,
+}
+
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct ProductHit {
+    #[serde(flatten)]
+    pub product: ProductDto,
+    pub rank: f32,
+    // HTML 이스케이프된 제목, 검색어는 <mark>로 감쌈
+    pub snippet: String,
+}
+
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct CategoryFacet {
+    pub category_id: i32,
+    pub name: String,
+    pub count: i64,
+}
+
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct PriceFacet {
+    pub currency: Currency,
+    pub min: i64,
+    // 없으면 상한 없음
+    pub max: Option<i64>,
+    pub count: i64,
+}
+
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct SearchFacets {
+    pub categories: Vec<CategoryFacet>,
+    pub prices: Vec<PriceFacet>,
+}
+
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct ProductSearchRes {
+    pub items: Vec<ProductHit>,
+    pub total: i64,
+    // 본문 검색 결과가 없어서 오타 검색(pg_trgm)으로 찾은 결과인지
+    pub fuzzy: bool,
+    // 페이지와 관계없이 검색 결과 전체에 대한 개수
+    pub facets: SearchFacets,
+}
+
+#[cfg(feature = "server")]
+#[derive(sea_orm::FromQueryResult)]
+struct HitRow {
+    id: i32,
+    title: String,
+    price: i64,
+    currency: String,
+    category_id: i32,
+    sku: Option<String>,
+    rank: f32,
+    snippet: String,
+}
+
+#[cfg(feature = "server")]
+#[derive(sea_orm::FromQueryResult)]
+struct CountRow {
+    count: i64,
+}
+
+#[cfg(feature = "server")]
+#[derive(sea_orm::FromQueryResult)]
+struct CategoryRow {
+    category_id: i32,
+    name: String,
+    count: i64,
+}
+
+#[cfg(feature = "server")]
+#[derive(sea_orm::FromQueryResult)]
+struct BucketRow {
+    currency: String,
+    // 모르는 통화 코드면 없음
+    bucket: Option<i32>,
+    count: i64,
+}
+
+#[cfg(feature = "server")]
+const ESCAPED_TITLE: &str =
+    "replace(replace(replace(p.title, '&', '&amp;'), '<', '&lt;'), '>', '&gt;')";
+
+#[cfg(feature = "server")]
+impl ProductSearchReq {
+    // 검색 조건에 맞는 상품을 matched CTE로 만듦, $1은 검색어
+    fn matched(&self, fuzzy: bool, category_ids: Option<&[i32]>) -> (String, Vec<sea_orm::Value>) {
+        let mut values: Vec<sea_orm::Value> = vec![self.q.trim().into()];
+        let mut filters = String::new();
+        if let Some(ids) = category_ids {
+            // DB에서 읽은 정수라 그대로 넣어도 안전함
+            let ids = ids.iter().map(i32::to_string).collect::<Vec<_>>();
+            filters.push_str(&format!(" AND p.category_id IN ({})", ids.join(",")));
+        }
+        if let Some(currency) = self.currency {
+            values.push(currency.as_str().into());
+            filters.push_str(&format!(" AND p.currency = ${}", values.len()));
+        }
+        if let Some(min) = self.price_min {
+            values.push(min.into());
+            filters.push_str(&format!(" AND p.price >= ${}", values.len()));
+        }
+        if let Some(max) = self.price_max {
+            values.push(max.into());
+            filters.push_str(&format!(" AND p.price <= ${}", values.len()));
+        }
+
+        let sql = if fuzzy {
+            // <% 는 pg_trgm.word_similarity_threshold 이상인 행만 찾고 trgm 인덱스를 씀
+            format!(
+                "WITH matched AS (
+                     SELECT p.id, p.title, p.price, p.currency, p.category_id, p.sku,
+                            word_similarity($1, p.title) AS rank,
+                            {ESCAPED_TITLE} AS snippet
+                     FROM product p
+                     WHERE $1 <% p.title{filters}
+                 )"
+            )
+        } else {
+            format!(
+                "WITH q AS (SELECT websearch_to_tsquery('simple', $1) AS query),
+                 matched AS (
+                     SELECT p.id, p.title, p.price, p.currency, p.category_id, p.sku,
+                            ts_rank_cd(p.search_vector, q.query) AS rank,
+                            ts_headline('simple', {ESCAPED_TITLE}, q.query,
+                                'StartSel=<mark>, StopSel=</mark>, HighlightAll=true') AS snippet
+                     FROM product p, q
+                     WHERE p.search_vector @@ q.query{filters}
+                 )"
+            )
+        };
+        (sql, values)
+    }
+
+    async fn total(
+        &self,
+        fuzzy: bool,
+        category_ids: Option<&[i32]>,
+        conn: &impl sea_orm::ConnectionTrait,
+    ) -> Result<i64, ApiError> {
+        use sea_orm::{DbBackend, FromQueryResult, Statement};
+
+        let (cte, values) = self.matched(fuzzy, category_ids);
+        let sql = format!("{cte} SELECT count(*) AS count FROM matched");
+        let row = CountRow::find_by_statement(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            sql,
+            values,
+        ))
+        .one(conn)
+        .await?;
+        Ok(row.map(|r| r.count).unwrap_or(0))
+    }
+
+    async fn hits(
+        &self,
+        fuzzy: bool,
+        category_ids: Option<&[i32]>,
+        conn: &impl sea_orm::ConnectionTrait,
+    ) -> Result<Vec<ProductHit>, ApiError> {
+        use sea_orm::{DbBackend, FromQueryResult, Statement};
+
+        use crate::resources::dto::page::clamp_limit;
+
+        let (cte, mut values) = self.matched(fuzzy, category_ids);
+        values.push((clamp_limit(self.limit) as i64).into());
+        values.push((self.offset.unwrap_or(0) as i64).into());
+        let sql = format!(
+            "{cte} SELECT * FROM matched ORDER BY rank DESC, id LIMIT ${} OFFSET ${}",
+            values.len() - 1,
+            values.len()
+        );
+        let rows = HitRow::find_by_statement(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            sql,
+            values,
+        ))
+        .all(conn)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| ProductHit {
+                product: ProductDto {
+                    id: r.id,
+                    title: r.title,
+                    price: Money::from_columns(r.price, &r.currency),
+                    category_id: r.category_id,
+                    sku: r.sku,
+                    options: Vec::new(),
+                    variants: Vec::new(),
+                },
+                rank: r.rank,
+                snippet: r.snippet,
+            })
+            .collect())
+    }
+
+    async fn facets(
+        &self,
+        fuzzy: bool,
+        category_ids: Option<&[i32]>,
+        conn: &impl sea_orm::ConnectionTrait,
+    ) -> Result<SearchFacets, ApiError> {
+        use sea_orm::{DbBackend, FromQueryResult, Statement};
+
+        let (cte, values) = self.matched(fuzzy, category_ids);
+        let sql = format!(
+            "{cte} SELECT c.id AS category_id, c.name, count(*) AS count
+             FROM matched m JOIN category c ON c.id = m.category_id
+             GROUP BY c.id, c.name ORDER BY count DESC, c.name"
+        );
+        let categories = CategoryRow::find_by_statement(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            sql,
+            values.clone(),
+        ))
+        .all(conn)
+        .await?
+        .into_iter()
+        .map(|r| CategoryFacet {
+            category_id: r.category_id,
+            name: r.name,
+            count: r.count,
+        })
+        .collect();
+
+        // width_bucket은 첫 경계보다 작으면 0, 마지막 경계 이상이면 경계 개수를 돌려줌
+        // 통화마다 경계가 다르므로 통화별로 나눠서 셈
+        let bounds = Currency::ALL
+            .iter()
+            .map(|c| {
+                let bounds = price_buckets(*c).map(|b| b.to_string()).join(",");
+                format!("WHEN '{}' THEN ARRAY[{}]::bigint[]", c.as_str(), bounds)
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+        let sql = format!(
+            "{cte} SELECT currency, width_bucket(price, CASE currency {bounds} END) AS bucket,
+                    count(*) AS count
+             FROM matched GROUP BY currency, bucket ORDER BY currency, bucket"
+        );
+        let prices = BucketRow::find_by_statement(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            sql,
+            values,
+        ))
+        .all(conn)
+        .await?
+        .into_iter()
+        .filter_map(|r| {
+            let currency = Currency::parse(&r.currency)?;
+            let buckets = price_buckets(currency);
+            let i = r.bucket? as usize;
+            Some(PriceFacet {
+                currency,
+                min: if i == 0 { 0 } else { buckets[i - 1] },
+                max: buckets.get(i).copied(),
+                count: r.count,
+            })
+        })
+        .collect();
+
+        Ok(SearchFacets { categories, prices })
+    }
+
+    // 본문 검색으로 찾고, 결과가 없으면 제목 유사도로 다시 찾음
+    pub async fn search(
+        &self,
+        conn: &sea_orm::DatabaseConnection,
+    ) -> Result<ProductSearchRes, ApiError> {
+        use sea_orm::{ConnectionTrait, TransactionTrait};
+
+        use crate::resources::dto::category::subtree_ids;
+        use crate::resources::dto::product_variant::attach_variants;
+        use crate::utils::validate::ValidationErrors;
+
+        // 통화마다 최소 단위가 달라서 통화 없이 가격을 비교할 수 없음
+        if self.currency.is_none() && (self.price_min.is_some() || self.price_max.is_some()) {
+            let mut errors = ValidationErrors::default();
+            errors.add(
+                "currency",
+                "required",
+                "is required with price_min or price_max".to_string(),
+            );
+            errors.into_result()?;
+        }
+
+        let category_ids = match self.category_id {
+            Some(id) => {
+                let ids = subtree_ids(id, conn).await?;
+                if ids.is_empty() {
+                    return Err(ApiError::new(
+                        reqwest::StatusCode::NOT_FOUND,
+                        "not_found",
+                        "category is not found",
+                    ));
+                }
+                Some(ids)
+            }
+            None => None,
+        };
+        let category_ids = category_ids.as_deref();
+
+        let txn = conn.begin().await?;
+        let mut fuzzy = false;
+        let mut total = self.total(fuzzy, category_ids, &txn).await?;
+        if total == 0 {
+            // 이 트랜잭션 안에서만 유사도 기준을 낮춤
+            txn.execute_unprepared(&format!(
+                "SET LOCAL pg_trgm.word_similarity_threshold = {}",
+                FUZZY_THRESHOLD
+            ))
+            .await?;
+            fuzzy = true;
+            total = self.total(fuzzy, category_ids, &txn).await?;
+        }
+
+        let items = self.hits(fuzzy, category_ids, &txn).await?;
+        let facets = self.facets(fuzzy, category_ids, &txn).await?;
+        txn.commit().await?;
+
+        let mut products: Vec<ProductDto> = items.iter().map(|h| h.product.clone()).collect();
+        attach_variants(&mut products, conn).await?;
+        let items = items
+            .into_iter()
+            .zip(products)
+            .map(|(hit, product)| ProductHit { product, ..hit })
+            .collect();
+
+        Ok(ProductSearchRes {
+            items,
+            total,
+            fuzzy,
+            facets,
+        })
+    }
+}
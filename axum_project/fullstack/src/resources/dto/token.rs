@@ -0,0 +1,272 @@
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "server")]
+use crate::utils::errors::OAuthError;
+
+// RFC 7662 / RFC 7009 요청 본문 (application/x-www-form-urlencoded)
+// client_secret_post 방식을 위해 client_id, client_secret도 본문으로 받을 수 있음
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[derive(Deserialize, Serialize, Debug)]
+pub struct TokenReq {
+    pub token: String,
+    pub token_type_hint: Option<String>,
+    pub client_id: Option<String>,
+    pub client_secret: Option<String>,
+}
+
+// RFC 7662 2.2 응답, 비활성 토큰은 active: false 만 내려준다
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[derive(Deserialize, Serialize, Debug, Default)]
+pub struct IntrospectRes {
+    pub active: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sub: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exp: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub iat: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jti: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+}
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum TokenKind {
+    Access,
+    Refresh,
+}
+impl TokenKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TokenKind::Access => "access_token",
+            TokenKind::Refresh => "refresh_token",
+        }
+    }
+}
+
+impl TokenReq {
+    // 힌트는 검색 순서만 정할 뿐, 모르는 힌트라면 모든 종류를 찾아봐야함 (RFC 7009 2.1)
+    pub fn search_order(&self) -> [TokenKind; 2] {
+        match self.token_type_hint.as_deref() {
+            Some("refresh_token") => [TokenKind::Refresh, TokenKind::Access],
+            _ => [TokenKind::Access, TokenKind::Refresh],
+        }
+    }
+}
+
+// 토큰 엔드포인트를 호출하는 클라이언트의 인증 정보
+// public 클라이언트는 secret 없이 client_id만 보냄
+#[derive(Debug)]
+pub struct ClientCredentials {
+    pub client_id: String,
+    pub client_secret: Option<String>,
+}
+
+#[cfg(feature = "server")]
+impl ClientCredentials {
+    // client_secret_basic(Authorization 헤더)을 우선하고, 없으면 client_secret_post(본문)를 사용
+    pub fn from_request(
+        basic: Option<&axum_extra::headers::authorization::Basic>,
+        client_id: Option<&str>,
+        client_secret: Option<&str>,
+    ) -> Result<Self, OAuthError> {
+        if let Some(basic) = basic {
+            // RFC 6749 2.3.1: Basic 인증의 id, secret은 form-urlencoded 되어있음
+            let client_id = urlencoding::decode(basic.username())
+                .map_err(|_| OAuthError::invalid_client())?
+                .to_string();
+            let client_secret = urlencoding::decode(basic.password())
+                .map_err(|_| OAuthError::invalid_client())?
+                .to_string();
+            return Ok(Self {
+                client_id,
+                client_secret: Some(client_secret),
+            });
+        }
+
+        match client_id {
+            Some(client_id) => Ok(Self {
+                client_id: client_id.to_string(),
+                client_secret: client_secret.map(|s| s.to_string()),
+            }),
+            None => Err(OAuthError::invalid_client()),
+        }
+    }
+
+    // secret을 가진 클라이언트(confidential)만 허용, 토큰 검사/폐기용
+    pub async fn verify(
+        &self,
+        conn: &sea_orm::DatabaseConnection,
+    ) -> Result<crate::resources::entities::oauth_client::Model, OAuthError> {
+        self.find_client(false, conn).await
+    }
+
+    // public 클라이언트는 secret 검사를 생략함, 대신 PKCE를 강제해야함
+    pub async fn verify_allow_public(
+        &self,
+        conn: &sea_orm::DatabaseConnection,
+    ) -> Result<crate::resources::entities::oauth_client::Model, OAuthError> {
+        self.find_client(true, conn).await
+    }
+
+    async fn find_client(
+        &self,
+        allow_public: bool,
+        conn: &sea_orm::DatabaseConnection,
+    ) -> Result<crate::resources::entities::oauth_client::Model, OAuthError> {
+        use sea_orm::EntityTrait;
+
+        use crate::resources::entities::oauth_client;
+
+        let client = oauth_client::Entity::find_by_id(self.client_id.clone())
+            .one(conn)
+            .await?
+            .ok_or(OAuthError::invalid_client())?;
+
+        if client.is_public {
+            return if allow_public {
+                Ok(client)
+            } else {
+                Err(OAuthError::invalid_client())
+            };
+        }
+
+        let secret = self
+            .client_secret
+            .as_deref()
+            .ok_or(OAuthError::invalid_client())?;
+        // verify_password는 불일치시 에러를 반환하므로 모두 invalid_client로 취급
+        crate::utils::hash::verify_password(secret, &client.client_secret)
+            .map_err(|_| OAuthError::invalid_client())?;
+
+        Ok(client)
+    }
+}
+
+#[cfg(feature = "server")]
+impl TokenReq {
+    pub async fn introspect(
+        &self,
+        conn: &sea_orm::DatabaseConnection,
+    ) -> Result<IntrospectRes, OAuthError> {
+        for kind in self.search_order() {
+            let res = match kind {
+                TokenKind::Access => self.introspect_access(conn).await?,
+                TokenKind::Refresh => self.introspect_refresh(conn).await?,
+            };
+            if res.active {
+                return Ok(res);
+            }
+        }
+
+        Ok(IntrospectRes::default())
+    }
+
+    async fn introspect_access(
+        &self,
+        conn: &sea_orm::DatabaseConnection,
+    ) -> Result<IntrospectRes, OAuthError> {
+        use crate::utils::jwt::{is_token_active, validate_jwt_token};
+
+        // 서명, 만료가 올바르지 않으면 활성 토큰이 아님
+        let Ok(claims) = validate_jwt_token(&self.token) else {
+            return Ok(IntrospectRes::default());
+        };
+        if !is_token_active(&claims, conn).await? {
+            return Ok(IntrospectRes::default());
+        }
+
+        Ok(IntrospectRes {
+            active: true,
+            token_type: Some(TokenKind::Access.as_str().to_string()),
+            sub: Some(claims.user_id.to_string()),
+            username: Some(claims.username),
+            exp: Some(claims.exp),
+            iat: Some(claims.iat),
+            jti: Some(claims.jti),
+            client_id: claims.client_id,
+            scope: claims.scope,
+        })
+    }
+
+    async fn introspect_refresh(
+        &self,
+        conn: &sea_orm::DatabaseConnection,
+    ) -> Result<IntrospectRes, OAuthError> {
+        use sea_orm::EntityTrait;
+
+        use crate::resources::entities::refresh_token;
+        use crate::utils::jwt::validate_refresh_token;
+
+        let Ok(claims) = validate_refresh_token(&self.token) else {
+            return Ok(IntrospectRes::default());
+        };
+
+        // 리프레시 토큰의 만료일자는 DB에만 존재함
+        let Some(model) = refresh_token::Entity::find_by_id(self.token.clone())
+            .one(conn)
+            .await?
+        else {
+            return Ok(IntrospectRes::default());
+        };
+        if model.user_id != claims.user_id || chrono::Utc::now().naive_utc() > model.expires_at {
+            return Ok(IntrospectRes::default());
+        }
+
+        Ok(IntrospectRes {
+            active: true,
+            token_type: Some(TokenKind::Refresh.as_str().to_string()),
+            sub: Some(claims.user_id.to_string()),
+            username: Some(claims.username),
+            exp: Some(model.expires_at.and_utc().timestamp() as u64),
+            client_id: model.client_id,
+            scope: model.scope,
+            ..Default::default()
+        })
+    }
+
+    // 잘못된 토큰이나 이미 폐기된 토큰이어도 성공으로 취급 (RFC 7009 2.2)
+    // 요청한 클라이언트에게 발급된 토큰만 폐기함, 다른 클라이언트나 이 서버의 로그인 토큰이면 무시 (RFC 7009 2.1)
+    pub async fn revoke(
+        &self,
+        client_id: &str,
+        conn: &sea_orm::DatabaseConnection,
+    ) -> Result<(), OAuthError> {
+        use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+
+        use crate::resources::entities::refresh_token;
+        use crate::utils::jwt::{revoke_jwt, validate_jwt_token_without_exp};
+
+        for kind in self.search_order() {
+            match kind {
+                TokenKind::Access => {
+                    if let Ok(claims) = validate_jwt_token_without_exp(&self.token) {
+                        if claims.client_id.as_deref() == Some(client_id) {
+                            revoke_jwt(&claims, conn).await?;
+                        }
+                        return Ok(());
+                    }
+                }
+                TokenKind::Refresh => {
+                    let res = refresh_token::Entity::delete_many()
+                        .filter(refresh_token::Column::Token.eq(&self.token))
+                        .filter(refresh_token::Column::ClientId.eq(client_id))
+                        .exec(conn)
+                        .await?;
+                    if res.rows_affected > 0 {
+                        return Ok(());
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -1,7 +1,8 @@
 use serde::{Deserialize, Serialize};
+use validate_dto_macro::validate_dto;
 
 #[cfg(feature = "server")]
-use crate::utils::errors::AppError;
+use crate::utils::errors::{ApiError, AppError};
 
 #[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
 #[derive(Deserialize, Serialize, Debug)]
@@ -41,38 +42,43 @@ impl From<UserCondition> for crate::resources::entities::users::ActiveModel {
             kakao_oauth: Set(value.kakao),
             git_hub_oauth: Set(value.github),
             naver_oauth: Set(value.naver),
+            ..Default::default()
         }
     }
 }
 #[cfg(feature = "server")]
 impl UserCondition {
     pub fn make_condition(&self) -> Result<sea_orm::Condition, AppError> {
+        use crate::resources::dto::query::like_escape;
         use crate::resources::entities::users;
         use sea_orm::ColumnTrait;
         use sea_orm::Condition;
 
-        let condition = Condition::all();
-        if let Some(id) = self.id {
-            let res = condition.add(users::Column::Id.eq(id));
-            return Ok(res);
-        } else if let Some(oauth) = &self.google {
-            let res = condition.add(users::Column::GoogleOauth.eq(oauth));
-            return Ok(res);
-        } else if let Some(oauth) = &self.kakao {
-            let res = condition.add(users::Column::KakaoOauth.eq(oauth));
-            return Ok(res);
-        } else if let Some(oauth) = &self.naver {
-            let res = condition.add(users::Column::NaverOauth.eq(oauth));
-            return Ok(res);
-        } else if let Some(oauth) = &self.naver {
-            let res = condition.add(users::Column::NaverOauth.eq(oauth));
-            return Ok(res);
-        } else if let Some(username) = &self.username {
-            let res = condition.add(users::Column::Username.like(format!("%{}%", username)));
-            return Ok(res);
-        }
+        // 값이 있는 필드는 모두 AND로 묶음
+        let condition = Condition::all()
+            .add_option(self.id.map(|id| users::Column::Id.eq(id)))
+            .add_option(
+                self.google
+                    .as_ref()
+                    .map(|v| users::Column::GoogleOauth.eq(v)),
+            )
+            .add_option(self.kakao.as_ref().map(|v| users::Column::KakaoOauth.eq(v)))
+            .add_option(
+                self.github
+                    .as_ref()
+                    .map(|v| users::Column::GitHubOauth.eq(v)),
+            )
+            .add_option(self.naver.as_ref().map(|v| users::Column::NaverOauth.eq(v)))
+            .add_option(
+                self.username
+                    .as_ref()
+                    .map(|v| users::Column::Username.like(format!("%{}%", like_escape(v)))),
+            );
 
-        Err(AppError::any_t_error("There is no condition"))
+        if condition.is_empty() {
+            return Err(AppError::any_t_error("There is no condition"));
+        }
+        Ok(condition)
     }
     pub async fn post_user(self, conn: &sea_orm::DatabaseConnection) -> Result<UserDto, AppError> {
         use sea_orm::ActiveModelTrait;
@@ -86,10 +92,12 @@ impl UserCondition {
     }
 }
 
+#[validate_dto]
 #[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct UserDto {
     pub id: i32,
+    #[validate(length(min = 1, max = 100))]
     pub username: String,
     pub google: Option<String>,
     pub kakao: Option<String>,
@@ -109,33 +117,120 @@ impl From<crate::resources::entities::users::Model> for UserDto {
         }
     }
 }
+// 목록 API(filter, sort)에서 쓸 수 있는 컬럼
 #[cfg(feature = "server")]
-impl From<UserDto> for crate::resources::entities::users::Model {
+impl crate::resources::dto::query::QueryFields for crate::resources::entities::users::Entity {
+    const FIELDS: &'static [crate::resources::dto::query::QueryField<Self::Column>] = {
+        use crate::resources::dto::query::{FieldKind, QueryField};
+        use crate::resources::entities::users::Column;
+
+        &[
+            QueryField {
+                name: "id",
+                column: Column::Id,
+                kind: FieldKind::Int,
+                sortable: true,
+            },
+            QueryField {
+                name: "username",
+                column: Column::Username,
+                kind: FieldKind::Text,
+                sortable: true,
+            },
+            QueryField {
+                name: "display_name",
+                column: Column::DisplayName,
+                kind: FieldKind::Text,
+                sortable: false,
+            },
+            QueryField {
+                name: "created_at",
+                column: Column::CreatedAt,
+                kind: FieldKind::DateTime,
+                sortable: true,
+            },
+            QueryField {
+                name: "updated_at",
+                column: Column::UpdatedAt,
+                kind: FieldKind::DateTime,
+                sortable: true,
+            },
+        ]
+    };
+    const ID: Self::Column = crate::resources::entities::users::Column::Id;
+}
+
+// 프로필 컬럼은 건드리지 않도록 ActiveModel로 변환
+#[cfg(feature = "server")]
+impl From<UserDto> for crate::resources::entities::users::ActiveModel {
     fn from(value: UserDto) -> Self {
-        crate::resources::entities::users::Model {
-            id: value.id,
-            username: value.username,
-            google_oauth: value.google,
-            kakao_oauth: value.kakao,
-            git_hub_oauth: value.github,
-            naver_oauth: value.naver,
+        use sea_orm::ActiveValue::{Set, Unchanged};
+
+        crate::resources::entities::users::ActiveModel {
+            id: Unchanged(value.id),
+            username: Set(value.username),
+            google_oauth: Set(value.google),
+            kakao_oauth: Set(value.kakao),
+            git_hub_oauth: Set(value.github),
+            naver_oauth: Set(value.naver),
+            ..Default::default()
         }
     }
 }
 #[cfg(feature = "server")]
 impl UserDto {
     pub async fn update_user(self, conn: &sea_orm::DatabaseConnection) -> Result<Self, AppError> {
-        use sea_orm::{ActiveModelTrait, IntoActiveModel};
-
         use crate::resources::entities::users;
 
-        let model = users::Model::from(self);
-        let model = model.into_active_model();
+        let id = self.id;
+        let model = users::ActiveModel::from(self);
 
-        let res = model.update(conn).await?;
+        let res = update_versioned(model, id, None, conn)
+            .await?
+            .ok_or(AppError::new(
+                reqwest::StatusCode::NOT_FOUND,
+                "user is not found",
+                None,
+            ))?;
         Ok(res.into())
     }
 
+    // PUT 전체 교체, PATCH처럼 If-Match의 ETag가 현재 버전과 다르면 412
+    // 연결된 소셜 계정은 로그인 과정에서만 바뀌어야 하므로 기존 값을 유지함
+    pub async fn replace(
+        mut self,
+        if_match: &axum_extra::headers::IfMatch,
+        conn: &sea_orm::DatabaseConnection,
+    ) -> Result<(Self, axum_extra::headers::ETag), ApiError> {
+        use sea_orm::EntityTrait;
+
+        use crate::resources::entities::users;
+
+        let current = users::Entity::find_by_id(self.id)
+            .one(conn)
+            .await?
+            .ok_or(AppError::auth_error())?;
+        if !if_match.precondition_passes(&user_etag(&current)) {
+            return Err(ApiError::precondition_failed());
+        }
+        self.google = current.google_oauth;
+        self.kakao = current.kakao_oauth;
+        self.github = current.git_hub_oauth;
+        self.naver = current.naver_oauth;
+
+        let id = self.id;
+        let updated = update_versioned(
+            users::ActiveModel::from(self),
+            id,
+            Some(current.version),
+            conn,
+        )
+        .await?
+        .ok_or(ApiError::precondition_failed())?;
+        let etag = user_etag(&updated);
+        Ok((updated.into(), etag))
+    }
+
     pub async fn get_user(
         user_condition: &UserCondition,
         conn: &sea_orm::DatabaseConnection,
@@ -168,6 +263,134 @@ impl UserDto {
     }
 }
 
+// 유저 정보가 바뀔 때마다 version을 올림
+// expected_version이 있으면 그 버전일 때만 갱신하고, 갱신된 행이 없으면 None
+#[cfg(feature = "server")]
+pub async fn update_versioned(
+    model: crate::resources::entities::users::ActiveModel,
+    id: i32,
+    expected_version: Option<i32>,
+    conn: &sea_orm::DatabaseConnection,
+) -> Result<Option<crate::resources::entities::users::Model>, AppError> {
+    use sea_orm::sea_query::Expr;
+    use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+
+    use crate::resources::entities::users;
+
+    let mut update = users::Entity::update_many()
+        .set(model)
+        .col_expr(
+            users::Column::Version,
+            Expr::col(users::Column::Version).add(1),
+        )
+        .col_expr(users::Column::UpdatedAt, Expr::current_timestamp().into())
+        .filter(users::Column::Id.eq(id));
+    if let Some(version) = expected_version {
+        update = update.filter(users::Column::Version.eq(version));
+    }
+
+    Ok(update.exec_with_returning(conn).await?.pop())
+}
+
+#[cfg(feature = "server")]
+pub fn user_etag(model: &crate::resources::entities::users::Model) -> axum_extra::headers::ETag {
+    format!("\"{}-{}\"", model.id, model.version)
+        .parse()
+        .expect("etag is always quoted ascii")
+}
+
+// JSON merge-patch (RFC 7396): 없는 필드는 그대로, null은 삭제, 값이 있으면 변경
+// 수정 가능한 필드만 받고, 소셜 계정 연결 같은 필드는 거절함
+#[validate_dto]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[derive(Deserialize, Serialize, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct UserPatch {
+    #[serde(
+        default,
+        deserialize_with = "patch_field",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[cfg_attr(feature = "server", schema(value_type = Option<String>))]
+    #[validate(length(max = 50))]
+    pub display_name: Option<Option<String>>,
+    #[serde(
+        default,
+        deserialize_with = "patch_field",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[cfg_attr(feature = "server", schema(value_type = Option<String>))]
+    #[validate(length(max = 500))]
+    pub bio: Option<Option<String>>,
+}
+
+// 필드가 있으면 null이어도 Some(None)이 되도록 함
+fn patch_field<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+#[cfg(feature = "server")]
+impl UserPatch {
+    // If-Match의 ETag가 현재 버전과 다르면 412
+    // 검사와 갱신 사이에 다른 요청이 끼어들어도 version 조건으로 한번 더 막음
+    pub async fn apply(
+        self,
+        user_id: i32,
+        if_match: &axum_extra::headers::IfMatch,
+        conn: &sea_orm::DatabaseConnection,
+        storage: &dyn crate::utils::storage::Storage,
+    ) -> Result<
+        (
+            crate::resources::dto::profile::ProfileDto,
+            axum_extra::headers::ETag,
+        ),
+        ApiError,
+    > {
+        use sea_orm::{ActiveModelTrait, ActiveValue::Set, EntityTrait};
+
+        use crate::resources::dto::profile::{
+            BIO_MAX, DISPLAY_NAME_MAX, ProfileDto, normalize_text,
+        };
+        use crate::resources::entities::users;
+
+        let current = users::Entity::find_by_id(user_id)
+            .one(conn)
+            .await?
+            .ok_or(AppError::auth_error())?;
+        if !if_match.precondition_passes(&user_etag(&current)) {
+            return Err(ApiError::precondition_failed());
+        }
+
+        let mut model = <users::ActiveModel as Default>::default();
+        if let Some(display_name) = self.display_name {
+            model.display_name = Set(normalize_text(
+                display_name,
+                DISPLAY_NAME_MAX,
+                "display_name",
+            )?);
+        }
+        if let Some(bio) = self.bio {
+            model.bio = Set(normalize_text(bio, BIO_MAX, "bio")?);
+        }
+
+        // 바뀌는 필드가 없으면 버전도 그대로 둠
+        if !model.is_changed() {
+            let etag = user_etag(&current);
+            return Ok((ProfileDto::from_model(current, storage), etag));
+        }
+
+        let updated = update_versioned(model, user_id, Some(current.version), conn)
+            .await?
+            .ok_or(ApiError::precondition_failed())?;
+        let etag = user_etag(&updated);
+        Ok((ProfileDto::from_model(updated, storage), etag))
+    }
+}
+
 #[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
 #[derive(Deserialize, Serialize, Debug)]
 pub struct Tokens {
@@ -189,8 +412,16 @@ pub struct TokensUserId {
 #[derive(Serialize, Deserialize)]
 pub struct JwtClaims {
     pub exp: u64,
+    pub iat: u64,
+    // 토큰 폐기(denylist)를 위한 고유 식별자
+    pub jti: String,
     pub user_id: i32,
     pub username: String,
+    // 외부 클라이언트(OAuth)에게 발급한 토큰만 가지고 있음
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
 }
 
 // 리프레시 토큰 만료일자는 DB에 저장하기 때문에 제외
@@ -198,6 +429,9 @@ pub struct JwtClaims {
 pub struct RefreshClaims {
     pub user_id: i32,
     pub username: String,
+    // 같은 유저에게 여러 토큰을 발급해도 겹치지 않도록 함
+    #[serde(default)]
+    pub jti: String,
 }
 
 #[derive(Clone)]
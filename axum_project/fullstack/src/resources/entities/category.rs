@@ -6,20 +6,52 @@ use serde::{Deserialize, Serialize};
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
 #[sea_orm(table_name = "category")]
 pub struct Model {
-    #[sea_orm(primary_key, auto_increment = false)]
+    #[sea_orm(primary_key)]
+    pub id: i32,
     pub name: String,
+    pub parent_id: Option<i32>,
+    #[sea_orm(unique)]
+    pub slug: String,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub description: Option<String>,
+    pub sort_order: i32,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(
+        belongs_to = "Entity",
+        from = "Column::ParentId",
+        to = "Column::Id",
+        on_update = "NoAction",
+        on_delete = "Restrict"
+    )]
+    SelfRef,
+    #[sea_orm(has_many = "super::coupon_category::Entity")]
+    CouponCategory,
     #[sea_orm(has_many = "super::product::Entity")]
     Product,
 }
 
+impl Related<super::coupon_category::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::CouponCategory.def()
+    }
+}
+
 impl Related<super::product::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::Product.def()
     }
 }
 
+impl Related<super::coupon::Entity> for Entity {
+    fn to() -> RelationDef {
+        super::coupon_category::Relation::Coupon.def()
+    }
+    fn via() -> Option<RelationDef> {
+        Some(super::coupon_category::Relation::Category.def().rev())
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
@@ -2,7 +2,32 @@
 
 pub mod prelude;
 
+pub mod authorization_code;
+pub mod cart;
+pub mod cart_coupon;
 pub mod category;
+pub mod comment;
+pub mod coupon;
+pub mod coupon_category;
+pub mod coupon_redemption;
+pub mod import_job;
+pub mod inventory;
+pub mod oauth_client;
+pub mod order_item;
+pub mod orders;
+pub mod payment;
+pub mod payment_event;
+pub mod post;
 pub mod product;
+pub mod product_image;
+pub mod product_option;
+pub mod product_option_value;
+pub mod product_price_history;
+pub mod product_rating;
+pub mod product_variant;
+pub mod product_variant_value;
 pub mod refresh_token;
+pub mod review;
+pub mod revoked_token;
+pub mod stock_ledger;
 pub mod users;
@@ -1,6 +1,31 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.19
 
+pub use super::authorization_code::Entity as AuthorizationCode;
+pub use super::cart::Entity as Cart;
+pub use super::cart_coupon::Entity as CartCoupon;
 pub use super::category::Entity as Category;
+pub use super::comment::Entity as Comment;
+pub use super::coupon::Entity as Coupon;
+pub use super::coupon_category::Entity as CouponCategory;
+pub use super::coupon_redemption::Entity as CouponRedemption;
+pub use super::import_job::Entity as ImportJob;
+pub use super::inventory::Entity as Inventory;
+pub use super::oauth_client::Entity as OauthClient;
+pub use super::order_item::Entity as OrderItem;
+pub use super::orders::Entity as Orders;
+pub use super::payment::Entity as Payment;
+pub use super::payment_event::Entity as PaymentEvent;
+pub use super::post::Entity as Post;
 pub use super::product::Entity as Product;
+pub use super::product_image::Entity as ProductImage;
+pub use super::product_option::Entity as ProductOption;
+pub use super::product_option_value::Entity as ProductOptionValue;
+pub use super::product_price_history::Entity as ProductPriceHistory;
+pub use super::product_rating::Entity as ProductRating;
+pub use super::product_variant::Entity as ProductVariant;
+pub use super::product_variant_value::Entity as ProductVariantValue;
 pub use super::refresh_token::Entity as RefreshToken;
+pub use super::review::Entity as Review;
+pub use super::revoked_token::Entity as RevokedToken;
+pub use super::stock_ledger::Entity as StockLedger;
 pub use super::users::Entity as Users;
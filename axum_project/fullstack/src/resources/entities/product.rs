@@ -9,20 +9,49 @@ pub struct Model {
     #[sea_orm(primary_key)]
     pub id: i32,
     pub title: String,
-    pub price: i32,
-    pub category: String,
+    pub price: i64,
+    pub category_id: i32,
+    pub currency: String,
+    #[sea_orm(unique)]
+    pub sku: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(has_many = "super::cart::Entity")]
+    Cart,
     #[sea_orm(
         belongs_to = "super::category::Entity",
-        from = "Column::Category",
-        to = "super::category::Column::Name",
+        from = "Column::CategoryId",
+        to = "super::category::Column::Id",
         on_update = "NoAction",
-        on_delete = "NoAction"
+        on_delete = "Restrict"
     )]
     Category,
+    #[sea_orm(has_many = "super::inventory::Entity")]
+    Inventory,
+    #[sea_orm(has_many = "super::order_item::Entity")]
+    OrderItem,
+    #[sea_orm(has_many = "super::product_image::Entity")]
+    ProductImage,
+    #[sea_orm(has_many = "super::product_option::Entity")]
+    ProductOption,
+    #[sea_orm(has_many = "super::product_price_history::Entity")]
+    ProductPriceHistory,
+    #[sea_orm(has_one = "super::product_rating::Entity")]
+    ProductRating,
+    #[sea_orm(has_many = "super::product_variant::Entity")]
+    ProductVariant,
+    #[sea_orm(has_many = "super::review::Entity")]
+    Review,
+    #[sea_orm(has_many = "super::stock_ledger::Entity")]
+    StockLedger,
+}
+
+impl Related<super::cart::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Cart.def()
+    }
 }
 
 impl Related<super::category::Entity> for Entity {
@@ -31,4 +60,58 @@ impl Related<super::category::Entity> for Entity {
     }
 }
 
+impl Related<super::inventory::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Inventory.def()
+    }
+}
+
+impl Related<super::order_item::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::OrderItem.def()
+    }
+}
+
+impl Related<super::product_image::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ProductImage.def()
+    }
+}
+
+impl Related<super::product_option::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ProductOption.def()
+    }
+}
+
+impl Related<super::product_price_history::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ProductPriceHistory.def()
+    }
+}
+
+impl Related<super::product_rating::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ProductRating.def()
+    }
+}
+
+impl Related<super::product_variant::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ProductVariant.def()
+    }
+}
+
+impl Related<super::review::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Review.def()
+    }
+}
+
+impl Related<super::stock_ledger::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::StockLedger.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
@@ -10,10 +10,20 @@ pub struct Model {
     pub token: String,
     pub user_id: i32,
     pub expires_at: DateTime,
+    pub client_id: Option<String>,
+    pub scope: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::oauth_client::Entity",
+        from = "Column::ClientId",
+        to = "super::oauth_client::Column::ClientId",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    OauthClient,
     #[sea_orm(
         belongs_to = "super::users::Entity",
         from = "Column::UserId",
@@ -24,6 +34,12 @@ pub enum Relation {
     Users,
 }
 
+impl Related<super::oauth_client::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::OauthClient.def()
+    }
+}
+
 impl Related<super::users::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::Users.def()
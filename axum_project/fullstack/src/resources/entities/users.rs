@@ -17,12 +17,101 @@ pub struct Model {
     pub kakao_oauth: Option<String>,
     #[sea_orm(unique)]
     pub git_hub_oauth: Option<String>,
+    pub display_name: Option<String>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub bio: Option<String>,
+    pub avatar_key: Option<String>,
+    pub version: i32,
+    pub updated_at: DateTime,
+    pub role: String,
+    pub status: String,
+    pub created_at: DateTime,
+    pub tokens_valid_after: Option<DateTime>,
+    pub deleted_at: Option<DateTime>,
+    #[sea_orm(unique)]
+    pub external_id: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(has_many = "super::authorization_code::Entity")]
+    AuthorizationCode,
+    #[sea_orm(has_many = "super::cart::Entity")]
+    Cart,
+    #[sea_orm(has_one = "super::cart_coupon::Entity")]
+    CartCoupon,
+    #[sea_orm(has_many = "super::comment::Entity")]
+    Comment,
+    #[sea_orm(has_many = "super::coupon_redemption::Entity")]
+    CouponRedemption,
+    #[sea_orm(has_many = "super::import_job::Entity")]
+    ImportJob,
+    #[sea_orm(has_many = "super::orders::Entity")]
+    Orders,
+    #[sea_orm(has_many = "super::post::Entity")]
+    Post,
+    #[sea_orm(has_many = "super::product_price_history::Entity")]
+    ProductPriceHistory,
     #[sea_orm(has_many = "super::refresh_token::Entity")]
     RefreshToken,
+    #[sea_orm(has_many = "super::review::Entity")]
+    Review,
+    #[sea_orm(has_many = "super::stock_ledger::Entity")]
+    StockLedger,
+}
+
+impl Related<super::authorization_code::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::AuthorizationCode.def()
+    }
+}
+
+impl Related<super::cart::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Cart.def()
+    }
+}
+
+impl Related<super::cart_coupon::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::CartCoupon.def()
+    }
+}
+
+impl Related<super::comment::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Comment.def()
+    }
+}
+
+impl Related<super::coupon_redemption::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::CouponRedemption.def()
+    }
+}
+
+impl Related<super::import_job::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ImportJob.def()
+    }
+}
+
+impl Related<super::orders::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Orders.def()
+    }
+}
+
+impl Related<super::post::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Post.def()
+    }
+}
+
+impl Related<super::product_price_history::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ProductPriceHistory.def()
+    }
 }
 
 impl Related<super::refresh_token::Entity> for Entity {
@@ -31,4 +120,16 @@ impl Related<super::refresh_token::Entity> for Entity {
     }
 }
 
+impl Related<super::review::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Review.def()
+    }
+}
+
+impl Related<super::stock_ledger::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::StockLedger.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,241 @@
+use crate::resources::dto::admin::{
+    AdminUserDto, AdminUserQuery, ROLE_ADMIN, UserStatus, force_logout, hard_delete, has_role,
+    not_self, restore_user, set_status,
+};
+use crate::resources::dto::fullstack_extension::AppExtension;
+use crate::resources::dto::page::Page;
+use crate::resources::dto::user::CurrentUser;
+use crate::utils::jwt::authenticate;
+use crate::utils::storage::DynStorage;
+use axum::body::Body;
+use axum::http::Request;
+use axum::middleware::{self, Next};
+use axum::response::Response;
+use axum::{
+    Extension, Json, Router,
+    extract::{FromRef, Path, State},
+};
+use reqwest::StatusCode;
+use sea_orm::DatabaseConnection;
+use utoipa::OpenApi;
+use utoipa_axum::{router::OpenApiRouter, routes};
+use utoipa_scalar::{Scalar, Servable};
+
+use crate::router::api::auth::SecurityAddon;
+use crate::utils::errors::ApiError;
+use crate::utils::validate::ValidatedQuery;
+
+const TAG: &str = "ADMIN";
+
+// authenticate 뒤에 붙여서 role이 admin인 유저만 통과시킴
+pub(super) async fn require_admin(
+    State(db): State<DatabaseConnection>,
+    Extension(user): Extension<CurrentUser>,
+    request: Request<Body>,
+    next: Next,
+) -> Result<Response, ApiError> {
+    if !has_role(user.0, &[ROLE_ADMIN], &db).await? {
+        return Err(ApiError::new(
+            StatusCode::FORBIDDEN,
+            "forbidden",
+            "admin role is required",
+        ));
+    }
+    Ok(next.run(request).await)
+}
+
+#[utoipa::path(
+    get,
+    path = "/users",
+    tag = TAG,
+    params (
+        AdminUserQuery
+    ),
+    responses (
+        (status = StatusCode::OK, body = Page<AdminUserDto>),
+        (status = StatusCode::FORBIDDEN, body = ApiError)
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+async fn search_users(
+    State(db): State<DatabaseConnection>,
+    ValidatedQuery(query): ValidatedQuery<AdminUserQuery>,
+) -> Result<Json<Page<AdminUserDto>>, ApiError> {
+    Ok(Json(query.search(&db).await?))
+}
+
+#[utoipa::path(
+    post,
+    path = "/users/{id}/suspend",
+    tag = TAG,
+    params (
+        ("id" = i32, Path, description = "user id")
+    ),
+    responses (
+        (status = StatusCode::OK, body = AdminUserDto),
+        (status = StatusCode::NOT_FOUND, body = ApiError)
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+// 로그인과 토큰 갱신을 막고, 발급된 토큰도 모두 끊음
+async fn suspend_user(
+    State(db): State<DatabaseConnection>,
+    Extension(admin): Extension<CurrentUser>,
+    Path(id): Path<i32>,
+) -> Result<Json<AdminUserDto>, ApiError> {
+    not_self(admin.0, id)?;
+    Ok(Json(set_status(id, UserStatus::Suspended, &db).await?))
+}
+
+#[utoipa::path(
+    post,
+    path = "/users/{id}/unsuspend",
+    tag = TAG,
+    params (
+        ("id" = i32, Path, description = "user id")
+    ),
+    responses (
+        (status = StatusCode::OK, body = AdminUserDto),
+        (status = StatusCode::NOT_FOUND, body = ApiError)
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+// 정지 전에 발급된 토큰은 다시 살리지 않으므로 새로 로그인해야함
+async fn unsuspend_user(
+    State(db): State<DatabaseConnection>,
+    Path(id): Path<i32>,
+) -> Result<Json<AdminUserDto>, ApiError> {
+    Ok(Json(set_status(id, UserStatus::Active, &db).await?))
+}
+
+#[utoipa::path(
+    post,
+    path = "/users/{id}/logout",
+    tag = TAG,
+    params (
+        ("id" = i32, Path, description = "user id")
+    ),
+    responses (
+        (status = StatusCode::OK, body = AdminUserDto),
+        (status = StatusCode::NOT_FOUND, body = ApiError)
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+// 모든 기기에서 로그아웃, 계정은 그대로 사용할 수 있음
+async fn logout_user(
+    State(db): State<DatabaseConnection>,
+    Path(id): Path<i32>,
+) -> Result<Json<AdminUserDto>, ApiError> {
+    Ok(Json(force_logout(id, &db).await?))
+}
+
+#[utoipa::path(
+    post,
+    path = "/users/{id}/restore",
+    tag = TAG,
+    params (
+        ("id" = i32, Path, description = "user id")
+    ),
+    responses (
+        (status = StatusCode::OK, body = AdminUserDto),
+        (status = StatusCode::CONFLICT, body = ApiError)
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+// 탈퇴 취소, 유저는 다시 로그인해야함
+async fn restore_deleted_user(
+    State(db): State<DatabaseConnection>,
+    Path(id): Path<i32>,
+) -> Result<Json<AdminUserDto>, ApiError> {
+    Ok(Json(restore_user(id, &db).await?))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/users/{id}",
+    tag = TAG,
+    params (
+        ("id" = i32, Path, description = "user id")
+    ),
+    responses (
+        (status = StatusCode::NO_CONTENT),
+        (status = StatusCode::NOT_FOUND, body = ApiError)
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+// 되돌릴 수 없음, 연결된 토큰과 인가 코드, 아바타 파일까지 지움
+async fn delete_user(
+    State(db): State<DatabaseConnection>,
+    State(storage): State<DynStorage>,
+    Extension(admin): Extension<CurrentUser>,
+    Path(id): Path<i32>,
+) -> Result<StatusCode, ApiError> {
+    not_self(admin.0, id)?;
+    hard_delete(id, &db, storage.as_ref()).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(FromRef, Clone)]
+struct AdminState {
+    db: DatabaseConnection,
+    storage: DynStorage,
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    servers(
+        (url = "/api/admin", description = "Admin API base path")
+    ),
+    modifiers(&SecurityAddon),
+    tags(
+        (name = TAG, description = "User management API, admin role only")
+    )
+)]
+pub(super) struct ApiDoc;
+
+// 모든 라우터가 관리자 권한을 요구함, 문서만 인증없이 볼 수 있음
+pub(super) fn init_route(aex: AppExtension) -> Router {
+    let state = AdminState {
+        db: aex.db.0.clone(),
+        storage: aex.storage.0.clone(),
+    };
+
+    // layer는 나중에 붙인 것이 먼저 실행되므로 authenticate -> require_admin 순서
+    let auth_router = OpenApiRouter::new()
+        .routes(routes!(search_users))
+        .routes(routes!(suspend_user))
+        .routes(routes!(unsuspend_user))
+        .routes(routes!(logout_user))
+        .routes(routes!(restore_deleted_user))
+        .routes(routes!(delete_user))
+        .with_state(state)
+        .layer(middleware::from_fn_with_state(
+            aex.db.0.clone(),
+            require_admin,
+        ))
+        .layer(middleware::from_fn_with_state(
+            aex.db.0.clone(),
+            authenticate,
+        ));
+
+    let (auth_router, auth_api) = auth_router.split_for_parts();
+
+    let mut api = ApiDoc::openapi();
+    api.merge(auth_api);
+
+    let router = auth_router.merge(Scalar::with_url("/doc/scalar", api));
+
+    Router::new().nest("/admin", router)
+}
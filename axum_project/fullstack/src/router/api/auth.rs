@@ -1,11 +1,13 @@
 use crate::front::page::component::login::OauthUrl;
 use crate::resources::dto::fullstack_extension::AppExtension;
+use crate::resources::dto::token::{ClientCredentials, IntrospectRes, TokenReq};
 use crate::resources::dto::user::{CurrentUser, Tokens, UserCondition, UserDto};
 use axum::body::Body;
 use axum::extract::{FromRef, Query, State};
 use axum::http::{HeaderValue, Response};
 use axum::{Extension, Form, Json, Router, debug_handler};
 use axum_extra::TypedHeader;
+use axum_extra::headers::{Authorization, authorization::Basic};
 use reqwest::StatusCode;
 use reqwest::header::{LOCATION, SET_COOKIE};
 use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, ModelTrait, QueryFilter};
@@ -15,7 +17,8 @@ use utoipa_axum::router::OpenApiRouter;
 use utoipa_axum::routes;
 use utoipa_scalar::{Scalar, Servable};
 
-use crate::utils::errors::AppError;
+use crate::utils::errors::{AppError, OAuthError};
+use crate::utils::validate::ValidatedForm;
 
 use crate::resources::entities::refresh_token;
 use crate::utils::jwt::{create_token, validate_jwt_token_without_exp, validate_refresh_token};
@@ -49,7 +52,9 @@ impl Modify for SecurityAddon {
         (status = StatusCode::SEE_OTHER)
     )
 )]
-pub async fn state_setting(Form(oauth): Form<OauthUrl>) -> Result<Response<Body>, AppError> {
+pub async fn state_setting(
+    ValidatedForm(oauth): ValidatedForm<OauthUrl>,
+) -> Result<Response<Body>, AppError> {
     let mut response = Response::new(Body::empty());
     *response.status_mut() = StatusCode::SEE_OTHER;
 
@@ -96,6 +101,11 @@ pub struct GoogleTokenRes {
 pub struct GoogleClaims {
     pub sub: String,
     pub email: String,
+    // 구글이 이메일 소유를 확인했는지, 없으면 확인하지 않은 것으로 봄
+    #[serde(default)]
+    pub email_verified: bool,
+    // Google Workspace 계정의 도메인
+    pub hd: Option<String>,
 }
 
 async fn get_google_claims(
@@ -147,9 +157,9 @@ async fn set_token_cookie(
     *response.status_mut() = StatusCode::SEE_OTHER;
 
     let headers = response.headers_mut();
-    let jwt_val = format!("jwt={}; Path=/; HttpOnly", jwt);
-    let username_val = format!("username={}; Path=/; HttpOnly", user.username);
-    let refresh_val = format!("refresh={}; Path=/; HttpOnly", refresh);
+    let jwt_val = format!("jwt={}; Path=/; HttpOnly; SameSite=Lax", jwt);
+    let username_val = format!("username={}; Path=/; HttpOnly; SameSite=Lax", user.username);
+    let refresh_val = format!("refresh={}; Path=/; HttpOnly; SameSite=Lax", refresh);
     headers.append(SET_COOKIE, HeaderValue::from_bytes(jwt_val.as_bytes())?);
     headers.append(
         SET_COOKIE,
@@ -186,17 +196,41 @@ pub async fn google_login(
 
     let google_token = get_google_claims(reqwest.clone(), req, cookies).await?;
 
-    let user_condition = UserCondition {
-        username: Some(google_token.email),
-        google: Some(google_token.sub),
-        ..Default::default()
-    };
-
-    let mut user = UserDto::get_user(&user_condition, &db).await?;
+    // 조건은 AND로 묶이므로 이메일이 바뀐 유저도 찾을 수 있도록 구글 계정으로만 찾음
+    let mut user = UserDto::get_user(
+        &UserCondition {
+            google: Some(google_token.sub.clone()),
+            ..Default::default()
+        },
+        &db,
+    )
+    .await?;
     if user.is_empty() {
-        user.push(user_condition.post_user(&db).await?);
+        // SCIM으로 미리 만들어진 계정이면 구글 계정을 연결해서 로그인
+        let linked = crate::resources::dto::scim::link_provisioned(
+            &google_token.email,
+            google_token.email_verified,
+            google_token.hd.as_deref(),
+            &google_token.sub,
+            &db,
+        )
+        .await?;
+        match linked {
+            Some(linked) => user.push(linked),
+            None => {
+                let user_condition = UserCondition {
+                    username: Some(google_token.email),
+                    google: Some(google_token.sub),
+                    ..Default::default()
+                };
+                user.push(user_condition.post_user(&db).await?);
+            }
+        }
     }
 
+    // 탈퇴한 계정은 복구 기간 안에 다시 로그인하면 복구됨
+    crate::resources::dto::account::restore_on_login(user[0].id, &db).await?;
+
     set_token_cookie(&user[0], &db).await
 }
 
@@ -228,7 +262,6 @@ pub async fn google_update(
     // 유저 id와 이미 로그인되어있는 id가 일치한다면, 해당 정보를 user데이터베이스에 갱신
     let google_token = get_google_claims(reqwest.clone(), req, cookies).await?;
     let user_condition = UserCondition {
-        username: Some(google_token.email),
         google: Some(google_token.sub.clone()),
         ..Default::default()
     };
@@ -293,6 +326,11 @@ async fn refresh(
     let jwt_claims = validate_jwt_token_without_exp(&tokens.jwt)?;
     let refresh_claims = validate_refresh_token(&tokens.refresh)?;
 
+    // OAuth 클라이언트에 발급한 토큰은 /oauth/token 으로만 갱신함
+    if jwt_claims.client_id.is_some() {
+        return Err(jsonwebtoken::errors::ErrorKind::InvalidToken.into());
+    }
+
     // user_id가 동일해야 DB에 접속함
     if refresh_claims.user_id != jwt_claims.user_id {
         return Err(jsonwebtoken::errors::ErrorKind::InvalidSignature.into());
@@ -305,7 +343,8 @@ async fn refresh(
         .filter(
             refresh_token::Column::Token
                 .eq(&tokens.refresh)
-                .and(refresh_token::Column::UserId.eq(user_id)),
+                .and(refresh_token::Column::UserId.eq(user_id))
+                .and(refresh_token::Column::ClientId.is_null()),
         )
         .one(&db)
         .await?;
@@ -331,6 +370,90 @@ async fn refresh(
     }
 }
 
+#[utoipa::path(
+    path = "/introspect",
+    post,
+    tag = TAG,
+    request_body(
+        content = TokenReq,
+        content_type = mime::APPLICATION_WWW_FORM_URLENCODED.as_ref()
+    ),
+    responses(
+        (status = StatusCode::OK, body = IntrospectRes),
+        (status = StatusCode::UNAUTHORIZED, description = "invalid_client")
+    ),
+    security(
+        ("client_basic" = [])
+    )
+)]
+// RFC 7662, 등록된 클라이언트만 토큰의 상태를 조회할 수 있음
+async fn introspect(
+    State(db): State<DatabaseConnection>,
+    basic: Option<TypedHeader<Authorization<Basic>>>,
+    Form(req): Form<TokenReq>,
+) -> Result<Json<IntrospectRes>, OAuthError> {
+    ClientCredentials::from_request(
+        basic.as_ref().map(|b| &b.0.0),
+        req.client_id.as_deref(),
+        req.client_secret.as_deref(),
+    )?
+    .verify(&db)
+    .await?;
+
+    Ok(Json(req.introspect(&db).await?))
+}
+
+#[utoipa::path(
+    path = "/revoke",
+    post,
+    tag = TAG,
+    request_body(
+        content = TokenReq,
+        content_type = mime::APPLICATION_WWW_FORM_URLENCODED.as_ref()
+    ),
+    responses(
+        (status = StatusCode::OK, description = "revoked or already invalid"),
+        (status = StatusCode::UNAUTHORIZED, description = "invalid_client")
+    ),
+    security(
+        ("client_basic" = [])
+    )
+)]
+// RFC 7009, access 토큰은 jti를 denylist에 올리고 refresh 토큰은 DB에서 제거
+async fn revoke(
+    State(db): State<DatabaseConnection>,
+    basic: Option<TypedHeader<Authorization<Basic>>>,
+    Form(req): Form<TokenReq>,
+) -> Result<StatusCode, OAuthError> {
+    let client = ClientCredentials::from_request(
+        basic.as_ref().map(|b| &b.0.0),
+        req.client_id.as_deref(),
+        req.client_secret.as_deref(),
+    )?
+    .verify(&db)
+    .await?;
+
+    req.revoke(&client.client_id, &db).await?;
+    Ok(StatusCode::OK)
+}
+
+// 토큰 검사/폐기 엔드포인트는 클라이언트 인증(Basic)을 사용함
+pub struct ClientSecurityAddon;
+impl Modify for ClientSecurityAddon {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        if let Some(components) = openapi.components.as_mut() {
+            components.add_security_scheme(
+                "client_basic",
+                SecurityScheme::Http(
+                    HttpBuilder::new()
+                        .scheme(utoipa::openapi::security::HttpAuthScheme::Basic)
+                        .build(),
+                ),
+            );
+        }
+    }
+}
+
 // OpenAPI
 const TAG: &str = "AUTH";
 #[derive(OpenApi)]
@@ -338,6 +461,7 @@ const TAG: &str = "AUTH";
     servers(
         (url = "/api/auth", description = "Login API base path")
     ),
+    modifiers(&ClientSecurityAddon),
     tags(
         (name = TAG, description = "Get JWT Token")
     )
@@ -356,6 +480,8 @@ pub fn init_router(aex: AppExtension) -> Router {
         .routes(routes!(logout))
         .routes(routes!(refresh))
         .routes(routes!(state_setting))
+        .routes(routes!(introspect))
+        .routes(routes!(revoke))
         .with_state(AuthState {
             db: aex.db.0,
             reqwest: aex.reqwest.0,
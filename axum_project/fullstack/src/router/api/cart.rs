@@ -0,0 +1,201 @@
+use crate::resources::dto::cart::{
+    CartDto, CartItemReq, apply_coupon, clear, remove_coupon, remove_item,
+};
+use crate::resources::dto::coupon::ApplyCouponReq;
+use crate::resources::dto::fullstack_extension::AppExtension;
+use crate::resources::dto::product_variant::VariantQuery;
+use crate::resources::dto::user::CurrentUser;
+use crate::router::api::auth::SecurityAddon;
+use crate::utils::jwt::authenticate;
+use axum::middleware;
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, State},
+};
+use sea_orm::DatabaseConnection;
+use utoipa::OpenApi;
+use utoipa_axum::{router::OpenApiRouter, routes};
+use utoipa_scalar::{Scalar, Servable};
+
+use crate::utils::errors::ApiError;
+use crate::utils::validate::{ValidatedJson, ValidatedQuery};
+
+const TAG: &str = "CART";
+
+#[utoipa::path(
+    get,
+    path = "/items",
+    tag = TAG,
+    responses (
+        (status = StatusCode::OK, body = CartDto)
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+// 내 장바구니, 가격은 지금 상품 가격
+async fn get_cart(
+    State(conn): State<DatabaseConnection>,
+    Extension(user): Extension<CurrentUser>,
+) -> Result<Json<CartDto>, ApiError> {
+    Ok(Json(CartDto::get(user.0, &conn).await?))
+}
+
+#[utoipa::path(
+    put,
+    path = "/items/{product_id}",
+    tag = TAG,
+    params (
+        ("product_id" = i32, Path, description = "product id"),
+        VariantQuery
+    ),
+    request_body (
+        content = CartItemReq,
+        content_type = mime::APPLICATION_JSON.as_ref()
+    ),
+    responses (
+        (status = StatusCode::OK, body = CartDto),
+        (status = StatusCode::NOT_FOUND, body = ApiError, description = "product or variant"),
+        (status = StatusCode::CONFLICT, body = ApiError, description = "cart has products in another currency"),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, body = ApiError, description = "invalid quantity or product has variants and none is picked")
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+// 상품을 담거나 담긴 수량을 바꿈, 변형이 있는 상품은 ?variant_id= 로 변형을 고름
+async fn put_item(
+    State(conn): State<DatabaseConnection>,
+    Extension(user): Extension<CurrentUser>,
+    Path(product_id): Path<i32>,
+    ValidatedQuery(variant): ValidatedQuery<VariantQuery>,
+    ValidatedJson(req): ValidatedJson<CartItemReq>,
+) -> Result<Json<CartDto>, ApiError> {
+    Ok(Json(
+        req.put(user.0, product_id, variant.variant_id, &conn)
+            .await?,
+    ))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/items/{product_id}",
+    tag = TAG,
+    params (
+        ("product_id" = i32, Path, description = "product id"),
+        VariantQuery
+    ),
+    responses (
+        (status = StatusCode::OK, body = CartDto)
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+async fn delete_item(
+    State(conn): State<DatabaseConnection>,
+    Extension(user): Extension<CurrentUser>,
+    Path(product_id): Path<i32>,
+    ValidatedQuery(variant): ValidatedQuery<VariantQuery>,
+) -> Result<Json<CartDto>, ApiError> {
+    Ok(Json(
+        remove_item(user.0, product_id, variant.variant_id, &conn).await?,
+    ))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/items",
+    tag = TAG,
+    responses (
+        (status = StatusCode::NO_CONTENT)
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+async fn clear_cart(
+    State(conn): State<DatabaseConnection>,
+    Extension(user): Extension<CurrentUser>,
+) -> Result<reqwest::StatusCode, ApiError> {
+    clear(user.0, &conn).await?;
+    Ok(reqwest::StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    put,
+    path = "/coupon",
+    tag = TAG,
+    request_body (
+        content = ApplyCouponReq,
+        content_type = mime::APPLICATION_JSON.as_ref()
+    ),
+    responses (
+        (status = StatusCode::OK, body = CartDto),
+        (status = StatusCode::NOT_FOUND, body = ApiError),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, body = ApiError, description = "coupon can't be used for this cart")
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+// 장바구니에 쿠폰을 걺, 쿠폰은 하나만 걸 수 있고 다시 걸면 바뀜
+async fn put_coupon(
+    State(conn): State<DatabaseConnection>,
+    Extension(user): Extension<CurrentUser>,
+    ValidatedJson(req): ValidatedJson<ApplyCouponReq>,
+) -> Result<Json<CartDto>, ApiError> {
+    Ok(Json(apply_coupon(user.0, &req.code, &conn).await?))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/coupon",
+    tag = TAG,
+    responses (
+        (status = StatusCode::OK, body = CartDto)
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+async fn delete_coupon(
+    State(conn): State<DatabaseConnection>,
+    Extension(user): Extension<CurrentUser>,
+) -> Result<Json<CartDto>, ApiError> {
+    Ok(Json(remove_coupon(user.0, &conn).await?))
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    servers(
+        (url = "/api/cart", description = "Cart API base path")
+    ),
+    modifiers(&SecurityAddon),
+    tags(
+        (name = TAG, description = "Shopping cart API")
+    )
+)]
+pub(super) struct ApiDoc;
+
+// 모두 로그인한 유저 본인의 장바구니
+pub(super) fn init_route(aex: AppExtension) -> Router {
+    let auth_router = OpenApiRouter::new()
+        .routes(routes!(get_cart, clear_cart))
+        .routes(routes!(put_item, delete_item))
+        .routes(routes!(put_coupon, delete_coupon))
+        .with_state(aex.db.0.clone())
+        .layer(middleware::from_fn_with_state(
+            aex.db.0.clone(),
+            authenticate,
+        ));
+
+    let (auth_router, auth_api) = auth_router.split_for_parts();
+
+    let mut api = ApiDoc::openapi();
+    api.merge(auth_api);
+
+    let router = auth_router.merge(Scalar::with_url("/doc/scalar", api));
+
+    Router::new().nest("/cart", router)
+}
@@ -0,0 +1,231 @@
+use crate::resources::dto::catalog::{
+    ExportQuery, ImportJobDto, ImportJobListQuery, ImportKind, ImportQuery, export_categories,
+    export_products, get_job, list_jobs, start_import,
+};
+use crate::resources::dto::fullstack_extension::AppExtension;
+use crate::resources::dto::page::Page;
+use crate::resources::dto::user::CurrentUser;
+use crate::router::api::auth::SecurityAddon;
+use crate::router::api::product::require_editor;
+use crate::utils::jwt::authenticate;
+use axum::body::Body;
+use axum::middleware;
+use axum::response::{IntoResponse, Response};
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, Query, State},
+};
+use reqwest::StatusCode;
+use reqwest::header::{CACHE_CONTROL, CONTENT_DISPOSITION, CONTENT_TYPE};
+use sea_orm::DatabaseConnection;
+use utoipa::OpenApi;
+use utoipa_axum::{router::OpenApiRouter, routes};
+use utoipa_scalar::{Scalar, Servable};
+
+use crate::utils::errors::ApiError;
+use crate::utils::validate::ValidatedQuery;
+
+const TAG: &str = "CATALOG";
+
+#[utoipa::path(
+    post,
+    path = "/import/{kind}",
+    tag = TAG,
+    params (
+        ("kind" = ImportKind, Path, description = "products or categories"),
+        ImportQuery
+    ),
+    request_body (
+        content = String,
+        description = "CSV (첫 줄은 헤더) 또는 JSON Lines 파일 본문",
+        content_type = "text/csv"
+    ),
+    responses (
+        (status = StatusCode::ACCEPTED, body = ImportJobDto, description = "작업이 만들어짐, 결과는 /jobs/{id}로 확인"),
+        (status = StatusCode::FORBIDDEN, body = ApiError),
+        (status = StatusCode::PAYLOAD_TOO_LARGE, body = ApiError),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, body = ApiError)
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+// 파일 전체를 한 트랜잭션으로 반영함, 한 행이라도 오류가 있으면 아무것도 반영하지 않음
+async fn import_file(
+    State(conn): State<DatabaseConnection>,
+    Extension(user): Extension<CurrentUser>,
+    Path(kind): Path<ImportKind>,
+    ValidatedQuery(query): ValidatedQuery<ImportQuery>,
+    body: Body,
+) -> Result<(StatusCode, Json<ImportJobDto>), ApiError> {
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(start_import(kind, query, user.0, body, &conn).await?),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/jobs",
+    tag = TAG,
+    params (
+        ImportJobListQuery
+    ),
+    responses (
+        (status = StatusCode::OK, body = Page<ImportJobDto>),
+        (status = StatusCode::FORBIDDEN, body = ApiError)
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+async fn import_jobs(
+    State(conn): State<DatabaseConnection>,
+    ValidatedQuery(query): ValidatedQuery<ImportJobListQuery>,
+) -> Result<Json<Page<ImportJobDto>>, ApiError> {
+    Ok(Json(list_jobs(query, &conn).await?))
+}
+
+#[utoipa::path(
+    get,
+    path = "/jobs/{id}",
+    tag = TAG,
+    params (
+        ("id" = i32, Path, description = "job id")
+    ),
+    responses (
+        (status = StatusCode::OK, body = ImportJobDto),
+        (status = StatusCode::FORBIDDEN, body = ApiError),
+        (status = StatusCode::NOT_FOUND, body = ApiError)
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+// 진행 상태와 행별 오류 보고서
+async fn import_job(
+    State(conn): State<DatabaseConnection>,
+    Path(id): Path<i32>,
+) -> Result<Json<ImportJobDto>, ApiError> {
+    Ok(Json(get_job(id, &conn).await?))
+}
+
+fn attachment(name: &str, query: &ExportQuery, body: Body) -> Response {
+    (
+        [
+            (CONTENT_TYPE, query.format.content_type().to_string()),
+            (
+                CONTENT_DISPOSITION,
+                format!(
+                    "attachment; filename=\"{}.{}\"",
+                    name,
+                    query.format.as_str()
+                ),
+            ),
+            (CACHE_CONTROL, "no-store".to_string()),
+        ],
+        body,
+    )
+        .into_response()
+}
+
+#[utoipa::path(
+    get,
+    path = "/export/products",
+    tag = TAG,
+    params (
+        ExportQuery
+    ),
+    responses (
+        (
+            status = StatusCode::OK,
+            description = "가져오기와 같은 모양의 파일",
+            content(
+                (String = "text/csv"),
+                (String = "application/x-ndjson")
+            )
+        ),
+        (status = StatusCode::FORBIDDEN, body = ApiError)
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+// 상품이 많아도 나눠 읽으면서 바로 내려보냄
+async fn export_product_file(
+    State(conn): State<DatabaseConnection>,
+    Query(query): Query<ExportQuery>,
+) -> Response {
+    let body = Body::from_stream(export_products(query.format, conn));
+    attachment("products", &query, body)
+}
+
+#[utoipa::path(
+    get,
+    path = "/export/categories",
+    tag = TAG,
+    params (
+        ExportQuery
+    ),
+    responses (
+        (
+            status = StatusCode::OK,
+            description = "가져오기와 같은 모양의 파일, 부모가 먼저 나옴",
+            content(
+                (String = "text/csv"),
+                (String = "application/x-ndjson")
+            )
+        ),
+        (status = StatusCode::FORBIDDEN, body = ApiError)
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+async fn export_category_file(
+    State(conn): State<DatabaseConnection>,
+    Query(query): Query<ExportQuery>,
+) -> Result<Response, ApiError> {
+    let body = export_categories(query.format, &conn).await?;
+    Ok(attachment("categories", &query, Body::from(body)))
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    servers(
+        (url = "/api/catalog", description = "Catalog import/export API base path")
+    ),
+    modifiers(&SecurityAddon),
+    tags(
+        (name = TAG, description = "Bulk product and category import/export API")
+    )
+)]
+pub(super) struct ApiDoc;
+
+// 모두 editor나 admin만 가능함
+pub(super) fn init_route(aex: AppExtension) -> Router {
+    let editor_router = OpenApiRouter::new()
+        .routes(routes!(import_file))
+        .routes(routes!(import_jobs))
+        .routes(routes!(import_job))
+        .routes(routes!(export_product_file))
+        .routes(routes!(export_category_file))
+        .with_state(aex.db.0.clone())
+        .layer(middleware::from_fn_with_state(
+            aex.db.0.clone(),
+            require_editor,
+        ))
+        .layer(middleware::from_fn_with_state(
+            aex.db.0.clone(),
+            authenticate,
+        ));
+
+    let (editor_router, editor_api) = editor_router.split_for_parts();
+
+    let mut api = ApiDoc::openapi();
+    api.merge(editor_api);
+
+    let router = editor_router.merge(Scalar::with_url("/doc/scalar", api));
+
+    Router::new().nest("/catalog", router)
+}
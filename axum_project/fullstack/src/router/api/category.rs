@@ -0,0 +1,264 @@
+use crate::resources::dto::category::{CategoryDto, CategoryNode, CategoryReq};
+use crate::resources::dto::fullstack_extension::AppExtension;
+use crate::resources::dto::page::Page;
+use crate::resources::dto::product::{ProductDto, find_products};
+use crate::resources::dto::query::ListQuery;
+use crate::resources::entities::product;
+use crate::router::api::auth::SecurityAddon;
+use crate::router::api::product::require_editor;
+use crate::utils::jwt::authenticate;
+use axum::middleware;
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+};
+use reqwest::StatusCode;
+use sea_orm::DatabaseConnection;
+use serde::{Deserialize, Serialize};
+use utoipa::OpenApi;
+use utoipa_axum::{router::OpenApiRouter, routes};
+use utoipa_scalar::{Scalar, Servable};
+
+use crate::utils::errors::ApiError;
+use crate::utils::validate::{ValidatedJson, ValidatedQuery};
+use validate_dto_macro::validate_dto;
+
+const TAG: &str = "CATEGORY";
+
+#[validate_dto(params)]
+#[derive(utoipa::IntoParams, utoipa::ToSchema, Serialize, Deserialize)]
+pub struct CategoryListReq {
+    // 기본 20, 최대 100
+    #[validate(range(min = 1, max = 100))]
+    limit: Option<u64>,
+    // 이전 응답의 next_cursor를 그대로 넣음
+    cursor: Option<String>,
+    // true일 때만 전체 개수를 계산함
+    total: Option<bool>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/list",
+    tag = TAG,
+    params (
+        CategoryListReq
+    ),
+    responses (
+        (status = StatusCode::OK, body = Page<CategoryDto>),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, body = ApiError)
+    )
+)]
+// id순
+async fn list_categories(
+    State(conn): State<DatabaseConnection>,
+    ValidatedQuery(req): ValidatedQuery<CategoryListReq>,
+) -> Result<Json<Page<CategoryDto>>, ApiError> {
+    let page = CategoryDto::list(
+        req.cursor.as_deref(),
+        req.limit,
+        req.total.unwrap_or(false),
+        &conn,
+    )
+    .await?;
+    Ok(Json(page))
+}
+
+#[utoipa::path(
+    get,
+    path = "/tree",
+    tag = TAG,
+    responses (
+        (status = StatusCode::OK, body = Vec<CategoryNode>)
+    )
+)]
+// 최상위 카테고리부터 하위 카테고리를 children으로 중첩해서 내려줌
+async fn category_tree(
+    State(conn): State<DatabaseConnection>,
+) -> Result<Json<Vec<CategoryNode>>, ApiError> {
+    Ok(Json(CategoryDto::tree(&conn).await?))
+}
+
+#[utoipa::path(
+    get,
+    path = "/{id}",
+    tag = TAG,
+    params (
+        ("id" = i32, Path, description = "category id")
+    ),
+    responses (
+        (status = StatusCode::OK, body = CategoryDto),
+        (status = StatusCode::NOT_FOUND, body = ApiError)
+    )
+)]
+async fn get_category(
+    State(conn): State<DatabaseConnection>,
+    Path(id): Path<i32>,
+) -> Result<Json<CategoryDto>, ApiError> {
+    Ok(Json(CategoryDto::get(id, &conn).await?))
+}
+
+#[utoipa::path(
+    get,
+    path = "/{id}/products",
+    tag = TAG,
+    params (
+        ("id" = i32, Path, description = "category id"),
+        CategoryListReq,
+        ListQuery<product::Entity>
+    ),
+    responses (
+        (status = StatusCode::OK, body = Page<ProductDto>),
+        (status = StatusCode::NOT_FOUND, body = ApiError),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, body = ApiError)
+    )
+)]
+// 이 카테고리와 모든 하위 카테고리의 상품
+async fn category_products(
+    State(conn): State<DatabaseConnection>,
+    Path(id): Path<i32>,
+    ValidatedQuery(req): ValidatedQuery<CategoryListReq>,
+    list: ListQuery<product::Entity>,
+) -> Result<Json<Page<ProductDto>>, ApiError> {
+    Ok(Json(
+        find_products(
+            Some(id),
+            list,
+            req.cursor.as_deref(),
+            req.limit,
+            req.total.unwrap_or(false),
+            &conn,
+        )
+        .await?,
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/create",
+    tag = TAG,
+    request_body (
+        content = CategoryReq,
+        content_type = mime::APPLICATION_JSON.as_ref()
+    ),
+    responses (
+        (status = StatusCode::CREATED, body = CategoryDto),
+        (status = StatusCode::FORBIDDEN, body = ApiError),
+        (status = StatusCode::CONFLICT, body = ApiError, description = "slug is already used or parent doesn't exist"),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, body = ApiError)
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+async fn create_category(
+    State(conn): State<DatabaseConnection>,
+    ValidatedJson(req): ValidatedJson<CategoryReq>,
+) -> Result<(StatusCode, Json<CategoryDto>), ApiError> {
+    Ok((StatusCode::CREATED, Json(req.create(&conn).await?)))
+}
+
+#[utoipa::path(
+    put,
+    path = "/{id}",
+    tag = TAG,
+    params (
+        ("id" = i32, Path, description = "category id")
+    ),
+    request_body (
+        content = CategoryReq,
+        content_type = mime::APPLICATION_JSON.as_ref()
+    ),
+    responses (
+        (status = StatusCode::OK, body = CategoryDto),
+        (status = StatusCode::FORBIDDEN, body = ApiError),
+        (status = StatusCode::NOT_FOUND, body = ApiError),
+        (status = StatusCode::CONFLICT, body = ApiError, description = "slug is already used or parent is invalid"),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, body = ApiError)
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+// 이름, slug, 부모, 정렬 순서를 바꿈, 상품은 id로 가리키므로 그대로 유지됨
+async fn update_category(
+    State(conn): State<DatabaseConnection>,
+    Path(id): Path<i32>,
+    ValidatedJson(req): ValidatedJson<CategoryReq>,
+) -> Result<Json<CategoryDto>, ApiError> {
+    Ok(Json(req.update(id, &conn).await?))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/{id}",
+    tag = TAG,
+    params (
+        ("id" = i32, Path, description = "category id")
+    ),
+    responses (
+        (status = StatusCode::NO_CONTENT),
+        (status = StatusCode::FORBIDDEN, body = ApiError),
+        (status = StatusCode::NOT_FOUND, body = ApiError),
+        (status = StatusCode::CONFLICT, body = ApiError, description = "category still has products or subcategories")
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+async fn delete_category(
+    State(conn): State<DatabaseConnection>,
+    Path(id): Path<i32>,
+) -> Result<StatusCode, ApiError> {
+    CategoryDto::delete(id, &conn).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    servers(
+        (url = "/api/category", description = "Category API base path")
+    ),
+    modifiers(&SecurityAddon),
+    tags(
+        (name = TAG, description = "Product category API")
+    )
+)]
+pub(super) struct ApiDoc;
+
+// 조회는 로그인 없이, 등록, 수정, 삭제는 editor나 admin만 가능함
+pub(super) fn init_route(aex: AppExtension) -> Router {
+    // layer는 나중에 붙인 것이 먼저 실행되므로 authenticate -> require_editor 순서
+    let auth_router = OpenApiRouter::new()
+        .routes(routes!(create_category))
+        .routes(routes!(update_category, delete_category))
+        .with_state(aex.db.0.clone())
+        .layer(middleware::from_fn_with_state(
+            aex.db.0.clone(),
+            require_editor,
+        ))
+        .layer(middleware::from_fn_with_state(
+            aex.db.0.clone(),
+            authenticate,
+        ));
+
+    let unauth_router = OpenApiRouter::new()
+        .routes(routes!(list_categories))
+        .routes(routes!(category_tree))
+        .routes(routes!(get_category))
+        .routes(routes!(category_products))
+        .with_state(aex.db.0.clone());
+
+    let (auth_router, auth_api) = auth_router.split_for_parts();
+    let (unauth_router, unauth_api) = unauth_router.split_for_parts();
+
+    let mut api = ApiDoc::openapi();
+    api.merge(auth_api);
+    api.merge(unauth_api);
+
+    let unauth_router = unauth_router.merge(Scalar::with_url("/doc/scalar", api));
+
+    let router = auth_router.merge(unauth_router);
+
+    Router::new().nest("/category", router)
+}
@@ -0,0 +1,192 @@
+use crate::resources::dto::admin::{ROLE_ADMIN, has_role};
+use crate::resources::dto::comment::{
+    CommentDto, CommentEditReq, CommentReq, delete_comment, list_threads,
+};
+use crate::resources::dto::fullstack_extension::AppExtension;
+use crate::resources::dto::page::Page;
+use crate::resources::dto::user::CurrentUser;
+use crate::router::api::auth::SecurityAddon;
+use crate::utils::jwt::authenticate;
+use axum::middleware;
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, State},
+};
+use reqwest::StatusCode;
+use sea_orm::DatabaseConnection;
+use serde::{Deserialize, Serialize};
+use utoipa::OpenApi;
+use utoipa_axum::{router::OpenApiRouter, routes};
+use utoipa_scalar::{Scalar, Servable};
+
+use crate::utils::errors::ApiError;
+use crate::utils::validate::{ValidatedJson, ValidatedQuery};
+use validate_dto_macro::validate_dto;
+
+const TAG: &str = "COMMENT";
+
+#[validate_dto(params)]
+#[derive(utoipa::IntoParams, utoipa::ToSchema, Serialize, Deserialize)]
+pub struct CommentListReq {
+    // 최상위 댓글 수, 기본 20, 최대 100
+    #[validate(range(min = 1, max = 100))]
+    limit: Option<u64>,
+    // 이전 응답의 next_cursor를 그대로 넣음
+    cursor: Option<String>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/posts/{post_id}",
+    tag = TAG,
+    params (
+        ("post_id" = i32, Path, description = "post id"),
+        CommentListReq
+    ),
+    responses (
+        (status = StatusCode::OK, body = Page<CommentDto>),
+        (status = StatusCode::NOT_FOUND, body = ApiError)
+    )
+)]
+// 최상위 댓글을 오래된 순으로 나누어 주고, 각 댓글에는 답글이 모두 달려있음
+async fn post_comments(
+    State(conn): State<DatabaseConnection>,
+    Path(post_id): Path<i32>,
+    ValidatedQuery(req): ValidatedQuery<CommentListReq>,
+) -> Result<Json<Page<CommentDto>>, ApiError> {
+    Ok(Json(
+        list_threads(post_id, req.cursor.as_deref(), req.limit, &conn).await?,
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/posts/{post_id}",
+    tag = TAG,
+    params (
+        ("post_id" = i32, Path, description = "post id")
+    ),
+    request_body (
+        content = CommentReq,
+        content_type = mime::APPLICATION_JSON.as_ref()
+    ),
+    responses (
+        (status = StatusCode::CREATED, body = CommentDto),
+        (status = StatusCode::NOT_FOUND, body = ApiError, description = "post or parent comment"),
+        (status = StatusCode::CONFLICT, body = ApiError, description = "parent comment is deleted"),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, body = ApiError)
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+async fn create_comment(
+    State(conn): State<DatabaseConnection>,
+    Extension(user): Extension<CurrentUser>,
+    Path(post_id): Path<i32>,
+    ValidatedJson(req): ValidatedJson<CommentReq>,
+) -> Result<(StatusCode, Json<CommentDto>), ApiError> {
+    Ok((
+        StatusCode::CREATED,
+        Json(req.create(post_id, user.0, &conn).await?),
+    ))
+}
+
+#[utoipa::path(
+    put,
+    path = "/{id}",
+    tag = TAG,
+    params (
+        ("id" = i32, Path, description = "comment id")
+    ),
+    request_body (
+        content = CommentEditReq,
+        content_type = mime::APPLICATION_JSON.as_ref()
+    ),
+    responses (
+        (status = StatusCode::OK, body = CommentDto),
+        (status = StatusCode::FORBIDDEN, body = ApiError, description = "not the author or edit window closed"),
+        (status = StatusCode::NOT_FOUND, body = ApiError),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, body = ApiError)
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+// 작성자만, 작성 후 15분 안에만 고칠 수 있음
+async fn update_comment(
+    State(conn): State<DatabaseConnection>,
+    Extension(user): Extension<CurrentUser>,
+    Path(id): Path<i32>,
+    ValidatedJson(req): ValidatedJson<CommentEditReq>,
+) -> Result<Json<CommentDto>, ApiError> {
+    Ok(Json(req.update(id, user.0, &conn).await?))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/{id}",
+    tag = TAG,
+    params (
+        ("id" = i32, Path, description = "comment id")
+    ),
+    responses (
+        (status = StatusCode::NO_CONTENT),
+        (status = StatusCode::FORBIDDEN, body = ApiError, description = "not the author"),
+        (status = StatusCode::NOT_FOUND, body = ApiError)
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+// 본문만 지우고 자리는 남겨서 답글이 그대로 보임, 관리자는 남의 댓글도 지울 수 있음
+async fn remove_comment(
+    State(conn): State<DatabaseConnection>,
+    Extension(user): Extension<CurrentUser>,
+    Path(id): Path<i32>,
+) -> Result<StatusCode, ApiError> {
+    let is_admin = has_role(user.0, &[ROLE_ADMIN], &conn).await?;
+    delete_comment(id, user.0, is_admin, &conn).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    servers(
+        (url = "/api/comment", description = "Comment API base path")
+    ),
+    modifiers(&SecurityAddon),
+    tags(
+        (name = TAG, description = "Blog post comment API")
+    )
+)]
+pub(super) struct ApiDoc;
+
+// 조회는 로그인 없이, 작성은 로그인이 필요하고 수정, 삭제는 작성자만 가능함
+pub(super) fn init_route(aex: AppExtension) -> Router {
+    let auth_router = OpenApiRouter::new()
+        .routes(routes!(create_comment))
+        .routes(routes!(update_comment, remove_comment))
+        .with_state(aex.db.0.clone())
+        .layer(middleware::from_fn_with_state(
+            aex.db.0.clone(),
+            authenticate,
+        ));
+
+    let unauth_router = OpenApiRouter::new()
+        .routes(routes!(post_comments))
+        .with_state(aex.db.0);
+
+    let (auth_router, auth_api) = auth_router.split_for_parts();
+    let (unauth_router, unauth_api) = unauth_router.split_for_parts();
+
+    let mut api = ApiDoc::openapi();
+    api.merge(auth_api);
+    api.merge(unauth_api);
+
+    let unauth_router = unauth_router.merge(Scalar::with_url("/doc/scalar", api));
+
+    let router = auth_router.merge(unauth_router);
+
+    Router::new().nest("/comment", router)
+}
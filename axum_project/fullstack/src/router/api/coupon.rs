@@ -0,0 +1,186 @@
+use crate::resources::dto::coupon::{CouponDto, CouponListQuery, CouponReq, delete_coupon};
+use crate::resources::dto::fullstack_extension::AppExtension;
+use crate::resources::dto::page::Page;
+use crate::router::api::admin::require_admin;
+use crate::router::api::auth::SecurityAddon;
+use crate::utils::jwt::authenticate;
+use axum::middleware;
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+};
+use reqwest::StatusCode;
+use sea_orm::DatabaseConnection;
+use utoipa::OpenApi;
+use utoipa_axum::{router::OpenApiRouter, routes};
+use utoipa_scalar::{Scalar, Servable};
+
+use crate::utils::errors::ApiError;
+use crate::utils::validate::{ValidatedJson, ValidatedQuery};
+
+const TAG: &str = "COUPON";
+
+#[utoipa::path(
+    get,
+    path = "/list",
+    tag = TAG,
+    params (
+        CouponListQuery
+    ),
+    responses (
+        (status = StatusCode::OK, body = Page<CouponDto>),
+        (status = StatusCode::FORBIDDEN, body = ApiError)
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+async fn list_coupons(
+    State(conn): State<DatabaseConnection>,
+    ValidatedQuery(query): ValidatedQuery<CouponListQuery>,
+) -> Result<Json<Page<CouponDto>>, ApiError> {
+    Ok(Json(CouponDto::list(query, &conn).await?))
+}
+
+#[utoipa::path(
+    post,
+    path = "/create",
+    tag = TAG,
+    request_body (
+        content = CouponReq,
+        content_type = mime::APPLICATION_JSON.as_ref()
+    ),
+    responses (
+        (status = StatusCode::CREATED, body = CouponDto),
+        (status = StatusCode::FORBIDDEN, body = ApiError),
+        (status = StatusCode::CONFLICT, body = ApiError, description = "code already exists"),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, body = ApiError)
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+async fn create_coupon(
+    State(conn): State<DatabaseConnection>,
+    ValidatedJson(req): ValidatedJson<CouponReq>,
+) -> Result<(StatusCode, Json<CouponDto>), ApiError> {
+    Ok((StatusCode::CREATED, Json(req.create(&conn).await?)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/{id}",
+    tag = TAG,
+    params (
+        ("id" = i32, Path, description = "coupon id")
+    ),
+    responses (
+        (status = StatusCode::OK, body = CouponDto),
+        (status = StatusCode::FORBIDDEN, body = ApiError),
+        (status = StatusCode::NOT_FOUND, body = ApiError)
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+async fn get_coupon(
+    State(conn): State<DatabaseConnection>,
+    Path(id): Path<i32>,
+) -> Result<Json<CouponDto>, ApiError> {
+    Ok(Json(CouponDto::get(id, &conn).await?))
+}
+
+#[utoipa::path(
+    put,
+    path = "/{id}",
+    tag = TAG,
+    params (
+        ("id" = i32, Path, description = "coupon id")
+    ),
+    request_body (
+        content = CouponReq,
+        content_type = mime::APPLICATION_JSON.as_ref()
+    ),
+    responses (
+        (status = StatusCode::OK, body = CouponDto),
+        (status = StatusCode::FORBIDDEN, body = ApiError),
+        (status = StatusCode::NOT_FOUND, body = ApiError),
+        (status = StatusCode::CONFLICT, body = ApiError, description = "code already exists"),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, body = ApiError)
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+// 이미 쓰인 쿠폰도 고칠 수 있음, 지난 주문의 할인은 바뀌지 않음
+async fn update_coupon(
+    State(conn): State<DatabaseConnection>,
+    Path(id): Path<i32>,
+    ValidatedJson(req): ValidatedJson<CouponReq>,
+) -> Result<Json<CouponDto>, ApiError> {
+    Ok(Json(req.update(id, &conn).await?))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/{id}",
+    tag = TAG,
+    params (
+        ("id" = i32, Path, description = "coupon id")
+    ),
+    responses (
+        (status = StatusCode::NO_CONTENT),
+        (status = StatusCode::FORBIDDEN, body = ApiError),
+        (status = StatusCode::NOT_FOUND, body = ApiError)
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+// 그만 쓰게 하려면 지우는 대신 active를 false로 바꾸면 사용 기록이 남음
+async fn remove_coupon(
+    State(conn): State<DatabaseConnection>,
+    Path(id): Path<i32>,
+) -> Result<StatusCode, ApiError> {
+    delete_coupon(id, &conn).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    servers(
+        (url = "/api/coupon", description = "Coupon API base path")
+    ),
+    modifiers(&SecurityAddon),
+    tags(
+        (name = TAG, description = "Coupon management API")
+    )
+)]
+pub(super) struct ApiDoc;
+
+// 쿠폰 관리는 관리자만, 장바구니에 쿠폰을 거는 API는 /api/cart/coupon
+pub(super) fn init_route(aex: AppExtension) -> Router {
+    // layer는 나중에 붙인 것이 먼저 실행되므로 authenticate -> require_admin 순서
+    let admin_router = OpenApiRouter::new()
+        .routes(routes!(list_coupons))
+        .routes(routes!(create_coupon))
+        .routes(routes!(get_coupon, update_coupon, remove_coupon))
+        .with_state(aex.db.0.clone())
+        .layer(middleware::from_fn_with_state(
+            aex.db.0.clone(),
+            require_admin,
+        ))
+        .layer(middleware::from_fn_with_state(
+            aex.db.0.clone(),
+            authenticate,
+        ));
+
+    let (admin_router, admin_api) = admin_router.split_for_parts();
+
+    let mut api = ApiDoc::openapi();
+    api.merge(admin_api);
+
+    let router = admin_router.merge(Scalar::with_url("/doc/scalar", api));
+
+    Router::new().nest("/coupon", router)
+}
@@ -0,0 +1,292 @@
+use crate::resources::dto::fullstack_extension::AppExtension;
+use crate::resources::dto::inventory::{
+    InventoryDto, StockAdjustReq, StockLedgerDto, StockListQuery, StockMoveReq,
+};
+use crate::resources::dto::page::Page;
+use crate::resources::dto::product_variant::VariantQuery;
+use crate::resources::dto::user::CurrentUser;
+use crate::router::api::auth::SecurityAddon;
+use crate::router::api::product::require_editor;
+use crate::utils::jwt::authenticate;
+use axum::middleware;
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, State},
+};
+use sea_orm::DatabaseConnection;
+use utoipa::OpenApi;
+use utoipa_axum::{router::OpenApiRouter, routes};
+use utoipa_scalar::{Scalar, Servable};
+
+use crate::utils::errors::ApiError;
+use crate::utils::validate::{ValidatedJson, ValidatedQuery};
+
+const TAG: &str = "INVENTORY";
+
+#[utoipa::path(
+    get,
+    path = "/low",
+    tag = TAG,
+    params (
+        StockListQuery
+    ),
+    responses (
+        (status = StatusCode::OK, body = Page<InventoryDto>),
+        (status = StatusCode::FORBIDDEN, body = ApiError)
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+// 팔 수 있는 수량이 low_stock_threshold 이하인 상품
+async fn low_stock(
+    State(conn): State<DatabaseConnection>,
+    ValidatedQuery(query): ValidatedQuery<StockListQuery>,
+) -> Result<Json<Page<InventoryDto>>, ApiError> {
+    Ok(Json(InventoryDto::low_stock(query, &conn).await?))
+}
+
+#[utoipa::path(
+    get,
+    path = "/{product_id}",
+    tag = TAG,
+    params (
+        ("product_id" = i32, Path, description = "product id"),
+        VariantQuery
+    ),
+    responses (
+        (status = StatusCode::OK, body = InventoryDto),
+        (status = StatusCode::FORBIDDEN, body = ApiError),
+        (status = StatusCode::NOT_FOUND, body = ApiError),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, body = ApiError, description = "product has variants and none is picked")
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+async fn get_stock(
+    State(conn): State<DatabaseConnection>,
+    Path(product_id): Path<i32>,
+    ValidatedQuery(variant): ValidatedQuery<VariantQuery>,
+) -> Result<Json<InventoryDto>, ApiError> {
+    Ok(Json(
+        InventoryDto::get(product_id, variant.variant_id, &conn).await?,
+    ))
+}
+
+#[utoipa::path(
+    put,
+    path = "/{product_id}",
+    tag = TAG,
+    params (
+        ("product_id" = i32, Path, description = "product id"),
+        VariantQuery
+    ),
+    request_body (
+        content = StockAdjustReq,
+        content_type = mime::APPLICATION_JSON.as_ref()
+    ),
+    responses (
+        (status = StatusCode::OK, body = InventoryDto),
+        (status = StatusCode::FORBIDDEN, body = ApiError),
+        (status = StatusCode::NOT_FOUND, body = ApiError),
+        (status = StatusCode::CONFLICT, body = ApiError, description = "on hand would be less than reserved"),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, body = ApiError)
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+// 입고, 실사 등으로 창고 수량을 늘리거나 줄이고 기준 수량을 바꿈
+async fn adjust_stock(
+    State(conn): State<DatabaseConnection>,
+    Extension(user): Extension<CurrentUser>,
+    Path(product_id): Path<i32>,
+    ValidatedQuery(variant): ValidatedQuery<VariantQuery>,
+    ValidatedJson(req): ValidatedJson<StockAdjustReq>,
+) -> Result<Json<InventoryDto>, ApiError> {
+    Ok(Json(
+        req.apply(product_id, variant.variant_id, user.0, &conn)
+            .await?,
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/{product_id}/ledger",
+    tag = TAG,
+    params (
+        ("product_id" = i32, Path, description = "product id"),
+        VariantQuery,
+        StockListQuery
+    ),
+    responses (
+        (status = StatusCode::OK, body = Page<StockLedgerDto>),
+        (status = StatusCode::FORBIDDEN, body = ApiError)
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+// 재고 변동 기록, 오래된 것부터, variant_id가 없으면 상품의 모든 변형 기록
+async fn stock_ledger(
+    State(conn): State<DatabaseConnection>,
+    Path(product_id): Path<i32>,
+    ValidatedQuery(variant): ValidatedQuery<VariantQuery>,
+    ValidatedQuery(query): ValidatedQuery<StockListQuery>,
+) -> Result<Json<Page<StockLedgerDto>>, ApiError> {
+    Ok(Json(
+        StockLedgerDto::list(product_id, variant.variant_id, query, &conn).await?,
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/{product_id}/reserve",
+    tag = TAG,
+    params (
+        ("product_id" = i32, Path, description = "product id"),
+        VariantQuery
+    ),
+    request_body (
+        content = StockMoveReq,
+        content_type = mime::APPLICATION_JSON.as_ref()
+    ),
+    responses (
+        (status = StatusCode::OK, body = InventoryDto),
+        (status = StatusCode::FORBIDDEN, body = ApiError),
+        (status = StatusCode::NOT_FOUND, body = ApiError),
+        (status = StatusCode::CONFLICT, body = ApiError, description = "not enough available stock"),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, body = ApiError)
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+async fn reserve_stock(
+    State(conn): State<DatabaseConnection>,
+    Extension(user): Extension<CurrentUser>,
+    Path(product_id): Path<i32>,
+    ValidatedQuery(variant): ValidatedQuery<VariantQuery>,
+    ValidatedJson(req): ValidatedJson<StockMoveReq>,
+) -> Result<Json<InventoryDto>, ApiError> {
+    Ok(Json(
+        req.reserve(product_id, variant.variant_id, user.0, &conn)
+            .await?,
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/{product_id}/release",
+    tag = TAG,
+    params (
+        ("product_id" = i32, Path, description = "product id"),
+        VariantQuery
+    ),
+    request_body (
+        content = StockMoveReq,
+        content_type = mime::APPLICATION_JSON.as_ref()
+    ),
+    responses (
+        (status = StatusCode::OK, body = InventoryDto),
+        (status = StatusCode::FORBIDDEN, body = ApiError),
+        (status = StatusCode::NOT_FOUND, body = ApiError),
+        (status = StatusCode::CONFLICT, body = ApiError, description = "more than reserved for the reference"),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, body = ApiError)
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+async fn release_stock(
+    State(conn): State<DatabaseConnection>,
+    Extension(user): Extension<CurrentUser>,
+    Path(product_id): Path<i32>,
+    ValidatedQuery(variant): ValidatedQuery<VariantQuery>,
+    ValidatedJson(req): ValidatedJson<StockMoveReq>,
+) -> Result<Json<InventoryDto>, ApiError> {
+    Ok(Json(
+        req.release(product_id, variant.variant_id, user.0, &conn)
+            .await?,
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/{product_id}/commit",
+    tag = TAG,
+    params (
+        ("product_id" = i32, Path, description = "product id"),
+        VariantQuery
+    ),
+    request_body (
+        content = StockMoveReq,
+        content_type = mime::APPLICATION_JSON.as_ref()
+    ),
+    responses (
+        (status = StatusCode::OK, body = InventoryDto),
+        (status = StatusCode::FORBIDDEN, body = ApiError),
+        (status = StatusCode::NOT_FOUND, body = ApiError),
+        (status = StatusCode::CONFLICT, body = ApiError, description = "more than reserved for the reference"),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, body = ApiError)
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+async fn commit_stock(
+    State(conn): State<DatabaseConnection>,
+    Extension(user): Extension<CurrentUser>,
+    Path(product_id): Path<i32>,
+    ValidatedQuery(variant): ValidatedQuery<VariantQuery>,
+    ValidatedJson(req): ValidatedJson<StockMoveReq>,
+) -> Result<Json<InventoryDto>, ApiError> {
+    Ok(Json(
+        req.commit(product_id, variant.variant_id, user.0, &conn)
+            .await?,
+    ))
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    servers(
+        (url = "/api/inventory", description = "Inventory API base path")
+    ),
+    modifiers(&SecurityAddon),
+    tags(
+        (name = TAG, description = "Product stock and reservation API")
+    )
+)]
+pub(super) struct ApiDoc;
+
+// 모든 재고 API는 editor나 admin만 호출할 수 있음
+// 변형이 있는 상품은 ?variant_id= 로 변형마다 다룸
+pub(super) fn init_route(aex: AppExtension) -> Router {
+    // layer는 나중에 붙인 것이 먼저 실행되므로 authenticate -> require_editor 순서
+    let auth_router = OpenApiRouter::new()
+        .routes(routes!(low_stock))
+        .routes(routes!(get_stock, adjust_stock))
+        .routes(routes!(stock_ledger))
+        .routes(routes!(reserve_stock))
+        .routes(routes!(release_stock))
+        .routes(routes!(commit_stock))
+        .with_state(aex.db.0.clone())
+        .layer(middleware::from_fn_with_state(
+            aex.db.0.clone(),
+            require_editor,
+        ))
+        .layer(middleware::from_fn_with_state(
+            aex.db.0.clone(),
+            authenticate,
+        ));
+
+    let (auth_router, auth_api) = auth_router.split_for_parts();
+
+    let mut api = ApiDoc::openapi();
+    api.merge(auth_api);
+
+    let router = auth_router.merge(Scalar::with_url("/doc/scalar", api));
+
+    Router::new().nest("/inventory", router)
+}
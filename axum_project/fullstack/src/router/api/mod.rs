@@ -2,7 +2,19 @@ use axum::Router;
 
 use crate::resources::dto::fullstack_extension::AppExtension;
 
+mod admin;
 pub mod auth;
+mod cart;
+mod catalog;
+mod category;
+mod comment;
+mod coupon;
+mod inventory;
+mod order;
+mod payment;
+mod post;
+mod product;
+mod review;
 mod user;
 
 pub struct ApiRouters {
@@ -41,5 +53,20 @@ impl From<(Router, Router)> for ApiRouters {
 }
 
 pub fn init_route(aex: AppExtension) -> Router {
-    Router::new().nest("/api", user::init_route(aex))
+    Router::new().nest(
+        "/api",
+        user::init_route(aex.clone())
+            .merge(admin::init_route(aex.clone()))
+            .merge(product::init_route(aex.clone()))
+            .merge(category::init_route(aex.clone()))
+            .merge(inventory::init_route(aex.clone()))
+            .merge(cart::init_route(aex.clone()))
+            .merge(order::init_route(aex.clone()))
+            .merge(payment::init_route(aex.clone()))
+            .merge(catalog::init_route(aex.clone()))
+            .merge(review::init_route(aex.clone()))
+            .merge(coupon::init_route(aex.clone()))
+            .merge(post::init_route(aex.clone()))
+            .merge(comment::init_route(aex)),
+    )
 }
@@ -0,0 +1,238 @@
+use crate::resources::dto::fullstack_extension::AppExtension;
+use crate::resources::dto::order::{
+    OrderDto, OrderListQuery, OrderStatus, OrderStatusReq, OrderSummaryDto, checkout, transition,
+};
+use crate::resources::dto::page::Page;
+use crate::resources::dto::user::CurrentUser;
+use crate::router::api::admin::require_admin;
+use crate::router::api::auth::SecurityAddon;
+use crate::utils::jwt::authenticate;
+use axum::middleware;
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, State},
+};
+use reqwest::StatusCode;
+use sea_orm::DatabaseConnection;
+use utoipa::OpenApi;
+use utoipa_axum::{router::OpenApiRouter, routes};
+use utoipa_scalar::{Scalar, Servable};
+
+use crate::utils::errors::ApiError;
+use crate::utils::validate::ValidatedQuery;
+
+const TAG: &str = "ORDER";
+
+#[utoipa::path(
+    post,
+    path = "/checkout",
+    tag = TAG,
+    responses (
+        (status = StatusCode::CREATED, body = OrderDto),
+        (status = StatusCode::CONFLICT, body = ApiError, description = "not enough stock or mixed currencies"),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, body = ApiError, description = "cart is empty")
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+// 장바구니로 pending 주문을 만들고 재고를 예약함, 장바구니는 비워짐
+async fn checkout_cart(
+    State(conn): State<DatabaseConnection>,
+    Extension(user): Extension<CurrentUser>,
+) -> Result<(StatusCode, Json<OrderDto>), ApiError> {
+    Ok((StatusCode::CREATED, Json(checkout(user.0, &conn).await?)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/list",
+    tag = TAG,
+    params (
+        OrderListQuery
+    ),
+    responses (
+        (status = StatusCode::OK, body = Page<OrderSummaryDto>),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, body = ApiError)
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+// 내 주문 목록
+async fn list_orders(
+    State(conn): State<DatabaseConnection>,
+    Extension(user): Extension<CurrentUser>,
+    ValidatedQuery(query): ValidatedQuery<OrderListQuery>,
+) -> Result<Json<Page<OrderSummaryDto>>, ApiError> {
+    Ok(Json(
+        OrderSummaryDto::list(Some(user.0), query, &conn).await?,
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/{id}",
+    tag = TAG,
+    params (
+        ("id" = i32, Path, description = "order id")
+    ),
+    responses (
+        (status = StatusCode::OK, body = OrderDto),
+        (status = StatusCode::NOT_FOUND, body = ApiError)
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+async fn get_order(
+    State(conn): State<DatabaseConnection>,
+    Extension(user): Extension<CurrentUser>,
+    Path(id): Path<i32>,
+) -> Result<Json<OrderDto>, ApiError> {
+    Ok(Json(OrderDto::get(id, Some(user.0), &conn).await?))
+}
+
+#[utoipa::path(
+    post,
+    path = "/{id}/cancel",
+    tag = TAG,
+    params (
+        ("id" = i32, Path, description = "order id")
+    ),
+    responses (
+        (status = StatusCode::OK, body = OrderDto),
+        (status = StatusCode::NOT_FOUND, body = ApiError),
+        (status = StatusCode::CONFLICT, body = ApiError, description = "order is not pending")
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+// 결제 전 주문만 취소할 수 있음, 예약한 재고는 풀림
+async fn cancel_order(
+    State(conn): State<DatabaseConnection>,
+    Extension(user): Extension<CurrentUser>,
+    Path(id): Path<i32>,
+) -> Result<Json<OrderDto>, ApiError> {
+    Ok(Json(
+        transition(
+            id,
+            OrderStatus::Cancelled,
+            Some(user.0),
+            Some(user.0),
+            &conn,
+        )
+        .await?,
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/all",
+    tag = TAG,
+    params (
+        OrderListQuery
+    ),
+    responses (
+        (status = StatusCode::OK, body = Page<OrderSummaryDto>),
+        (status = StatusCode::FORBIDDEN, body = ApiError),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, body = ApiError)
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+// 모든 유저의 주문, 관리자만
+async fn list_all_orders(
+    State(conn): State<DatabaseConnection>,
+    ValidatedQuery(query): ValidatedQuery<OrderListQuery>,
+) -> Result<Json<Page<OrderSummaryDto>>, ApiError> {
+    Ok(Json(OrderSummaryDto::list(None, query, &conn).await?))
+}
+
+#[utoipa::path(
+    put,
+    path = "/{id}/status",
+    tag = TAG,
+    params (
+        ("id" = i32, Path, description = "order id")
+    ),
+    request_body (
+        content = OrderStatusReq,
+        content_type = mime::APPLICATION_JSON.as_ref()
+    ),
+    responses (
+        (status = StatusCode::OK, body = OrderDto),
+        (status = StatusCode::FORBIDDEN, body = ApiError),
+        (status = StatusCode::NOT_FOUND, body = ApiError),
+        (status = StatusCode::CONFLICT, body = ApiError, description = "illegal status transition")
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+// 관리자가 주문 상태를 바꿈, 허용되지 않은 변경은 409
+async fn change_status(
+    State(conn): State<DatabaseConnection>,
+    Extension(user): Extension<CurrentUser>,
+    Path(id): Path<i32>,
+    Json(req): Json<OrderStatusReq>,
+) -> Result<Json<OrderDto>, ApiError> {
+    Ok(Json(
+        transition(id, req.status, None, Some(user.0), &conn).await?,
+    ))
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    servers(
+        (url = "/api/order", description = "Order API base path")
+    ),
+    modifiers(&SecurityAddon),
+    tags(
+        (name = TAG, description = "Checkout and order API")
+    )
+)]
+pub(super) struct ApiDoc;
+
+// 주문, 조회, 취소는 본인 주문만, 전체 조회와 상태 변경은 관리자만
+pub(super) fn init_route(aex: AppExtension) -> Router {
+    let auth_router = OpenApiRouter::new()
+        .routes(routes!(checkout_cart))
+        .routes(routes!(list_orders))
+        .routes(routes!(get_order))
+        .routes(routes!(cancel_order))
+        .with_state(aex.db.0.clone())
+        .layer(middleware::from_fn_with_state(
+            aex.db.0.clone(),
+            authenticate,
+        ));
+
+    // layer는 나중에 붙인 것이 먼저 실행되므로 authenticate -> require_admin 순서
+    let admin_router = OpenApiRouter::new()
+        .routes(routes!(list_all_orders))
+        .routes(routes!(change_status))
+        .with_state(aex.db.0.clone())
+        .layer(middleware::from_fn_with_state(
+            aex.db.0.clone(),
+            require_admin,
+        ))
+        .layer(middleware::from_fn_with_state(
+            aex.db.0.clone(),
+            authenticate,
+        ));
+
+    let (auth_router, auth_api) = auth_router.split_for_parts();
+    let (admin_router, admin_api) = admin_router.split_for_parts();
+
+    let mut api = ApiDoc::openapi();
+    api.merge(auth_api);
+    api.merge(admin_api);
+
+    let router = auth_router
+        .merge(admin_router)
+        .merge(Scalar::with_url("/doc/scalar", api));
+
+    Router::new().nest("/order", router)
+}
@@ -0,0 +1,194 @@
+use crate::resources::dto::fullstack_extension::AppExtension;
+use crate::resources::dto::payment::{
+    PaymentConfirmReq, PaymentDto, confirm, create_intent, handle_webhook, list_for_order,
+};
+use crate::resources::dto::user::CurrentUser;
+use crate::router::api::auth::SecurityAddon;
+use crate::utils::jwt::authenticate;
+use crate::utils::payment::DynPayment;
+use axum::body::Bytes;
+use axum::http::HeaderMap;
+use axum::middleware;
+use axum::{
+    Extension, Json, Router,
+    extract::{FromRef, Path, State},
+};
+use reqwest::StatusCode;
+use sea_orm::DatabaseConnection;
+use utoipa::OpenApi;
+use utoipa_axum::{router::OpenApiRouter, routes};
+use utoipa_scalar::{Scalar, Servable};
+
+use crate::utils::errors::ApiError;
+use crate::utils::validate::ValidatedJson;
+
+const TAG: &str = "PAYMENT";
+
+#[utoipa::path(
+    post,
+    path = "/orders/{order_id}",
+    tag = TAG,
+    params (
+        ("order_id" = i32, Path, description = "order id")
+    ),
+    responses (
+        (status = StatusCode::CREATED, body = PaymentDto),
+        (status = StatusCode::NOT_FOUND, body = ApiError),
+        (status = StatusCode::CONFLICT, body = ApiError, description = "order is not pending or already has an open payment")
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+// 내 pending 주문의 결제를 만듦, client_secret은 이 응답에만 들어있음
+async fn create_payment(
+    State(conn): State<DatabaseConnection>,
+    State(payment): State<DynPayment>,
+    Extension(user): Extension<CurrentUser>,
+    Path(order_id): Path<i32>,
+) -> Result<(StatusCode, Json<PaymentDto>), ApiError> {
+    Ok((
+        StatusCode::CREATED,
+        Json(create_intent(order_id, user.0, payment.as_ref(), &conn).await?),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/orders/{order_id}",
+    tag = TAG,
+    params (
+        ("order_id" = i32, Path, description = "order id")
+    ),
+    responses (
+        (status = StatusCode::OK, body = Vec<PaymentDto>),
+        (status = StatusCode::NOT_FOUND, body = ApiError)
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+async fn order_payments(
+    State(conn): State<DatabaseConnection>,
+    Extension(user): Extension<CurrentUser>,
+    Path(order_id): Path<i32>,
+) -> Result<Json<Vec<PaymentDto>>, ApiError> {
+    Ok(Json(list_for_order(order_id, user.0, &conn).await?))
+}
+
+#[utoipa::path(
+    post,
+    path = "/{id}/confirm",
+    tag = TAG,
+    params (
+        ("id" = i32, Path, description = "payment id")
+    ),
+    request_body (
+        content = PaymentConfirmReq,
+        content_type = mime::APPLICATION_JSON.as_ref()
+    ),
+    responses (
+        (status = StatusCode::OK, body = PaymentDto, description = "status가 pending이면 결과는 웹훅으로 확정됨"),
+        (status = StatusCode::NOT_FOUND, body = ApiError),
+        (status = StatusCode::CONFLICT, body = ApiError, description = "order is not pending"),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, body = ApiError)
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+// 결제 승인, 승인되면 주문이 paid가 됨
+async fn confirm_payment(
+    State(conn): State<DatabaseConnection>,
+    State(payment): State<DynPayment>,
+    Extension(user): Extension<CurrentUser>,
+    Path(id): Path<i32>,
+    ValidatedJson(req): ValidatedJson<PaymentConfirmReq>,
+) -> Result<Json<PaymentDto>, ApiError> {
+    Ok(Json(
+        confirm(id, user.0, req, payment.as_ref(), &conn).await?,
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/webhook",
+    tag = TAG,
+    params (
+        ("Stripe-Signature" = String, Header, description = "t=timestamp,v1=hex(HMAC-SHA256(secret, \"{t}.{body}\"))")
+    ),
+    request_body (
+        content = serde_json::Value,
+        content_type = mime::APPLICATION_JSON.as_ref()
+    ),
+    responses (
+        (status = StatusCode::OK),
+        (status = StatusCode::BAD_REQUEST, body = ApiError, description = "invalid signature")
+    )
+)]
+// 결제 대행사가 보내는 결과 알림, 서명으로 확인하므로 로그인은 필요 없음
+async fn payment_webhook(
+    State(conn): State<DatabaseConnection>,
+    State(payment): State<DynPayment>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<StatusCode, ApiError> {
+    handle_webhook(payment.as_ref(), &headers, &body, &conn).await?;
+    Ok(StatusCode::OK)
+}
+
+#[derive(FromRef, Clone)]
+struct PaymentState {
+    db: DatabaseConnection,
+    payment: DynPayment,
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    servers(
+        (url = "/api/payment", description = "Payment API base path")
+    ),
+    modifiers(&SecurityAddon),
+    tags(
+        (name = TAG, description = "Order payment API")
+    )
+)]
+pub(super) struct ApiDoc;
+
+// 결제 생성, 승인은 본인 주문만, 웹훅은 로그인 없이 서명으로 확인
+// 결제 대행사를 설정하지 않았으면 결제 API를 열지 않음
+pub(super) fn init_route(aex: AppExtension) -> Router {
+    let Some(payment) = aex.payment.0.clone() else {
+        return Router::new();
+    };
+    let state = PaymentState {
+        db: aex.db.0.clone(),
+        payment,
+    };
+
+    let auth_router = OpenApiRouter::new()
+        .routes(routes!(create_payment, order_payments))
+        .routes(routes!(confirm_payment))
+        .with_state(state.clone())
+        .layer(middleware::from_fn_with_state(
+            aex.db.0.clone(),
+            authenticate,
+        ));
+
+    let unauth_router = OpenApiRouter::new()
+        .routes(routes!(payment_webhook))
+        .with_state(state);
+
+    let (auth_router, auth_api) = auth_router.split_for_parts();
+    let (unauth_router, unauth_api) = unauth_router.split_for_parts();
+
+    let mut api = ApiDoc::openapi();
+    api.merge(auth_api);
+    api.merge(unauth_api);
+
+    let unauth_router = unauth_router.merge(Scalar::with_url("/doc/scalar", api));
+
+    let router = auth_router.merge(unauth_router);
+
+    Router::new().nest("/payment", router)
+}
@@ -0,0 +1,308 @@
+use crate::resources::dto::admin::{ROLE_ADMIN, ROLE_AUTHOR, has_role};
+use crate::resources::dto::fullstack_extension::AppExtension;
+use crate::resources::dto::page::Page;
+use crate::resources::dto::post::{PostDetailDto, PostDto, PostReq, delete_post, list_posts};
+use crate::resources::dto::query::ListQuery;
+use crate::resources::dto::user::CurrentUser;
+use crate::resources::entities::post;
+use crate::router::api::auth::SecurityAddon;
+use crate::utils::jwt::authenticate;
+use axum::body::Body;
+use axum::http::Request;
+use axum::middleware::{self, Next};
+use axum::response::Response;
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, State},
+};
+use reqwest::StatusCode;
+use sea_orm::DatabaseConnection;
+use serde::{Deserialize, Serialize};
+use utoipa::OpenApi;
+use utoipa_axum::{router::OpenApiRouter, routes};
+use utoipa_scalar::{Scalar, Servable};
+
+use crate::utils::errors::ApiError;
+use crate::utils::validate::{ValidatedJson, ValidatedQuery};
+use validate_dto_macro::validate_dto;
+
+const TAG: &str = "POST";
+
+// authenticate 뒤에 붙여서 author나 admin인 유저만 통과시킴
+async fn require_author(
+    State(db): State<DatabaseConnection>,
+    Extension(user): Extension<CurrentUser>,
+    request: Request<Body>,
+    next: Next,
+) -> Result<Response, ApiError> {
+    if !has_role(user.0, &[ROLE_AUTHOR, ROLE_ADMIN], &db).await? {
+        return Err(ApiError::new(
+            StatusCode::FORBIDDEN,
+            "forbidden",
+            "author role is required",
+        ));
+    }
+    Ok(next.run(request).await)
+}
+
+#[validate_dto(params)]
+#[derive(utoipa::IntoParams, utoipa::ToSchema, Serialize, Deserialize)]
+pub struct PostListReq {
+    // 기본 20, 최대 100
+    #[validate(range(min = 1, max = 100))]
+    limit: Option<u64>,
+    // 이전 응답의 next_cursor를 그대로 넣음
+    cursor: Option<String>,
+    // true일 때만 전체 개수를 계산함
+    total: Option<bool>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/list",
+    tag = TAG,
+    params (
+        PostListReq,
+        ListQuery<post::Entity>
+    ),
+    responses (
+        (status = StatusCode::OK, body = Page<PostDto>),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, body = ApiError)
+    )
+)]
+// 게시된 글만, 로그인하지 않아도 볼 수 있음
+async fn published_posts(
+    State(conn): State<DatabaseConnection>,
+    ValidatedQuery(req): ValidatedQuery<PostListReq>,
+    list: ListQuery<post::Entity>,
+) -> Result<Json<Page<PostDto>>, ApiError> {
+    Ok(Json(
+        list_posts(
+            None,
+            list,
+            req.cursor.as_deref(),
+            req.limit,
+            req.total.unwrap_or(false),
+            &conn,
+        )
+        .await?,
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/slug/{slug}",
+    tag = TAG,
+    params (
+        ("slug" = String, Path, description = "post slug")
+    ),
+    responses (
+        (status = StatusCode::OK, body = PostDetailDto),
+        (status = StatusCode::NOT_FOUND, body = ApiError)
+    )
+)]
+// 본문 Markdown과 정리된 HTML, 초안은 404
+async fn get_published(
+    State(conn): State<DatabaseConnection>,
+    Path(slug): Path<String>,
+) -> Result<Json<PostDetailDto>, ApiError> {
+    Ok(Json(PostDetailDto::published(&slug, &conn).await?))
+}
+
+#[utoipa::path(
+    get,
+    path = "/mine",
+    tag = TAG,
+    params (
+        PostListReq,
+        ListQuery<post::Entity>
+    ),
+    responses (
+        (status = StatusCode::OK, body = Page<PostDto>),
+        (status = StatusCode::FORBIDDEN, body = ApiError),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, body = ApiError)
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+// 내가 쓴 글, 초안 포함
+async fn my_posts(
+    State(conn): State<DatabaseConnection>,
+    Extension(user): Extension<CurrentUser>,
+    ValidatedQuery(req): ValidatedQuery<PostListReq>,
+    list: ListQuery<post::Entity>,
+) -> Result<Json<Page<PostDto>>, ApiError> {
+    Ok(Json(
+        list_posts(
+            Some(user.0),
+            list,
+            req.cursor.as_deref(),
+            req.limit,
+            req.total.unwrap_or(false),
+            &conn,
+        )
+        .await?,
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/create",
+    tag = TAG,
+    request_body (
+        content = PostReq,
+        content_type = mime::APPLICATION_JSON.as_ref()
+    ),
+    responses (
+        (status = StatusCode::CREATED, body = PostDetailDto),
+        (status = StatusCode::FORBIDDEN, body = ApiError),
+        (status = StatusCode::CONFLICT, body = ApiError, description = "slug already exists"),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, body = ApiError)
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+async fn create_post(
+    State(conn): State<DatabaseConnection>,
+    Extension(user): Extension<CurrentUser>,
+    ValidatedJson(req): ValidatedJson<PostReq>,
+) -> Result<(StatusCode, Json<PostDetailDto>), ApiError> {
+    Ok((StatusCode::CREATED, Json(req.create(user.0, &conn).await?)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/{id}",
+    tag = TAG,
+    params (
+        ("id" = i32, Path, description = "post id")
+    ),
+    responses (
+        (status = StatusCode::OK, body = PostDetailDto),
+        (status = StatusCode::FORBIDDEN, body = ApiError, description = "not the author"),
+        (status = StatusCode::NOT_FOUND, body = ApiError)
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+// 초안 미리보기
+async fn get_post(
+    State(conn): State<DatabaseConnection>,
+    Extension(user): Extension<CurrentUser>,
+    Path(id): Path<i32>,
+) -> Result<Json<PostDetailDto>, ApiError> {
+    let is_admin = has_role(user.0, &[ROLE_ADMIN], &conn).await?;
+    Ok(Json(
+        PostDetailDto::get_own(id, user.0, is_admin, &conn).await?,
+    ))
+}
+
+#[utoipa::path(
+    put,
+    path = "/{id}",
+    tag = TAG,
+    params (
+        ("id" = i32, Path, description = "post id")
+    ),
+    request_body (
+        content = PostReq,
+        content_type = mime::APPLICATION_JSON.as_ref()
+    ),
+    responses (
+        (status = StatusCode::OK, body = PostDetailDto),
+        (status = StatusCode::FORBIDDEN, body = ApiError, description = "not the author"),
+        (status = StatusCode::NOT_FOUND, body = ApiError),
+        (status = StatusCode::CONFLICT, body = ApiError, description = "slug already exists"),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, body = ApiError)
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+// 관리자는 남의 글도 고칠 수 있음
+async fn update_post(
+    State(conn): State<DatabaseConnection>,
+    Extension(user): Extension<CurrentUser>,
+    Path(id): Path<i32>,
+    ValidatedJson(req): ValidatedJson<PostReq>,
+) -> Result<Json<PostDetailDto>, ApiError> {
+    let is_admin = has_role(user.0, &[ROLE_ADMIN], &conn).await?;
+    Ok(Json(req.update(id, user.0, is_admin, &conn).await?))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/{id}",
+    tag = TAG,
+    params (
+        ("id" = i32, Path, description = "post id")
+    ),
+    responses (
+        (status = StatusCode::NO_CONTENT),
+        (status = StatusCode::FORBIDDEN, body = ApiError, description = "not the author"),
+        (status = StatusCode::NOT_FOUND, body = ApiError)
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+async fn remove_post(
+    State(conn): State<DatabaseConnection>,
+    Extension(user): Extension<CurrentUser>,
+    Path(id): Path<i32>,
+) -> Result<StatusCode, ApiError> {
+    let is_admin = has_role(user.0, &[ROLE_ADMIN], &conn).await?;
+    delete_post(id, user.0, is_admin, &conn).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    servers(
+        (url = "/api/post", description = "Blog post API base path")
+    ),
+    modifiers(&SecurityAddon),
+    tags(
+        (name = TAG, description = "Blog post API, writing needs author role")
+    )
+)]
+pub(super) struct ApiDoc;
+
+// 게시된 글은 로그인 없이, 쓰고 고치는 것은 author나 admin만 가능함
+pub(super) fn init_route(aex: AppExtension) -> Router {
+    // layer는 나중에 붙인 것이 먼저 실행되므로 authenticate -> require_author 순서
+    let author_router = OpenApiRouter::new()
+        .routes(routes!(my_posts))
+        .routes(routes!(create_post))
+        .routes(routes!(get_post, update_post, remove_post))
+        .with_state(aex.db.0.clone())
+        .layer(middleware::from_fn_with_state(
+            aex.db.0.clone(),
+            require_author,
+        ))
+        .layer(middleware::from_fn_with_state(
+            aex.db.0.clone(),
+            authenticate,
+        ));
+
+    let unauth_router = OpenApiRouter::new()
+        .routes(routes!(published_posts))
+        .routes(routes!(get_published))
+        .with_state(aex.db.0);
+
+    let (author_router, author_api) = author_router.split_for_parts();
+    let (unauth_router, unauth_api) = unauth_router.split_for_parts();
+
+    let mut api = ApiDoc::openapi();
+    api.merge(author_api);
+    api.merge(unauth_api);
+
+    let unauth_router = unauth_router.merge(Scalar::with_url("/doc/scalar", api));
+
+    let router = author_router.merge(unauth_router);
+
+    Router::new().nest("/post", router)
+}
@@ -0,0 +1,675 @@
+use crate::resources::dto::admin::{ROLE_ADMIN, ROLE_EDITOR, has_role};
+use crate::resources::dto::fullstack_extension::AppExtension;
+use crate::resources::dto::page::Page;
+use crate::resources::dto::product::{
+    ProductDto, ProductPriceDto, ProductReq, delete_product, find_products, get_product_detail,
+    price_history,
+};
+use crate::resources::dto::product_image::{
+    IMAGE_RULES, ProductImageDto, ProductImageForm, ProductImageOrderReq, ProductImageUpdateReq,
+    ProductImageUpload, delete_image,
+};
+use crate::resources::dto::product_variant::{
+    OptionValueReq, ProductOptionDto, ProductOptionReq, ProductVariantDto, ProductVariantReq,
+    delete_option, delete_option_value, delete_variant,
+};
+use crate::resources::dto::query::ListQuery;
+use crate::resources::dto::search::{ProductSearchReq, ProductSearchRes};
+use crate::resources::dto::user::CurrentUser;
+use crate::resources::entities::product;
+use crate::router::api::auth::SecurityAddon;
+use crate::utils::jwt::authenticate;
+use crate::utils::storage::DynStorage;
+use axum::body::Body;
+use axum::http::Request;
+use axum::middleware::{self, Next};
+use axum::response::Response;
+use axum::{
+    Extension, Json, Router,
+    extract::{FromRef, Multipart, Path, State},
+};
+use reqwest::StatusCode;
+use sea_orm::DatabaseConnection;
+use serde::{Deserialize, Serialize};
+use utoipa::OpenApi;
+use utoipa_axum::{router::OpenApiRouter, routes};
+use utoipa_scalar::{Scalar, Servable};
+
+use crate::utils::errors::ApiError;
+use crate::utils::validate::{ValidatedJson, ValidatedQuery};
+use validate_dto_macro::validate_dto;
+
+const TAG: &str = "PRODUCT";
+
+// authenticate 뒤에 붙여서 editor나 admin인 유저만 통과시킴
+pub(super) async fn require_editor(
+    State(db): State<DatabaseConnection>,
+    Extension(user): Extension<CurrentUser>,
+    request: Request<Body>,
+    next: Next,
+) -> Result<Response, ApiError> {
+    if !has_role(user.0, &[ROLE_EDITOR, ROLE_ADMIN], &db).await? {
+        return Err(ApiError::new(
+            StatusCode::FORBIDDEN,
+            "forbidden",
+            "editor role is required",
+        ));
+    }
+    Ok(next.run(request).await)
+}
+
+// 검색 조건과 정렬은 filter, sort 파라미터로 받음 (resources::dto::query)
+#[validate_dto(params)]
+#[derive(utoipa::IntoParams, utoipa::ToSchema, Serialize, Deserialize)]
+pub struct ProductListReq {
+    // 기본 20, 최대 100
+    #[validate(range(min = 1, max = 100))]
+    limit: Option<u64>,
+    // 이전 응답의 next_cursor를 그대로 넣음
+    cursor: Option<String>,
+    // true일 때만 전체 개수를 계산함
+    total: Option<bool>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/list",
+    tag = TAG,
+    params (
+        ProductListReq,
+        ListQuery<product::Entity>
+    ),
+    responses (
+        (status = StatusCode::OK, body = Page<ProductDto>),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, body = ApiError)
+    )
+)]
+// 상품 목록은 로그인하지 않아도 볼 수 있음
+async fn list_products(
+    State(conn): State<DatabaseConnection>,
+    ValidatedQuery(req): ValidatedQuery<ProductListReq>,
+    list: ListQuery<product::Entity>,
+) -> Result<Json<Page<ProductDto>>, ApiError> {
+    Ok(Json(
+        find_products(
+            None,
+            list,
+            req.cursor.as_deref(),
+            req.limit,
+            req.total.unwrap_or(false),
+            &conn,
+        )
+        .await?,
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/search",
+    tag = TAG,
+    params (
+        ProductSearchReq
+    ),
+    responses (
+        (status = StatusCode::OK, body = ProductSearchRes),
+        (status = StatusCode::NOT_FOUND, body = ApiError, description = "category is not found"),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, body = ApiError)
+    )
+)]
+// 제목 검색, 순위순으로 내려주고 카테고리, 가격대별 개수를 같이 계산함
+async fn search_products(
+    State(conn): State<DatabaseConnection>,
+    ValidatedQuery(req): ValidatedQuery<ProductSearchReq>,
+) -> Result<Json<ProductSearchRes>, ApiError> {
+    Ok(Json(req.search(&conn).await?))
+}
+
+#[utoipa::path(
+    get,
+    path = "/{id}",
+    tag = TAG,
+    params (
+        ("id" = i32, Path, description = "product id")
+    ),
+    responses (
+        (status = StatusCode::OK, body = ProductDto),
+        (status = StatusCode::NOT_FOUND, body = ApiError)
+    )
+)]
+async fn get_product_by_id(
+    State(conn): State<DatabaseConnection>,
+    Path(id): Path<i32>,
+) -> Result<Json<ProductDto>, ApiError> {
+    Ok(Json(get_product_detail(id, &conn).await?))
+}
+
+#[utoipa::path(
+    get,
+    path = "/{id}/prices",
+    tag = TAG,
+    params (
+        ("id" = i32, Path, description = "product id")
+    ),
+    responses (
+        (status = StatusCode::OK, body = Vec<ProductPriceDto>),
+        (status = StatusCode::NOT_FOUND, body = ApiError)
+    )
+)]
+// 가격 변경 이력, 오래된 것부터
+async fn get_price_history(
+    State(conn): State<DatabaseConnection>,
+    Path(id): Path<i32>,
+) -> Result<Json<Vec<ProductPriceDto>>, ApiError> {
+    Ok(Json(price_history(id, &conn).await?))
+}
+
+#[utoipa::path(
+    post,
+    path = "/create",
+    tag = TAG,
+    request_body (
+        content = ProductReq,
+        content_type = mime::APPLICATION_JSON.as_ref()
+    ),
+    responses (
+        (status = StatusCode::CREATED, body = ProductDto),
+        (status = StatusCode::FORBIDDEN, body = ApiError),
+        (status = StatusCode::CONFLICT, body = ApiError, description = "category doesn't exist or sku is already used"),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, body = ApiError)
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+async fn create_product(
+    State(conn): State<DatabaseConnection>,
+    Extension(user): Extension<CurrentUser>,
+    ValidatedJson(req): ValidatedJson<ProductReq>,
+) -> Result<(StatusCode, Json<ProductDto>), ApiError> {
+    Ok((
+        StatusCode::CREATED,
+        Json(req.create(Some(user.0), &conn).await?),
+    ))
+}
+
+#[utoipa::path(
+    put,
+    path = "/{id}",
+    tag = TAG,
+    params (
+        ("id" = i32, Path, description = "product id")
+    ),
+    request_body (
+        content = ProductReq,
+        content_type = mime::APPLICATION_JSON.as_ref()
+    ),
+    responses (
+        (status = StatusCode::OK, body = ProductDto),
+        (status = StatusCode::FORBIDDEN, body = ApiError),
+        (status = StatusCode::NOT_FOUND, body = ApiError),
+        (status = StatusCode::CONFLICT, body = ApiError, description = "category doesn't exist or sku is already used"),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, body = ApiError)
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+// 가격이 바뀌면 가격 이력에 남음
+async fn update_product(
+    State(conn): State<DatabaseConnection>,
+    Extension(user): Extension<CurrentUser>,
+    Path(id): Path<i32>,
+    ValidatedJson(req): ValidatedJson<ProductReq>,
+) -> Result<Json<ProductDto>, ApiError> {
+    Ok(Json(req.update(id, Some(user.0), &conn).await?))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/{id}",
+    tag = TAG,
+    params (
+        ("id" = i32, Path, description = "product id")
+    ),
+    responses (
+        (status = StatusCode::NO_CONTENT),
+        (status = StatusCode::FORBIDDEN, body = ApiError),
+        (status = StatusCode::NOT_FOUND, body = ApiError)
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+async fn remove_product(
+    State(conn): State<DatabaseConnection>,
+    State(storage): State<DynStorage>,
+    Path(id): Path<i32>,
+) -> Result<StatusCode, ApiError> {
+    delete_product(id, &conn, storage.as_ref()).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    get,
+    path = "/{id}/images",
+    tag = TAG,
+    params (
+        ("id" = i32, Path, description = "product id")
+    ),
+    responses (
+        (status = StatusCode::OK, body = Vec<ProductImageDto>)
+    )
+)]
+// position 순서, 각 이미지는 크기별 webp, jpg 주소를 가짐
+async fn list_images(
+    State(conn): State<DatabaseConnection>,
+    State(storage): State<DynStorage>,
+    Path(id): Path<i32>,
+) -> Result<Json<Vec<ProductImageDto>>, ApiError> {
+    Ok(Json(
+        ProductImageDto::list(id, &conn, storage.as_ref()).await?,
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/{id}/images",
+    tag = TAG,
+    params (
+        ("id" = i32, Path, description = "product id")
+    ),
+    request_body (
+        content = ProductImageForm,
+        content_type = mime::MULTIPART_FORM_DATA.as_ref()
+    ),
+    responses (
+        (status = StatusCode::CREATED, body = ProductImageDto),
+        (status = StatusCode::FORBIDDEN, body = ApiError),
+        (status = StatusCode::NOT_FOUND, body = ApiError),
+        (status = StatusCode::PAYLOAD_TOO_LARGE, body = ApiError),
+        (status = StatusCode::UNSUPPORTED_MEDIA_TYPE, body = ApiError)
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+// png, jpeg, gif, webp를 받아서 크기별 webp, jpg로 저장, 첫 이미지는 대표 이미지가 됨
+async fn upload_image(
+    State(conn): State<DatabaseConnection>,
+    State(storage): State<DynStorage>,
+    Path(id): Path<i32>,
+    mut multipart: Multipart,
+) -> Result<(StatusCode, Json<ProductImageDto>), ApiError> {
+    let upload = ProductImageUpload::from_multipart(&mut multipart).await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(upload.save(id, &conn, storage.as_ref()).await?),
+    ))
+}
+
+#[utoipa::path(
+    put,
+    path = "/{id}/images/order",
+    tag = TAG,
+    params (
+        ("id" = i32, Path, description = "product id")
+    ),
+    request_body (
+        content = ProductImageOrderReq,
+        content_type = mime::APPLICATION_JSON.as_ref()
+    ),
+    responses (
+        (status = StatusCode::OK, body = Vec<ProductImageDto>),
+        (status = StatusCode::FORBIDDEN, body = ApiError),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, body = ApiError)
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+async fn reorder_images(
+    State(conn): State<DatabaseConnection>,
+    State(storage): State<DynStorage>,
+    Path(id): Path<i32>,
+    Json(req): Json<ProductImageOrderReq>,
+) -> Result<Json<Vec<ProductImageDto>>, ApiError> {
+    Ok(Json(req.reorder(id, &conn, storage.as_ref()).await?))
+}
+
+#[utoipa::path(
+    put,
+    path = "/{id}/images/{image_id}",
+    tag = TAG,
+    params (
+        ("id" = i32, Path, description = "product id"),
+        ("image_id" = i32, Path, description = "image id")
+    ),
+    request_body (
+        content = ProductImageUpdateReq,
+        content_type = mime::APPLICATION_JSON.as_ref()
+    ),
+    responses (
+        (status = StatusCode::OK, body = ProductImageDto),
+        (status = StatusCode::FORBIDDEN, body = ApiError),
+        (status = StatusCode::NOT_FOUND, body = ApiError),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, body = ApiError)
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+async fn update_image(
+    State(conn): State<DatabaseConnection>,
+    State(storage): State<DynStorage>,
+    Path((id, image_id)): Path<(i32, i32)>,
+    ValidatedJson(req): ValidatedJson<ProductImageUpdateReq>,
+) -> Result<Json<ProductImageDto>, ApiError> {
+    Ok(Json(
+        req.update(id, image_id, &conn, storage.as_ref()).await?,
+    ))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/{id}/images/{image_id}",
+    tag = TAG,
+    params (
+        ("id" = i32, Path, description = "product id"),
+        ("image_id" = i32, Path, description = "image id")
+    ),
+    responses (
+        (status = StatusCode::NO_CONTENT),
+        (status = StatusCode::FORBIDDEN, body = ApiError),
+        (status = StatusCode::NOT_FOUND, body = ApiError)
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+async fn remove_image(
+    State(conn): State<DatabaseConnection>,
+    State(storage): State<DynStorage>,
+    Path((id, image_id)): Path<(i32, i32)>,
+) -> Result<StatusCode, ApiError> {
+    delete_image(id, image_id, &conn, storage.as_ref()).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    post,
+    path = "/{id}/options",
+    tag = TAG,
+    params (
+        ("id" = i32, Path, description = "product id")
+    ),
+    request_body (
+        content = ProductOptionReq,
+        content_type = mime::APPLICATION_JSON.as_ref()
+    ),
+    responses (
+        (status = StatusCode::CREATED, body = ProductOptionDto),
+        (status = StatusCode::FORBIDDEN, body = ApiError),
+        (status = StatusCode::NOT_FOUND, body = ApiError),
+        (status = StatusCode::CONFLICT, body = ApiError, description = "option name is used, product has variants or too many options"),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, body = ApiError)
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+// 옵션은 변형을 만들기 전에 정해야함
+async fn create_option(
+    State(conn): State<DatabaseConnection>,
+    Path(id): Path<i32>,
+    ValidatedJson(req): ValidatedJson<ProductOptionReq>,
+) -> Result<(StatusCode, Json<ProductOptionDto>), ApiError> {
+    Ok((StatusCode::CREATED, Json(req.create(id, &conn).await?)))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/{id}/options/{option_id}",
+    tag = TAG,
+    params (
+        ("id" = i32, Path, description = "product id"),
+        ("option_id" = i32, Path, description = "option id")
+    ),
+    responses (
+        (status = StatusCode::NO_CONTENT),
+        (status = StatusCode::FORBIDDEN, body = ApiError),
+        (status = StatusCode::NOT_FOUND, body = ApiError),
+        (status = StatusCode::CONFLICT, body = ApiError, description = "option is used by variants")
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+async fn remove_option(
+    State(conn): State<DatabaseConnection>,
+    Path((id, option_id)): Path<(i32, i32)>,
+) -> Result<StatusCode, ApiError> {
+    delete_option(id, option_id, &conn).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    post,
+    path = "/{id}/options/{option_id}/values",
+    tag = TAG,
+    params (
+        ("id" = i32, Path, description = "product id"),
+        ("option_id" = i32, Path, description = "option id")
+    ),
+    request_body (
+        content = OptionValueReq,
+        content_type = mime::APPLICATION_JSON.as_ref()
+    ),
+    responses (
+        (status = StatusCode::CREATED, body = ProductOptionDto),
+        (status = StatusCode::FORBIDDEN, body = ApiError),
+        (status = StatusCode::NOT_FOUND, body = ApiError),
+        (status = StatusCode::CONFLICT, body = ApiError, description = "value is used or too many values"),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, body = ApiError)
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+async fn add_option_value(
+    State(conn): State<DatabaseConnection>,
+    Path((id, option_id)): Path<(i32, i32)>,
+    ValidatedJson(req): ValidatedJson<OptionValueReq>,
+) -> Result<(StatusCode, Json<ProductOptionDto>), ApiError> {
+    Ok((
+        StatusCode::CREATED,
+        Json(req.add(id, option_id, &conn).await?),
+    ))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/{id}/options/{option_id}/values/{value_id}",
+    tag = TAG,
+    params (
+        ("id" = i32, Path, description = "product id"),
+        ("option_id" = i32, Path, description = "option id"),
+        ("value_id" = i32, Path, description = "option value id")
+    ),
+    responses (
+        (status = StatusCode::NO_CONTENT),
+        (status = StatusCode::FORBIDDEN, body = ApiError),
+        (status = StatusCode::NOT_FOUND, body = ApiError),
+        (status = StatusCode::CONFLICT, body = ApiError, description = "value is used by variants")
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+async fn remove_option_value(
+    State(conn): State<DatabaseConnection>,
+    Path((id, option_id, value_id)): Path<(i32, i32, i32)>,
+) -> Result<StatusCode, ApiError> {
+    delete_option_value(id, option_id, value_id, &conn).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    post,
+    path = "/{id}/variants",
+    tag = TAG,
+    params (
+        ("id" = i32, Path, description = "product id")
+    ),
+    request_body (
+        content = ProductVariantReq,
+        content_type = mime::APPLICATION_JSON.as_ref()
+    ),
+    responses (
+        (status = StatusCode::CREATED, body = ProductVariantDto),
+        (status = StatusCode::FORBIDDEN, body = ApiError),
+        (status = StatusCode::NOT_FOUND, body = ApiError),
+        (status = StatusCode::CONFLICT, body = ApiError, description = "sku or option combination is used, or price currency differs from the product"),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, body = ApiError)
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+// 모든 옵션에 값을 하나씩 골라야함, price를 주면 상품 가격 대신 씀
+async fn create_variant(
+    State(conn): State<DatabaseConnection>,
+    Path(id): Path<i32>,
+    ValidatedJson(req): ValidatedJson<ProductVariantReq>,
+) -> Result<(StatusCode, Json<ProductVariantDto>), ApiError> {
+    Ok((StatusCode::CREATED, Json(req.create(id, &conn).await?)))
+}
+
+#[utoipa::path(
+    put,
+    path = "/{id}/variants/{variant_id}",
+    tag = TAG,
+    params (
+        ("id" = i32, Path, description = "product id"),
+        ("variant_id" = i32, Path, description = "variant id")
+    ),
+    request_body (
+        content = ProductVariantReq,
+        content_type = mime::APPLICATION_JSON.as_ref()
+    ),
+    responses (
+        (status = StatusCode::OK, body = ProductVariantDto),
+        (status = StatusCode::FORBIDDEN, body = ApiError),
+        (status = StatusCode::NOT_FOUND, body = ApiError),
+        (status = StatusCode::CONFLICT, body = ApiError, description = "sku or option combination is used, or price currency differs from the product"),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, body = ApiError)
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+async fn update_variant(
+    State(conn): State<DatabaseConnection>,
+    Path((id, variant_id)): Path<(i32, i32)>,
+    ValidatedJson(req): ValidatedJson<ProductVariantReq>,
+) -> Result<Json<ProductVariantDto>, ApiError> {
+    Ok(Json(req.update(id, variant_id, &conn).await?))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/{id}/variants/{variant_id}",
+    tag = TAG,
+    params (
+        ("id" = i32, Path, description = "product id"),
+        ("variant_id" = i32, Path, description = "variant id")
+    ),
+    responses (
+        (status = StatusCode::NO_CONTENT),
+        (status = StatusCode::FORBIDDEN, body = ApiError),
+        (status = StatusCode::NOT_FOUND, body = ApiError)
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+async fn remove_variant(
+    State(conn): State<DatabaseConnection>,
+    Path((id, variant_id)): Path<(i32, i32)>,
+) -> Result<StatusCode, ApiError> {
+    delete_variant(id, variant_id, &conn).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(FromRef, Clone)]
+struct ProductState {
+    db: DatabaseConnection,
+    storage: DynStorage,
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    servers(
+        (url = "/api/product", description = "Product API base path")
+    ),
+    modifiers(&SecurityAddon),
+    tags(
+        (name = TAG, description = "Product catalog API")
+    )
+)]
+pub(super) struct ApiDoc;
+
+// 조회는 로그인 없이, 상품과 이미지, 옵션, 변형의 등록, 수정, 삭제는 editor나 admin만 가능함
+pub(super) fn init_route(aex: AppExtension) -> Router {
+    let state = ProductState {
+        db: aex.db.0.clone(),
+        storage: aex.storage.0.clone(),
+    };
+
+    let upload_router = OpenApiRouter::new()
+        .routes(routes!(upload_image))
+        .layer(IMAGE_RULES.body_limit());
+
+    // layer는 나중에 붙인 것이 먼저 실행되므로 authenticate -> require_editor 순서
+    let editor_router = OpenApiRouter::new()
+        .routes(routes!(create_product))
+        .routes(routes!(update_product, remove_product))
+        .routes(routes!(reorder_images))
+        .routes(routes!(update_image, remove_image))
+        .routes(routes!(create_option))
+        .routes(routes!(remove_option))
+        .routes(routes!(add_option_value))
+        .routes(routes!(remove_option_value))
+        .routes(routes!(create_variant))
+        .routes(routes!(update_variant, remove_variant))
+        .merge(upload_router)
+        .with_state(state.clone())
+        .layer(middleware::from_fn_with_state(
+            aex.db.0.clone(),
+            require_editor,
+        ))
+        .layer(middleware::from_fn_with_state(
+            aex.db.0.clone(),
+            authenticate,
+        ));
+
+    let unauth_router = OpenApiRouter::new()
+        .routes(routes!(list_products))
+        .routes(routes!(search_products))
+        .routes(routes!(get_product_by_id))
+        .routes(routes!(get_price_history))
+        .routes(routes!(list_images))
+        .with_state(state);
+
+    let (editor_router, editor_api) = editor_router.split_for_parts();
+    let (unauth_router, unauth_api) = unauth_router.split_for_parts();
+
+    let mut api = ApiDoc::openapi();
+    api.merge(editor_api);
+    api.merge(unauth_api);
+
+    let unauth_router = unauth_router.merge(Scalar::with_url("/doc/scalar", api));
+
+    let router = editor_router.merge(unauth_router);
+
+    Router::new().nest("/product", router)
+}
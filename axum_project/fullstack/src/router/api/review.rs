@@ -0,0 +1,249 @@
+use crate::resources::dto::fullstack_extension::AppExtension;
+use crate::resources::dto::page::Page;
+use crate::resources::dto::query::ListQuery;
+use crate::resources::dto::review::{
+    RatingSummaryDto, ReviewDto, ReviewReq, delete_review, list_reviews, my_review, rating_summary,
+};
+use crate::resources::dto::user::CurrentUser;
+use crate::resources::entities::review;
+use crate::router::api::auth::SecurityAddon;
+use crate::utils::jwt::authenticate;
+use axum::middleware;
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, State},
+};
+use reqwest::StatusCode;
+use sea_orm::DatabaseConnection;
+use serde::{Deserialize, Serialize};
+use utoipa::OpenApi;
+use utoipa_axum::{router::OpenApiRouter, routes};
+use utoipa_scalar::{Scalar, Servable};
+
+use crate::utils::errors::ApiError;
+use crate::utils::validate::{ValidatedJson, ValidatedQuery};
+use validate_dto_macro::validate_dto;
+
+const TAG: &str = "REVIEW";
+
+#[validate_dto(params)]
+#[derive(utoipa::IntoParams, utoipa::ToSchema, Serialize, Deserialize)]
+pub struct ReviewListReq {
+    // 기본 20, 최대 100
+    #[validate(range(min = 1, max = 100))]
+    limit: Option<u64>,
+    // 이전 응답의 next_cursor를 그대로 넣음
+    cursor: Option<String>,
+    // true일 때만 전체 개수를 계산함
+    total: Option<bool>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/products/{product_id}",
+    tag = TAG,
+    params (
+        ("product_id" = i32, Path, description = "product id"),
+        ReviewListReq,
+        ListQuery<review::Entity>
+    ),
+    responses (
+        (status = StatusCode::OK, body = Page<ReviewDto>),
+        (status = StatusCode::NOT_FOUND, body = ApiError),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, body = ApiError)
+    )
+)]
+// 리뷰 목록은 로그인하지 않아도 볼 수 있음
+async fn product_reviews(
+    State(conn): State<DatabaseConnection>,
+    Path(product_id): Path<i32>,
+    ValidatedQuery(req): ValidatedQuery<ReviewListReq>,
+    list: ListQuery<review::Entity>,
+) -> Result<Json<Page<ReviewDto>>, ApiError> {
+    Ok(Json(
+        list_reviews(
+            product_id,
+            list,
+            req.cursor.as_deref(),
+            req.limit,
+            req.total.unwrap_or(false),
+            &conn,
+        )
+        .await?,
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/products/{product_id}/summary",
+    tag = TAG,
+    params (
+        ("product_id" = i32, Path, description = "product id")
+    ),
+    responses (
+        (status = StatusCode::OK, body = RatingSummaryDto),
+        (status = StatusCode::NOT_FOUND, body = ApiError)
+    )
+)]
+// 평균 별점과 별점별 리뷰 수
+async fn product_summary(
+    State(conn): State<DatabaseConnection>,
+    Path(product_id): Path<i32>,
+) -> Result<Json<RatingSummaryDto>, ApiError> {
+    Ok(Json(rating_summary(product_id, &conn).await?))
+}
+
+#[utoipa::path(
+    post,
+    path = "/products/{product_id}",
+    tag = TAG,
+    params (
+        ("product_id" = i32, Path, description = "product id")
+    ),
+    request_body (
+        content = ReviewReq,
+        content_type = mime::APPLICATION_JSON.as_ref()
+    ),
+    responses (
+        (status = StatusCode::CREATED, body = ReviewDto),
+        (status = StatusCode::NOT_FOUND, body = ApiError),
+        (status = StatusCode::CONFLICT, body = ApiError, description = "already reviewed"),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, body = ApiError)
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+// 상품마다 한 번만 쓸 수 있음, 바꾸려면 수정해야함
+async fn create_review(
+    State(conn): State<DatabaseConnection>,
+    Extension(user): Extension<CurrentUser>,
+    Path(product_id): Path<i32>,
+    ValidatedJson(req): ValidatedJson<ReviewReq>,
+) -> Result<(StatusCode, Json<ReviewDto>), ApiError> {
+    Ok((
+        StatusCode::CREATED,
+        Json(req.create(product_id, user.0, &conn).await?),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/products/{product_id}/mine",
+    tag = TAG,
+    params (
+        ("product_id" = i32, Path, description = "product id")
+    ),
+    responses (
+        (status = StatusCode::OK, body = Option<ReviewDto>)
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+// 내가 이 상품에 쓴 리뷰, 없으면 null
+async fn mine(
+    State(conn): State<DatabaseConnection>,
+    Extension(user): Extension<CurrentUser>,
+    Path(product_id): Path<i32>,
+) -> Result<Json<Option<ReviewDto>>, ApiError> {
+    Ok(Json(my_review(product_id, user.0, &conn).await?))
+}
+
+#[utoipa::path(
+    put,
+    path = "/{id}",
+    tag = TAG,
+    params (
+        ("id" = i32, Path, description = "review id")
+    ),
+    request_body (
+        content = ReviewReq,
+        content_type = mime::APPLICATION_JSON.as_ref()
+    ),
+    responses (
+        (status = StatusCode::OK, body = ReviewDto),
+        (status = StatusCode::FORBIDDEN, body = ApiError, description = "not the author"),
+        (status = StatusCode::NOT_FOUND, body = ApiError),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, body = ApiError)
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+async fn update_review(
+    State(conn): State<DatabaseConnection>,
+    Extension(user): Extension<CurrentUser>,
+    Path(id): Path<i32>,
+    ValidatedJson(req): ValidatedJson<ReviewReq>,
+) -> Result<Json<ReviewDto>, ApiError> {
+    Ok(Json(req.update(id, user.0, &conn).await?))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/{id}",
+    tag = TAG,
+    params (
+        ("id" = i32, Path, description = "review id")
+    ),
+    responses (
+        (status = StatusCode::NO_CONTENT),
+        (status = StatusCode::FORBIDDEN, body = ApiError, description = "not the author"),
+        (status = StatusCode::NOT_FOUND, body = ApiError)
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+async fn remove_review(
+    State(conn): State<DatabaseConnection>,
+    Extension(user): Extension<CurrentUser>,
+    Path(id): Path<i32>,
+) -> Result<StatusCode, ApiError> {
+    delete_review(id, user.0, &conn).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    servers(
+        (url = "/api/review", description = "Review API base path")
+    ),
+    modifiers(&SecurityAddon),
+    tags(
+        (name = TAG, description = "Product review API")
+    )
+)]
+pub(super) struct ApiDoc;
+
+// 조회는 로그인 없이, 작성은 로그인이 필요하고 수정, 삭제는 작성자만 가능함
+pub(super) fn init_route(aex: AppExtension) -> Router {
+    let auth_router = OpenApiRouter::new()
+        .routes(routes!(create_review))
+        .routes(routes!(mine))
+        .routes(routes!(update_review, remove_review))
+        .with_state(aex.db.0.clone())
+        .layer(middleware::from_fn_with_state(
+            aex.db.0.clone(),
+            authenticate,
+        ));
+
+    let unauth_router = OpenApiRouter::new()
+        .routes(routes!(product_reviews))
+        .routes(routes!(product_summary))
+        .with_state(aex.db.0);
+
+    let (auth_router, auth_api) = auth_router.split_for_parts();
+    let (unauth_router, unauth_api) = unauth_router.split_for_parts();
+
+    let mut api = ApiDoc::openapi();
+    api.merge(auth_api);
+    api.merge(unauth_api);
+
+    let unauth_router = unauth_router.merge(Scalar::with_url("/doc/scalar", api));
+
+    let router = auth_router.merge(unauth_router);
+
+    Router::new().nest("/review", router)
+}
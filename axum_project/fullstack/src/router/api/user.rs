@@ -1,64 +1,72 @@
+use crate::resources::dto::account::{AccountExport, ExportFormat, ExportReq, soft_delete};
 use crate::resources::dto::fullstack_extension::AppExtension;
-use crate::resources::dto::user::{CurrentUser, UserDto};
+use crate::resources::dto::page::Page;
+use crate::resources::dto::profile::{
+    AVATAR_RULES, AvatarForm, AvatarUpload, ProfileDto, ProfileUpdateReq,
+};
+use crate::resources::dto::query::ListQuery;
+use crate::resources::dto::user::{CurrentUser, UserDto, UserPatch};
+use crate::resources::entities::users;
 use crate::utils::jwt::authenticate;
+use crate::utils::storage::DynStorage;
+use axum::middleware;
+use axum::response::{IntoResponse, Response};
 use axum::{
     Extension, Json, Router,
-    extract::{Query, State},
+    extract::{FromRef, Multipart, Path, Query, State},
 };
-use axum::{Form, middleware};
 use reqwest::StatusCode;
+use reqwest::header::{CACHE_CONTROL, CONTENT_DISPOSITION, CONTENT_TYPE};
 use sea_orm::DatabaseConnection;
 use serde::{Deserialize, Serialize};
 use utoipa::OpenApi;
 use utoipa_axum::{router::OpenApiRouter, routes};
 use utoipa_scalar::{Scalar, Servable};
 
-use crate::{router::api::auth::SecurityAddon, utils::errors::AppError};
+use crate::utils::validate::{ValidatedJson, ValidatedQuery};
+use crate::{
+    router::api::auth::SecurityAddon,
+    utils::errors::{ApiError, AppError},
+};
+use axum_extra::TypedHeader;
+use axum_extra::headers::{ETag, IfMatch};
+use validate_dto_macro::validate_dto;
 
 const TAG: &str = "USER";
 
+// 검색 조건과 정렬은 filter, sort 파라미터로 받음 (resources::dto::query)
+#[validate_dto(params)]
 #[cfg_attr(feature = "server", derive(utoipa::IntoParams, utoipa::ToSchema))]
 #[derive(Serialize, Deserialize)]
 pub struct UserGetReq {
-    id: Option<i32>,
-    username: Option<String>,
-    limit: u64,
-    page: u64,
+    // 기본 20, 최대 100
+    #[validate(range(min = 1, max = 100))]
+    limit: Option<u64>,
+    // 이전 응답의 next_cursor를 그대로 넣음
+    cursor: Option<String>,
+    // true일 때만 전체 개수를 계산함
+    total: Option<bool>,
 }
 impl UserGetReq {
     #[cfg(feature = "server")]
-    pub async fn get_users(&self, conn: &DatabaseConnection) -> Result<Vec<UserDto>, AppError> {
-        use sea_orm::{EntityTrait, PaginatorTrait, QueryFilter};
-
-        use crate::resources::entities::users;
-
-        if let Some(id) = self.id {
-            return Ok(users::Entity::find_by_id(id)
-                .all(conn)
-                .await?
-                .into_iter()
-                .map(|v| v.into())
-                .collect());
-        } else if let Some(username) = &self.username {
-            use sea_orm::{ColumnTrait, PaginatorTrait};
-
-            return Ok(users::Entity::find()
-                .filter(users::Column::Username.like(format!("%{}%", username)))
-                .paginate(conn, self.limit)
-                .fetch_page(self.page)
-                .await?
-                .into_iter()
-                .map(|v| v.into())
-                .collect());
-        }
+    pub async fn get_users(
+        &self,
+        list: &ListQuery<users::Entity>,
+        conn: &DatabaseConnection,
+    ) -> Result<Page<UserDto>, ApiError> {
+        use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+
+        // 탈퇴한 유저는 목록에서 제외
+        let select = users::Entity::find().filter(users::Column::DeletedAt.is_null());
 
-        Ok(users::Entity::find()
-            .paginate(conn, self.limit)
-            .fetch_page(self.page)
-            .await?
-            .into_iter()
-            .map(|v| v.into())
-            .collect())
+        list.page(
+            select,
+            self.cursor.as_deref(),
+            self.limit,
+            self.total.unwrap_or(false),
+            conn,
+        )
+        .await
     }
 }
 #[utoipa::path(
@@ -66,13 +74,15 @@ impl UserGetReq {
     path = "/get",
     tag = TAG,
     params (
-        UserGetReq
+        UserGetReq,
+        ListQuery<users::Entity>
     ),
     responses (
         (
             status = StatusCode::OK,
-            body = Vec<UserGetReq>,
-        )
+            body = Page<UserDto>,
+        ),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, body = ApiError)
     ),
     security(
         ("api_jwt_token" = [])
@@ -80,15 +90,19 @@ impl UserGetReq {
 )]
 async fn find_users(
     State(conn): State<DatabaseConnection>,
-    Query(user): Query<UserGetReq>,
-) -> Result<Json<Vec<UserDto>>, AppError> {
-    Ok(Json(user.get_users(&conn).await?))
+    ValidatedQuery(user): ValidatedQuery<UserGetReq>,
+    list: ListQuery<users::Entity>,
+) -> Result<Json<Page<UserDto>>, ApiError> {
+    Ok(Json(user.get_users(&list, &conn).await?))
 }
 
 #[utoipa::path(
     put,
     path = "/put",
     tag = TAG,
+    params (
+        ("If-Match" = String, Header, description = "ETag from GET /profile/{id}")
+    ),
     request_body (
         content = UserDto,
         content_type = mime::APPLICATION_JSON.as_ref()
@@ -97,23 +111,33 @@ async fn find_users(
         (
             status = StatusCode::OK,
             body = UserDto,
-        )
+            headers(
+                ("ETag" = String)
+            )
+        ),
+        (status = StatusCode::UNPROCESSABLE_ENTITY, body = ApiError),
+        (status = StatusCode::PRECONDITION_FAILED, body = ApiError),
+        (status = StatusCode::PRECONDITION_REQUIRED, body = ApiError)
     ),
     security(
         ("api_jwt_token" = [])
     )
 )]
+// 부분 수정은 PATCH /patch 를 사용
 async fn put_user(
     State(conn): State<DatabaseConnection>,
     Extension(id): Extension<CurrentUser>,
-    Form(user): Form<UserDto>,
-) -> Result<Json<UserDto>, AppError> {
+    if_match: Option<TypedHeader<IfMatch>>,
+    ValidatedJson(user): ValidatedJson<UserDto>,
+) -> Result<(TypedHeader<ETag>, Json<UserDto>), ApiError> {
     // 본인만 변경 가능함
-    Ok(if &id == &user.id {
-        Ok(Json(user.update_user(&conn).await?))
-    } else {
-        Err(AppError::auth_error())
-    }?)
+    if id != user.id {
+        return Err(AppError::auth_error().into());
+    }
+    let TypedHeader(if_match) = if_match.ok_or(ApiError::precondition_required())?;
+    let (user, etag) = user.replace(&if_match, &conn).await?;
+
+    Ok((TypedHeader(etag), Json(user)))
 }
 
 #[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
@@ -123,14 +147,9 @@ pub struct UserDeleteReq {
 }
 #[cfg(feature = "server")]
 impl UserDeleteReq {
+    // 바로 지우지 않고 탈퇴 표시만 함, 복구 기간이 지나면 정리 작업이 지움
     pub async fn delete_user(&self, conn: &DatabaseConnection) -> Result<StatusCode, AppError> {
-        use sea_orm::EntityTrait;
-
-        use crate::resources::entities::users;
-
-        let res = users::Entity::delete_by_id(self.id).exec(conn).await?;
-
-        if res.rows_affected == 0 {
+        if !soft_delete(self.id, conn).await? {
             Ok(StatusCode::NOT_FOUND)
         } else {
             Ok(StatusCode::OK)
@@ -167,6 +186,194 @@ async fn delete_user(
     }?)
 }
 
+#[utoipa::path(
+    get,
+    path = "/profile/{id}",
+    tag = TAG,
+    params (
+        ("id" = i32, Path, description = "user id")
+    ),
+    responses (
+        (
+            status = StatusCode::OK,
+            body = ProfileDto,
+            headers(
+                ("ETag" = String, description = "PUT /put, PATCH /patch 의 If-Match에 사용")
+            )
+        )
+    )
+)]
+// 공개 프로필은 로그인하지 않아도 볼 수 있음
+async fn get_profile(
+    State(conn): State<DatabaseConnection>,
+    State(storage): State<DynStorage>,
+    Path(id): Path<i32>,
+) -> Result<(TypedHeader<ETag>, Json<ProfileDto>), AppError> {
+    let (profile, etag) = ProfileDto::get_with_etag(id, &conn, storage.as_ref()).await?;
+    Ok((TypedHeader(etag), Json(profile)))
+}
+
+#[utoipa::path(
+    put,
+    path = "/profile",
+    tag = TAG,
+    request_body (
+        content = ProfileUpdateReq,
+        content_type = mime::APPLICATION_JSON.as_ref()
+    ),
+    responses (
+        (
+            status = StatusCode::OK,
+            body = ProfileDto,
+        )
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+async fn put_profile(
+    State(conn): State<DatabaseConnection>,
+    State(storage): State<DynStorage>,
+    Extension(id): Extension<CurrentUser>,
+    ValidatedJson(req): ValidatedJson<ProfileUpdateReq>,
+) -> Result<Json<ProfileDto>, AppError> {
+    Ok(Json(req.update(id.0, &conn, storage.as_ref()).await?))
+}
+
+#[utoipa::path(
+    patch,
+    path = "/patch",
+    tag = TAG,
+    params (
+        ("If-Match" = String, Header, description = "ETag from GET /profile/{id}")
+    ),
+    request_body (
+        content = UserPatch,
+        content_type = "application/merge-patch+json"
+    ),
+    responses (
+        (
+            status = StatusCode::OK,
+            body = ProfileDto,
+            headers(
+                ("ETag" = String)
+            )
+        ),
+        (status = StatusCode::PRECONDITION_FAILED, body = ApiError),
+        (status = StatusCode::PRECONDITION_REQUIRED, body = ApiError)
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+// 로그인한 유저의 수정 가능한 필드만 부분 수정
+async fn patch_user(
+    State(conn): State<DatabaseConnection>,
+    State(storage): State<DynStorage>,
+    Extension(id): Extension<CurrentUser>,
+    if_match: Option<TypedHeader<IfMatch>>,
+    ValidatedJson(patch): ValidatedJson<UserPatch>,
+) -> Result<(TypedHeader<ETag>, Json<ProfileDto>), ApiError> {
+    let TypedHeader(if_match) = if_match.ok_or(ApiError::precondition_required())?;
+    let (profile, etag) = patch
+        .apply(id.0, &if_match, &conn, storage.as_ref())
+        .await?;
+
+    Ok((TypedHeader(etag), Json(profile)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/avatar",
+    tag = TAG,
+    request_body (
+        content = AvatarForm,
+        content_type = mime::MULTIPART_FORM_DATA.as_ref()
+    ),
+    responses (
+        (
+            status = StatusCode::OK,
+            body = ProfileDto,
+        )
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+// png, jpeg, gif, webp를 받아서 정사각형 썸네일로 저장
+async fn upload_avatar(
+    State(conn): State<DatabaseConnection>,
+    State(storage): State<DynStorage>,
+    Extension(id): Extension<CurrentUser>,
+    mut multipart: Multipart,
+) -> Result<Json<ProfileDto>, AppError> {
+    let upload = AvatarUpload::from_multipart(&mut multipart).await?;
+    Ok(Json(upload.save(id.0, &conn, storage.as_ref()).await?))
+}
+
+#[utoipa::path(
+    get,
+    path = "/export",
+    tag = TAG,
+    params (
+        ExportReq
+    ),
+    responses (
+        (
+            status = StatusCode::OK,
+            description = "format=json 이면 JSON, format=zip 이면 account.json과 아바타 파일을 묶은 ZIP",
+            content(
+                (AccountExport = "application/json"),
+                (Vec<u8> = "application/zip")
+            )
+        )
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+// 내 데이터 내려받기, 프로필, 연결된 소셜 계정, 로그인 세션, 주문, 결제, 장바구니, 쿠폰 사용 내역, 리뷰, 글, 댓글
+async fn export_user(
+    State(conn): State<DatabaseConnection>,
+    State(storage): State<DynStorage>,
+    Extension(id): Extension<CurrentUser>,
+    Query(req): Query<ExportReq>,
+) -> Result<Response, ApiError> {
+    let (export, avatar_key) = AccountExport::collect(id.0, &conn, storage.as_ref()).await?;
+
+    let (content_type, ext, body) = match req.format {
+        ExportFormat::Json => (
+            mime::APPLICATION_JSON.as_ref(),
+            "json",
+            serde_json::to_vec_pretty(&export).map_err(AppError::any_t_error)?,
+        ),
+        ExportFormat::Zip => (
+            "application/zip",
+            "zip",
+            export
+                .to_zip(avatar_key.as_deref(), storage.as_ref())
+                .await?,
+        ),
+    };
+    let disposition = format!("attachment; filename=\"account-{}.{}\"", id.0, ext);
+
+    Ok((
+        [
+            (CONTENT_TYPE, content_type.to_string()),
+            (CONTENT_DISPOSITION, disposition),
+            (CACHE_CONTROL, "no-store".to_string()),
+        ],
+        body,
+    )
+        .into_response())
+}
+
+#[derive(FromRef, Clone)]
+struct UserState {
+    db: DatabaseConnection,
+    storage: DynStorage,
+}
+
 #[derive(OpenApi)]
 #[openapi(
     servers(
@@ -182,16 +389,34 @@ pub(super) struct ApiDoc;
 // 인증이 필요한 라우터 모음
 // 인증이 필요 없는 라우터모음으로 나눔
 pub(super) fn init_route(aex: AppExtension) -> Router {
+    let state = UserState {
+        db: aex.db.0.clone(),
+        storage: aex.storage.0.clone(),
+    };
+
+    let avatar_router = OpenApiRouter::new()
+        .routes(routes!(upload_avatar))
+        .layer(AVATAR_RULES.body_limit());
+
     let auth_router = OpenApiRouter::new()
         .routes(routes!(find_users))
         .routes(routes!(put_user))
         .routes(routes!(delete_user))
-        .with_state(aex.db.0.clone())
+        .routes(routes!(put_profile))
+        .routes(routes!(patch_user))
+        .routes(routes!(export_user))
+        .merge(avatar_router)
+        .with_state(state.clone())
         // 인증 미들웨어 삽입
-        .layer(middleware::from_fn(authenticate));
+        .layer(middleware::from_fn_with_state(
+            aex.db.0.clone(),
+            authenticate,
+        ));
 
     // 회원가입은 로그인하지 않아도 할 수 있어야함
-    let unauth_router = OpenApiRouter::new().with_state(aex.db.0);
+    let unauth_router = OpenApiRouter::new()
+        .routes(routes!(get_profile))
+        .with_state(state);
 
     // 각각 문서화
     let (auth_router, auth_api) = auth_router.split_for_parts();
@@ -1,2 +1,4 @@
 pub mod api;
 pub mod hello;
+pub mod oauth;
+pub mod scim;
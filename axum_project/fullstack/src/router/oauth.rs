@@ -0,0 +1,201 @@
+use axum::body::Body;
+use axum::extract::{Query, State};
+use axum::http::{HeaderValue, Response};
+use axum::{Form, Json, Router};
+use axum_extra::TypedHeader;
+use axum_extra::headers::{Authorization, Cookie, authorization::Basic, authorization::Bearer};
+use jsonwebtoken::jwk::JwkSet;
+use reqwest::StatusCode;
+use reqwest::header::{CACHE_CONTROL, LOCATION, PRAGMA};
+use sea_orm::DatabaseConnection;
+use utoipa::OpenApi;
+use utoipa_axum::router::OpenApiRouter;
+use utoipa_axum::routes;
+use utoipa_scalar::{Scalar, Servable};
+
+use crate::resources::dto::fullstack_extension::AppExtension;
+use crate::resources::dto::oauth::{
+    AuthorizeReq, ConsentDecision, DiscoveryRes, GrantReq, GrantRes, UserInfoRes,
+};
+use crate::resources::dto::token::ClientCredentials;
+use crate::router::api::auth::{ClientSecurityAddon, SecurityAddon};
+use crate::utils::errors::{AppError, OAuthError};
+use crate::utils::jwt::current_user;
+use crate::utils::oidc;
+
+fn see_other(location: &str) -> Result<Response<Body>, AppError> {
+    let mut response = Response::new(Body::empty());
+    *response.status_mut() = StatusCode::SEE_OTHER;
+    response
+        .headers_mut()
+        .insert(LOCATION, HeaderValue::from_str(location)?);
+
+    Ok(response)
+}
+
+#[utoipa::path(
+    path = "/oauth/authorize",
+    get,
+    tag = TAG,
+    params(
+        AuthorizeReq
+    ),
+    responses(
+        (status = StatusCode::SEE_OTHER, description = "consent page or redirect_uri with error")
+    )
+)]
+// 요청을 저장하고 동의 화면으로 보냄, 로그인은 동의 화면에서 진행
+async fn authorize(
+    State(db): State<DatabaseConnection>,
+    Query(req): Query<AuthorizeReq>,
+) -> Result<Response<Body>, AppError> {
+    let location = req.start(&db).await?;
+    see_other(&location)
+}
+
+#[utoipa::path(
+    path = "/oauth/authorize/decision",
+    post,
+    tag = TAG,
+    request_body(
+        content = ConsentDecision,
+        content_type = mime::APPLICATION_WWW_FORM_URLENCODED.as_ref()
+    ),
+    responses(
+        (status = StatusCode::SEE_OTHER, description = "redirect_uri with code or error")
+    )
+)]
+// 동의 화면의 form, 로그인 쿠키로 사용자를 확인함
+async fn decision(
+    State(db): State<DatabaseConnection>,
+    TypedHeader(cookies): TypedHeader<Cookie>,
+    Form(req): Form<ConsentDecision>,
+) -> Result<Response<Body>, AppError> {
+    let user = current_user(&cookies, &db).await?;
+
+    let location = req.decide(user.0, &db).await?;
+    see_other(&location)
+}
+
+#[utoipa::path(
+    path = "/oauth/token",
+    post,
+    tag = TAG,
+    request_body(
+        content = GrantReq,
+        content_type = mime::APPLICATION_WWW_FORM_URLENCODED.as_ref()
+    ),
+    responses(
+        (status = StatusCode::OK, body = GrantRes),
+        (status = StatusCode::BAD_REQUEST, description = "invalid_grant, invalid_request, ..."),
+        (status = StatusCode::UNAUTHORIZED, description = "invalid_client")
+    ),
+    security(
+        (),
+        ("client_basic" = [])
+    )
+)]
+// RFC 6749 3.2, 토큰 응답은 캐시되면 안됨
+async fn token(
+    State(db): State<DatabaseConnection>,
+    basic: Option<TypedHeader<Authorization<Basic>>>,
+    Form(req): Form<GrantReq>,
+) -> Result<Response<Body>, OAuthError> {
+    use axum::response::IntoResponse;
+
+    let client = ClientCredentials::from_request(
+        basic.as_ref().map(|b| &b.0.0),
+        req.client_id.as_deref(),
+        req.client_secret.as_deref(),
+    )?
+    .verify_allow_public(&db)
+    .await?;
+
+    let res = req.exchange(&client, &db).await?;
+
+    let mut response = Json(res).into_response();
+    let headers = response.headers_mut();
+    headers.insert(CACHE_CONTROL, HeaderValue::from_static("no-store"));
+    headers.insert(PRAGMA, HeaderValue::from_static("no-cache"));
+
+    Ok(response)
+}
+
+#[utoipa::path(
+    path = "/oauth/userinfo",
+    get,
+    tag = TAG,
+    responses(
+        (status = StatusCode::OK, body = UserInfoRes),
+        (status = StatusCode::UNAUTHORIZED, description = "invalid_token")
+    ),
+    security(
+        ("api_jwt_token" = [])
+    )
+)]
+async fn userinfo(
+    State(db): State<DatabaseConnection>,
+    bearer: Option<TypedHeader<Authorization<Bearer>>>,
+) -> Result<Json<UserInfoRes>, OAuthError> {
+    let bearer = bearer.ok_or(OAuthError::invalid_token())?;
+
+    Ok(Json(
+        UserInfoRes::from_bearer(bearer.0.0.token(), &db).await?,
+    ))
+}
+
+#[utoipa::path(
+    path = "/oauth/jwks",
+    get,
+    tag = TAG,
+    responses(
+        (status = StatusCode::OK, description = "JWK Set (RFC 7517)")
+    )
+)]
+// id_token 서명을 검증하기 위한 공개키
+async fn jwks() -> Result<Json<JwkSet>, AppError> {
+    Ok(Json(oidc::jwks()?))
+}
+
+#[utoipa::path(
+    path = "/.well-known/openid-configuration",
+    get,
+    tag = TAG,
+    responses(
+        (status = StatusCode::OK, body = DiscoveryRes)
+    )
+)]
+async fn discovery() -> Result<Json<DiscoveryRes>, AppError> {
+    Ok(Json(DiscoveryRes::new()?))
+}
+
+// OpenAPI
+const TAG: &str = "OAUTH";
+#[derive(OpenApi)]
+#[openapi(
+    servers(
+        (url = "/", description = "OAuth 2.0 / OpenID Connect provider")
+    ),
+    modifiers(&SecurityAddon, &ClientSecurityAddon),
+    tags(
+        (name = TAG, description = "Authorization server for third-party apps")
+    )
+)]
+struct ApiDoc;
+
+pub fn init_router(aex: AppExtension) -> Router {
+    let open_router = OpenApiRouter::new()
+        .routes(routes!(authorize))
+        .routes(routes!(decision))
+        .routes(routes!(token))
+        .routes(routes!(userinfo))
+        .routes(routes!(jwks))
+        .routes(routes!(discovery))
+        .with_state(aex.db.0);
+
+    let (router, oauth_api) = open_router.split_for_parts();
+    let mut api = ApiDoc::openapi();
+    api.merge(oauth_api);
+
+    router.merge(Scalar::with_url("/oauth/doc/scalar", api))
+}
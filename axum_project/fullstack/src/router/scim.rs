@@ -0,0 +1,630 @@
+use axum::body::Body;
+use axum::extract::rejection::JsonRejection;
+use axum::extract::{FromRequest, Path, Query, Request, State};
+use axum::http::{HeaderValue, header::AUTHORIZATION};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::{Json, Router};
+use reqwest::StatusCode;
+use reqwest::header::{CONTENT_TYPE, LOCATION};
+use sea_orm::DatabaseConnection;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use sha2::{Digest, Sha256};
+use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
+use utoipa::{Modify, OpenApi};
+use utoipa_axum::{router::OpenApiRouter, routes};
+use utoipa_scalar::{Scalar, Servable};
+
+use crate::resources::dto::account::soft_delete;
+use crate::resources::dto::fullstack_extension::AppExtension;
+use crate::resources::dto::scim::{
+    MAX_COUNT, ScimListReq, ScimListRes, ScimPatchReq, ScimUser, ScimUserReq, find_active, parse_id,
+};
+use crate::utils::errors::{SCIM_CONTENT_TYPE, ScimError};
+
+const TAG: &str = "SCIM";
+
+// 응답은 모두 application/scim+json
+struct Scim<T>(StatusCode, T);
+
+impl<T: Serialize> IntoResponse for Scim<T> {
+    fn into_response(self) -> Response {
+        let mut response = (self.0, Json(self.1)).into_response();
+        response
+            .headers_mut()
+            .insert(CONTENT_TYPE, HeaderValue::from_static(SCIM_CONTENT_TYPE));
+        response
+    }
+}
+
+// 본문 오류도 SCIM 에러 형식으로 내려줌
+struct ScimBody<T>(T);
+
+impl<T, S> FromRequest<S> for ScimBody<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = ScimError;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let Json(value) =
+            Json::<T>::from_request(req, state)
+                .await
+                .map_err(|e: JsonRejection| {
+                    ScimError::new(
+                        StatusCode::BAD_REQUEST,
+                        Some("invalidSyntax"),
+                        e.body_text(),
+                    )
+                })?;
+        Ok(ScimBody(value))
+    }
+}
+
+fn digest(value: &str) -> Vec<u8> {
+    Sha256::digest(value.as_bytes()).to_vec()
+}
+
+// IdP에 등록한 프로비저닝 토큰(SCIM_TOKEN)으로만 접근할 수 있음
+// 환경변수가 없으면 SCIM은 꺼진 것으로 보고 모두 거절함
+async fn require_token(request: Request<Body>, next: Next) -> Result<Response, ScimError> {
+    let expected = std::env::var("SCIM_TOKEN")
+        .ok()
+        .filter(|t| !t.is_empty())
+        .ok_or_else(ScimError::unauthorized)?;
+    let token = request
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .ok_or_else(ScimError::unauthorized)?;
+
+    // 길이와 상관없이 같은 시간이 걸리도록 해시로 비교
+    if digest(token) != digest(&expected) {
+        return Err(ScimError::unauthorized());
+    }
+    Ok(next.run(request).await)
+}
+
+#[utoipa::path(
+    get,
+    path = "/Users",
+    tag = TAG,
+    params (
+        ScimListReq
+    ),
+    responses (
+        (status = StatusCode::OK, body = ScimListRes, content_type = SCIM_CONTENT_TYPE),
+        (status = StatusCode::BAD_REQUEST, body = ScimError)
+    ),
+    security(
+        ("scim_token" = [])
+    )
+)]
+async fn list_users(
+    State(db): State<DatabaseConnection>,
+    Query(req): Query<ScimListReq>,
+) -> Result<Scim<ScimListRes>, ScimError> {
+    Ok(Scim(StatusCode::OK, req.list(&db).await?))
+}
+
+#[utoipa::path(
+    get,
+    path = "/Users/{id}",
+    tag = TAG,
+    params (
+        ("id" = String, Path, description = "user id")
+    ),
+    responses (
+        (status = StatusCode::OK, body = ScimUser, content_type = SCIM_CONTENT_TYPE),
+        (status = StatusCode::NOT_FOUND, body = ScimError)
+    ),
+    security(
+        ("scim_token" = [])
+    )
+)]
+async fn get_user(
+    State(db): State<DatabaseConnection>,
+    Path(id): Path<String>,
+) -> Result<Scim<ScimUser>, ScimError> {
+    let model = find_active(parse_id(&id)?, &db).await?;
+    Ok(Scim(StatusCode::OK, model.into()))
+}
+
+#[utoipa::path(
+    post,
+    path = "/Users",
+    tag = TAG,
+    request_body(
+        content = ScimUserReq,
+        content_type = SCIM_CONTENT_TYPE
+    ),
+    responses (
+        (status = StatusCode::CREATED, body = ScimUser, content_type = SCIM_CONTENT_TYPE),
+        (status = StatusCode::CONFLICT, body = ScimError)
+    ),
+    security(
+        ("scim_token" = [])
+    )
+)]
+async fn create_user(
+    State(db): State<DatabaseConnection>,
+    ScimBody(req): ScimBody<ScimUserReq>,
+) -> Result<Response, ScimError> {
+    let user = req.create(&db).await?;
+    let location = HeaderValue::from_str(&user.meta.location)
+        .map_err(|e| ScimError::invalid_value(e.to_string()))?;
+
+    let mut response = Scim(StatusCode::CREATED, user).into_response();
+    response.headers_mut().insert(LOCATION, location);
+    Ok(response)
+}
+
+#[utoipa::path(
+    put,
+    path = "/Users/{id}",
+    tag = TAG,
+    params (
+        ("id" = String, Path, description = "user id")
+    ),
+    request_body(
+        content = ScimUserReq,
+        content_type = SCIM_CONTENT_TYPE
+    ),
+    responses (
+        (status = StatusCode::OK, body = ScimUser, content_type = SCIM_CONTENT_TYPE),
+        (status = StatusCode::NOT_FOUND, body = ScimError),
+        (status = StatusCode::CONFLICT, body = ScimError)
+    ),
+    security(
+        ("scim_token" = [])
+    )
+)]
+// 보내지 않은 displayName, externalId는 지워짐
+async fn replace_user(
+    State(db): State<DatabaseConnection>,
+    Path(id): Path<String>,
+    ScimBody(req): ScimBody<ScimUserReq>,
+) -> Result<Scim<ScimUser>, ScimError> {
+    Ok(Scim(
+        StatusCode::OK,
+        req.replace(parse_id(&id)?, &db).await?,
+    ))
+}
+
+#[utoipa::path(
+    patch,
+    path = "/Users/{id}",
+    tag = TAG,
+    params (
+        ("id" = String, Path, description = "user id")
+    ),
+    request_body(
+        content = ScimPatchReq,
+        content_type = SCIM_CONTENT_TYPE
+    ),
+    responses (
+        (status = StatusCode::OK, body = ScimUser, content_type = SCIM_CONTENT_TYPE),
+        (status = StatusCode::BAD_REQUEST, body = ScimError),
+        (status = StatusCode::NOT_FOUND, body = ScimError)
+    ),
+    security(
+        ("scim_token" = [])
+    )
+)]
+// active를 false로 바꾸면 정지되고 발급된 토큰이 모두 끊김
+async fn patch_user(
+    State(db): State<DatabaseConnection>,
+    Path(id): Path<String>,
+    ScimBody(req): ScimBody<ScimPatchReq>,
+) -> Result<Scim<ScimUser>, ScimError> {
+    Ok(Scim(StatusCode::OK, req.patch(parse_id(&id)?, &db).await?))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/Users/{id}",
+    tag = TAG,
+    params (
+        ("id" = String, Path, description = "user id")
+    ),
+    responses (
+        (status = StatusCode::NO_CONTENT),
+        (status = StatusCode::NOT_FOUND, body = ScimError)
+    ),
+    security(
+        ("scim_token" = [])
+    )
+)]
+// 회원 탈퇴와 같이 처리함, 복구 기간 동안은 관리자가 되돌릴 수 있음
+async fn delete_user(
+    State(db): State<DatabaseConnection>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, ScimError> {
+    if !soft_delete(parse_id(&id)?, &db).await? {
+        return Err(ScimError::not_found());
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    get,
+    path = "/ServiceProviderConfig",
+    tag = TAG,
+    responses (
+        (status = StatusCode::OK, description = "supported SCIM features", content_type = SCIM_CONTENT_TYPE)
+    ),
+    security(
+        ("scim_token" = [])
+    )
+)]
+async fn service_provider_config() -> Scim<serde_json::Value> {
+    Scim(
+        StatusCode::OK,
+        serde_json::json!({
+            "schemas": ["urn:ietf:params:scim:schemas:core:2.0:ServiceProviderConfig"],
+            "patch": { "supported": true },
+            "bulk": { "supported": false, "maxOperations": 0, "maxPayloadSize": 0 },
+            "filter": { "supported": true, "maxResults": MAX_COUNT },
+            "changePassword": { "supported": false },
+            "sort": { "supported": false },
+            "etag": { "supported": false },
+            "authenticationSchemes": [{
+                "type": "oauthbearertoken",
+                "name": "Bearer Token",
+                "description": "Provisioning token set by SCIM_TOKEN",
+                "primary": true
+            }]
+        }),
+    )
+}
+
+struct ScimSecurityAddon;
+
+impl Modify for ScimSecurityAddon {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        if let Some(components) = openapi.components.as_mut() {
+            components.add_security_scheme(
+                "scim_token",
+                SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Bearer).build()),
+            )
+        }
+    }
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    servers(
+        (url = "/scim/v2", description = "SCIM 2.0 base path")
+    ),
+    modifiers(&ScimSecurityAddon),
+    tags(
+        (name = TAG, description = "SCIM 2.0 user provisioning (RFC 7644)")
+    )
+)]
+struct ApiDoc;
+
+pub fn init_router(aex: AppExtension) -> Router {
+    scim_router(aex.db.0)
+}
+
+// 문서를 제외한 모든 라우터가 프로비저닝 토큰을 요구함
+fn scim_router(db: DatabaseConnection) -> Router {
+    let scim_router = OpenApiRouter::new()
+        .routes(routes!(list_users, create_user))
+        .routes(routes!(get_user, replace_user, patch_user, delete_user))
+        .routes(routes!(service_provider_config))
+        .with_state(db)
+        .layer(middleware::from_fn(require_token));
+
+    let (router, scim_api) = scim_router.split_for_parts();
+    let mut api = ApiDoc::openapi();
+    api.merge(scim_api);
+
+    Router::new().nest(
+        "/scim/v2",
+        router.merge(Scalar::with_url("/doc/scalar", api)),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::Method;
+    use sea_orm::EntityTrait;
+    use serde_json::{Value, json};
+    use tower::ServiceExt;
+
+    use crate::resources::dto::admin::UserStatus;
+    use crate::resources::entities::users;
+    use crate::utils::testing;
+
+    const TOKEN: &str = "scim-test-token";
+
+    // 모든 테스트가 같은 값을 넣으므로 한 번만 설정함
+    fn router(db: DatabaseConnection) -> Router {
+        static SET_TOKEN: std::sync::Once = std::sync::Once::new();
+        SET_TOKEN.call_once(|| unsafe { std::env::set_var("SCIM_TOKEN", TOKEN) });
+        scim_router(db)
+    }
+
+    // IdP가 보내는 본문은 tests/fixtures/scim에 두고 테스트마다 userName 등을 바꿔서 씀
+    fn fixture(name: &str) -> Value {
+        let path = format!(
+            "{}/tests/fixtures/scim/{}.json",
+            env!("CARGO_MANIFEST_DIR"),
+            name
+        );
+        serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+    }
+
+    async fn send(
+        router: &Router,
+        method: Method,
+        uri: &str,
+        token: Option<&str>,
+        body: Option<Value>,
+    ) -> (StatusCode, Response<Value>) {
+        let mut request = Request::builder().method(method).uri(uri);
+        if let Some(token) = token {
+            request = request.header(AUTHORIZATION, format!("Bearer {}", token));
+        }
+        let request = match body {
+            Some(body) => request
+                .header(CONTENT_TYPE, "application/json")
+                .body(Body::from(body.to_string())),
+            None => request.body(Body::empty()),
+        }
+        .unwrap();
+
+        let response = router.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let (parts, body) = response.into_parts();
+        let bytes = axum::body::to_bytes(body, usize::MAX).await.unwrap();
+        let value = if bytes.is_empty() {
+            Value::Null
+        } else {
+            serde_json::from_slice(&bytes).unwrap()
+        };
+        (status, Response::from_parts(parts, value))
+    }
+
+    async fn scim(
+        router: &Router,
+        method: Method,
+        uri: &str,
+        body: Option<Value>,
+    ) -> (StatusCode, Value) {
+        let (status, response) = send(router, method, uri, Some(TOKEN), body).await;
+        (status, response.into_body())
+    }
+
+    fn assert_scim_error(status: StatusCode, body: &Value, expected: StatusCode) {
+        assert_eq!(status, expected, "{}", body);
+        assert_eq!(
+            body["schemas"],
+            json!(["urn:ietf:params:scim:api:messages:2.0:Error"])
+        );
+        assert_eq!(body["status"], json!(expected.as_u16().to_string()));
+        assert!(body["detail"].is_string());
+    }
+
+    async fn remove_users(ids: &[&str], db: &DatabaseConnection) {
+        for id in ids {
+            users::Entity::delete_by_id(id.parse::<i32>().unwrap())
+                .exec(db)
+                .await
+                .unwrap();
+        }
+    }
+
+    // 토큰 검사와 형식 오류는 DB에 가기 전에 끝남
+    #[tokio::test]
+    async fn rejects_missing_or_wrong_token() {
+        let router = router(DatabaseConnection::Disconnected);
+
+        let (status, response) = send(&router, Method::GET, "/scim/v2/Users", None, None).await;
+        assert_eq!(
+            response.headers().get(CONTENT_TYPE).unwrap(),
+            SCIM_CONTENT_TYPE
+        );
+        assert_scim_error(status, response.body(), StatusCode::UNAUTHORIZED);
+
+        let (status, response) =
+            send(&router, Method::GET, "/scim/v2/Users", Some("wrong"), None).await;
+        assert_scim_error(status, response.body(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    #[ignore = "needs DATABASE_URL"]
+    async fn create_then_filter_by_user_name() {
+        let db = testing::connect().await;
+        let router = router(db.clone());
+        let prefix = testing::unique("scim");
+        let first = format!("{}-a@example.com", prefix);
+        let second = format!("{}-b@example.com", prefix);
+        let create = |user_name: &str, external_id: &str| {
+            let mut body = fixture("create_user");
+            body["userName"] = json!(user_name);
+            body["externalId"] = json!(format!("{}-{}", prefix, external_id));
+            body
+        };
+
+        let (status, response) = send(
+            &router,
+            Method::POST,
+            "/scim/v2/Users",
+            Some(TOKEN),
+            Some(create(&first, "a")),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED, "{}", response.body());
+        let created = response.body().clone();
+        assert_eq!(
+            response.headers().get(LOCATION).unwrap(),
+            created["meta"]["location"].as_str().unwrap()
+        );
+        assert_eq!(created["userName"], json!(first));
+        assert_eq!(created["displayName"], json!("Kim A"));
+        assert_eq!(created["active"], json!(true));
+        assert_eq!(created["emails"][0]["value"], json!(first));
+
+        let (status, other) = scim(
+            &router,
+            Method::POST,
+            "/scim/v2/Users",
+            Some(create(&second, "b")),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED, "{}", other);
+
+        // 같은 userName은 대소문자가 달라도 409
+        let (status, body) = scim(
+            &router,
+            Method::POST,
+            "/scim/v2/Users",
+            Some(create(&first.to_uppercase(), "c")),
+        )
+        .await;
+        assert_scim_error(status, &body, StatusCode::CONFLICT);
+        assert_eq!(body["scimType"], json!("uniqueness"));
+
+        let (status, list) = scim(
+            &router,
+            Method::GET,
+            &format!("/scim/v2/Users?filter=userName%20eq%20%22{}%22", first),
+            None,
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK, "{}", list);
+        assert_eq!(
+            list["schemas"],
+            json!(["urn:ietf:params:scim:api:messages:2.0:ListResponse"])
+        );
+        assert_eq!(list["totalResults"], json!(1));
+        assert_eq!(list["startIndex"], json!(1));
+        assert_eq!(list["itemsPerPage"], json!(1));
+        assert_eq!(list["Resources"][0]["id"], created["id"]);
+
+        // 두 명 중 두 번째부터 한 명
+        let (status, page) = scim(
+            &router,
+            Method::GET,
+            &format!(
+                "/scim/v2/Users?filter=userName%20sw%20%22{}%22&startIndex=2&count=1",
+                prefix
+            ),
+            None,
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK, "{}", page);
+        assert_eq!(page["totalResults"], json!(2));
+        assert_eq!(page["startIndex"], json!(2));
+        assert_eq!(page["itemsPerPage"], json!(1));
+        assert_eq!(page["Resources"][0]["id"], other["id"]);
+
+        remove_users(
+            &[
+                created["id"].as_str().unwrap(),
+                other["id"].as_str().unwrap(),
+            ],
+            &db,
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    #[ignore = "needs DATABASE_URL"]
+    async fn replace_patch_and_delete() {
+        let db = testing::connect().await;
+        let router = router(db.clone());
+        let user_name = format!("{}@example.com", testing::unique("scim"));
+        let mut create = fixture("create_user");
+        create["userName"] = json!(user_name);
+        create["externalId"] = json!(testing::unique("ext"));
+        create["displayName"] = json!("Before");
+
+        let (status, created) = scim(&router, Method::POST, "/scim/v2/Users", Some(create)).await;
+        assert_eq!(status, StatusCode::CREATED, "{}", created);
+        let id = created["id"].as_str().unwrap().to_string();
+        let uri = format!("/scim/v2/Users/{}", id);
+
+        // PUT은 보내지 않은 속성을 지움
+        let mut replace = fixture("replace_user");
+        replace["userName"] = json!(user_name);
+        let (status, replaced) = scim(&router, Method::PUT, &uri, Some(replace)).await;
+        assert_eq!(status, StatusCode::OK, "{}", replaced);
+        assert_eq!(replaced["displayName"], json!("After"));
+        assert!(replaced.get("externalId").is_none());
+        assert_ne!(replaced["meta"]["version"], created["meta"]["version"]);
+
+        let (status, patched) = scim(
+            &router,
+            Method::PATCH,
+            &uri,
+            Some(fixture("deactivate_user")),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK, "{}", patched);
+        assert_eq!(patched["active"], json!(false));
+        assert_eq!(patched["displayName"], json!("After"));
+        let model = users::Entity::find_by_id(id.parse::<i32>().unwrap())
+            .one(&db)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(model.status, UserStatus::Suspended.as_str());
+        assert!(model.tokens_valid_after.is_some());
+
+        let (status, body) = scim(
+            &router,
+            Method::PATCH,
+            &uri,
+            Some(fixture("remove_user_name")),
+        )
+        .await;
+        assert_scim_error(status, &body, StatusCode::BAD_REQUEST);
+        assert_eq!(body["scimType"], json!("mutability"));
+
+        let (status, body) = scim(&router, Method::DELETE, &uri, None).await;
+        assert_eq!(status, StatusCode::NO_CONTENT, "{}", body);
+
+        // 탈퇴한 유저는 없는 리소스
+        let (status, body) = scim(&router, Method::GET, &uri, None).await;
+        assert_scim_error(status, &body, StatusCode::NOT_FOUND);
+        let (status, body) = scim(&router, Method::DELETE, &uri, None).await;
+        assert_scim_error(status, &body, StatusCode::NOT_FOUND);
+
+        remove_users(&[&id], &db).await;
+    }
+
+    #[tokio::test]
+    async fn invalid_requests_use_scim_errors() {
+        let router = router(DatabaseConnection::Disconnected);
+
+        let (status, body) = scim(
+            &router,
+            Method::GET,
+            "/scim/v2/Users?filter=userName%20eq%20%22a%22%20or%20active%20eq%20true",
+            None,
+        )
+        .await;
+        assert_scim_error(status, &body, StatusCode::BAD_REQUEST);
+        assert_eq!(body["scimType"], json!("invalidFilter"));
+
+        let (status, body) = scim(
+            &router,
+            Method::POST,
+            "/scim/v2/Users",
+            Some(fixture("missing_user_name")),
+        )
+        .await;
+        assert_scim_error(status, &body, StatusCode::BAD_REQUEST);
+        assert_eq!(body["scimType"], json!("invalidSyntax"));
+
+        let (status, body) = scim(&router, Method::GET, "/scim/v2/Users/abc", None).await;
+        assert_scim_error(status, &body, StatusCode::NOT_FOUND);
+    }
+}
@@ -1,6 +1,7 @@
 use std::{
     env::VarError,
     fmt::{Debug, Display},
+    str::FromStr,
 };
 
 use axum::{http::HeaderValue, response::IntoResponse};
@@ -8,13 +9,17 @@ use bcrypt::BcryptError;
 use dioxus::{CapturedError, fullstack::AsStatusCode, server::ServerFnError};
 use reqwest::{
     StatusCode,
-    header::{InvalidHeaderValue, LOCATION, SET_COOKIE, ToStrError},
+    header::{
+        CACHE_CONTROL, CONTENT_TYPE, InvalidHeaderValue, LOCATION, SET_COOKIE, ToStrError,
+        WWW_AUTHENTICATE,
+    },
 };
-use sea_orm::DbErr;
+use sea_orm::{DbErr, SqlErr};
 use serde::{Deserialize, Serialize};
 use tracing::error;
 
 use crate::database::DB_ERR_MESSAGE;
+use crate::utils::validate::{FieldError, ValidationErrors};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppError {
@@ -182,6 +187,297 @@ impl From<InvalidHeaderValue> for AppError {
         AppError::auth_error()
     }
 }
+
+// OAuth2 계열 엔드포인트(RFC 6749 5.2)는 리디렉션이 아니라 JSON 에러 본문으로 응답해야함
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OAuthError {
+    #[serde(skip)]
+    pub code: u16,
+    pub error: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_description: Option<String>,
+}
+
+impl Display for OAuthError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[OAuth Err {}]: {}", self.code, self.error)
+    }
+}
+impl std::error::Error for OAuthError {}
+
+impl OAuthError {
+    pub fn new(code: StatusCode, error: &str, description: Option<String>) -> Self {
+        Self {
+            code: code.as_u16(),
+            error: error.to_string(),
+            error_description: description,
+        }
+    }
+    pub fn invalid_client() -> Self {
+        Self::new(
+            StatusCode::UNAUTHORIZED,
+            "invalid_client",
+            Some("client authentication failed".to_string()),
+        )
+    }
+    pub fn invalid_request(description: impl Into<String>) -> Self {
+        Self::new(
+            StatusCode::BAD_REQUEST,
+            "invalid_request",
+            Some(description.into()),
+        )
+    }
+    pub fn invalid_grant(description: impl Into<String>) -> Self {
+        Self::new(
+            StatusCode::BAD_REQUEST,
+            "invalid_grant",
+            Some(description.into()),
+        )
+    }
+    pub fn invalid_scope() -> Self {
+        Self::new(StatusCode::BAD_REQUEST, "invalid_scope", None)
+    }
+    pub fn unsupported_grant_type() -> Self {
+        Self::new(StatusCode::BAD_REQUEST, "unsupported_grant_type", None)
+    }
+    // 보호된 자원(userinfo)에 접근할때 쓰는 Bearer 토큰 에러 (RFC 6750 3.1)
+    pub fn invalid_token() -> Self {
+        Self::new(StatusCode::UNAUTHORIZED, "invalid_token", None)
+    }
+    pub fn server_error() -> Self {
+        Self::new(StatusCode::INTERNAL_SERVER_ERROR, "server_error", None)
+    }
+}
+
+impl From<AppError> for OAuthError {
+    fn from(value: AppError) -> Self {
+        error!("OAuth Error! {:?}", value);
+        OAuthError::server_error()
+    }
+}
+impl From<DbErr> for OAuthError {
+    fn from(value: DbErr) -> Self {
+        error!("Data base Error {:?}", value);
+        OAuthError::server_error()
+    }
+}
+
+impl IntoResponse for OAuthError {
+    fn into_response(self) -> axum::response::Response {
+        let status = StatusCode::from_u16(self.code).unwrap_or(StatusCode::BAD_REQUEST);
+        let mut response = (status, axum::Json(&self)).into_response();
+        let headers = response.headers_mut();
+        headers.insert(CACHE_CONTROL, HeaderValue::from_static("no-store"));
+        // 인증 실패는 어떤 인증 방식을 써야하는지 알려줘야함 (RFC 6749 5.2, RFC 6750 3)
+        if self.error == "invalid_token" {
+            headers.insert(
+                WWW_AUTHENTICATE,
+                HeaderValue::from_static("Bearer error=\"invalid_token\""),
+            );
+        } else if status == StatusCode::UNAUTHORIZED {
+            headers.insert(
+                WWW_AUTHENTICATE,
+                HeaderValue::from_static("Basic realm=\"dolto\""),
+            );
+        }
+        response
+    }
+}
+
+// 리디렉션 대신 상태코드와 JSON 본문을 그대로 돌려줘야하는 API용 에러 (412, 428 등)
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ApiError {
+    #[serde(skip)]
+    pub code: u16,
+    pub error: String,
+    pub message: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<FieldError>,
+}
+
+impl Display for ApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[Api Err {}]: {}", self.code, self.message)
+    }
+}
+impl std::error::Error for ApiError {}
+
+impl ApiError {
+    pub fn new(code: StatusCode, error: &str, message: impl Into<String>) -> Self {
+        Self {
+            code: code.as_u16(),
+            error: error.to_string(),
+            message: message.into(),
+            fields: Vec::new(),
+        }
+    }
+    pub fn precondition_failed() -> Self {
+        Self::new(
+            StatusCode::PRECONDITION_FAILED,
+            "precondition_failed",
+            "resource was modified, reload and try again",
+        )
+    }
+    pub fn precondition_required() -> Self {
+        Self::new(
+            StatusCode::PRECONDITION_REQUIRED,
+            "precondition_required",
+            "If-Match header is required",
+        )
+    }
+    // 외래키, 유니크 제약 위반은 409로 바꾸고 나머지 DB 에러는 그대로 둠
+    pub fn conflict_on(err: DbErr, error: &str, message: impl Into<String>) -> Self {
+        match err.sql_err() {
+            Some(SqlErr::ForeignKeyConstraintViolation(_))
+            | Some(SqlErr::UniqueConstraintViolation(_)) => {
+                Self::new(StatusCode::CONFLICT, error, message)
+            }
+            _ => err.into(),
+        }
+    }
+}
+
+impl From<AppError> for ApiError {
+    fn from(value: AppError) -> Self {
+        let code = StatusCode::from_u16(value.code).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        let error = code
+            .canonical_reason()
+            .unwrap_or("error")
+            .to_lowercase()
+            .replace(' ', "_");
+        Self::new(code, &error, value.message)
+    }
+}
+// 폼 요청처럼 리다이렉트로 에러를 보여주는 곳에서 JSON API의 로직을 재사용할 때
+impl From<ApiError> for AppError {
+    fn from(value: ApiError) -> Self {
+        let code = StatusCode::from_u16(value.code).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        let message = if value.fields.is_empty() {
+            value.message
+        } else {
+            value
+                .fields
+                .iter()
+                .map(|f| format!("{}: {}", f.field, f.message))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        Self::new(code, message, None)
+    }
+}
+impl From<ValidationErrors> for ApiError {
+    fn from(value: ValidationErrors) -> Self {
+        Self {
+            fields: value.0,
+            ..Self::new(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "validation_failed",
+                "request has invalid fields",
+            )
+        }
+    }
+}
+impl From<DbErr> for ApiError {
+    fn from(value: DbErr) -> Self {
+        AppError::from(value).into()
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> axum::response::Response {
+        let status = StatusCode::from_u16(self.code).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        (status, axum::Json(&self)).into_response()
+    }
+}
+
+pub const SCIM_CONTENT_TYPE: &str = "application/scim+json";
+
+// SCIM 2.0 에러 본문 (RFC 7644 3.12), status는 문자열이어야함
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ScimError {
+    pub schemas: Vec<String>,
+    pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scim_type: Option<String>,
+    pub detail: String,
+}
+
+impl Display for ScimError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[SCIM Err {}]: {}", self.status, self.detail)
+    }
+}
+impl std::error::Error for ScimError {}
+
+impl ScimError {
+    pub fn new(code: StatusCode, scim_type: Option<&str>, detail: impl Into<String>) -> Self {
+        Self {
+            schemas: vec!["urn:ietf:params:scim:api:messages:2.0:Error".to_string()],
+            status: code.as_u16().to_string(),
+            scim_type: scim_type.map(str::to_string),
+            detail: detail.into(),
+        }
+    }
+    pub fn not_found() -> Self {
+        Self::new(StatusCode::NOT_FOUND, None, "resource is not found")
+    }
+    pub fn unauthorized() -> Self {
+        Self::new(
+            StatusCode::UNAUTHORIZED,
+            None,
+            "valid provisioning token is required",
+        )
+    }
+    pub fn invalid_value(detail: impl Into<String>) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, Some("invalidValue"), detail)
+    }
+    pub fn invalid_filter(detail: impl Into<String>) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, Some("invalidFilter"), detail)
+    }
+    pub fn invalid_path(detail: impl Into<String>) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, Some("invalidPath"), detail)
+    }
+    pub fn uniqueness(detail: impl Into<String>) -> Self {
+        Self::new(StatusCode::CONFLICT, Some("uniqueness"), detail)
+    }
+}
+
+impl From<AppError> for ScimError {
+    fn from(value: AppError) -> Self {
+        let code = StatusCode::from_u16(value.code).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        Self::new(code, None, value.message)
+    }
+}
+impl From<ApiError> for ScimError {
+    fn from(value: ApiError) -> Self {
+        let code = StatusCode::from_u16(value.code).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        Self::new(code, None, value.message)
+    }
+}
+impl From<DbErr> for ScimError {
+    fn from(value: DbErr) -> Self {
+        AppError::from(value).into()
+    }
+}
+
+impl IntoResponse for ScimError {
+    fn into_response(self) -> axum::response::Response {
+        let status =
+            StatusCode::from_str(&self.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        let mut response = (status, axum::Json(&self)).into_response();
+        let headers = response.headers_mut();
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static(SCIM_CONTENT_TYPE));
+        if status == StatusCode::UNAUTHORIZED {
+            headers.insert(
+                WWW_AUTHENTICATE,
+                HeaderValue::from_static("Bearer realm=\"scim\""),
+            );
+        }
+        response
+    }
+}
+
 impl IntoResponse for AppError {
     fn into_response(self) -> axum::response::Response {
         let mut response = axum::response::Response::new(axum::body::Body::empty());
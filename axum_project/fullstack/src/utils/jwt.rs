@@ -1,20 +1,27 @@
 use axum::{
     body::Body,
+    extract::State,
     http::{HeaderMap, Request},
     middleware::Next,
     response::Response,
 };
 use chrono::{DateTime, Duration, Utc};
+use hmac::{Hmac, Mac};
 use jsonwebtoken::{DecodingKey, EncodingKey, Header, Validation, decode, encode};
 use lazy_static::lazy_static;
 use reqwest::header::AUTHORIZATION;
-use sea_orm::{DatabaseConnection, EntityTrait, sea_query::OnConflict};
+use sea_orm::{
+    ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter,
+    sea_query::OnConflict,
+};
+use sha2::Sha256;
 use std::env;
 use tracing::debug;
 
 use crate::resources::{
+    dto::admin::UserStatus,
     dto::user::{CurrentUser, JwtClaims, RefreshClaims},
-    entities::refresh_token,
+    entities::{refresh_token, revoked_token, users},
 };
 use crate::utils::errors::AppError;
 
@@ -23,21 +30,64 @@ lazy_static! {
     static ref SECRET_KEY: String = env::var("SECRET_KEY").expect("SECRET_KEY must be set");
 }
 
+// access 토큰 유효시간(초)
+pub const ACCESS_TOKEN_EXPIRES_IN: i64 = 15 * 60;
+
 pub async fn create_token(
     user_id: i32,
     username: String,
     conn: &DatabaseConnection,
 ) -> Result<(String, String), AppError> {
+    create_token_for(user_id, username, None, conn).await
+}
+
+// OAuth 클라이언트에게 발급하는 토큰의 대상 정보
+pub struct TokenClient<'a> {
+    pub client_id: &'a str,
+    pub scope: &'a str,
+}
+
+// client가 있으면 외부 클라이언트용 토큰, 없으면 이 서버의 로그인 토큰
+pub async fn create_token_for(
+    user_id: i32,
+    username: String,
+    client: Option<TokenClient<'_>>,
+    conn: &DatabaseConnection,
+) -> Result<(String, String), AppError> {
+    // 정지된 계정에는 로그인, refresh, OAuth 토큰 모두 발급하지 않음
+    let user = users::Entity::find_by_id(user_id)
+        .one(conn)
+        .await?
+        .ok_or(AppError::auth_error())?;
+    if user.status != UserStatus::Active.as_str() {
+        return Err(AppError::new(
+            reqwest::StatusCode::FORBIDDEN,
+            "account is suspended",
+            Some("/".to_string()),
+        ));
+    }
+    if user.deleted_at.is_some() {
+        return Err(AppError::new(
+            reqwest::StatusCode::FORBIDDEN,
+            "account is deleted",
+            Some("/".to_string()),
+        ));
+    }
+
     // 현재시간
     let now = chrono::Utc::now();
     // 토큰 만료시간
-    let expires_at = now + Duration::minutes(15);
+    let expires_at = now + Duration::seconds(ACCESS_TOKEN_EXPIRES_IN);
     let exp = expires_at.timestamp() as u64;
     // 사용자 이름과, 만료시간을 구조체로 저장
     let claims = JwtClaims {
         exp,
+        iat: now.timestamp() as u64,
+        jti: random_id(),
         user_id,
         username: username.clone(),
+        client_id: client.as_ref().map(|c| c.client_id.to_string()),
+        scope: client.as_ref().map(|c| c.scope.to_string()),
     };
     // 기본 헤더와 시크릿 키를 사용하여 암호화 키 객체를 생성
     let token_header = Header::default();
@@ -45,20 +95,51 @@ pub async fn create_token(
 
     // 토큰 인코딩
     let jwt_res = encode(&token_header, &claims, &key)?;
-    let refresh_res = create_refresh(user_id, username, now, conn).await?;
+    let refresh_res = create_refresh(user_id, username, client, now, conn).await?;
     Ok((jwt_res, refresh_res))
 }
 
+// 토큰마다 겹치지 않는 128비트 랜덤 식별자 (jti, 인가 코드 등)
+pub fn random_id() -> String {
+    format!("{:032x}", rand::random::<u128>())
+}
+
+// 동의 화면 form의 CSRF 토큰, 인가 요청과 로그인한 유저에 묶어서 SECRET_KEY로 서명함
+// 인가 요청 id는 요청을 시작한 쪽도 알고 있으므로 그것만으로는 form을 위조할 수 있음
+pub fn consent_csrf_token(request_id: &str, user_id: i32) -> String {
+    hex::encode(consent_mac(request_id, user_id).finalize().into_bytes())
+}
+
+pub fn verify_consent_csrf_token(token: &str, request_id: &str, user_id: i32) -> bool {
+    hex::decode(token).is_ok_and(|token| {
+        consent_mac(request_id, user_id)
+            .verify_slice(&token)
+            .is_ok()
+    })
+}
+
+fn consent_mac(request_id: &str, user_id: i32) -> Hmac<Sha256> {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(SECRET_KEY.as_bytes()).expect("hmac accepts any key");
+    mac.update(format!("consent:{}:{}", request_id, user_id).as_bytes());
+    mac
+}
+
 pub async fn create_refresh(
     user_id: i32,
     username: String,
+    client: Option<TokenClient<'_>>,
     now: DateTime<Utc>,
     conn: &DatabaseConnection,
 ) -> Result<String, AppError> {
     let exp = now + Duration::days(15);
     let exp = exp.naive_utc();
     // 사용자 이름과, 만료시간을 구조체로 저장
-    let claims = RefreshClaims { user_id, username };
+    let claims = RefreshClaims {
+        user_id,
+        username,
+        jti: random_id(),
+    };
     // 기본 헤더와 시크릿 키를 사용하여 암호화 키 객체를 생성
     let token_header = Header::default();
     let key = EncodingKey::from_secret(SECRET_KEY.as_bytes());
@@ -69,6 +150,8 @@ pub async fn create_refresh(
         user_id: sea_orm::ActiveValue::Set(user_id),
         token: sea_orm::ActiveValue::Set(res.clone()),
         expires_at: sea_orm::ActiveValue::Set(exp),
+        client_id: sea_orm::ActiveValue::Set(client.as_ref().map(|c| c.client_id.to_string())),
+        scope: sea_orm::ActiveValue::Set(client.as_ref().map(|c| c.scope.to_string())),
     };
     refresh_token::Entity::insert(active)
         .on_conflict(
@@ -139,7 +222,86 @@ pub fn validate_refresh_token(token: &str) -> Result<RefreshClaims, jsonwebtoken
     Ok(res)
 }
 
+// access 토큰의 jti를 denylist에 올림, 토큰이 만료될때까지만 보관하면 된다
+pub async fn revoke_jwt(claims: &JwtClaims, conn: &DatabaseConnection) -> Result<(), AppError> {
+    let now = Utc::now();
+    let Some(expires_at) = DateTime::from_timestamp(claims.exp as i64, 0) else {
+        return Ok(());
+    };
+    // 이미 만료된 토큰은 폐기할 필요가 없음
+    if expires_at <= now {
+        return Ok(());
+    }
+
+    let active = revoked_token::ActiveModel {
+        jti: sea_orm::ActiveValue::Set(claims.jti.clone()),
+        expires_at: sea_orm::ActiveValue::Set(expires_at.naive_utc()),
+    };
+    revoked_token::Entity::insert(active)
+        .on_conflict(
+            OnConflict::column(revoked_token::Column::Jti)
+                .do_nothing()
+                .to_owned(),
+        )
+        .do_nothing()
+        .exec(conn)
+        .await?;
+
+    // 만료된 jti는 검사할 일이 없으니 폐기할 때 같이 정리
+    revoked_token::Entity::delete_many()
+        .filter(revoked_token::Column::ExpiresAt.lt(now.naive_utc()))
+        .exec(conn)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn is_revoked(jti: &str, conn: &DatabaseConnection) -> Result<bool, AppError> {
+    let count = revoked_token::Entity::find_by_id(jti.to_string())
+        .count(conn)
+        .await?;
+    Ok(count > 0)
+}
+
+// 폐기된 토큰, 정지되거나 탈퇴한 계정의 토큰, 강제 로그아웃 이전에 발급된 토큰은 쓸 수 없음
+pub async fn is_token_active(
+    claims: &JwtClaims,
+    conn: &DatabaseConnection,
+) -> Result<bool, AppError> {
+    if is_revoked(&claims.jti, conn).await? {
+        return Ok(false);
+    }
+    let Some(user) = users::Entity::find_by_id(claims.user_id).one(conn).await? else {
+        return Ok(false);
+    };
+    let logged_out = user
+        .tokens_valid_after
+        .is_some_and(|after| (claims.iat as i64) < after.and_utc().timestamp());
+
+    Ok(user.status == UserStatus::Active.as_str() && user.deleted_at.is_none() && !logged_out)
+}
+
+// 브라우저에서 쿠키(jwt)로 로그인한 유저를 확인, 만료되었거나 외부 클라이언트용 토큰이면 None
+pub fn claims_from_cookie(cookies: &axum_extra::headers::Cookie) -> Option<JwtClaims> {
+    let claims = validate_jwt_token(cookies.get("jwt")?).ok()?;
+    claims.client_id.is_none().then_some(claims)
+}
+
+// 쿠키로 쓰기 요청을 허용할 때는 서명만 보지 말고 이걸로 확인함
+// 브라우저 form은 Authorization 헤더를 보낼 수 없으므로 쿠키를 쓰지만, 폐기된 토큰이나 정지, 탈퇴, 강제 로그아웃된 계정은 헤더와 똑같이 거절
+pub async fn current_user(
+    cookies: &axum_extra::headers::Cookie,
+    conn: &DatabaseConnection,
+) -> Result<CurrentUser, AppError> {
+    let claims = claims_from_cookie(cookies).ok_or(AppError::auth_error())?;
+    if !is_token_active(&claims, conn).await? {
+        return Err(AppError::auth_error());
+    }
+    Ok(CurrentUser(claims.user_id, claims.username))
+}
+
 pub async fn authenticate(
+    State(db): State<DatabaseConnection>,
     headers: HeaderMap,
     mut request: Request<Body>,
     next: Next,
@@ -148,6 +310,12 @@ pub async fn authenticate(
         let token = value.to_str()?;
         let claim = validate_jwt_token(token)?;
 
+        // 외부 클라이언트에게 발급한 토큰으로는 이 서버의 API를 쓸 수 없음
+        // 폐기된 토큰, 정지된 계정은 만료시간이 남아있어도 거절
+        if claim.client_id.is_some() || !is_token_active(&claim, &db).await? {
+            return Err(AppError::auth_error());
+        }
+
         debug!("Authenticated user: {}", claim.user_id);
 
         // 유저 정보를 건내줌으로서, 현재 로그인된 유저를 알 수 있음
@@ -159,3 +327,34 @@ pub async fn authenticate(
         Err(AppError::auth_error())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // SECRET_KEY가 없으면 테스트용 값을 씀
+    fn init() {
+        static INIT: std::sync::Once = std::sync::Once::new();
+        INIT.call_once(|| {
+            if env::var("SECRET_KEY").is_err() {
+                unsafe { env::set_var("SECRET_KEY", "test-secret") };
+            }
+        });
+    }
+
+    #[test]
+    fn consent_csrf_token_is_bound_to_request_and_user() {
+        init();
+        let token = consent_csrf_token("req-1", 7);
+        assert!(verify_consent_csrf_token(&token, "req-1", 7));
+        assert!(!verify_consent_csrf_token(&token, "req-2", 7));
+        assert!(!verify_consent_csrf_token(&token, "req-1", 8));
+        assert!(!verify_consent_csrf_token("", "req-1", 7));
+        assert!(!verify_consent_csrf_token("not hex", "req-1", 7));
+        assert!(!verify_consent_csrf_token(
+            &token[..token.len() - 2],
+            "req-1",
+            7
+        ));
+    }
+}
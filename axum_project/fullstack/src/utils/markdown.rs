@@ -0,0 +1,150 @@
+use std::borrow::Cow;
+
+use lazy_static::lazy_static;
+use pulldown_cmark::{CodeBlockKind, Event, Options, Parser, Tag, TagEnd};
+use syntect::highlighting::ThemeSet;
+use syntect::html::{ClassStyle, ClassedHTMLGenerator, css_for_theme_with_class_style};
+use syntect::parsing::SyntaxSet;
+use syntect::util::LinesWithEndings;
+
+// 하이라이트 결과는 hl-로 시작하는 class만 붙이고 색은 스타일시트에서 정함
+const CLASS_STYLE: ClassStyle = ClassStyle::SpacedPrefixed { prefix: "hl-" };
+const CLASS_PREFIX: &str = "hl-";
+const THEME: &str = "InspiredGitHub";
+
+lazy_static! {
+    static ref SYNTAXES: SyntaxSet = SyntaxSet::load_defaults_newlines();
+    // /front/blog/highlight.css 로 내려줌
+    pub static ref HIGHLIGHT_CSS: String = ThemeSet::load_defaults()
+        .themes
+        .get(THEME)
+        .and_then(|theme| css_for_theme_with_class_style(theme, CLASS_STYLE).ok())
+        .unwrap_or_default();
+    static ref SANITIZER: ammonia::Builder<'static> = sanitizer();
+}
+
+// 본문에 쓴 HTML도 ammonia 기본 허용 목록만 남김 (script, style, on* 속성 등은 지워짐)
+// class는 하이라이트용 hl- 로 시작하는 것만 남겨서 페이지의 다른 스타일을 흉내낼 수 없게 함
+fn sanitizer() -> ammonia::Builder<'static> {
+    let mut builder = ammonia::Builder::default();
+    builder
+        .add_tag_attributes("span", &["class"])
+        .add_tag_attributes("pre", &["class"])
+        .attribute_filter(|_, attribute, value| {
+            if attribute != "class" {
+                return Some(Cow::Borrowed(value));
+            }
+            let classes = value
+                .split_whitespace()
+                .filter(|c| c.starts_with(CLASS_PREFIX))
+                .collect::<Vec<_>>();
+            (!classes.is_empty()).then(|| Cow::Owned(classes.join(" ")))
+        });
+    builder
+}
+
+// Markdown을 HTML로 바꾸고 정리함, 코드 블록은 ```rust 처럼 적은 언어로 하이라이트
+pub fn render(markdown: &str) -> String {
+    let options = Options::ENABLE_TABLES
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_FOOTNOTES
+        | Options::ENABLE_TASKLISTS;
+
+    let mut events = Vec::new();
+    // 코드 블록 안이라면 (언어, 모은 코드)
+    let mut code: Option<(String, String)> = None;
+    for event in Parser::new_ext(markdown, options) {
+        match (event, code.as_mut()) {
+            (Event::Start(Tag::CodeBlock(kind)), _) => {
+                let lang = match kind {
+                    CodeBlockKind::Fenced(info) => {
+                        info.split_whitespace().next().unwrap_or("").to_string()
+                    }
+                    CodeBlockKind::Indented => String::new(),
+                };
+                code = Some((lang, String::new()));
+            }
+            (Event::Text(text), Some((_, buf))) => buf.push_str(&text),
+            (Event::End(TagEnd::CodeBlock), _) => {
+                if let Some((lang, buf)) = code.take() {
+                    events.push(Event::Html(highlight(&lang, &buf).into()));
+                }
+            }
+            (event, _) => events.push(event),
+        }
+    }
+
+    let mut html = String::new();
+    pulldown_cmark::html::push_html(&mut html, events.into_iter());
+    SANITIZER.clean(&html).to_string()
+}
+
+// 모르는 언어는 일반 텍스트로, 하이라이트에 실패하면 escape만 함
+fn highlight(lang: &str, code: &str) -> String {
+    let syntax = SYNTAXES
+        .find_syntax_by_token(lang)
+        .unwrap_or_else(|| SYNTAXES.find_syntax_plain_text());
+    let mut generator = ClassedHTMLGenerator::new_with_class_style(syntax, &SYNTAXES, CLASS_STYLE);
+    for line in LinesWithEndings::from(code) {
+        if generator
+            .parse_html_for_line_which_includes_newline(line)
+            .is_err()
+        {
+            let escaped = code
+                .replace('&', "&amp;")
+                .replace('<', "&lt;")
+                .replace('>', "&gt;");
+            return format!("<pre class=\"hl-code\"><code>{}</code></pre>", escaped);
+        }
+    }
+    format!(
+        "<pre class=\"hl-code\"><code>{}</code></pre>",
+        generator.finalize()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strips_scripts_and_event_handlers() {
+        let html = render(
+            "hi <script>alert(1)</script>\n\n<img src=\"a.png\" onerror=\"alert(1)\">\n\n<a href=\"#\" onclick=\"alert(1)\">x</a>",
+        );
+        assert!(!html.contains("<script"), "{}", html);
+        assert!(!html.contains("alert"), "{}", html);
+        assert!(html.contains("<img src=\"a.png\""), "{}", html);
+    }
+
+    #[test]
+    fn strips_javascript_links() {
+        for markdown in [
+            "[x](javascript:alert(1))",
+            "<a href=\"javascript:alert(1)\">x</a>",
+            "<a href=\"JavaScript:alert(1)\">x</a>",
+        ] {
+            let html = render(markdown);
+            assert!(!html.to_lowercase().contains("javascript:"), "{}", html);
+        }
+        assert!(render("[x](https://example.com)").contains("href=\"https://example.com\""));
+    }
+
+    #[test]
+    fn keeps_only_highlight_classes() {
+        let html = render(
+            "<span class=\"hl-keyword admin-banner\">a</span> <span class=\"danger\">b</span>",
+        );
+        assert!(
+            html.contains("<span class=\"hl-keyword\">a</span>"),
+            "{}",
+            html
+        );
+        assert!(html.contains("<span>b</span>"), "{}", html);
+
+        let html = render("```rust\nfn main() {}\n```");
+        assert!(html.contains("<pre class=\"hl-code\">"), "{}", html);
+        assert!(html.contains("class=\"hl-"), "{}", html);
+        assert!(!html.contains("class=\"source"), "{}", html);
+    }
+}
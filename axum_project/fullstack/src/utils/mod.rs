@@ -4,3 +4,16 @@ pub mod errors;
 pub mod hash;
 #[cfg(feature = "server")]
 pub mod jwt;
+#[cfg(feature = "server")]
+pub mod markdown;
+#[cfg(feature = "server")]
+pub mod oidc;
+#[cfg(feature = "server")]
+pub mod payment;
+#[cfg(feature = "server")]
+pub mod storage;
+#[cfg(all(test, feature = "server"))]
+pub mod testing;
+#[cfg(feature = "server")]
+pub mod upload;
+pub mod validate;
@@ -0,0 +1,91 @@
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use jsonwebtoken::{
+    Algorithm, EncodingKey, Header, encode,
+    jwk::{Jwk, JwkSet, ThumbprintHash},
+};
+use lazy_static::lazy_static;
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::env;
+use tracing::error;
+
+use crate::utils::errors::AppError;
+
+// id_token은 외부 앱이 JWKS로 검증할 수 있도록 RS256 비대칭키로 서명함
+// OIDC_PRIVATE_KEY는 PEM 파일 경로
+// openssl genpkey -algorithm RSA -pkeyopt rsa_keygen_bits:2048 -out oidc.pem
+struct OidcKey {
+    encoding: EncodingKey,
+    jwk: Jwk,
+}
+
+lazy_static! {
+    static ref OIDC_KEY: Option<OidcKey> = load_key();
+}
+
+fn load_key() -> Option<OidcKey> {
+    let path = env::var("OIDC_PRIVATE_KEY").ok()?;
+    let pem = std::fs::read(&path)
+        .map_err(|e| error!("Can't read OIDC key {}: {:?}", path, e))
+        .ok()?;
+    let encoding = EncodingKey::from_rsa_pem(&pem)
+        .map_err(|e| error!("Invalid OIDC key: {:?}", e))
+        .ok()?;
+    let mut jwk = Jwk::from_encoding_key(&encoding, Algorithm::RS256)
+        .map_err(|e| error!("Can't make JWK: {:?}", e))
+        .ok()?;
+    // 키 식별자는 공개키 thumbprint (RFC 7638)
+    jwk.common.key_id = Some(jwk.thumbprint(ThumbprintHash::SHA256));
+
+    Some(OidcKey { encoding, jwk })
+}
+
+fn oidc_key() -> Result<&'static OidcKey, AppError> {
+    OIDC_KEY.as_ref().ok_or(AppError::new(
+        StatusCode::INTERNAL_SERVER_ERROR,
+        "OIDC key is not configured",
+        None,
+    ))
+}
+
+// 발급자 식별자, discovery 문서와 id_token의 iss가 같아야함 (예: http://localhost:8080)
+pub fn issuer() -> Result<String, AppError> {
+    Ok(env::var("OIDC_ISSUER")?.trim_end_matches('/').to_string())
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct IdTokenClaims {
+    pub iss: String,
+    pub sub: String,
+    pub aud: String,
+    pub exp: u64,
+    pub iat: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preferred_username: Option<String>,
+}
+
+pub fn create_id_token(claims: &IdTokenClaims) -> Result<String, AppError> {
+    let key = oidc_key()?;
+    let mut header = Header::new(Algorithm::RS256);
+    header.kid = key.jwk.common.key_id.clone();
+
+    Ok(encode(&header, claims, &key.encoding)?)
+}
+
+pub fn jwks() -> Result<JwkSet, AppError> {
+    Ok(JwkSet {
+        keys: vec![oidc_key()?.jwk.clone()],
+    })
+}
+
+// RFC 7636 4.6: code_verifier를 code_challenge_method에 맞게 변환해서 비교
+pub fn verify_pkce(verifier: &str, challenge: &str, method: &str) -> bool {
+    match method {
+        "S256" => URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes())) == challenge,
+        "plain" => verifier == challenge,
+        _ => false,
+    }
+}
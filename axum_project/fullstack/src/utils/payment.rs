@@ -0,0 +1,573 @@
+use std::{env, sync::Arc, time::Duration};
+
+use axum::http::HeaderMap;
+use futures_util::future::BoxFuture;
+use hmac::{Hmac, Mac};
+use reqwest::StatusCode;
+use serde::Deserialize;
+use sha2::Sha256;
+use tracing::{error, warn};
+
+use crate::resources::dto::money::Money;
+use crate::utils::errors::AppError;
+
+// 결제 대행사(PSP) 연동, 결제 생성 -> 승인 -> 웹훅으로 결과 확정 순서
+// PAYMENT_PROVIDER 로 고름, 기본값이 없어서 mock도 직접 골라야 함
+pub trait PaymentProvider: Send + Sync {
+    // payment.provider 컬럼 값
+    fn name(&self) -> &'static str;
+    // 결제를 만들고 대행사의 결제 식별자를 돌려줌, 금액은 주문 총액
+    fn create_intent<'a>(
+        &'a self,
+        order_id: i32,
+        amount: Money,
+    ) -> BoxFuture<'a, Result<PaymentIntent, AppError>>;
+    // 결제 수단으로 승인을 요청함, 결과가 바로 나오지 않으면 Pending
+    fn confirm<'a>(
+        &'a self,
+        provider_ref: &'a str,
+        payment_method: &'a str,
+    ) -> BoxFuture<'a, Result<PaymentIntent, AppError>>;
+    // 서명을 확인하고 이벤트를 해석함, 결제와 관계없는 이벤트는 None
+    fn parse_webhook(
+        &self,
+        headers: &HeaderMap,
+        body: &[u8],
+    ) -> Result<Option<WebhookEvent>, AppError>;
+}
+
+pub type DynPayment = Arc<dyn PaymentProvider>;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PaymentStatus {
+    Pending,
+    Succeeded,
+    Declined,
+}
+impl PaymentStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PaymentStatus::Pending => "pending",
+            PaymentStatus::Succeeded => "succeeded",
+            PaymentStatus::Declined => "declined",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PaymentIntent {
+    pub provider_ref: String,
+    pub status: PaymentStatus,
+    // 브라우저에서 결제창을 띄울 때 쓰는 값 (Stripe client_secret)
+    pub client_secret: Option<String>,
+    pub failure_reason: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct WebhookEvent {
+    // 대행사의 이벤트 id, 같은 이벤트는 한 번만 처리함
+    pub event_id: String,
+    pub kind: String,
+    pub provider_ref: String,
+    pub status: PaymentStatus,
+    // 대행사가 알려준 결제 금액, 주문 금액과 다르면 결제 완료로 보지 않음
+    pub amount: Option<i64>,
+    pub failure_reason: Option<String>,
+}
+
+// 서명 시각과 지금의 차이가 이보다 크면 재전송 공격으로 보고 거절함
+const SIGNATURE_TOLERANCE_SECS: i64 = 300;
+pub const SIGNATURE_HEADER: &str = "stripe-signature";
+
+// "t=시각,v1=hex(HMAC-SHA256(secret, "{t}.{body}"))" 형식 (Stripe와 같음)
+pub fn sign(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("hmac accepts any key");
+    mac.update(format!("{}.", timestamp).as_bytes());
+    mac.update(body);
+    format!(
+        "t={},v1={}",
+        timestamp,
+        hex::encode(mac.finalize().into_bytes())
+    )
+}
+
+pub fn verify(secret: &str, headers: &HeaderMap, body: &[u8]) -> Result<(), AppError> {
+    let invalid = || AppError::new(StatusCode::BAD_REQUEST, "invalid webhook signature", None);
+
+    let header = headers
+        .get(SIGNATURE_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .ok_or_else(invalid)?;
+    let mut timestamp = None;
+    let mut signatures = Vec::new();
+    for part in header.split(',') {
+        match part.trim().split_once('=') {
+            Some(("t", v)) => timestamp = v.parse::<i64>().ok(),
+            Some(("v1", v)) => signatures.extend(hex::decode(v).ok()),
+            _ => {}
+        }
+    }
+    let timestamp = timestamp.ok_or_else(invalid)?;
+    if (chrono::Utc::now().timestamp() - timestamp).abs() > SIGNATURE_TOLERANCE_SECS {
+        return Err(invalid());
+    }
+
+    // 비밀키를 바꾸는 동안에는 서명이 여러 개 올 수 있음
+    let valid = signatures.iter().any(|signature| {
+        let mut mac =
+            Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("hmac accepts any key");
+        mac.update(format!("{}.", timestamp).as_bytes());
+        mac.update(body);
+        mac.verify_slice(signature).is_ok()
+    });
+    if !valid {
+        return Err(invalid());
+    }
+    Ok(())
+}
+
+// Stripe 결제 객체 (PaymentIntent) 중 쓰는 필드
+#[derive(Deserialize)]
+struct StripeIntent {
+    id: String,
+    status: String,
+    amount: Option<i64>,
+    client_secret: Option<String>,
+    last_payment_error: Option<StripeError>,
+}
+
+#[derive(Deserialize)]
+struct StripeError {
+    message: Option<String>,
+    payment_intent: Option<Box<StripeIntent>>,
+}
+
+#[derive(Deserialize)]
+struct StripeErrorBody {
+    error: StripeError,
+}
+
+#[derive(Deserialize)]
+struct StripeEvent {
+    id: String,
+    #[serde(rename = "type")]
+    kind: String,
+    data: StripeEventData,
+}
+
+#[derive(Deserialize)]
+struct StripeEventData {
+    object: StripeIntent,
+}
+
+impl StripeIntent {
+    fn status(&self) -> PaymentStatus {
+        match self.status.as_str() {
+            "succeeded" => PaymentStatus::Succeeded,
+            "requires_payment_method" | "canceled" => PaymentStatus::Declined,
+            // processing, requires_action, requires_confirmation, requires_capture
+            _ => PaymentStatus::Pending,
+        }
+    }
+
+    fn into_intent(self) -> PaymentIntent {
+        let status = self.status();
+        PaymentIntent {
+            provider_ref: self.id,
+            status,
+            client_secret: self.client_secret,
+            failure_reason: self
+                .last_payment_error
+                .and_then(|e| e.message)
+                .filter(|_| status == PaymentStatus::Declined),
+        }
+    }
+}
+
+// payment_intent.* 이벤트만 해석함 (mock도 같은 형식으로 보냄)
+fn parse_event(body: &[u8]) -> Result<Option<WebhookEvent>, AppError> {
+    let event: StripeEvent = serde_json::from_slice(body)
+        .map_err(|_| AppError::new(StatusCode::BAD_REQUEST, "invalid webhook body", None))?;
+    let status = match event.kind.as_str() {
+        "payment_intent.succeeded" => PaymentStatus::Succeeded,
+        "payment_intent.payment_failed" | "payment_intent.canceled" => PaymentStatus::Declined,
+        "payment_intent.processing" => PaymentStatus::Pending,
+        _ => return Ok(None),
+    };
+    let object = event.data.object;
+    Ok(Some(WebhookEvent {
+        event_id: event.id,
+        kind: event.kind,
+        amount: object.amount,
+        failure_reason: object.last_payment_error.and_then(|e| e.message),
+        provider_ref: object.id,
+        status,
+    }))
+}
+
+// Stripe PaymentIntent API
+// STRIPE_SECRET_KEY, STRIPE_WEBHOOK_SECRET
+pub struct StripeProvider {
+    client: reqwest::Client,
+    secret_key: String,
+    webhook_secret: String,
+    api_base: String,
+}
+
+impl StripeProvider {
+    pub fn from_env(client: reqwest::Client) -> Result<Self, AppError> {
+        let var = |name: &str| {
+            env::var(name).map_err(|_| AppError::any_t_error(format!("{} is not set", name)))
+        };
+        Ok(StripeProvider {
+            client,
+            secret_key: var("STRIPE_SECRET_KEY")?,
+            webhook_secret: var("STRIPE_WEBHOOK_SECRET")?,
+            api_base: env::var("STRIPE_API_BASE")
+                .unwrap_or("https://api.stripe.com/v1".to_string()),
+        })
+    }
+
+    async fn post(&self, path: &str, form: &[(&str, String)]) -> Result<PaymentIntent, AppError> {
+        let res = self
+            .client
+            .post(format!("{}{}", self.api_base, path))
+            .bearer_auth(&self.secret_key)
+            .form(form)
+            .send()
+            .await
+            .map_err(AppError::any_t_error)?;
+
+        if res.status().is_success() {
+            let intent: StripeIntent = res.json().await.map_err(AppError::any_t_error)?;
+            return Ok(intent.into_intent());
+        }
+
+        // 카드 거절은 402와 함께 결제 객체가 같이 옴
+        let status = res.status();
+        let body: StripeErrorBody = res.json().await.map_err(AppError::any_t_error)?;
+        match body.error.payment_intent {
+            Some(intent) => Ok(PaymentIntent {
+                failure_reason: body.error.message,
+                status: PaymentStatus::Declined,
+                ..(*intent).into_intent()
+            }),
+            None => {
+                error!("Stripe error {}: {:?}", status, body.error.message);
+                Err(AppError::new(
+                    StatusCode::BAD_GATEWAY,
+                    body.error
+                        .message
+                        .unwrap_or("payment provider error".to_string()),
+                    None,
+                ))
+            }
+        }
+    }
+}
+
+impl PaymentProvider for StripeProvider {
+    fn name(&self) -> &'static str {
+        "stripe"
+    }
+
+    fn create_intent<'a>(
+        &'a self,
+        order_id: i32,
+        amount: Money,
+    ) -> BoxFuture<'a, Result<PaymentIntent, AppError>> {
+        // Stripe도 금액을 통화의 최소 단위로 받음 (KRW는 소수점 없음)
+        Box::pin(async move {
+            self.post(
+                "/payment_intents",
+                &[
+                    ("amount", amount.amount.to_string()),
+                    ("currency", amount.currency.as_str().to_lowercase()),
+                    ("metadata[order_id]", order_id.to_string()),
+                ],
+            )
+            .await
+        })
+    }
+
+    fn confirm<'a>(
+        &'a self,
+        provider_ref: &'a str,
+        payment_method: &'a str,
+    ) -> BoxFuture<'a, Result<PaymentIntent, AppError>> {
+        Box::pin(async move {
+            self.post(
+                &format!("/payment_intents/{}/confirm", provider_ref),
+                &[("payment_method", payment_method.to_string())],
+            )
+            .await
+        })
+    }
+
+    fn parse_webhook(
+        &self,
+        headers: &HeaderMap,
+        body: &[u8],
+    ) -> Result<Option<WebhookEvent>, AppError> {
+        verify(&self.webhook_secret, headers, body)?;
+        parse_event(body)
+    }
+}
+
+// 외부 통신 없이 결제를 흉내냄, 결제 수단 값으로 결과를 고름
+// - success: 바로 승인, decline: 바로 거절, delay: 처리 중으로 두고 MOCK_PAYMENT_DELAY_SECS 뒤에 승인
+// 모든 결과는 실제 대행사처럼 MOCK_WEBHOOK_URL 로 서명된 웹훅을 다시 보냄
+// 누구나 success로 결제를 끝낼 수 있으므로 운영 환경에서는 쓰면 안 됨
+pub struct MockProvider {
+    client: reqwest::Client,
+    webhook_secret: String,
+    webhook_url: String,
+    delay: Duration,
+}
+
+pub const MOCK_SUCCESS: &str = "success";
+pub const MOCK_DECLINE: &str = "decline";
+pub const MOCK_DELAY: &str = "delay";
+
+impl MockProvider {
+    pub fn from_env(client: reqwest::Client) -> Result<Self, AppError> {
+        // 알려진 기본 비밀키를 쓰면 누구나 웹훅을 위조할 수 있으므로 반드시 설정해야 함
+        let webhook_secret = env::var("MOCK_WEBHOOK_SECRET")
+            .ok()
+            .filter(|v| !v.is_empty())
+            .ok_or_else(|| AppError::any_t_error("MOCK_WEBHOOK_SECRET is not set"))?;
+        Ok(MockProvider::new(
+            client,
+            webhook_secret,
+            env::var("MOCK_WEBHOOK_URL")
+                .unwrap_or("http://127.0.0.1:8080/api/payment/webhook".to_string()),
+            Duration::from_secs(
+                env::var("MOCK_PAYMENT_DELAY_SECS")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(3),
+            ),
+        ))
+    }
+
+    pub fn new(
+        client: reqwest::Client,
+        webhook_secret: String,
+        webhook_url: String,
+        delay: Duration,
+    ) -> Self {
+        MockProvider {
+            client,
+            webhook_secret,
+            webhook_url,
+            delay,
+        }
+    }
+
+    // 응답을 돌려준 뒤에 웹훅이 도착하도록 별도 작업으로 보냄
+    fn send_webhook(&self, provider_ref: &str, status: PaymentStatus, delay: Duration) {
+        let kind = match status {
+            PaymentStatus::Succeeded => "payment_intent.succeeded",
+            PaymentStatus::Declined => "payment_intent.payment_failed",
+            PaymentStatus::Pending => "payment_intent.processing",
+        };
+        let body = serde_json::json!({
+            "id": format!("evt_mock_{}", crate::utils::jwt::random_id()),
+            "type": kind,
+            "data": { "object": {
+                "id": provider_ref,
+                "status": status.as_str(),
+                "last_payment_error": (status == PaymentStatus::Declined)
+                    .then(|| serde_json::json!({ "message": "card declined (mock)" })),
+            }},
+        })
+        .to_string();
+
+        let client = self.client.clone();
+        let secret = self.webhook_secret.clone();
+        let url = self.webhook_url.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(delay).await;
+            let signature = sign(&secret, chrono::Utc::now().timestamp(), body.as_bytes());
+            let res = client
+                .post(&url)
+                .header(SIGNATURE_HEADER, signature)
+                .header(
+                    reqwest::header::CONTENT_TYPE,
+                    mime::APPLICATION_JSON.as_ref(),
+                )
+                .body(body)
+                .send()
+                .await;
+            match res {
+                Ok(res) if res.status().is_success() => {}
+                Ok(res) => warn!("Mock webhook rejected: {}", res.status()),
+                Err(e) => warn!("Mock webhook failed: {:?}", e),
+            }
+        });
+    }
+}
+
+impl PaymentProvider for MockProvider {
+    fn name(&self) -> &'static str {
+        "mock"
+    }
+
+    fn create_intent<'a>(
+        &'a self,
+        _order_id: i32,
+        _amount: Money,
+    ) -> BoxFuture<'a, Result<PaymentIntent, AppError>> {
+        Box::pin(async move {
+            let provider_ref = format!("mock_pi_{}", crate::utils::jwt::random_id());
+            Ok(PaymentIntent {
+                client_secret: Some(format!("{}_secret", provider_ref)),
+                provider_ref,
+                status: PaymentStatus::Pending,
+                failure_reason: None,
+            })
+        })
+    }
+
+    fn confirm<'a>(
+        &'a self,
+        provider_ref: &'a str,
+        payment_method: &'a str,
+    ) -> BoxFuture<'a, Result<PaymentIntent, AppError>> {
+        Box::pin(async move {
+            let (status, webhook, delay) = match payment_method {
+                MOCK_SUCCESS => (
+                    PaymentStatus::Succeeded,
+                    PaymentStatus::Succeeded,
+                    Duration::ZERO,
+                ),
+                MOCK_DECLINE => (
+                    PaymentStatus::Declined,
+                    PaymentStatus::Declined,
+                    Duration::ZERO,
+                ),
+                MOCK_DELAY => (PaymentStatus::Pending, PaymentStatus::Succeeded, self.delay),
+                _ => {
+                    return Err(AppError::new(
+                        StatusCode::BAD_REQUEST,
+                        "mock payment method must be success, decline or delay",
+                        None,
+                    ));
+                }
+            };
+            self.send_webhook(provider_ref, webhook, delay);
+
+            Ok(PaymentIntent {
+                provider_ref: provider_ref.to_string(),
+                status,
+                client_secret: None,
+                failure_reason: (status == PaymentStatus::Declined)
+                    .then(|| "card declined (mock)".to_string()),
+            })
+        })
+    }
+
+    fn parse_webhook(
+        &self,
+        headers: &HeaderMap,
+        body: &[u8],
+    ) -> Result<Option<WebhookEvent>, AppError> {
+        verify(&self.webhook_secret, headers, body)?;
+        parse_event(body)
+    }
+}
+
+// PAYMENT_PROVIDER=stripe | mock, 설정하지 않으면 결제 없이 시작하고 모르는 값이면 시작하지 않음
+pub fn from_env(client: reqwest::Client) -> Result<Option<DynPayment>, AppError> {
+    match env::var("PAYMENT_PROVIDER").as_deref() {
+        Ok("stripe") => Ok(Some(Arc::new(StripeProvider::from_env(client)?))),
+        Ok("mock") => {
+            warn!(
+                "PAYMENT_PROVIDER=mock, payments are simulated and must not be used in production"
+            );
+            Ok(Some(Arc::new(MockProvider::from_env(client)?)))
+        }
+        Ok(other) => Err(AppError::any_t_error(format!(
+            "unknown PAYMENT_PROVIDER {}",
+            other
+        ))),
+        Err(_) => {
+            warn!("PAYMENT_PROVIDER is not set, payment routes are disabled");
+            Ok(None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    const SECRET: &str = "whsec_test";
+    const BODY: &[u8] = br#"{"id":"evt_1"}"#;
+
+    fn headers(signature: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(SIGNATURE_HEADER, HeaderValue::from_str(signature).unwrap());
+        headers
+    }
+
+    fn now() -> i64 {
+        chrono::Utc::now().timestamp()
+    }
+
+    #[test]
+    fn accepts_fresh_signature() {
+        let signature = sign(SECRET, now(), BODY);
+        assert!(verify(SECRET, &headers(&signature), BODY).is_ok());
+    }
+
+    #[test]
+    fn accepts_any_of_several_signatures() {
+        let old = sign("whsec_old", now(), BODY);
+        let new = sign(SECRET, now(), BODY);
+        let v1 = new.split_once(",v1=").unwrap().1;
+        let rotating = format!("{},v1={}", old, v1);
+        assert!(verify(SECRET, &headers(&rotating), BODY).is_ok());
+    }
+
+    #[test]
+    fn rejects_bad_or_stale_signatures() {
+        let stale = now() - SIGNATURE_TOLERANCE_SECS - 1;
+        let future = now() + SIGNATURE_TOLERANCE_SECS + 1;
+        let cases = [
+            sign("whsec_other", now(), BODY),
+            sign(SECRET, stale, BODY),
+            sign(SECRET, future, BODY),
+            sign(SECRET, now(), br#"{"id":"evt_2"}"#),
+            format!("t={}", now()),
+            format!("t={},v1=zz", now()),
+            "garbage".to_string(),
+        ];
+        for signature in cases {
+            let err = verify(SECRET, &headers(&signature), BODY).unwrap_err();
+            assert_eq!(err.code, 400, "{}", signature);
+        }
+        assert!(verify(SECRET, &HeaderMap::new(), BODY).is_err());
+    }
+
+    #[test]
+    fn parses_only_payment_intent_events() {
+        let event = parse_event(
+            br#"{"id":"evt_1","type":"payment_intent.succeeded","data":{"object":{"id":"pi_1","status":"succeeded","amount":1500}}}"#,
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(event.event_id, "evt_1");
+        assert_eq!(event.provider_ref, "pi_1");
+        assert_eq!(event.status, PaymentStatus::Succeeded);
+        assert_eq!(event.amount, Some(1500));
+
+        let ignored = parse_event(
+            br#"{"id":"evt_2","type":"charge.refunded","data":{"object":{"id":"ch_1","status":"succeeded"}}}"#,
+        )
+        .unwrap();
+        assert!(ignored.is_none());
+        assert!(parse_event(b"not json").is_err());
+    }
+}
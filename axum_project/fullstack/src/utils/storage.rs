@@ -0,0 +1,88 @@
+use std::{env, path::PathBuf, sync::Arc};
+
+use futures_util::future::BoxFuture;
+use tracing::error;
+
+use crate::utils::errors::AppError;
+
+// 업로드 파일 저장소, 지금은 로컬 디렉터리만 구현함
+// S3 같은 외부 저장소로 바꿀 때는 이 trait만 구현하면 됨
+pub trait Storage: Send + Sync {
+    fn put<'a>(&'a self, key: &'a str, bytes: Vec<u8>) -> BoxFuture<'a, Result<(), AppError>>;
+    fn get<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Result<Vec<u8>, AppError>>;
+    fn delete<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Result<(), AppError>>;
+    // 브라우저에서 접근할 수 있는 주소
+    fn url(&self, key: &str) -> String;
+}
+
+pub type DynStorage = Arc<dyn Storage>;
+
+// STORAGE_DIR(기본 ./media) 에 저장하고 MEDIA_URL(/media) 에서 ServeDir로 제공
+pub const MEDIA_URL: &str = "/media";
+
+pub struct LocalStorage {
+    pub root: PathBuf,
+}
+
+impl LocalStorage {
+    pub fn from_env() -> Self {
+        let root = env::var("STORAGE_DIR").unwrap_or("./media".to_string());
+        LocalStorage { root: root.into() }
+    }
+
+    // 키는 서버에서 만들지만, 저장소 밖으로 나가는 경로는 막아둠
+    fn path(&self, key: &str) -> Result<PathBuf, AppError> {
+        if key
+            .split('/')
+            .any(|p| p.is_empty() || p == "." || p == "..")
+        {
+            return Err(AppError::any_t_error(format!(
+                "invalid storage key {}",
+                key
+            )));
+        }
+        Ok(self.root.join(key))
+    }
+}
+
+impl Storage for LocalStorage {
+    fn put<'a>(&'a self, key: &'a str, bytes: Vec<u8>) -> BoxFuture<'a, Result<(), AppError>> {
+        Box::pin(async move {
+            let path = self.path(key)?;
+            if let Some(parent) = path.parent() {
+                tokio::fs::create_dir_all(parent)
+                    .await
+                    .map_err(AppError::any_t_error)?;
+            }
+            tokio::fs::write(&path, bytes)
+                .await
+                .map_err(AppError::any_t_error)
+        })
+    }
+
+    fn get<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Result<Vec<u8>, AppError>> {
+        Box::pin(async move {
+            let path = self.path(key)?;
+            tokio::fs::read(&path).await.map_err(AppError::any_t_error)
+        })
+    }
+
+    fn delete<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Result<(), AppError>> {
+        Box::pin(async move {
+            let path = self.path(key)?;
+            match tokio::fs::remove_file(&path).await {
+                Ok(_) => Ok(()),
+                // 이미 없는 파일은 지워진 것으로 취급
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+                Err(e) => {
+                    error!("Can't delete {:?}: {:?}", path, e);
+                    Err(AppError::any_error())
+                }
+            }
+        })
+    }
+
+    fn url(&self, key: &str) -> String {
+        format!("{}/{}", MEDIA_URL, key)
+    }
+}
@@ -0,0 +1,88 @@
+// DB를 쓰는 테스트의 공통 준비
+// 마이그레이션을 끝낸 Postgres가 필요함, 테스트마다 이름이 겹치지 않는 행을 만들고 끝나면 지움
+// DB 테스트는 #[ignore = "needs DATABASE_URL"]로 표시해서 DB 없이 cargo test를 돌려도 실패하지 않게 함
+use sea_orm::{ActiveModelTrait, ActiveValue::Set, Database, DatabaseConnection, EntityTrait};
+
+use crate::resources::entities::{category, orders, product, users};
+
+pub async fn connect() -> DatabaseConnection {
+    let database_url = std::env::var("DATABASE_URL")
+        .expect("DB tests need DATABASE_URL pointing at a migrated database");
+    Database::connect(database_url)
+        .await
+        .expect("cannot connect to DATABASE_URL")
+}
+
+pub fn unique(prefix: &str) -> String {
+    format!("{}-{:016x}", prefix, rand::random::<u64>())
+}
+
+// 카테고리 하나에 상품 하나, 재고와 재고 기록은 상품을 지우면 같이 지워짐
+pub async fn product(price: i64, conn: &DatabaseConnection) -> product::Model {
+    let slug = unique("test");
+    let category = category::ActiveModel {
+        name: Set(slug.clone()),
+        slug: Set(slug.clone()),
+        sort_order: Set(0),
+        ..Default::default()
+    }
+    .insert(conn)
+    .await
+    .unwrap();
+    product::ActiveModel {
+        title: Set(slug),
+        price: Set(price),
+        category_id: Set(category.id),
+        currency: Set("KRW".to_string()),
+        ..Default::default()
+    }
+    .insert(conn)
+    .await
+    .unwrap()
+}
+
+pub async fn remove_product(product: product::Model, conn: &DatabaseConnection) {
+    product::Entity::delete_by_id(product.id)
+        .exec(conn)
+        .await
+        .unwrap();
+    category::Entity::delete_by_id(product.category_id)
+        .exec(conn)
+        .await
+        .unwrap();
+}
+
+pub async fn user(conn: &DatabaseConnection) -> users::Model {
+    users::ActiveModel {
+        username: Set(format!("{}@example.com", unique("user"))),
+        ..Default::default()
+    }
+    .insert(conn)
+    .await
+    .unwrap()
+}
+
+// 상품 없이 금액만 있는 pending 주문, remove_order가 주문한 유저도 같이 지움
+pub async fn order(user: &users::Model, total: i64, conn: &DatabaseConnection) -> orders::Model {
+    orders::ActiveModel {
+        user_id: Set(Some(user.id)),
+        total: Set(total),
+        ..Default::default()
+    }
+    .insert(conn)
+    .await
+    .unwrap()
+}
+
+pub async fn remove_order(order: orders::Model, conn: &DatabaseConnection) {
+    orders::Entity::delete_by_id(order.id)
+        .exec(conn)
+        .await
+        .unwrap();
+    if let Some(user_id) = order.user_id {
+        users::Entity::delete_by_id(user_id)
+            .exec(conn)
+            .await
+            .unwrap();
+    }
+}
@@ -0,0 +1,111 @@
+// 프로필 사진, 상품 이미지 업로드의 공통 처리
+use std::collections::HashMap;
+
+use axum::extract::{DefaultBodyLimit, Multipart};
+use image::{DynamicImage, ImageFormat, ImageReader, Limits};
+use reqwest::StatusCode;
+
+use crate::utils::errors::{ApiError, AppError};
+
+pub struct ImageRules {
+    // 파일을 담은 multipart 필드
+    pub field: &'static str,
+    // 파일과 같이 받을 텍스트 필드, 나머지 필드는 무시함
+    pub text_fields: &'static [&'static str],
+    pub max_bytes: usize,
+    // 가로, 세로 최대 픽셀
+    pub max_side: u32,
+}
+
+pub struct ImageUpload {
+    pub bytes: Vec<u8>,
+    pub fields: HashMap<&'static str, String>,
+}
+
+impl ImageRules {
+    // 업로드는 기본 본문 제한(2MB)보다 크게 허용, multipart 경계 여유분 포함
+    pub fn body_limit(&self) -> DefaultBodyLimit {
+        DefaultBodyLimit::max(self.max_bytes + 64 * 1024)
+    }
+
+    fn too_large(&self) -> ApiError {
+        ApiError::new(
+            StatusCode::PAYLOAD_TOO_LARGE,
+            "too_large",
+            format!(
+                "{} must be {}MB or less",
+                self.field,
+                self.max_bytes / 1024 / 1024
+            ),
+        )
+    }
+
+    fn unsupported(&self) -> ApiError {
+        ApiError::new(
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            "unsupported_media_type",
+            format!(
+                "{} must be png, jpeg, gif or webp and {}px or less",
+                self.field, self.max_side
+            ),
+        )
+    }
+
+    pub async fn read(&self, multipart: &mut Multipart) -> Result<ImageUpload, ApiError> {
+        let mut bytes = None;
+        let mut fields = HashMap::new();
+        // 본문 크기 제한(DefaultBodyLimit)에 걸리면 필드를 읽다가 에러가 남
+        while let Some(field) = multipart.next_field().await.map_err(|_| self.too_large())? {
+            let name = field.name().unwrap_or_default();
+            if name == self.field {
+                let data = field.bytes().await.map_err(|_| self.too_large())?;
+                if data.len() > self.max_bytes {
+                    return Err(self.too_large());
+                }
+                bytes = Some(data.to_vec());
+            } else if let Some(name) = self.text_fields.iter().find(|f| **f == name) {
+                let text = field.text().await.map_err(|_| self.too_large())?;
+                fields.insert(*name, text);
+            }
+        }
+
+        let bytes = bytes.ok_or_else(|| {
+            ApiError::new(
+                StatusCode::BAD_REQUEST,
+                "invalid_request",
+                format!("{} field is required", self.field),
+            )
+        })?;
+        Ok(ImageUpload { bytes, fields })
+    }
+
+    // Content-Type은 클라이언트가 정하므로 실제 내용으로 형식을 확인하고, 디코딩 전에 가로세로 크기를 제한함
+    pub fn decode(&self, bytes: &[u8]) -> Result<DynamicImage, ApiError> {
+        let mut reader = ImageReader::new(std::io::Cursor::new(bytes))
+            .with_guessed_format()
+            .map_err(|_| self.unsupported())?;
+        if !matches!(
+            reader.format(),
+            Some(ImageFormat::Png | ImageFormat::Jpeg | ImageFormat::Gif | ImageFormat::WebP)
+        ) {
+            return Err(self.unsupported());
+        }
+        let mut limits = Limits::default();
+        limits.max_image_width = Some(self.max_side);
+        limits.max_image_height = Some(self.max_side);
+        reader.limits(limits);
+        reader.decode().map_err(|_| self.unsupported())
+    }
+}
+
+// 이미지 변환은 CPU 작업이라 별도 스레드에서 처리
+pub async fn encode_blocking<T, E, F>(encode: F) -> Result<T, E>
+where
+    F: FnOnce() -> Result<T, E> + Send + 'static,
+    T: Send + 'static,
+    E: From<AppError> + Send + 'static,
+{
+    tokio::task::spawn_blocking(encode)
+        .await
+        .map_err(|e| E::from(AppError::any_t_error(e)))?
+}
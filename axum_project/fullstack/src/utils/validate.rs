@@ -0,0 +1,241 @@
+use serde::{Deserialize, Serialize};
+
+// #[validate_dto] 매크로가 구현해주는 검사
+// 규칙은 validate-dto-macro 참고
+pub trait Validate {
+    fn validate(&self) -> Result<(), ValidationErrors>;
+}
+
+// nested로 쓸 수 있도록, None은 검사하지 않음
+impl<T: Validate> Validate for Option<T> {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        self.as_ref().map_or(Ok(()), |v| v.validate())
+    }
+}
+
+// 에러 필드 이름 앞에 순번을 붙임 (attributes.0.value)
+impl<T: Validate> Validate for Vec<T> {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::default();
+        for (i, value) in self.iter().enumerate() {
+            check_nested(&mut errors, &i.to_string(), value);
+        }
+        errors.into_result()
+    }
+}
+
+// 어떤 필드가 어떤 규칙을 어겼는지 클라이언트가 읽을 수 있도록 그대로 내려줌
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct FieldError {
+    pub field: String,
+    // length, range, url, email
+    pub code: String,
+    pub message: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct ValidationErrors(pub Vec<FieldError>);
+
+impl ValidationErrors {
+    pub fn add(&mut self, field: &str, code: &str, message: String) {
+        self.0.push(FieldError {
+            field: field.to_string(),
+            code: code.to_string(),
+            message,
+        });
+    }
+    pub fn into_result(self) -> Result<(), Self> {
+        if self.0.is_empty() { Ok(()) } else { Err(self) }
+    }
+}
+
+// Option 필드는 값이 있을 때만 검사함 (None이면 검사하지 않음)
+pub trait CheckValue {
+    fn text(&self) -> Option<&str> {
+        None
+    }
+    fn number(&self) -> Option<f64> {
+        None
+    }
+}
+impl CheckValue for String {
+    fn text(&self) -> Option<&str> {
+        Some(self)
+    }
+}
+impl<T: CheckValue> CheckValue for Option<T> {
+    fn text(&self) -> Option<&str> {
+        self.as_ref().and_then(|v| v.text())
+    }
+    fn number(&self) -> Option<f64> {
+        self.as_ref().and_then(|v| v.number())
+    }
+}
+macro_rules! impl_check_number {
+    ($($t:ty),*) => {
+        $(impl CheckValue for $t {
+            fn number(&self) -> Option<f64> {
+                Some(*self as f64)
+            }
+        })*
+    };
+}
+impl_check_number!(i32, i64, u32, u64, usize, f32, f64);
+
+pub fn check_length<T: CheckValue>(
+    errors: &mut ValidationErrors,
+    field: &str,
+    value: &T,
+    min: Option<usize>,
+    max: Option<usize>,
+) {
+    let Some(len) = value.text().map(|v| v.chars().count()) else {
+        return;
+    };
+    if min.is_some_and(|min| len < min) || max.is_some_and(|max| len > max) {
+        let message = match (min, max) {
+            (Some(min), Some(max)) => format!("length must be between {} and {}", min, max),
+            (Some(min), None) => format!("length must be at least {}", min),
+            (None, _) => format!("length must be at most {}", max.unwrap_or_default()),
+        };
+        errors.add(field, "length", message);
+    }
+}
+
+pub fn check_range<T: CheckValue>(
+    errors: &mut ValidationErrors,
+    field: &str,
+    value: &T,
+    min: Option<f64>,
+    max: Option<f64>,
+) {
+    let Some(number) = value.number() else {
+        return;
+    };
+    if min.is_some_and(|min| number < min) || max.is_some_and(|max| number > max) {
+        let message = match (min, max) {
+            (Some(min), Some(max)) => format!("must be between {} and {}", min, max),
+            (Some(min), None) => format!("must be at least {}", min),
+            (None, _) => format!("must be at most {}", max.unwrap_or_default()),
+        };
+        errors.add(field, "range", message);
+    }
+}
+
+pub fn check_url<T: CheckValue>(errors: &mut ValidationErrors, field: &str, value: &T) {
+    let Some(text) = value.text() else {
+        return;
+    };
+    let valid = reqwest::Url::parse(text)
+        .is_ok_and(|url| matches!(url.scheme(), "http" | "https") && url.has_host());
+    if !valid {
+        errors.add(field, "url", "must be an absolute http(s) url".to_string());
+    }
+}
+
+// 메일 형식은 최소한만 확인함 (local@domain.tld)
+pub fn check_email<T: CheckValue>(errors: &mut ValidationErrors, field: &str, value: &T) {
+    let Some(text) = value.text() else {
+        return;
+    };
+    let valid = match text.split_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty()
+                && !domain.contains('@')
+                && domain.split('.').filter(|p| !p.is_empty()).count() >= 2
+                && !domain.starts_with('.')
+                && !domain.ends_with('.')
+                && !text.contains(char::is_whitespace)
+        }
+        None => false,
+    };
+    if !valid {
+        errors.add(field, "email", "must be an email address".to_string());
+    }
+}
+
+// 안쪽 타입의 검사 결과를 "필드.안쪽필드" 이름으로 붙임
+pub fn check_nested<T: Validate>(errors: &mut ValidationErrors, field: &str, value: &T) {
+    if let Err(inner) = value.validate() {
+        for e in inner.0 {
+            errors.add(&format!("{}.{}", field, e.field), &e.code, e.message);
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+pub use extract::{ValidatedForm, ValidatedJson, ValidatedQuery};
+
+// 역직렬화 후 검사까지 통과해야 핸들러로 넘어가는 추출기
+// 실패하면 리디렉션하지 않고 422와 필드 에러 목록을 JSON으로 돌려줌
+#[cfg(feature = "server")]
+mod extract {
+    use axum::extract::{FromRequest, FromRequestParts, Request};
+    use axum::http::request::Parts;
+    use axum::response::IntoResponse;
+    use serde::de::DeserializeOwned;
+
+    use super::Validate;
+    use crate::utils::errors::ApiError;
+
+    // 형식이 잘못된 본문은 axum이 정한 상태코드(400, 415, 422)를 그대로 사용
+    fn rejected(rejection: impl IntoResponse + std::fmt::Display) -> ApiError {
+        let message = rejection.to_string();
+        let status = rejection.into_response().status();
+        ApiError::new(status, "invalid_body", message)
+    }
+
+    pub struct ValidatedJson<T>(pub T);
+    pub struct ValidatedForm<T>(pub T);
+    pub struct ValidatedQuery<T>(pub T);
+
+    impl<T, S> FromRequest<S> for ValidatedJson<T>
+    where
+        T: DeserializeOwned + Validate,
+        S: Send + Sync,
+    {
+        type Rejection = ApiError;
+
+        async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+            let axum::Json(value) = axum::Json::<T>::from_request(req, state)
+                .await
+                .map_err(rejected)?;
+            value.validate()?;
+            Ok(Self(value))
+        }
+    }
+
+    impl<T, S> FromRequest<S> for ValidatedForm<T>
+    where
+        T: DeserializeOwned + Validate,
+        S: Send + Sync,
+    {
+        type Rejection = ApiError;
+
+        async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+            let axum::Form(value) = axum::Form::<T>::from_request(req, state)
+                .await
+                .map_err(rejected)?;
+            value.validate()?;
+            Ok(Self(value))
+        }
+    }
+
+    impl<T, S> FromRequestParts<S> for ValidatedQuery<T>
+    where
+        T: DeserializeOwned + Validate,
+        S: Send + Sync,
+    {
+        type Rejection = ApiError;
+
+        async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+            let axum::extract::Query(value) =
+                axum::extract::Query::<T>::from_request_parts(parts, state)
+                    .await
+                    .map_err(rejected)?;
+            value.validate()?;
+            Ok(Self(value))
+        }
+    }
+}
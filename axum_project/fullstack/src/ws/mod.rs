@@ -35,7 +35,10 @@ pub fn init_router(aex: AppExtension) -> Router {
         .routes(routes!(websocket_handler))
         .routes(routes!(chat::chat_ws_handler))
         .with_state(aex.ws.clone())
-        .layer(middleware::from_fn(authenticate));
+        .layer(middleware::from_fn_with_state(
+            aex.db.0.clone(),
+            authenticate,
+        ));
 
     let unauth_router = OpenApiRouter::new().with_state(aex.ws);
 
@@ -0,0 +1,213 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{Data, DataStruct, DeriveInput, Expr, Fields, Ident, parse_macro_input};
+
+// 필드의 #[validate(...)] 규칙을 읽어서
+// 1. crate::utils::validate::Validate 구현을 만들고
+// 2. 같은 제약을 utoipa의 #[schema(...)] (params 인자가 있으면 #[param(...)]도) 로 옮겨줌
+// derive는 필드 속성을 바꿀 수 없어서 attribute 매크로로 만들었음, 그래서 derive보다 위에 있어야함
+//
+// #[validate_dto(params)]
+// #[cfg_attr(feature = "server", derive(utoipa::IntoParams, utoipa::ToSchema))]
+// #[derive(Deserialize)]
+// struct Req {
+//     #[validate(range(min = 1, max = 100))]
+//     limit: Option<u64>,
+// }
+#[proc_macro_attribute]
+pub fn validate_dto(args: TokenStream, item: TokenStream) -> TokenStream {
+    let mut with_params = false;
+    let args_parser = syn::meta::parser(|meta| {
+        if meta.path.is_ident("params") {
+            with_params = true;
+            Ok(())
+        } else {
+            Err(meta.error("only `params` is supported"))
+        }
+    });
+    parse_macro_input!(args with args_parser);
+
+    let mut ast = parse_macro_input!(item as DeriveInput);
+    let st_name = ast.ident.clone();
+
+    let fields = match &mut ast.data {
+        Data::Struct(DataStruct {
+            fields: Fields::Named(named),
+            ..
+        }) => &mut named.named,
+        _ => {
+            return syn::Error::new_spanned(&ast.ident, "only works for struct with named fields")
+                .to_compile_error()
+                .into();
+        }
+    };
+
+    let mut checks = Vec::new();
+    for field in fields.iter_mut() {
+        let name = field.ident.clone().expect("named field");
+        let mut rules = Vec::new();
+
+        // validate 속성은 컴파일러가 모르는 속성이라 반드시 제거해야함
+        let mut error = None;
+        field.attrs.retain(|attr| {
+            if !attr.path().is_ident("validate") {
+                return true;
+            }
+            if let Err(e) = parse_rules(attr, &mut rules) {
+                error = Some(e);
+            }
+            false
+        });
+        if let Some(e) = error {
+            return e.to_compile_error().into();
+        }
+        if rules.is_empty() {
+            continue;
+        }
+
+        let schema_args: Vec<TokenStream2> = rules.iter().flat_map(Rule::schema_args).collect();
+        // nested는 안쪽 타입의 스키마를 그대로 씀
+        if !schema_args.is_empty() {
+            field.attrs.push(syn::parse_quote! {
+                #[cfg_attr(feature = "server", schema(#(#schema_args),*))]
+            });
+        }
+        if with_params && !schema_args.is_empty() {
+            field.attrs.push(syn::parse_quote! {
+                #[cfg_attr(feature = "server", param(#(#schema_args),*))]
+            });
+        }
+
+        let field_name = name.to_string();
+        checks.extend(rules.iter().map(|rule| rule.check(&name, &field_name)));
+    }
+
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+    let res = quote! {
+        #ast
+
+        impl #impl_generics crate::utils::validate::Validate for #st_name #ty_generics #where_clause {
+            fn validate(&self) -> Result<(), crate::utils::validate::ValidationErrors> {
+                let mut errors = crate::utils::validate::ValidationErrors::default();
+                #(#checks)*
+                errors.into_result()
+            }
+        }
+    };
+
+    res.into()
+}
+
+enum Rule {
+    Length(Option<Expr>, Option<Expr>),
+    Range(Option<Expr>, Option<Expr>),
+    Url,
+    Email,
+    Nested,
+}
+
+fn parse_bounds(meta: &syn::meta::ParseNestedMeta) -> syn::Result<(Option<Expr>, Option<Expr>)> {
+    let mut min = None;
+    let mut max = None;
+    meta.parse_nested_meta(|inner| {
+        if inner.path.is_ident("min") {
+            min = Some(inner.value()?.parse()?);
+        } else if inner.path.is_ident("max") {
+            max = Some(inner.value()?.parse()?);
+        } else {
+            return Err(inner.error("expected `min` or `max`"));
+        }
+        Ok(())
+    })?;
+    Ok((min, max))
+}
+
+fn parse_rules(attr: &syn::Attribute, rules: &mut Vec<Rule>) -> syn::Result<()> {
+    attr.parse_nested_meta(|meta| {
+        if meta.path.is_ident("length") {
+            let (min, max) = parse_bounds(&meta)?;
+            rules.push(Rule::Length(min, max));
+        } else if meta.path.is_ident("range") {
+            let (min, max) = parse_bounds(&meta)?;
+            rules.push(Rule::Range(min, max));
+        } else if meta.path.is_ident("url") {
+            rules.push(Rule::Url);
+        } else if meta.path.is_ident("email") {
+            rules.push(Rule::Email);
+        } else if meta.path.is_ident("nested") {
+            rules.push(Rule::Nested);
+        } else {
+            return Err(meta.error("expected `length`, `range`, `url`, `email` or `nested`"));
+        }
+        Ok(())
+    })
+}
+
+// 검사 함수는 usize, f64로 비교하므로 정수 리터럴을 변환해줌
+fn option_tokens(value: &Option<Expr>, ty: TokenStream2) -> TokenStream2 {
+    match value {
+        Some(v) => quote! { Some((#v) as #ty) },
+        None => quote! { None },
+    }
+}
+
+impl Rule {
+    fn schema_args(&self) -> Vec<TokenStream2> {
+        let mut args = Vec::new();
+        match self {
+            Rule::Length(min, max) => {
+                if let Some(min) = min {
+                    args.push(quote! { min_length = #min });
+                }
+                if let Some(max) = max {
+                    args.push(quote! { max_length = #max });
+                }
+            }
+            Rule::Range(min, max) => {
+                if let Some(min) = min {
+                    args.push(quote! { minimum = #min });
+                }
+                if let Some(max) = max {
+                    args.push(quote! { maximum = #max });
+                }
+            }
+            Rule::Url => args.push(quote! { format = "uri" }),
+            Rule::Email => args.push(quote! { format = Email }),
+            Rule::Nested => {}
+        }
+        args
+    }
+
+    fn check(&self, name: &Ident, field_name: &str) -> TokenStream2 {
+        match self {
+            Rule::Length(min, max) => {
+                let (min, max) = (
+                    option_tokens(min, quote!(usize)),
+                    option_tokens(max, quote!(usize)),
+                );
+                quote! {
+                    crate::utils::validate::check_length(&mut errors, #field_name, &self.#name, #min, #max);
+                }
+            }
+            Rule::Range(min, max) => {
+                let (min, max) = (
+                    option_tokens(min, quote!(f64)),
+                    option_tokens(max, quote!(f64)),
+                );
+                quote! {
+                    crate::utils::validate::check_range(&mut errors, #field_name, &self.#name, #min, #max);
+                }
+            }
+            Rule::Url => quote! {
+                crate::utils::validate::check_url(&mut errors, #field_name, &self.#name);
+            },
+            Rule::Email => quote! {
+                crate::utils::validate::check_email(&mut errors, #field_name, &self.#name);
+            },
+            Rule::Nested => quote! {
+                crate::utils::validate::check_nested(&mut errors, #field_name, &self.#name);
+            },
+        }
+    }
+}
@@ -3,6 +3,27 @@ pub use sea_orm_migration::prelude::*;
 mod m20251228_110826_create_table;
 mod m20260109_003305_update;
 mod m20260119_020622_update;
+mod m20260203_101512_update;
+mod m20260207_142033_update;
+mod m20260212_093115_update;
+mod m20260216_201547_update;
+mod m20260221_104233_update;
+mod m20260224_183409_update;
+mod m20260302_091722_update;
+mod m20260306_143850_update;
+mod m20260310_201144_update;
+mod m20260314_110307_update;
+mod m20260318_094512_update;
+mod m20260322_161203_update;
+mod m20260326_203417_update;
+mod m20260330_101522_update;
+mod m20260403_090215_update;
+mod m20260408_143022_update;
+mod m20260412_101746_update;
+mod m20260417_152408_update;
+mod m20260421_103615_update;
+mod m20260425_141937_update;
+mod m20260427_093024_update;
 
 pub struct Migrator;
 
@@ -13,6 +34,27 @@ impl MigratorTrait for Migrator {
             Box::new(m20251228_110826_create_table::Migration),
             Box::new(m20260109_003305_update::Migration),
             Box::new(m20260119_020622_update::Migration),
+            Box::new(m20260203_101512_update::Migration),
+            Box::new(m20260207_142033_update::Migration),
+            Box::new(m20260212_093115_update::Migration),
+            Box::new(m20260216_201547_update::Migration),
+            Box::new(m20260221_104233_update::Migration),
+            Box::new(m20260224_183409_update::Migration),
+            Box::new(m20260302_091722_update::Migration),
+            Box::new(m20260306_143850_update::Migration),
+            Box::new(m20260310_201144_update::Migration),
+            Box::new(m20260314_110307_update::Migration),
+            Box::new(m20260318_094512_update::Migration),
+            Box::new(m20260322_161203_update::Migration),
+            Box::new(m20260326_203417_update::Migration),
+            Box::new(m20260330_101522_update::Migration),
+            Box::new(m20260403_090215_update::Migration),
+            Box::new(m20260408_143022_update::Migration),
+            Box::new(m20260412_101746_update::Migration),
+            Box::new(m20260417_152408_update::Migration),
+            Box::new(m20260421_103615_update::Migration),
+            Box::new(m20260425_141937_update::Migration),
+            Box::new(m20260427_093024_update::Migration),
         ]
     }
 }
@@ -0,0 +1,61 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 토큰 검사/폐기를 요청할 수 있는 클라이언트 (client_secret은 bcrypt 해시로 저장)
+        manager
+            .create_table(
+                Table::create()
+                    .table(OauthClient::Table)
+                    .if_not_exists()
+                    .col(string(OauthClient::ClientId).primary_key())
+                    .col(string(OauthClient::ClientSecret))
+                    .col(string(OauthClient::Name))
+                    .col(date_time(OauthClient::CreatedAt).default(Expr::current_timestamp()))
+                    .to_owned(),
+            )
+            .await?;
+
+        // 폐기된 access 토큰의 jti 목록, 토큰이 만료되면 더이상 보관할 필요가 없음
+        manager
+            .create_table(
+                Table::create()
+                    .table(RevokedToken::Table)
+                    .if_not_exists()
+                    .col(string(RevokedToken::Jti).primary_key())
+                    .col(date_time(RevokedToken::ExpiresAt))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(RevokedToken::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(OauthClient::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum OauthClient {
+    Table,
+    ClientId,
+    ClientSecret,
+    Name,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum RevokedToken {
+    Table,
+    Jti,
+    ExpiresAt,
+}
@@ -0,0 +1,143 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 인가 서버로 동작하기 위해 클라이언트의 리디렉션 주소와 허용 scope를 저장
+        // redirect_uris는 공백으로 구분한 목록, public 클라이언트는 secret 대신 PKCE를 강제함
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(OauthClient::Table)
+                    .add_column(text(OauthClient::RedirectUris).default(""))
+                    .add_column(string(OauthClient::AllowedScopes).default("openid profile email"))
+                    .add_column(boolean(OauthClient::IsPublic).default(false))
+                    .to_owned(),
+            )
+            .await?;
+
+        // /oauth/authorize 요청을 보관하고, 동의하면 code를 발급함
+        manager
+            .create_table(
+                Table::create()
+                    .table(AuthorizationCode::Table)
+                    .if_not_exists()
+                    .col(string(AuthorizationCode::RequestId).primary_key())
+                    .col(string_null(AuthorizationCode::Code).unique_key())
+                    .col(string(AuthorizationCode::ClientId))
+                    .col(integer_null(AuthorizationCode::UserId))
+                    .col(text(AuthorizationCode::RedirectUri))
+                    .col(string(AuthorizationCode::Scope))
+                    .col(text_null(AuthorizationCode::State))
+                    .col(text_null(AuthorizationCode::Nonce))
+                    .col(string_null(AuthorizationCode::CodeChallenge))
+                    .col(string_null(AuthorizationCode::CodeChallengeMethod))
+                    .col(date_time(AuthorizationCode::ExpiresAt))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_authorization_code_client")
+                            .from(AuthorizationCode::Table, AuthorizationCode::ClientId)
+                            .to(OauthClient::Table, OauthClient::ClientId)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_authorization_code_user")
+                            .from(AuthorizationCode::Table, AuthorizationCode::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // 외부 클라이언트에게 발급한 리프레시 토큰은 클라이언트와 scope를 같이 기억해야함
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(RefreshToken::Table)
+                    .add_column(string_null(RefreshToken::ClientId))
+                    .add_column(string_null(RefreshToken::Scope))
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_refresh_token_client")
+                            .from_tbl(RefreshToken::Table)
+                            .from_col(RefreshToken::ClientId)
+                            .to_tbl(OauthClient::Table)
+                            .to_col(OauthClient::ClientId)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(RefreshToken::Table)
+                    .drop_foreign_key(Alias::new("fk_refresh_token_client"))
+                    .drop_column(RefreshToken::ClientId)
+                    .drop_column(RefreshToken::Scope)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(AuthorizationCode::Table).to_owned())
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(OauthClient::Table)
+                    .drop_column(OauthClient::RedirectUris)
+                    .drop_column(OauthClient::AllowedScopes)
+                    .drop_column(OauthClient::IsPublic)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum OauthClient {
+    Table,
+    ClientId,
+    RedirectUris,
+    AllowedScopes,
+    IsPublic,
+}
+
+#[derive(DeriveIden)]
+enum AuthorizationCode {
+    Table,
+    RequestId,
+    Code,
+    ClientId,
+    UserId,
+    RedirectUri,
+    Scope,
+    State,
+    Nonce,
+    CodeChallenge,
+    CodeChallengeMethod,
+    ExpiresAt,
+}
+
+#[derive(DeriveIden)]
+enum RefreshToken {
+    Table,
+    ClientId,
+    Scope,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
@@ -0,0 +1,43 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 공개 프로필
+        // avatar_key는 저장소의 키 접두사, 썸네일 크기별로 "-{크기}.png"를 붙여서 저장함
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Users::Table)
+                    .add_column(string_null(Users::DisplayName))
+                    .add_column(text_null(Users::Bio))
+                    .add_column(string_null(Users::AvatarKey))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Users::Table)
+                    .drop_column(Users::DisplayName)
+                    .drop_column(Users::Bio)
+                    .drop_column(Users::AvatarKey)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    DisplayName,
+    Bio,
+    AvatarKey,
+}
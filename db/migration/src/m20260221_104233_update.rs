@@ -0,0 +1,65 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 관리자 기능, role은 user/admin, status는 active/suspended
+        // tokens_valid_after 이전에 발급된 access 토큰은 거절함 (강제 로그아웃)
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Users::Table)
+                    .add_column(string(Users::Role).default("user"))
+                    .add_column(string(Users::Status).default("active"))
+                    .add_column(date_time(Users::CreatedAt).default(Expr::current_timestamp()))
+                    .add_column(date_time_null(Users::TokensValidAfter))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_users_created_at")
+                    .table(Users::Table)
+                    .col(Users::CreatedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_users_created_at")
+                    .table(Users::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Users::Table)
+                    .drop_column(Users::Role)
+                    .drop_column(Users::Status)
+                    .drop_column(Users::CreatedAt)
+                    .drop_column(Users::TokensValidAfter)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Role,
+    Status,
+    CreatedAt,
+    TokensValidAfter,
+}
@@ -0,0 +1,228 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    // 카테고리를 이름 대신 id로 가리키도록 바꾸고, 부모, slug, 정렬 순서를 추가
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Category::Table)
+                    .add_column(
+                        ColumnDef::new(Category::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment(),
+                    )
+                    .add_column(integer_null(Category::ParentId))
+                    .add_column(string_null(Category::Slug))
+                    .add_column(text_null(Category::Description))
+                    .add_column(integer(Category::SortOrder).default(0))
+                    .to_owned(),
+            )
+            .await?;
+
+        // 기존 이름으로 slug를 만들고, 비었거나 겹치면 id를 붙여서 구분
+        db.execute_unprepared(
+            "UPDATE category SET slug = trim(both '-' from regexp_replace(lower(name), '[^[:alnum:]]+', '-', 'g'));
+             UPDATE category c SET slug = concat_ws('-', nullif(c.slug, ''), c.id::text)
+             WHERE c.slug = '' OR EXISTS (SELECT 1 FROM category o WHERE o.slug = c.slug AND o.id < c.id);",
+        )
+        .await?;
+
+        // 상품이 가리키던 이름을 id로 옮김
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Product::Table)
+                    .add_column(integer_null(Product::CategoryId))
+                    .to_owned(),
+            )
+            .await?;
+        db.execute_unprepared(
+            "UPDATE product p SET category_id = c.id FROM category c WHERE c.name = p.category;",
+        )
+        .await?;
+        manager
+            .drop_foreign_key(
+                ForeignKey::drop()
+                    .name("fk_product_category")
+                    .table(Product::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Product::Table)
+                    .drop_column(Product::Category)
+                    .modify_column(integer(Product::CategoryId))
+                    .to_owned(),
+            )
+            .await?;
+
+        // 기본키를 name에서 id로 바꿈
+        db.execute_unprepared(
+            "ALTER TABLE category DROP CONSTRAINT category_pkey, ADD PRIMARY KEY (id);",
+        )
+        .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Category::Table)
+                    .modify_column(string(Category::Slug))
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_category_slug")
+                    .table(Category::Table)
+                    .col(Category::Slug)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_category_parent_id")
+                    .table(Category::Table)
+                    .col(Category::ParentId)
+                    .to_owned(),
+            )
+            .await?;
+
+        // 하위 카테고리나 상품이 남아있으면 지울 수 없음
+        manager
+            .create_foreign_key(
+                ForeignKey::create()
+                    .name("fk_category_parent")
+                    .from(Category::Table, Category::ParentId)
+                    .to(Category::Table, Category::Id)
+                    .on_delete(ForeignKeyAction::Restrict)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_foreign_key(
+                ForeignKey::create()
+                    .name("fk_product_category")
+                    .from(Product::Table, Product::CategoryId)
+                    .to(Category::Table, Category::Id)
+                    .on_delete(ForeignKeyAction::Restrict)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_product_category_id")
+                    .table(Product::Table)
+                    .col(Product::CategoryId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    // 같은 이름의 카테고리가 여러개라면 되돌릴 수 없음
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        manager
+            .drop_foreign_key(
+                ForeignKey::drop()
+                    .name("fk_product_category")
+                    .table(Product::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_foreign_key(
+                ForeignKey::drop()
+                    .name("fk_category_parent")
+                    .table(Category::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_product_category_id")
+                    .table(Product::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Product::Table)
+                    .add_column(string_null(Product::Category))
+                    .to_owned(),
+            )
+            .await?;
+        db.execute_unprepared(
+            "UPDATE product p SET category = c.name FROM category c WHERE c.id = p.category_id;
+             ALTER TABLE category DROP CONSTRAINT category_pkey, ADD PRIMARY KEY (name);",
+        )
+        .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Product::Table)
+                    .drop_column(Product::CategoryId)
+                    .modify_column(string(Product::Category))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Category::Table)
+                    .drop_column(Category::Id)
+                    .drop_column(Category::ParentId)
+                    .drop_column(Category::Slug)
+                    .drop_column(Category::Description)
+                    .drop_column(Category::SortOrder)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_foreign_key(
+                ForeignKey::create()
+                    .name("fk_product_category")
+                    .from(Product::Table, Product::Category)
+                    .to(Category::Table, Category::Name)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Category {
+    Table,
+    Id,
+    Name,
+    ParentId,
+    Slug,
+    Description,
+    SortOrder,
+}
+
+#[derive(DeriveIden)]
+enum Product {
+    Table,
+    Category,
+    CategoryId,
+}
@@ -0,0 +1,39 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    // 상품 검색용 tsvector 생성 컬럼과 GIN 인덱스, 오타 검색용 pg_trgm 인덱스
+    // 한글은 형태소 분석기가 없으므로 simple 설정으로 단어 그대로 색인함
+    // 설명 같은 필드가 생기면 search_vector 식에 setweight로 추가하면 됨
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        db.execute_unprepared(
+            "CREATE EXTENSION IF NOT EXISTS pg_trgm;
+             ALTER TABLE product ADD COLUMN search_vector tsvector
+                 GENERATED ALWAYS AS (setweight(to_tsvector('simple', coalesce(title, '')), 'A')) STORED;
+             CREATE INDEX idx_product_search_vector ON product USING GIN (search_vector);
+             CREATE INDEX idx_product_title_trgm ON product USING GIN (title gin_trgm_ops);",
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    // 다른 곳에서 쓸 수 있으므로 pg_trgm 확장은 남겨둠
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        db.execute_unprepared(
+            "DROP INDEX IF EXISTS idx_product_title_trgm;
+             DROP INDEX IF EXISTS idx_product_search_vector;
+             ALTER TABLE product DROP COLUMN IF EXISTS search_vector;",
+        )
+        .await?;
+
+        Ok(())
+    }
+}
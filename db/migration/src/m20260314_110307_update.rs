@@ -0,0 +1,82 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    // 상품 이미지, 파일은 저장소에 크기, 형식별로 저장하고 키 앞부분만 기록함
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ProductImage::Table)
+                    .if_not_exists()
+                    .col(pk_auto(ProductImage::Id))
+                    .col(integer(ProductImage::ProductId))
+                    .col(string(ProductImage::StorageKey))
+                    .col(integer(ProductImage::Width))
+                    .col(integer(ProductImage::Height))
+                    .col(string_null(ProductImage::Alt))
+                    .col(integer(ProductImage::Position).default(0))
+                    .col(boolean(ProductImage::IsPrimary).default(false))
+                    .col(date_time(ProductImage::CreatedAt).default(Expr::current_timestamp()))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_product_image_product")
+                            .from(ProductImage::Table, ProductImage::ProductId)
+                            .to(Product::Table, Product::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_product_image_product_position")
+                    .table(ProductImage::Table)
+                    .col(ProductImage::ProductId)
+                    .col(ProductImage::Position)
+                    .to_owned(),
+            )
+            .await?;
+
+        // 상품마다 대표 이미지는 하나뿐
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE UNIQUE INDEX idx_product_image_primary ON product_image (product_id) WHERE is_primary;",
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ProductImage::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ProductImage {
+    Table,
+    Id,
+    ProductId,
+    StorageKey,
+    Width,
+    Height,
+    Alt,
+    Position,
+    IsPrimary,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Product {
+    Table,
+    Id,
+}
@@ -0,0 +1,137 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    // 상품별 재고와 재고 변동 기록
+    // inventory는 현재값, stock_ledger는 모든 변동을 남기는 기록이고 두 테이블은 같은 트랜잭션에서 바뀜
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Inventory::Table)
+                    .if_not_exists()
+                    .col(integer(Inventory::ProductId).primary_key())
+                    .col(integer(Inventory::OnHand).default(0))
+                    .col(integer(Inventory::Reserved).default(0))
+                    .col(integer(Inventory::LowStockThreshold).default(0))
+                    .col(date_time(Inventory::UpdatedAt).default(Expr::current_timestamp()))
+                    // 창고에 있는 수량보다 많이 예약할 수 없음
+                    .check(Expr::col(Inventory::Reserved).gte(0))
+                    .check(Expr::col(Inventory::Reserved).lte(Expr::col(Inventory::OnHand)))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_inventory_product")
+                            .from(Inventory::Table, Inventory::ProductId)
+                            .to(Product::Table, Product::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(StockLedger::Table)
+                    .if_not_exists()
+                    .col(pk_auto(StockLedger::Id))
+                    .col(integer(StockLedger::ProductId))
+                    .col(string_len(StockLedger::Kind, 20))
+                    .col(integer(StockLedger::OnHandDelta))
+                    .col(integer(StockLedger::ReservedDelta))
+                    .col(string_len_null(StockLedger::Reference, 100))
+                    .col(string_null(StockLedger::Note))
+                    .col(integer_null(StockLedger::UserId))
+                    .col(date_time(StockLedger::CreatedAt).default(Expr::current_timestamp()))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_stock_ledger_product")
+                            .from(StockLedger::Table, StockLedger::ProductId)
+                            .to(Product::Table, Product::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_stock_ledger_user")
+                            .from(StockLedger::Table, StockLedger::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_stock_ledger_product")
+                    .table(StockLedger::Table)
+                    .col(StockLedger::ProductId)
+                    .col(StockLedger::Id)
+                    .to_owned(),
+            )
+            .await?;
+
+        // 주문 번호 등으로 예약을 찾아서 해제, 확정할 때 씀
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_stock_ledger_reference")
+                    .table(StockLedger::Table)
+                    .col(StockLedger::Reference)
+                    .col(StockLedger::ProductId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(StockLedger::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(Inventory::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Inventory {
+    Table,
+    ProductId,
+    OnHand,
+    Reserved,
+    LowStockThreshold,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum StockLedger {
+    Table,
+    Id,
+    ProductId,
+    Kind,
+    OnHandDelta,
+    ReservedDelta,
+    Reference,
+    Note,
+    UserId,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Product {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
@@ -0,0 +1,185 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    // 장바구니와 주문
+    // order는 SQL 예약어라서 users처럼 복수형 orders로 만듦
+    // order_item은 주문 시점의 상품명, 가격을 그대로 복사해둠
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Cart::Table)
+                    .if_not_exists()
+                    .col(pk_auto(Cart::Id))
+                    .col(integer(Cart::UserId))
+                    .col(integer(Cart::ProductId))
+                    .col(integer(Cart::Quantity))
+                    .col(date_time(Cart::CreatedAt).default(Expr::current_timestamp()))
+                    .check(Expr::col(Cart::Quantity).gt(0))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_cart_user")
+                            .from(Cart::Table, Cart::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_cart_product")
+                            .from(Cart::Table, Cart::ProductId)
+                            .to(Product::Table, Product::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_cart_user_product")
+                    .table(Cart::Table)
+                    .col(Cart::UserId)
+                    .col(Cart::ProductId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        // 탈퇴한 유저의 주문도 정산을 위해 남겨둠
+        manager
+            .create_table(
+                Table::create()
+                    .table(Orders::Table)
+                    .if_not_exists()
+                    .col(pk_auto(Orders::Id))
+                    .col(integer_null(Orders::UserId))
+                    .col(string_len(Orders::Status, 20).default("pending"))
+                    .col(big_integer(Orders::Total))
+                    .col(date_time(Orders::CreatedAt).default(Expr::current_timestamp()))
+                    .col(date_time(Orders::UpdatedAt).default(Expr::current_timestamp()))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_orders_user")
+                            .from(Orders::Table, Orders::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_orders_user")
+                    .table(Orders::Table)
+                    .col(Orders::UserId)
+                    .col(Orders::Id)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(OrderItem::Table)
+                    .if_not_exists()
+                    .col(pk_auto(OrderItem::Id))
+                    .col(integer(OrderItem::OrderId))
+                    .col(integer_null(OrderItem::ProductId))
+                    .col(string(OrderItem::Title))
+                    .col(integer(OrderItem::UnitPrice))
+                    .col(integer(OrderItem::Quantity))
+                    .check(Expr::col(OrderItem::Quantity).gt(0))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_order_item_order")
+                            .from(OrderItem::Table, OrderItem::OrderId)
+                            .to(Orders::Table, Orders::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_order_item_product")
+                            .from(OrderItem::Table, OrderItem::ProductId)
+                            .to(Product::Table, Product::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_order_item_order")
+                    .table(OrderItem::Table)
+                    .col(OrderItem::OrderId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(OrderItem::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(Orders::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(Cart::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Cart {
+    Table,
+    Id,
+    UserId,
+    ProductId,
+    Quantity,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Orders {
+    Table,
+    Id,
+    UserId,
+    Status,
+    Total,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum OrderItem {
+    Table,
+    Id,
+    OrderId,
+    ProductId,
+    Title,
+    UnitPrice,
+    Quantity,
+}
+
+#[derive(DeriveIden)]
+enum Product {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
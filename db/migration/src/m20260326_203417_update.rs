@@ -0,0 +1,134 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    // 결제와 결제 웹훅 기록
+    // payment_event는 같은 웹훅이 다시 와도 한 번만 처리하기 위해 이벤트 id를 남김
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Payment::Table)
+                    .if_not_exists()
+                    .col(pk_auto(Payment::Id))
+                    .col(integer(Payment::OrderId))
+                    .col(string_len(Payment::Provider, 20))
+                    .col(string_len(Payment::ProviderRef, 100))
+                    .col(big_integer(Payment::Amount))
+                    .col(string_len(Payment::Status, 20).default("pending"))
+                    .col(string_null(Payment::FailureReason))
+                    .col(date_time(Payment::CreatedAt).default(Expr::current_timestamp()))
+                    .col(date_time(Payment::UpdatedAt).default(Expr::current_timestamp()))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_payment_order")
+                            .from(Payment::Table, Payment::OrderId)
+                            .to(Orders::Table, Orders::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_payment_provider_ref")
+                    .table(Payment::Table)
+                    .col(Payment::Provider)
+                    .col(Payment::ProviderRef)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_payment_order")
+                    .table(Payment::Table)
+                    .col(Payment::OrderId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(PaymentEvent::Table)
+                    .if_not_exists()
+                    .col(pk_auto(PaymentEvent::Id))
+                    .col(string_len(PaymentEvent::Provider, 20))
+                    .col(string_len(PaymentEvent::EventId, 100))
+                    .col(integer_null(PaymentEvent::PaymentId))
+                    .col(string_len(PaymentEvent::Kind, 50))
+                    .col(date_time(PaymentEvent::ReceivedAt).default(Expr::current_timestamp()))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_payment_event_payment")
+                            .from(PaymentEvent::Table, PaymentEvent::PaymentId)
+                            .to(Payment::Table, Payment::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_payment_event_provider_event")
+                    .table(PaymentEvent::Table)
+                    .col(PaymentEvent::Provider)
+                    .col(PaymentEvent::EventId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(PaymentEvent::Table).to_owned())
+            .await?;
+        manager
+            .drop_table(Table::drop().table(Payment::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Payment {
+    Table,
+    Id,
+    OrderId,
+    Provider,
+    ProviderRef,
+    Amount,
+    Status,
+    FailureReason,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum PaymentEvent {
+    Table,
+    Id,
+    Provider,
+    EventId,
+    PaymentId,
+    Kind,
+    ReceivedAt,
+}
+
+#[derive(DeriveIden)]
+enum Orders {
+    Table,
+    Id,
+}
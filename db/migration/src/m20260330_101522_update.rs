@@ -0,0 +1,189 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    // 금액을 통화 최소 단위(bigint)와 ISO 통화 코드로 저장하고 상품 가격 변경 이력을 추가
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Product::Table)
+                    .modify_column(big_integer(Product::Price))
+                    .add_column(string_len(Product::Currency, 3).default("KRW"))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Orders::Table)
+                    .add_column(string_len(Orders::Currency, 3).default("KRW"))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(OrderItem::Table)
+                    .modify_column(big_integer(OrderItem::UnitPrice))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Payment::Table)
+                    .add_column(string_len(Payment::Currency, 3).default("KRW"))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(ProductPriceHistory::Table)
+                    .if_not_exists()
+                    .col(pk_auto(ProductPriceHistory::Id))
+                    .col(integer(ProductPriceHistory::ProductId))
+                    .col(big_integer(ProductPriceHistory::Amount))
+                    .col(string_len(ProductPriceHistory::Currency, 3))
+                    .col(integer_null(ProductPriceHistory::UserId))
+                    .col(
+                        date_time(ProductPriceHistory::ChangedAt)
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_product_price_history_product")
+                            .from(ProductPriceHistory::Table, ProductPriceHistory::ProductId)
+                            .to(Product::Table, Product::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_product_price_history_user")
+                            .from(ProductPriceHistory::Table, ProductPriceHistory::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_product_price_history_product")
+                    .table(ProductPriceHistory::Table)
+                    .col(ProductPriceHistory::ProductId)
+                    .col(ProductPriceHistory::Id)
+                    .to_owned(),
+            )
+            .await?;
+
+        // 지금 가격을 이력의 시작점으로 남김
+        db.execute_unprepared(
+            "INSERT INTO product_price_history (product_id, amount, currency)
+             SELECT id, price, currency FROM product;",
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ProductPriceHistory::Table).to_owned())
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Payment::Table)
+                    .drop_column(Payment::Currency)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(OrderItem::Table)
+                    .modify_column(integer(OrderItem::UnitPrice))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Orders::Table)
+                    .drop_column(Orders::Currency)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Product::Table)
+                    .drop_column(Product::Currency)
+                    .modify_column(integer(Product::Price))
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Product {
+    Table,
+    Id,
+    Price,
+    Currency,
+}
+
+#[derive(DeriveIden)]
+enum Orders {
+    Table,
+    Currency,
+}
+
+#[derive(DeriveIden)]
+enum OrderItem {
+    Table,
+    UnitPrice,
+}
+
+#[derive(DeriveIden)]
+enum Payment {
+    Table,
+    Currency,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum ProductPriceHistory {
+    Table,
+    Id,
+    ProductId,
+    Amount,
+    Currency,
+    UserId,
+    ChangedAt,
+}
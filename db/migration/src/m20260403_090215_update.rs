@@ -0,0 +1,121 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    // 상품을 외부 시트와 맞추기 위한 sku와 대량 가져오기 작업 기록
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Product::Table)
+                    .add_column(string_len_null(Product::Sku, 64))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_product_sku")
+                    .table(Product::Table)
+                    .col(Product::Sku)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(ImportJob::Table)
+                    .if_not_exists()
+                    .col(pk_auto(ImportJob::Id))
+                    .col(integer_null(ImportJob::UserId))
+                    .col(string_len(ImportJob::Kind, 20))
+                    .col(string_len(ImportJob::Format, 10))
+                    .col(boolean(ImportJob::DryRun).default(false))
+                    .col(boolean(ImportJob::CreateCategories).default(false))
+                    .col(string_len(ImportJob::Status, 20).default("pending"))
+                    .col(integer(ImportJob::TotalRows).default(0))
+                    .col(integer(ImportJob::CreatedRows).default(0))
+                    .col(integer(ImportJob::UpdatedRows).default(0))
+                    .col(integer(ImportJob::ErrorRows).default(0))
+                    .col(json_binary(ImportJob::Errors).default("[]"))
+                    .col(text_null(ImportJob::Message))
+                    .col(date_time(ImportJob::CreatedAt).default(Expr::current_timestamp()))
+                    .col(date_time_null(ImportJob::FinishedAt))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_import_job_user")
+                            .from(ImportJob::Table, ImportJob::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ImportJob::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_product_sku")
+                    .table(Product::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Product::Table)
+                    .drop_column(Product::Sku)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Product {
+    Table,
+    Sku,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum ImportJob {
+    Table,
+    Id,
+    UserId,
+    Kind,
+    Format,
+    DryRun,
+    CreateCategories,
+    Status,
+    TotalRows,
+    CreatedRows,
+    UpdatedRows,
+    ErrorRows,
+    Errors,
+    Message,
+    CreatedAt,
+    FinishedAt,
+}
@@ -0,0 +1,146 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    // 상품 리뷰와 상품별 별점 집계
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 탈퇴한 유저의 리뷰는 남기고 작성자만 비움, 집계가 어긋나지 않도록
+        manager
+            .create_table(
+                Table::create()
+                    .table(Review::Table)
+                    .if_not_exists()
+                    .col(pk_auto(Review::Id))
+                    .col(integer(Review::ProductId))
+                    .col(integer_null(Review::UserId))
+                    .col(integer(Review::Rating).check(Expr::col(Review::Rating).between(1, 5)))
+                    .col(text(Review::Body))
+                    .col(date_time(Review::CreatedAt).default(Expr::current_timestamp()))
+                    .col(date_time(Review::UpdatedAt).default(Expr::current_timestamp()))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_review_product")
+                            .from(Review::Table, Review::ProductId)
+                            .to(Product::Table, Product::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_review_user")
+                            .from(Review::Table, Review::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // 한 상품에 유저당 리뷰 하나
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_review_product_user")
+                    .table(Review::Table)
+                    .col(Review::ProductId)
+                    .col(Review::UserId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_review_product_rating")
+                    .table(Review::Table)
+                    .col(Review::ProductId)
+                    .col(Review::Rating)
+                    .to_owned(),
+            )
+            .await?;
+
+        // 리뷰를 쓸 때마다 같은 트랜잭션에서 증감함
+        manager
+            .create_table(
+                Table::create()
+                    .table(ProductRating::Table)
+                    .if_not_exists()
+                    .col(integer(ProductRating::ProductId).primary_key())
+                    .col(integer(ProductRating::ReviewCount).default(0))
+                    .col(big_integer(ProductRating::RatingSum).default(0))
+                    .col(integer(ProductRating::Star1).default(0))
+                    .col(integer(ProductRating::Star2).default(0))
+                    .col(integer(ProductRating::Star3).default(0))
+                    .col(integer(ProductRating::Star4).default(0))
+                    .col(integer(ProductRating::Star5).default(0))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_product_rating_product")
+                            .from(ProductRating::Table, ProductRating::ProductId)
+                            .to(Product::Table, Product::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ProductRating::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(Review::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Product {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Review {
+    Table,
+    Id,
+    ProductId,
+    UserId,
+    Rating,
+    Body,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum ProductRating {
+    Table,
+    ProductId,
+    ReviewCount,
+    RatingSum,
+    #[sea_orm(iden = "star_1")]
+    Star1,
+    #[sea_orm(iden = "star_2")]
+    Star2,
+    #[sea_orm(iden = "star_3")]
+    Star3,
+    #[sea_orm(iden = "star_4")]
+    Star4,
+    #[sea_orm(iden = "star_5")]
+    Star5,
+}
@@ -0,0 +1,213 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    // 사이즈, 색상처럼 옵션 조합으로 나뉘는 상품의 변형(variant)
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ProductOption::Table)
+                    .if_not_exists()
+                    .col(pk_auto(ProductOption::Id))
+                    .col(integer(ProductOption::ProductId))
+                    .col(string_len(ProductOption::Name, 50))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_product_option_product")
+                            .from(ProductOption::Table, ProductOption::ProductId)
+                            .to(Product::Table, Product::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_product_option_name")
+                    .table(ProductOption::Table)
+                    .col(ProductOption::ProductId)
+                    .col(ProductOption::Name)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(ProductOptionValue::Table)
+                    .if_not_exists()
+                    .col(pk_auto(ProductOptionValue::Id))
+                    .col(integer(ProductOptionValue::OptionId))
+                    .col(string_len(ProductOptionValue::Value, 50))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_product_option_value_option")
+                            .from(ProductOptionValue::Table, ProductOptionValue::OptionId)
+                            .to(ProductOption::Table, ProductOption::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_product_option_value_value")
+                    .table(ProductOptionValue::Table)
+                    .col(ProductOptionValue::OptionId)
+                    .col(ProductOptionValue::Value)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        // price, currency는 상품 가격을 덮어쓸 때만 채움
+        manager
+            .create_table(
+                Table::create()
+                    .table(ProductVariant::Table)
+                    .if_not_exists()
+                    .col(pk_auto(ProductVariant::Id))
+                    .col(integer(ProductVariant::ProductId))
+                    .col(string_len(ProductVariant::Sku, 64))
+                    .col(big_integer_null(ProductVariant::Price))
+                    .col(string_len_null(ProductVariant::Currency, 3))
+                    .col(date_time(ProductVariant::CreatedAt).default(Expr::current_timestamp()))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_product_variant_product")
+                            .from(ProductVariant::Table, ProductVariant::ProductId)
+                            .to(Product::Table, Product::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_product_variant_sku")
+                    .table(ProductVariant::Table)
+                    .col(ProductVariant::Sku)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_product_variant_product")
+                    .table(ProductVariant::Table)
+                    .col(ProductVariant::ProductId)
+                    .to_owned(),
+            )
+            .await?;
+
+        // 변형이 고른 옵션 값, 쓰이고 있는 옵션 값은 지울 수 없음
+        // 상품을 지울 때는 한 문장 안에서 같이 지워지도록 RESTRICT 대신 NO ACTION
+        manager
+            .create_table(
+                Table::create()
+                    .table(ProductVariantValue::Table)
+                    .if_not_exists()
+                    .col(integer(ProductVariantValue::VariantId))
+                    .col(integer(ProductVariantValue::OptionValueId))
+                    .primary_key(
+                        Index::create()
+                            .col(ProductVariantValue::VariantId)
+                            .col(ProductVariantValue::OptionValueId),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_product_variant_value_variant")
+                            .from(ProductVariantValue::Table, ProductVariantValue::VariantId)
+                            .to(ProductVariant::Table, ProductVariant::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_product_variant_value_option_value")
+                            .from(
+                                ProductVariantValue::Table,
+                                ProductVariantValue::OptionValueId,
+                            )
+                            .to(ProductOptionValue::Table, ProductOptionValue::Id)
+                            .on_delete(ForeignKeyAction::NoAction),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ProductVariantValue::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(ProductVariant::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(ProductOptionValue::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(ProductOption::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Product {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum ProductOption {
+    Table,
+    Id,
+    ProductId,
+    Name,
+}
+
+#[derive(DeriveIden)]
+enum ProductOptionValue {
+    Table,
+    Id,
+    OptionId,
+    Value,
+}
+
+#[derive(DeriveIden)]
+enum ProductVariant {
+    Table,
+    Id,
+    ProductId,
+    Sku,
+    Price,
+    Currency,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum ProductVariantValue {
+    Table,
+    VariantId,
+    OptionValueId,
+}
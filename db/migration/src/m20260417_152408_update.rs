@@ -0,0 +1,269 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    // 할인 쿠폰과 사용 기록
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // kind가 percent면 value는 1~100, fixed면 currency 통화의 최소 단위 금액
+        // min_order는 currency 통화 기준이라 min_order가 있으면 currency도 있어야함
+        manager
+            .create_table(
+                Table::create()
+                    .table(Coupon::Table)
+                    .if_not_exists()
+                    .col(pk_auto(Coupon::Id))
+                    .col(string_len(Coupon::Code, 32))
+                    .col(string_len(Coupon::Kind, 16))
+                    .col(big_integer(Coupon::Value).check(Expr::col(Coupon::Value).gt(0)))
+                    .col(string_len_null(Coupon::Currency, 3))
+                    .col(big_integer_null(Coupon::MinOrder))
+                    .col(date_time_null(Coupon::StartsAt))
+                    .col(date_time_null(Coupon::EndsAt))
+                    .col(integer_null(Coupon::MaxUses))
+                    .col(integer_null(Coupon::MaxUsesPerUser))
+                    .col(boolean(Coupon::Active).default(true))
+                    .col(date_time(Coupon::CreatedAt).default(Expr::current_timestamp()))
+                    .col(date_time(Coupon::UpdatedAt).default(Expr::current_timestamp()))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_coupon_code")
+                    .table(Coupon::Table)
+                    .col(Coupon::Code)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        // 비어 있으면 모든 상품에 적용됨
+        manager
+            .create_table(
+                Table::create()
+                    .table(CouponCategory::Table)
+                    .if_not_exists()
+                    .col(integer(CouponCategory::CouponId))
+                    .col(integer(CouponCategory::CategoryId))
+                    .primary_key(
+                        Index::create()
+                            .col(CouponCategory::CouponId)
+                            .col(CouponCategory::CategoryId),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_coupon_category_coupon")
+                            .from(CouponCategory::Table, CouponCategory::CouponId)
+                            .to(Coupon::Table, Coupon::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_coupon_category_category")
+                            .from(CouponCategory::Table, CouponCategory::CategoryId)
+                            .to(Category::Table, Category::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // 주문 하나에 쿠폰 하나, 주문이 취소되면 지워서 사용 횟수를 돌려줌
+        manager
+            .create_table(
+                Table::create()
+                    .table(CouponRedemption::Table)
+                    .if_not_exists()
+                    .col(pk_auto(CouponRedemption::Id))
+                    .col(integer(CouponRedemption::CouponId))
+                    .col(integer_null(CouponRedemption::UserId))
+                    .col(integer(CouponRedemption::OrderId))
+                    .col(big_integer(CouponRedemption::Discount))
+                    .col(date_time(CouponRedemption::CreatedAt).default(Expr::current_timestamp()))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_coupon_redemption_coupon")
+                            .from(CouponRedemption::Table, CouponRedemption::CouponId)
+                            .to(Coupon::Table, Coupon::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_coupon_redemption_user")
+                            .from(CouponRedemption::Table, CouponRedemption::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_coupon_redemption_order")
+                            .from(CouponRedemption::Table, CouponRedemption::OrderId)
+                            .to(Orders::Table, Orders::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_coupon_redemption_order")
+                    .table(CouponRedemption::Table)
+                    .col(CouponRedemption::OrderId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_coupon_redemption_coupon_user")
+                    .table(CouponRedemption::Table)
+                    .col(CouponRedemption::CouponId)
+                    .col(CouponRedemption::UserId)
+                    .to_owned(),
+            )
+            .await?;
+
+        // 장바구니에 걸어둔 쿠폰, 유저마다 하나
+        manager
+            .create_table(
+                Table::create()
+                    .table(CartCoupon::Table)
+                    .if_not_exists()
+                    .col(integer(CartCoupon::UserId).primary_key())
+                    .col(integer(CartCoupon::CouponId))
+                    .col(date_time(CartCoupon::CreatedAt).default(Expr::current_timestamp()))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_cart_coupon_user")
+                            .from(CartCoupon::Table, CartCoupon::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_cart_coupon_coupon")
+                            .from(CartCoupon::Table, CartCoupon::CouponId)
+                            .to(Coupon::Table, Coupon::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // total은 할인을 뺀 금액, 쿠폰이 지워져도 주문에는 코드가 남음
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Orders::Table)
+                    .add_column(big_integer(Orders::Discount).default(0))
+                    .add_column(string_len_null(Orders::CouponCode, 32))
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Orders::Table)
+                    .drop_column(Orders::CouponCode)
+                    .drop_column(Orders::Discount)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(CartCoupon::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(CouponRedemption::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(CouponCategory::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(Coupon::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Category {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Orders {
+    Table,
+    Id,
+    Discount,
+    CouponCode,
+}
+
+#[derive(DeriveIden)]
+enum Coupon {
+    Table,
+    Id,
+    Code,
+    Kind,
+    Value,
+    Currency,
+    MinOrder,
+    StartsAt,
+    EndsAt,
+    MaxUses,
+    MaxUsesPerUser,
+    Active,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum CouponCategory {
+    Table,
+    CouponId,
+    CategoryId,
+}
+
+#[derive(DeriveIden)]
+enum CouponRedemption {
+    Table,
+    Id,
+    CouponId,
+    UserId,
+    OrderId,
+    Discount,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum CartCoupon {
+    Table,
+    UserId,
+    CouponId,
+    CreatedAt,
+}
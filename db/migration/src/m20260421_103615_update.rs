@@ -0,0 +1,88 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    // 블로그 글, 본문은 Markdown 원문을 저장하고 보여줄 때 HTML로 바꿈
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 탈퇴한 유저의 글은 남기고 작성자만 비움
+        // published_at은 처음 게시한 시각, 초안으로 돌려도 남겨서 다시 게시하면 순서가 유지됨
+        manager
+            .create_table(
+                Table::create()
+                    .table(Post::Table)
+                    .if_not_exists()
+                    .col(pk_auto(Post::Id))
+                    .col(integer_null(Post::AuthorId))
+                    .col(string_len(Post::Title, 200))
+                    .col(string_len(Post::Slug, 120))
+                    .col(text(Post::Body))
+                    .col(string_len(Post::Status, 16).default("draft"))
+                    .col(date_time_null(Post::PublishedAt))
+                    .col(date_time(Post::CreatedAt).default(Expr::current_timestamp()))
+                    .col(date_time(Post::UpdatedAt).default(Expr::current_timestamp()))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_post_author")
+                            .from(Post::Table, Post::AuthorId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_post_slug")
+                    .table(Post::Table)
+                    .col(Post::Slug)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_post_author")
+                    .table(Post::Table)
+                    .col(Post::AuthorId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Post::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Post {
+    Table,
+    Id,
+    AuthorId,
+    Title,
+    Slug,
+    Body,
+    Status,
+    PublishedAt,
+    CreatedAt,
+    UpdatedAt,
+}
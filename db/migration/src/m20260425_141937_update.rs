@@ -0,0 +1,116 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    // 블로그 글의 댓글, parent_id로 답글을 달고 root_id로 스레드 전체를 한 번에 읽음
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // root_id는 최상위 댓글이면 NULL, 답글이면 스레드의 최상위 댓글
+        // 지운 댓글은 deleted_at만 채우고 본문을 비워서 답글 구조를 유지함
+        manager
+            .create_table(
+                Table::create()
+                    .table(Comment::Table)
+                    .if_not_exists()
+                    .col(pk_auto(Comment::Id))
+                    .col(integer(Comment::PostId))
+                    .col(integer_null(Comment::ParentId))
+                    .col(integer_null(Comment::RootId))
+                    .col(integer_null(Comment::UserId))
+                    .col(text(Comment::Body))
+                    .col(date_time(Comment::CreatedAt).default(Expr::current_timestamp()))
+                    .col(date_time(Comment::UpdatedAt).default(Expr::current_timestamp()))
+                    .col(date_time_null(Comment::DeletedAt))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_comment_post")
+                            .from(Comment::Table, Comment::PostId)
+                            .to(Post::Table, Post::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_comment_parent")
+                            .from(Comment::Table, Comment::ParentId)
+                            .to(Comment::Table, Comment::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_comment_root")
+                            .from(Comment::Table, Comment::RootId)
+                            .to(Comment::Table, Comment::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_comment_user")
+                            .from(Comment::Table, Comment::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // 글의 최상위 댓글 목록
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_comment_post_root")
+                    .table(Comment::Table)
+                    .col(Comment::PostId)
+                    .col(Comment::RootId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_comment_root")
+                    .table(Comment::Table)
+                    .col(Comment::RootId)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Comment::Table).to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Post {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Comment {
+    Table,
+    Id,
+    PostId,
+    ParentId,
+    RootId,
+    UserId,
+    Body,
+    CreatedAt,
+    UpdatedAt,
+    DeletedAt,
+}
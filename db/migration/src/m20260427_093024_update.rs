@@ -0,0 +1,234 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    // 장바구니, 주문 상품, 재고를 변형 단위로 다룸
+    // 변형이 없는 상품은 variant_id가 null인 행 하나로 다루므로 유니크 인덱스는 null끼리도 같다고 봄 (NULLS NOT DISTINCT)
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Cart::Table)
+                    .add_column(integer_null(Cart::VariantId))
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_cart_variant")
+                            .from_tbl(Cart::Table)
+                            .from_col(Cart::VariantId)
+                            .to_tbl(ProductVariant::Table)
+                            .to_col(ProductVariant::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_cart_user_product")
+                    .table(Cart::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_cart_user_product_variant")
+                    .table(Cart::Table)
+                    .col(Cart::UserId)
+                    .col(Cart::ProductId)
+                    .col(Cart::VariantId)
+                    .unique()
+                    .nulls_not_distinct()
+                    .to_owned(),
+            )
+            .await?;
+
+        // 변형이 지워져도 주문 기록은 남기고 sku를 복사해둠
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(OrderItem::Table)
+                    .add_column(integer_null(OrderItem::VariantId))
+                    .add_column(string_len_null(OrderItem::Sku, 64))
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_order_item_variant")
+                            .from_tbl(OrderItem::Table)
+                            .from_col(OrderItem::VariantId)
+                            .to_tbl(ProductVariant::Table)
+                            .to_col(ProductVariant::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // 재고는 (상품, 변형)마다 한 행이라 product_id만으로는 기본키가 될 수 없음
+        db.execute_unprepared("ALTER TABLE inventory DROP CONSTRAINT inventory_pkey;")
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Inventory::Table)
+                    .add_column(pk_auto(Inventory::Id))
+                    .add_column(integer_null(Inventory::VariantId))
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_inventory_variant")
+                            .from_tbl(Inventory::Table)
+                            .from_col(Inventory::VariantId)
+                            .to_tbl(ProductVariant::Table)
+                            .to_col(ProductVariant::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_inventory_product_variant")
+                    .table(Inventory::Table)
+                    .col(Inventory::ProductId)
+                    .col(Inventory::VariantId)
+                    .unique()
+                    .nulls_not_distinct()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(StockLedger::Table)
+                    .add_column(integer_null(StockLedger::VariantId))
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_stock_ledger_variant")
+                            .from_tbl(StockLedger::Table)
+                            .from_col(StockLedger::VariantId)
+                            .to_tbl(ProductVariant::Table)
+                            .to_col(ProductVariant::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+
+        // 변형 단위의 행은 상품 단위로 되돌릴 수 없으므로 지움
+        db.execute_unprepared(
+            "DELETE FROM stock_ledger WHERE variant_id IS NOT NULL;
+             DELETE FROM inventory WHERE variant_id IS NOT NULL;
+             DELETE FROM cart WHERE variant_id IS NOT NULL;",
+        )
+        .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(StockLedger::Table)
+                    .drop_foreign_key(Alias::new("fk_stock_ledger_variant"))
+                    .drop_column(StockLedger::VariantId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Inventory::Table)
+                    .drop_foreign_key(Alias::new("fk_inventory_variant"))
+                    .drop_column(Inventory::VariantId)
+                    .drop_column(Inventory::Id)
+                    .to_owned(),
+            )
+            .await?;
+
+        db.execute_unprepared("ALTER TABLE inventory ADD PRIMARY KEY (product_id);")
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(OrderItem::Table)
+                    .drop_foreign_key(Alias::new("fk_order_item_variant"))
+                    .drop_column(OrderItem::VariantId)
+                    .drop_column(OrderItem::Sku)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Cart::Table)
+                    .drop_foreign_key(Alias::new("fk_cart_variant"))
+                    .drop_column(Cart::VariantId)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_cart_user_product")
+                    .table(Cart::Table)
+                    .col(Cart::UserId)
+                    .col(Cart::ProductId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Cart {
+    Table,
+    UserId,
+    ProductId,
+    VariantId,
+}
+
+#[derive(DeriveIden)]
+enum OrderItem {
+    Table,
+    VariantId,
+    Sku,
+}
+
+#[derive(DeriveIden)]
+enum Inventory {
+    Table,
+    Id,
+    ProductId,
+    VariantId,
+}
+
+#[derive(DeriveIden)]
+enum StockLedger {
+    Table,
+    VariantId,
+}
+
+#[derive(DeriveIden)]
+enum ProductVariant {
+    Table,
+    Id,
+}